@@ -22,6 +22,19 @@ impl Default for APTEDOptions {
     }
 }
 
+/// A single step of the edit script that turns one tree into another
+#[derive(Debug, Clone)]
+pub enum EditOperation {
+    /// Nodes are aligned and considered equal under the comparison options
+    Match(Rc<TreeNode>, Rc<TreeNode>),
+    /// Nodes are aligned but their label (or value) differs
+    Rename(Rc<TreeNode>, Rc<TreeNode>),
+    /// Node from the first tree has no counterpart in the second
+    Delete(Rc<TreeNode>),
+    /// Node from the second tree has no counterpart in the first
+    Insert(Rc<TreeNode>),
+}
+
 #[must_use]
 #[allow(clippy::cast_precision_loss)]
 pub fn compute_edit_distance(
@@ -33,6 +46,131 @@ pub fn compute_edit_distance(
     compute_edit_distance_recursive(tree1, tree2, options, &mut memo)
 }
 
+/// Compute the node mapping behind `compute_edit_distance`
+///
+/// Operations are returned in pre-order of the first tree, with insertions emitted
+/// at the position where they are aligned against it.
+#[must_use]
+pub fn compute_edit_mapping(
+    tree1: &Rc<TreeNode>,
+    tree2: &Rc<TreeNode>,
+    options: &APTEDOptions,
+) -> Vec<EditOperation> {
+    compute_edit_distance_with_mapping(tree1, tree2, options).1
+}
+
+/// Compute the edit distance and its node mapping from a single APTED run
+#[must_use]
+pub fn compute_edit_distance_with_mapping(
+    tree1: &Rc<TreeNode>,
+    tree2: &Rc<TreeNode>,
+    options: &APTEDOptions,
+) -> (f64, Vec<EditOperation>) {
+    let mut memo: HashMap<(usize, usize), f64> = HashMap::new();
+    let distance = compute_edit_distance_recursive(tree1, tree2, options, &mut memo);
+    let mut operations = Vec::new();
+    collect_edit_mapping(tree1, tree2, options, &mut memo, &mut operations);
+    (distance, operations)
+}
+
+fn collect_edit_mapping(
+    node1: &Rc<TreeNode>,
+    node2: &Rc<TreeNode>,
+    options: &APTEDOptions,
+    memo: &mut HashMap<(usize, usize), f64>,
+    operations: &mut Vec<EditOperation>,
+) {
+    let mut child_cost_matrix: HashMap<(usize, usize), f64> = HashMap::new();
+    for child1 in &node1.children {
+        for child2 in &node2.children {
+            let cost = compute_edit_distance_recursive(child1, child2, options, memo);
+            child_cost_matrix.insert((child1.id, child2.id), cost);
+        }
+    }
+    let (alignment_cost, alignment) =
+        compute_children_alignment(&node1.children, &node2.children, &child_cost_matrix, options);
+
+    let node_cost = rename_cost(node1, node2, options);
+    let both_leaves = node1.children.is_empty() && node2.children.is_empty();
+
+    // Replacing the whole subtree was cheaper than aligning it
+    if !both_leaves && node_cost + alignment_cost > replace_cost(node1, node2, options) {
+        operations.push(EditOperation::Delete(node1.clone()));
+        operations.push(EditOperation::Insert(node2.clone()));
+        return;
+    }
+
+    if node_cost == 0.0 {
+        operations.push(EditOperation::Match(node1.clone(), node2.clone()));
+    } else {
+        operations.push(EditOperation::Rename(node1.clone(), node2.clone()));
+    }
+
+    // Walk both child lists in order, emitting insertions where they fall
+    let mut next2 = 0;
+    for child1 in &node1.children {
+        match alignment.get(&child1.id).copied().flatten() {
+            Some(target_id) => {
+                while next2 < node2.children.len() && node2.children[next2].id != target_id {
+                    operations.push(EditOperation::Insert(node2.children[next2].clone()));
+                    next2 += 1;
+                }
+                if next2 < node2.children.len() {
+                    let child2 = node2.children[next2].clone();
+                    next2 += 1;
+                    collect_edit_mapping(child1, &child2, options, memo, operations);
+                }
+            }
+            None => operations.push(EditOperation::Delete(child1.clone())),
+        }
+    }
+    for child2 in &node2.children[next2..] {
+        operations.push(EditOperation::Insert(child2.clone()));
+    }
+}
+
+/// Cost of replacing one subtree by the other without aligning their children
+///
+/// Renamed subtrees of the same kind cost the rename plus the smaller size, since their
+/// nodes can mostly be renamed onto each other. A subtree of another kind (a `for` loop
+/// against a `return`) has to be rebuilt, and two equal nodes (two blocks) differ only in
+/// what they contain, so both cost the larger size.
+#[allow(clippy::cast_precision_loss)]
+fn replace_cost(node1: &TreeNode, node2: &TreeNode, options: &APTEDOptions) -> f64 {
+    let delete_all_cost = options.delete_cost * node1.get_subtree_size() as f64;
+    let insert_all_cost = options.insert_cost * node2.get_subtree_size() as f64;
+    let same_node = node1.label == node2.label && node1.value == node2.value;
+    if same_node || different_kind(node1, node2, options) {
+        delete_all_cost.max(insert_all_cost)
+    } else {
+        rename_cost(node1, node2, options) + delete_all_cost.min(insert_all_cost)
+    }
+}
+
+// In structural mode the TypeScript converter keeps the kind in `value` and the name in
+// `label`, while tree-sitter converters keep the kind in `label` and source text in `value`.
+// Nodes differing in both are of different kinds under either layout.
+fn different_kind(node1: &TreeNode, node2: &TreeNode, options: &APTEDOptions) -> bool {
+    !options.compare_values && node1.label != node2.label && node1.value != node2.value
+}
+
+// Relabelling a node to another kind (an assignment into an increment) is not a rename, so
+// it costs as much as replacing the node.
+fn rename_cost(node1: &TreeNode, node2: &TreeNode, options: &APTEDOptions) -> f64 {
+    let same = if options.compare_values {
+        node1.label == node2.label && node1.value == node2.value
+    } else {
+        node1.label == node2.label
+    };
+    if same {
+        0.0
+    } else if different_kind(node1, node2, options) {
+        options.delete_cost.max(options.insert_cost)
+    } else {
+        options.rename_cost
+    }
+}
+
 fn compute_edit_distance_recursive(
     node1: &Rc<TreeNode>,
     node2: &Rc<TreeNode>,
@@ -48,45 +186,13 @@ fn compute_edit_distance_recursive(
     // Base cases
     if node1.children.is_empty() && node2.children.is_empty() {
         // Both are leaves
-        let cost = if options.compare_values {
-            // Compare both label and value
-            if node1.label == node2.label && node1.value == node2.value {
-                0.0
-            } else {
-                options.rename_cost
-            }
-        } else {
-            // Compare only label (structural comparison)
-            if node1.label == node2.label {
-                0.0
-            } else {
-                options.rename_cost
-            }
-        };
+        let cost = rename_cost(node1, node2, options);
         memo.insert(key, cost);
         return cost;
     }
 
-    // Calculate costs for all three operations
-    let delete_all_cost = options.delete_cost * node1.get_subtree_size() as f64;
-    let insert_all_cost = options.insert_cost * node2.get_subtree_size() as f64;
-
     // Calculate rename + optimal children alignment
-    let mut rename_plus_cost = if options.compare_values {
-        // Compare both label and value
-        if node1.label == node2.label && node1.value == node2.value {
-            0.0
-        } else {
-            options.rename_cost
-        }
-    } else {
-        // Compare only label (structural comparison)
-        if node1.label == node2.label {
-            0.0
-        } else {
-            options.rename_cost
-        }
-    };
+    let mut rename_plus_cost = rename_cost(node1, node2, options);
 
     if !node1.children.is_empty() || !node2.children.is_empty() {
         // Compute all pairwise costs between children
@@ -110,7 +216,7 @@ fn compute_edit_distance_recursive(
         rename_plus_cost += alignment_cost;
     }
 
-    let min_cost = delete_all_cost.min(insert_all_cost).min(rename_plus_cost);
+    let min_cost = replace_cost(node1, node2, options).min(rename_plus_cost);
     memo.insert(key, min_cost);
     min_cost
 }
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Line ranges touched by a change set, keyed by file
+///
+/// Used to restrict duplicate reports to pairs where at least one side overlaps changed
+/// code, e.g. in pull request pipelines.
+#[derive(Debug, Clone, Default)]
+pub struct ChangedLines {
+    files: HashMap<PathBuf, Vec<(u32, u32)>>,
+}
+
+impl ChangedLines {
+    /// Build from the `--changed-since` / `--diff-from` CLI options
+    ///
+    /// Returns `Ok(None)` when neither option is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if git fails or the diff file cannot be read
+    pub fn from_cli_options(
+        changed_since: Option<&str>,
+        diff_from: Option<&Path>,
+    ) -> Result<Option<Self>, String> {
+        match (changed_since, diff_from) {
+            (Some(rev), _) => Self::from_git(rev).map(Some),
+            (None, Some(path)) => Self::from_diff_file(path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Lines changed in the working tree since `rev`, including untracked files
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if git is unavailable, the current directory is not a repository,
+    /// or `rev` cannot be resolved
+    pub fn from_git(rev: &str) -> Result<Self, String> {
+        let root = run_git(&["rev-parse", "--show-toplevel"])?;
+        let root = PathBuf::from(root.trim());
+
+        // Explicit prefixes, whatever `diff.noprefix` or `diff.mnemonicPrefix` say
+        let diff = run_git(&[
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            rev,
+            "--",
+        ])?;
+        let mut changed = Self::parse_unified_diff(&diff, &root);
+
+        // New files are not part of `git diff` until they are added
+        let untracked = run_git(&["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+        for file in untracked.lines().filter(|l| !l.is_empty()) {
+            changed.add_range(&root.join(file), 1, u32::MAX);
+        }
+
+        Ok(changed)
+    }
+
+    /// Lines changed by a unified diff file, with paths relative to the current directory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read
+    pub fn from_diff_file(path: &Path) -> Result<Self, String> {
+        let diff = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read diff {}: {}", path.display(), e))?;
+        let base = std::env::current_dir().map_err(|e| e.to_string())?;
+        Ok(Self::parse_unified_diff(&diff, &base))
+    }
+
+    /// Parse the new-side changed lines out of a unified diff
+    ///
+    /// Paths are resolved against `base_dir`; `a/` and `b/` prefixes are stripped. Only
+    /// added lines count, not context lines, so diffs with any `-U` setting work. Pure
+    /// deletions mark the line just before the removed block, so the enclosing function
+    /// still counts as changed. Deleted files are ignored.
+    pub fn parse_unified_diff(diff: &str, base_dir: &Path) -> Self {
+        let mut changed = Self::default();
+        let mut current: Option<PathBuf> = None;
+        // Next new-side line number and the old/new lines left in the current hunk
+        let mut new_line = 0;
+        let (mut old_left, mut new_left) = (0u32, 0u32);
+
+        let mut lines = diff.lines().peekable();
+        while let Some(line) = lines.next() {
+            if old_left > 0 || new_left > 0 {
+                match line.chars().next() {
+                    Some('+') => {
+                        if let Some(path) = &current {
+                            changed.add_range(path, new_line, new_line);
+                        }
+                        new_line += 1;
+                        new_left = new_left.saturating_sub(1);
+                    }
+                    Some('-') => {
+                        // Removed lines replaced by added ones are covered by the additions.
+                        // The hunk counts say whether more changes follow: past the hunk's last
+                        // line, the next file's `--- ` header is not one.
+                        let more_in_hunk = old_left > 1 || new_left > 0;
+                        let replaced = more_in_hunk
+                            && lines
+                                .peek()
+                                .is_some_and(|next| next.starts_with('+') || next.starts_with('-'));
+                        if let (Some(path), false) = (&current, replaced) {
+                            let at = new_line.saturating_sub(1).max(1);
+                            changed.add_range(path, at, at);
+                        }
+                        old_left = old_left.saturating_sub(1);
+                    }
+                    // `\ No newline at end of file`
+                    Some('\\') => {}
+                    _ => {
+                        new_line += 1;
+                        old_left = old_left.saturating_sub(1);
+                        new_left = new_left.saturating_sub(1);
+                    }
+                }
+            } else if let Some(hunk) = line.strip_prefix("@@ ") {
+                if let Some((old_count, new_start, new_count)) = parse_hunk_header(hunk) {
+                    // An empty new range starts *after* the given line
+                    new_line = if new_count == 0 { new_start + 1 } else { new_start };
+                    (old_left, new_left) = (old_count, new_count);
+                }
+            } else if let Some(target) = line.strip_prefix("+++ ") {
+                let target = target.split('\t').next().unwrap_or("").trim();
+                current = if target == "/dev/null" {
+                    None
+                } else {
+                    let relative = target.strip_prefix("b/").unwrap_or(target);
+                    Some(base_dir.join(relative))
+                };
+            }
+        }
+
+        changed
+    }
+
+    fn add_range(&mut self, path: &Path, start: u32, end: u32) {
+        self.files.entry(normalize(path)).or_default().push((start, end));
+    }
+
+    /// Number of files with changes
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// True if the file has any changed lines
+    pub fn touches_file(&self, path: &Path) -> bool {
+        self.files.contains_key(&normalize(path))
+    }
+
+    /// True if any changed line falls within `start..=end` of the file
+    pub fn overlaps(&self, path: &Path, start: u32, end: u32) -> bool {
+        self.files
+            .get(&normalize(path))
+            .is_some_and(|ranges| ranges.iter().any(|&(s, e)| s <= end && start <= e))
+    }
+}
+
+// `-12,3 +14,5 @@ fn context` -> (3, 14, 5); a missing count means one line
+fn parse_hunk_header(hunk: &str) -> Option<(u32, u32, u32)> {
+    fn range(part: &str) -> Option<(u32, u32)> {
+        let mut parts = part.splitn(2, ',');
+        let start = parts.next()?.parse().ok()?;
+        let count = parts.next().map_or(Some(1), |c| c.parse().ok())?;
+        Some((start, count))
+    }
+
+    let mut parts = hunk.split_whitespace();
+    let (_, old_count) = range(parts.next()?.strip_prefix('-')?)?;
+    let (new_start, new_count) = range(parts.next()?.strip_prefix('+')?)?;
+    Some((old_count, new_start, new_count))
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    if path.is_relative() {
+        if let Ok(cwd) = std::env::current_dir() {
+            return cwd.join(path);
+        }
+    }
+    path.to_path_buf()
+}
+
+fn run_git(args: &[&str]) -> Result<String, String> {
+    let output =
+        Command::new("git").args(args).output().map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/api.ts b/src/api.ts
+index 1111111..2222222 100644
+--- a/src/api.ts
++++ b/src/api.ts
+@@ -10,0 +11,4 @@ export function load() {
++  const a = 1;
++  const b = 2;
++  const c = 3;
++  return a + b + c;
+@@ -30,2 +34 @@ function other() {
+-  x();
+-  y();
++  z();
+@@ -50,3 +53,0 @@ function removed() {
+-  a();
+-  b();
+-  c();
+diff --git a/old.ts b/old.ts
+deleted file mode 100644
+--- a/old.ts
++++ /dev/null
+@@ -1,3 +0,0 @@
+-gone
+";
+
+    #[test]
+    fn test_parse_unified_diff_ranges() {
+        let base = Path::new("/nonexistent-repo");
+        let changed = ChangedLines::parse_unified_diff(DIFF, base);
+        let file = base.join("src/api.ts");
+
+        assert_eq!(changed.file_count(), 1);
+        assert!(changed.touches_file(&file));
+        assert!(!changed.touches_file(&base.join("old.ts")));
+
+        assert!(changed.overlaps(&file, 1, 11));
+        assert!(changed.overlaps(&file, 14, 20));
+        assert!(!changed.overlaps(&file, 15, 33));
+        assert!(changed.overlaps(&file, 34, 34));
+        // Pure deletion marks the line before the removed block
+        assert!(changed.overlaps(&file, 53, 53));
+        assert!(!changed.overlaps(&file, 54, 60));
+    }
+
+    #[test]
+    fn test_context_lines_are_not_changes() {
+        let diff = "\
+--- a.py
++++ a.py
+@@ -1,7 +1,7 @@
+ def f():
+     a = 1
+     b = 2
+-    c = 3
++    c = 4
+     d = 5
+     e = 6
+     return a
+--- b.py
++++ b.py
+@@ -2 +2 @@
+-x = 1
++x = 2
+";
+        let base = Path::new("/nonexistent-repo");
+        let changed = ChangedLines::parse_unified_diff(diff, base);
+        let file = base.join("a.py");
+
+        assert!(changed.overlaps(&file, 4, 4));
+        assert!(!changed.overlaps(&file, 5, 7));
+        assert!(!changed.overlaps(&file, 1, 2));
+        assert!(changed.overlaps(&base.join("b.py"), 2, 2));
+    }
+
+    #[test]
+    fn test_deletion_ending_a_file_before_the_next_header() {
+        let diff = "\
+--- a.py	2024-01-01 00:00:00
++++ a.py	2024-01-02 00:00:00
+@@ -5,3 +5,2 @@
+ def f():
+     a = 1
+-    b = 2
+--- other.py	2024-01-01 00:00:00
++++ other.py	2024-01-02 00:00:00
+@@ -1 +1 @@
+-x = 1
++x = 2
+";
+        let base = Path::new("/nonexistent-repo");
+        let changed = ChangedLines::parse_unified_diff(diff, base);
+
+        assert!(changed.overlaps(&base.join("a.py"), 6, 6));
+        assert!(changed.overlaps(&base.join("other.py"), 1, 1));
+    }
+
+    #[test]
+    fn test_parse_hunk_header() {
+        assert_eq!(parse_hunk_header("-1,2 +3,4 @@"), Some((2, 3, 4)));
+        assert_eq!(parse_hunk_header("-1 +7 @@ fn"), Some((1, 7, 1)));
+        assert_eq!(parse_hunk_header("-5,2 +4,0 @@"), Some((2, 4, 0)));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::class_extractor::{ClassDefinition, ClassMethod, ClassProperty};
+use crate::tsed::{calculate_tsed, TSEDOptions};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -50,6 +51,34 @@ pub struct SimilarClassPair {
     pub result: ClassComparisonResult,
 }
 
+/// Two methods with near-identical implementations
+#[derive(Debug, Clone)]
+pub struct DuplicateMethod {
+    pub method1: ClassMethod,
+    pub method2: ClassMethod,
+    pub similarity: f64,
+}
+
+/// Unrelated classes sharing several near-identical methods: a candidate base class or mixin
+#[derive(Debug, Clone)]
+pub struct SharedMethodsPair {
+    pub class1: ClassDefinition,
+    pub class2: ClassDefinition,
+    /// One-to-one method matches, most similar first
+    pub methods: Vec<DuplicateMethod>,
+}
+
+/// A method that repeats an implementation the class already inherits
+#[derive(Debug, Clone)]
+pub struct InheritedDuplicate {
+    pub class: ClassDefinition,
+    /// The ancestor declaring the inherited implementation
+    pub ancestor: ClassDefinition,
+    pub method: ClassMethod,
+    pub inherited: ClassMethod,
+    pub similarity: f64,
+}
+
 pub fn normalize_class(class: &ClassDefinition) -> NormalizedClass {
     let mut properties = HashMap::new();
     for prop in &class.properties {
@@ -60,14 +89,9 @@ pub fn normalize_class(class: &ClassDefinition) -> NormalizedClass {
     for method in &class.methods {
         // Normalize method signature
         let normalized_method = ClassMethod {
-            name: method.name.clone(),
             parameters: normalize_parameters(&method.parameters),
             return_type: normalize_type(&method.return_type),
-            is_static: method.is_static,
-            is_private: method.is_private,
-            is_async: method.is_async,
-            is_generator: method.is_generator,
-            kind: method.kind.clone(),
+            ..method.clone()
         };
         methods.insert(method.name.clone(), normalized_method);
     }
@@ -289,3 +313,267 @@ pub fn find_similar_classes_across_files(
 
     find_similar_classes(&all_classes, threshold)
 }
+
+/// Compare two method implementations with TSED
+///
+/// Returns 0.0 when either method has no comparison tree. Short methods get the same size
+/// penalty as short functions.
+pub fn compare_method_bodies(
+    method1: &ClassMethod,
+    method2: &ClassMethod,
+    options: &TSEDOptions,
+) -> f64 {
+    let (Some(body1), Some(body2)) = (&method1.body, &method2.body) else {
+        return 0.0;
+    };
+    let mut similarity = calculate_tsed(body1, body2, options);
+
+    if options.size_penalty {
+        let avg_lines = (method1.line_count() + method2.line_count()) as f64 / 2.0;
+        if avg_lines < 10.0 {
+            similarity *= avg_lines / 10.0;
+        }
+    }
+
+    similarity
+}
+
+/// Match the methods of two classes one-to-one, regardless of their names
+fn match_methods(
+    class1: &ClassDefinition,
+    class2: &ClassDefinition,
+    threshold: f64,
+    options: &TSEDOptions,
+) -> Vec<DuplicateMethod> {
+    let mut candidates = Vec::new();
+    for (i, method1) in class1.methods.iter().enumerate() {
+        for (j, method2) in class2.methods.iter().enumerate() {
+            let similarity = compare_method_bodies(method1, method2, options);
+            if similarity >= threshold {
+                candidates.push((i, j, similarity));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut used1 = vec![false; class1.methods.len()];
+    let mut used2 = vec![false; class2.methods.len()];
+    let mut matches = Vec::new();
+    for (i, j, similarity) in candidates {
+        if used1[i] || used2[j] {
+            continue;
+        }
+        used1[i] = true;
+        used2[j] = true;
+        matches.push(DuplicateMethod {
+            method1: class1.methods[i].clone(),
+            method2: class2.methods[j].clone(),
+            similarity,
+        });
+    }
+    matches
+}
+
+/// The classes `class` inherits from among `classes`, nearest first
+///
+/// Superclasses are looked up by name, preferring a class in the same file.
+fn ancestors<'a>(
+    class: &ClassDefinition,
+    classes: &'a [ClassDefinition],
+) -> Vec<&'a ClassDefinition> {
+    let mut chain: Vec<&ClassDefinition> = Vec::new();
+    let mut current_extends = class.extends.as_deref();
+    let mut current_file = class.file_path.as_str();
+
+    while let Some(name) = current_extends {
+        let candidates: Vec<&ClassDefinition> = classes.iter().filter(|c| c.name == name).collect();
+        let parent = candidates
+            .iter()
+            .find(|c| c.file_path == current_file)
+            .or_else(|| candidates.first())
+            .copied();
+        let Some(parent) = parent else { break };
+        // `class A extends A` or a cycle across files
+        if std::ptr::eq(parent, class) || chain.iter().any(|c| std::ptr::eq(*c, parent)) {
+            break;
+        }
+        chain.push(parent);
+        current_extends = parent.extends.as_deref();
+        current_file = parent.file_path.as_str();
+    }
+
+    chain
+}
+
+/// Find pairs of classes sharing at least `min_shared` near-identical methods
+///
+/// Classes related through `extends` are left to [`find_inherited_duplicates`].
+pub fn find_shared_methods(
+    classes: &[ClassDefinition],
+    threshold: f64,
+    min_shared: usize,
+    options: &TSEDOptions,
+) -> Vec<SharedMethodsPair> {
+    let mut pairs = Vec::new();
+
+    for i in 0..classes.len() {
+        let ancestors_i = ancestors(&classes[i], classes);
+        for j in i + 1..classes.len() {
+            let (class1, class2) = (&classes[i], &classes[j]);
+            let related = ancestors_i.iter().any(|c| std::ptr::eq(*c, class2))
+                || ancestors(class2, classes).iter().any(|c| std::ptr::eq(*c, class1));
+            if related {
+                continue;
+            }
+
+            let methods = match_methods(class1, class2, threshold, options);
+            if !methods.is_empty() && methods.len() >= min_shared {
+                pairs.push(SharedMethodsPair {
+                    class1: class1.clone(),
+                    class2: class2.clone(),
+                    methods,
+                });
+            }
+        }
+    }
+
+    pairs.sort_by_key(|pair| std::cmp::Reverse(pair.methods.len()));
+    pairs
+}
+
+/// Find methods that duplicate an implementation inherited from an ancestor class
+///
+/// Each method is compared against every method of its ancestors, so both redundant
+/// overrides and copies under a new name are found. The nearest, most similar ancestor
+/// method is reported.
+pub fn find_inherited_duplicates(
+    classes: &[ClassDefinition],
+    threshold: f64,
+    options: &TSEDOptions,
+) -> Vec<InheritedDuplicate> {
+    let mut duplicates = Vec::new();
+
+    for class in classes {
+        let ancestors = ancestors(class, classes);
+        for method in &class.methods {
+            let mut best: Option<(&ClassDefinition, &ClassMethod, f64)> = None;
+            for ancestor in &ancestors {
+                for inherited in &ancestor.methods {
+                    let similarity = compare_method_bodies(method, inherited, options);
+                    if similarity >= threshold && best.is_none_or(|(_, _, s)| similarity > s) {
+                        best = Some((ancestor, inherited, similarity));
+                    }
+                }
+            }
+
+            if let Some((ancestor, inherited, similarity)) = best {
+                duplicates.push(InheritedDuplicate {
+                    class: class.clone(),
+                    ancestor: ancestor.clone(),
+                    method: method.clone(),
+                    inherited: inherited.clone(),
+                    similarity,
+                });
+            }
+        }
+    }
+
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::class_extractor::extract_classes_with_method_bodies_from_code;
+
+    const SERVICES: &str = r#"
+class UserService {
+    async fetchAll(client: Client) {
+        const response = await client.get("/users");
+        if (!response.ok) {
+            throw new Error("Failed to load users");
+        }
+        const items = await response.json();
+        return items.map((item) => normalize(item));
+    }
+
+    describe(user: User) {
+        const parts = [user.firstName, user.lastName].filter(Boolean);
+        const label = parts.join(" ").trim();
+        if (label.length === 0) {
+            return "(unnamed)";
+        }
+        return label.toUpperCase();
+    }
+}
+
+class ProjectService {
+    async loadEverything(client: Client) {
+        const response = await client.get("/projects");
+        if (!response.ok) {
+            throw new Error("Failed to load projects");
+        }
+        const items = await response.json();
+        return items.map((item) => normalize(item));
+    }
+
+    describe(project: Project) {
+        const parts = [project.firstName, project.lastName].filter(Boolean);
+        const label = parts.join(" ").trim();
+        if (label.length === 0) {
+            return "(unnamed)";
+        }
+        return label.toUpperCase();
+    }
+}
+
+class AdminService extends UserService {
+    describe(admin: User) {
+        const parts = [admin.firstName, admin.lastName].filter(Boolean);
+        const label = parts.join(" ").trim();
+        if (label.length === 0) {
+            return "(unnamed)";
+        }
+        return label.toUpperCase();
+    }
+
+    promote(admin: User) {
+        return this.client.post(`/admins/${admin.id}`);
+    }
+}
+"#;
+
+    #[test]
+    fn test_find_shared_methods_across_unrelated_classes() {
+        let classes =
+            extract_classes_with_method_bodies_from_code(SERVICES, "services.ts").unwrap();
+        let pairs = find_shared_methods(&classes, 0.7, 2, &TSEDOptions::default());
+
+        let names: Vec<(&str, &str)> =
+            pairs.iter().map(|p| (p.class1.name.as_str(), p.class2.name.as_str())).collect();
+        assert_eq!(names, [("UserService", "ProjectService")]);
+
+        let methods: Vec<(&str, &str)> = pairs[0]
+            .methods
+            .iter()
+            .map(|m| (m.method1.name.as_str(), m.method2.name.as_str()))
+            .collect();
+        assert!(methods.contains(&("fetchAll", "loadEverything")));
+        assert!(methods.contains(&("describe", "describe")));
+    }
+
+    #[test]
+    fn test_find_inherited_duplicates() {
+        let classes =
+            extract_classes_with_method_bodies_from_code(SERVICES, "services.ts").unwrap();
+        let duplicates = find_inherited_duplicates(&classes, 0.7, &TSEDOptions::default());
+
+        assert_eq!(duplicates.len(), 1);
+        let duplicate = &duplicates[0];
+        assert_eq!(duplicate.class.name, "AdminService");
+        assert_eq!(duplicate.ancestor.name, "UserService");
+        assert_eq!(duplicate.method.name, "describe");
+        assert_eq!(duplicate.inherited.name, "describe");
+        assert_eq!(duplicate.method.start_line, 43);
+    }
+}
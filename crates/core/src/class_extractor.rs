@@ -1,7 +1,12 @@
 use oxc_allocator::Allocator;
 use oxc_ast::ast::{ClassElement, MethodDefinitionKind, Statement};
 use oxc_parser::Parser;
-use oxc_span::SourceType;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::parser::parse_and_convert_to_tree;
+use crate::sfc::script_source;
+use crate::tree::TreeNode;
 
 #[derive(Debug, Clone)]
 pub struct ClassDefinition {
@@ -37,6 +42,18 @@ pub struct ClassMethod {
     pub is_async: bool,
     pub is_generator: bool,
     pub kind: MethodKind,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Comparison tree of the method (name, parameters and body), used to compare
+    /// implementations rather than signatures; only filled by
+    /// `extract_classes_with_method_bodies`
+    pub body: Option<Rc<TreeNode>>,
+}
+
+impl ClassMethod {
+    pub fn line_count(&self) -> usize {
+        self.end_line - self.start_line + 1
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -257,7 +274,11 @@ impl ClassExtractor {
         param_strings.join(", ")
     }
 
-    fn extract_class(&self, class: &oxc_ast::ast::Class) -> ClassDefinition {
+    fn extract_class(
+        &self,
+        class: &oxc_ast::ast::Class,
+        method_trees: &HashMap<(u32, u32), Rc<TreeNode>>,
+    ) -> ClassDefinition {
         let name = class
             .id
             .as_ref()
@@ -378,6 +399,9 @@ impl ClassExtractor {
                             is_async: method.value.r#async,
                             is_generator: method.value.generator,
                             kind,
+                            start_line: self.get_line_number(method.span.start as usize),
+                            end_line: self.get_line_number(method.span.end as usize),
+                            body: method_trees.get(&(method.span.start, method.span.end)).cloned(),
                         });
                     }
                 }
@@ -400,9 +424,18 @@ impl ClassExtractor {
     }
 
     pub fn extract_classes(&self) -> Result<Vec<ClassDefinition>, String> {
+        self.extract(false)
+    }
+
+    /// Like `extract_classes`, with `ClassMethod::body` trees for comparing implementations
+    pub fn extract_classes_with_method_bodies(&self) -> Result<Vec<ClassDefinition>, String> {
+        self.extract(true)
+    }
+
+    fn extract(&self, method_bodies: bool) -> Result<Vec<ClassDefinition>, String> {
         let allocator = Allocator::default();
-        let source_type = SourceType::from_path(&self.file_path).unwrap_or(SourceType::tsx());
-        let ret = Parser::new(&allocator, &self.source_text, source_type).parse();
+        let (script, source_type) = script_source(&self.file_path, &self.source_text);
+        let ret = Parser::new(&allocator, &script, source_type).parse();
 
         if !ret.errors.is_empty() {
             let error_messages: Vec<String> =
@@ -410,6 +443,14 @@ impl ClassExtractor {
             return Err(format!("Parse errors: {}", error_messages.join(", ")));
         }
 
+        // Method trees come from converting the whole file, looked up by source span
+        let mut method_trees = HashMap::new();
+        if method_bodies {
+            if let Ok(tree) = parse_and_convert_to_tree(&self.file_path, &self.source_text) {
+                collect_method_trees(&tree, &mut method_trees);
+            }
+        }
+
         let mut classes = Vec::new();
 
         // Walk through all statements and find classes
@@ -419,18 +460,18 @@ impl ClassExtractor {
                     if let oxc_ast::ast::ExportDefaultDeclarationKind::ClassDeclaration(class) =
                         &export.declaration
                     {
-                        classes.push(self.extract_class(class));
+                        classes.push(self.extract_class(class, &method_trees));
                     }
                 }
                 Statement::ExportNamedDeclaration(export) => {
                     if let Some(oxc_ast::ast::Declaration::ClassDeclaration(class)) =
                         &export.declaration
                     {
-                        classes.push(self.extract_class(class));
+                        classes.push(self.extract_class(class, &method_trees));
                     }
                 }
                 Statement::ClassDeclaration(class) => {
-                    classes.push(self.extract_class(class));
+                    classes.push(self.extract_class(class, &method_trees));
                 }
                 _ => {}
             }
@@ -440,6 +481,15 @@ impl ClassExtractor {
     }
 }
 
+fn collect_method_trees(node: &Rc<TreeNode>, trees: &mut HashMap<(u32, u32), Rc<TreeNode>>) {
+    if let ("MethodDefinition", Some(span)) = (node.value.as_str(), node.span) {
+        trees.insert(span, node.clone());
+    }
+    for child in &node.children {
+        collect_method_trees(child, trees);
+    }
+}
+
 pub fn extract_classes_from_code(
     code: &str,
     file_path: &str,
@@ -448,6 +498,15 @@ pub fn extract_classes_from_code(
     extractor.extract_classes()
 }
 
+/// Extract classes together with the comparison trees of their methods
+pub fn extract_classes_with_method_bodies_from_code(
+    code: &str,
+    file_path: &str,
+) -> Result<Vec<ClassDefinition>, String> {
+    let extractor = ClassExtractor::new(code.to_string(), file_path.to_string());
+    extractor.extract_classes_with_method_bodies()
+}
+
 pub fn extract_classes_from_files(files: &[(String, String)]) -> Vec<ClassDefinition> {
     let mut all_classes = Vec::new();
 
@@ -0,0 +1,121 @@
+use crate::cli_output::extract_lines_from_content;
+use crate::structure_comparator::{
+    compute_structure_fingerprint, should_compare_fingerprints, MethodBodyComparison, Structure,
+    StructureDifferences,
+};
+use std::fs;
+use std::path::PathBuf;
+
+/// Classes, types and modules with fewer members are skipped: every marker interface, empty
+/// exception class or single-helper module would otherwise match every other
+pub const MIN_MEMBERS: usize = 2;
+
+/// Definitions extracted from a set of files, keeping each file's source for `--print`
+pub struct ExtractedDefinitions<T> {
+    pub definitions: Vec<T>,
+    contents: Vec<String>,
+    content_indices: Vec<usize>,
+}
+
+impl<T> ExtractedDefinitions<T> {
+    /// Read and extract the files one by one, skipping unreadable files and parse errors
+    ///
+    /// This runs on one thread: definitions keep method bodies as comparison trees, which can't
+    /// cross threads.
+    pub fn extract<E>(
+        files: &[PathBuf],
+        mut extract: impl FnMut(&str, &str) -> Result<Vec<T>, E>,
+    ) -> Self {
+        let mut extracted =
+            Self { definitions: Vec::new(), contents: Vec::new(), content_indices: Vec::new() };
+        for file in files {
+            let Ok(content) = fs::read_to_string(file) else { continue };
+            if let Ok(definitions) = extract(&content, &file.to_string_lossy()) {
+                extracted
+                    .content_indices
+                    .extend(definitions.iter().map(|_| extracted.contents.len()));
+                extracted.definitions.extend(definitions);
+            }
+            extracted.contents.push(content);
+        }
+        extracted
+    }
+
+    /// Print lines `start_line..=end_line` of the file the definition at `index` came from
+    pub fn print_code(&self, index: usize, title: &str, start_line: usize, end_line: usize) {
+        let content = &self.contents[self.content_indices[index]];
+        println!("\n\x1b[36m--- {title} ---\x1b[0m");
+        println!("{}", extract_lines_from_content(content, start_line as u32, end_line as u32));
+    }
+}
+
+/// Two definitions, by index, whose comparison reached the threshold
+pub struct SimilarPair<C> {
+    pub index1: usize,
+    pub index2: usize,
+    pub similarity: f64,
+    pub comparison: C,
+}
+
+/// Compare the pairs of definitions that `comparable` accepts and whose structure fingerprints are
+/// close, returning those at or above `threshold`, most similar first
+pub fn find_similar_pairs<T, C>(
+    definitions: &[T],
+    threshold: f64,
+    comparable: impl Fn(&T, &T) -> bool,
+    mut compare: impl FnMut(&T, &T) -> (f64, C),
+) -> Vec<SimilarPair<C>>
+where
+    T: Clone + Into<Structure>,
+{
+    let fingerprints: Vec<String> = definitions
+        .iter()
+        .map(|definition| compute_structure_fingerprint(&definition.clone().into()))
+        .collect();
+
+    let mut pairs = Vec::new();
+    for i in 0..definitions.len() {
+        for j in (i + 1)..definitions.len() {
+            let (definition1, definition2) = (&definitions[i], &definitions[j]);
+            if !comparable(definition1, definition2)
+                || !should_compare_fingerprints(&fingerprints[i], &fingerprints[j])
+            {
+                continue;
+            }
+
+            let (similarity, comparison) = compare(definition1, definition2);
+            if similarity >= threshold {
+                pairs.push(SimilarPair { index1: i, index2: j, similarity, comparison });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+    pairs
+}
+
+/// Print the similarity of a pair, split into members and method bodies when bodies were compared
+pub fn print_method_body_similarity(comparison: &MethodBodyComparison, members: &str) {
+    match comparison.method_body_similarity {
+        Some(body_similarity) => println!(
+            "\nSimilarity: {:.2}% ({} and signatures: {:.2}%, method bodies: {:.2}%)",
+            comparison.similarity * 100.0,
+            members,
+            comparison.structure.overall_similarity * 100.0,
+            body_similarity * 100.0
+        ),
+        None => println!("\nSimilarity: {:.2}%", comparison.similarity * 100.0),
+    }
+}
+
+/// Print the members found on one side only and the members typed differently
+pub fn print_differences(name1: &str, name2: &str, differences: &StructureDifferences) {
+    if !differences.missing_members.is_empty() {
+        println!("  only in {}: {}", name1, differences.missing_members.join(", "));
+    }
+    if !differences.extra_members.is_empty() {
+        println!("  only in {}: {}", name2, differences.extra_members.join(", "));
+    }
+    for (name, member_type1, member_type2) in &differences.type_mismatches {
+        println!("  {name}: {member_type1} vs {member_type2}");
+    }
+}
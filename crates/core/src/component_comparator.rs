@@ -0,0 +1,229 @@
+use crate::component_extractor::{ComponentDefinition, JsxSubtree};
+use crate::tsed::{calculate_tsed, TSEDOptions};
+
+#[derive(Debug, Clone)]
+pub struct SimilarComponentPair {
+    pub component1: ComponentDefinition,
+    pub component2: ComponentDefinition,
+    pub similarity: f64,
+    /// Hooks called by both components
+    pub shared_hooks: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateJsxPair {
+    pub jsx1: JsxSubtree,
+    pub jsx2: JsxSubtree,
+    pub similarity: f64,
+}
+
+/// Compare two components as a whole: props, hooks, handlers and rendered markup
+pub fn compare_components(
+    component1: &ComponentDefinition,
+    component2: &ComponentDefinition,
+    options: &TSEDOptions,
+) -> f64 {
+    let mut similarity = calculate_tsed(&component1.tree, &component2.tree, options);
+
+    // Same short-function penalty as function comparison
+    if options.size_penalty {
+        let avg_lines = (component1.line_count() + component2.line_count()) as f64 / 2.0;
+        if avg_lines < 10.0 {
+            similarity *= avg_lines / 10.0;
+        }
+    }
+
+    similarity
+}
+
+pub fn find_similar_components(
+    components: &[ComponentDefinition],
+    threshold: f64,
+    options: &TSEDOptions,
+) -> Vec<SimilarComponentPair> {
+    let mut similar_pairs = Vec::new();
+
+    for i in 0..components.len() {
+        for j in i + 1..components.len() {
+            let (component1, component2) = (&components[i], &components[j]);
+            let similarity = compare_components(component1, component2, options);
+            if similarity >= threshold {
+                let shared_hooks = component1
+                    .hooks
+                    .iter()
+                    .filter(|hook| component2.hooks.contains(hook))
+                    .fold(Vec::new(), |mut shared: Vec<String>, hook| {
+                        if !shared.contains(hook) {
+                            shared.push(hook.clone());
+                        }
+                        shared
+                    });
+                similar_pairs.push(SimilarComponentPair {
+                    component1: component1.clone(),
+                    component2: component2.clone(),
+                    similarity,
+                    shared_hooks,
+                });
+            }
+        }
+    }
+
+    similar_pairs.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+    similar_pairs
+}
+
+/// Find repeated JSX markup that could be extracted into a component
+///
+/// Only subtrees of at least `min_nodes` nodes are considered. Largest subtrees are matched
+/// first, and once a pair is reported the elements nested inside both sides are not reported
+/// again.
+pub fn find_duplicate_jsx(
+    components: &[ComponentDefinition],
+    threshold: f64,
+    min_nodes: usize,
+    options: &TSEDOptions,
+) -> Vec<DuplicateJsxPair> {
+    // `min_nodes` already keeps trivial markup out; the short-function penalty would mark
+    // every copy of a small element as different
+    let options = TSEDOptions { size_penalty: false, ..options.clone() };
+
+    let mut candidates: Vec<&JsxSubtree> = components
+        .iter()
+        .flat_map(|component| &component.jsx)
+        .filter(|jsx| jsx.tree.get_subtree_size() >= min_nodes)
+        .collect();
+    candidates.sort_by_key(|jsx| std::cmp::Reverse(jsx.tree.get_subtree_size()));
+
+    let mut duplicates: Vec<DuplicateJsxPair> = Vec::new();
+    for i in 0..candidates.len() {
+        for j in i + 1..candidates.len() {
+            let (jsx1, jsx2) = (candidates[i], candidates[j]);
+            if jsx1.contains(jsx2) || jsx2.contains(jsx1) {
+                continue;
+            }
+
+            // The edit distance is at least the size difference, so a pair this far apart in
+            // size can't reach the threshold
+            let size1 = jsx1.tree.get_subtree_size() as f64;
+            let size2 = jsx2.tree.get_subtree_size() as f64;
+            if 1.0 - (size1 - size2).abs() / size1.max(size2) < threshold {
+                continue;
+            }
+
+            let covered = duplicates.iter().any(|pair| {
+                (pair.jsx1.contains(jsx1) && pair.jsx2.contains(jsx2))
+                    || (pair.jsx1.contains(jsx2) && pair.jsx2.contains(jsx1))
+            });
+            if covered {
+                continue;
+            }
+
+            let similarity = calculate_tsed(&jsx1.tree, &jsx2.tree, &options);
+            if similarity >= threshold {
+                duplicates.push(DuplicateJsxPair {
+                    jsx1: jsx1.clone(),
+                    jsx2: jsx2.clone(),
+                    similarity,
+                });
+            }
+        }
+    }
+
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component_extractor::extract_components_from_code;
+
+    const CARDS: &str = r#"
+export function UserCard({ user, onSelect }: UserCardProps) {
+  const [expanded, setExpanded] = useState(false);
+  const theme = useTheme();
+  return (
+    <div className={theme.card} onClick={() => onSelect(user.id)}>
+      <img src={user.avatar} alt={user.name} />
+      <h2>{user.name}</h2>
+      <button onClick={() => setExpanded(!expanded)}>{expanded ? "Less" : "More"}</button>
+      {expanded && <p>{user.bio}</p>}
+    </div>
+  );
+}
+
+export function TeamCard({ team, onSelect }: TeamCardProps) {
+  const [open, setOpen] = useState(false);
+  const theme = useTheme();
+  return (
+    <div className={theme.card} onClick={() => onSelect(team.id)}>
+      <img src={team.logo} alt={team.title} />
+      <h2>{team.title}</h2>
+      <button onClick={() => setOpen(!open)}>{open ? "Less" : "More"}</button>
+      {open && <p>{team.description}</p>}
+    </div>
+  );
+}
+
+export function Settings({ settings }: SettingsProps) {
+  const { save, saving } = useSave();
+  useEffect(() => {
+    document.title = "Settings";
+  }, []);
+  return (
+    <form onSubmit={save}>
+      <label>
+        Email
+        <input name="email" defaultValue={settings.email} />
+      </label>
+      <footer>
+        <button type="submit" className="primary" disabled={saving}>Save</button>
+        <button type="reset" className="secondary">Reset</button>
+      </footer>
+    </form>
+  );
+}
+
+export function Profile({ profile }: ProfileProps) {
+  const { save, saving } = useSave();
+  const [tab, setTab] = useState("info");
+  if (!profile) return null;
+  return (
+    <section>
+      <nav>{TABS.map((t) => <a key={t} onClick={() => setTab(t)}>{t}</a>)}</nav>
+      <footer>
+        <button type="submit" className="primary" disabled={saving}>Save</button>
+        <button type="reset" className="secondary">Reset</button>
+      </footer>
+    </section>
+  );
+}
+"#;
+
+    #[test]
+    fn test_find_similar_components() {
+        let components = extract_components_from_code(CARDS, "cards.tsx").unwrap();
+        let options = TSEDOptions::default();
+
+        let pairs = find_similar_components(&components, 0.8, &options);
+        assert_eq!(pairs.len(), 1, "{:?}", pairs.iter().map(|p| p.similarity).collect::<Vec<_>>());
+        assert_eq!(pairs[0].component1.name, "UserCard");
+        assert_eq!(pairs[0].component2.name, "TeamCard");
+        assert_eq!(pairs[0].shared_hooks, ["useState", "useTheme"]);
+    }
+
+    #[test]
+    fn test_find_duplicate_jsx_reports_outermost_match() {
+        let components = extract_components_from_code(CARDS, "cards.tsx").unwrap();
+        let options = TSEDOptions::default();
+
+        let duplicates = find_duplicate_jsx(&components[2..], 0.8, 10, &options);
+        assert_eq!(duplicates.len(), 1);
+        let pair = &duplicates[0];
+        assert_eq!(pair.jsx1.tag, "footer");
+        assert_eq!(pair.jsx2.tag, "footer");
+        assert_eq!(
+            (pair.jsx1.component.as_str(), pair.jsx2.component.as_str()),
+            ("Settings", "Profile")
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::parser::parse_and_convert_to_tree;
+use crate::tree::TreeNode;
+use std::rc::Rc;
+
+/// A React function component: a capitalized function that renders JSX
+#[derive(Debug, Clone)]
+pub struct ComponentDefinition {
+    pub name: String,
+    pub file_path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    /// Hooks called by the component, in call order (`useState`, `React.useEffect` as `useEffect`)
+    pub hooks: Vec<String>,
+    /// Comparison tree of the component function, without `memo`/`forwardRef` wrappers
+    pub tree: Rc<TreeNode>,
+    /// Every JSX element and fragment rendered by the component, outermost first
+    pub jsx: Vec<JsxSubtree>,
+}
+
+impl ComponentDefinition {
+    pub fn line_count(&self) -> u32 {
+        self.end_line - self.start_line + 1
+    }
+}
+
+/// A JSX element or fragment inside a component
+#[derive(Debug, Clone)]
+pub struct JsxSubtree {
+    pub component: String,
+    pub file_path: String,
+    /// Element name (`div`, `Button`), or `<>` for fragments
+    pub tag: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    /// Byte range in the source file, used to tell nested subtrees apart
+    pub span: (u32, u32),
+    pub tree: Rc<TreeNode>,
+}
+
+impl JsxSubtree {
+    /// Whether `other` lies inside this subtree (or is this subtree)
+    pub fn contains(&self, other: &JsxSubtree) -> bool {
+        self.file_path == other.file_path
+            && self.span.0 <= other.span.0
+            && other.span.1 <= self.span.1
+    }
+}
+
+/// Wrappers whose function argument is the actual component
+const COMPONENT_WRAPPERS: &[&str] = &["memo", "forwardRef", "observer"];
+
+/// Extract React function components from TSX/JSX source
+pub fn extract_components_from_code(
+    source: &str,
+    file_path: &str,
+) -> Result<Vec<ComponentDefinition>, String> {
+    let tree = parse_and_convert_to_tree(file_path, source)?;
+    let line_starts = line_starts(source);
+
+    let mut components = Vec::new();
+    collect_components(&tree, &mut |name, function| {
+        if let Some(component) = build_component(name, function, file_path, &line_starts) {
+            components.push(component);
+        }
+    });
+    Ok(components)
+}
+
+fn collect_components(node: &Rc<TreeNode>, found: &mut impl FnMut(&str, &Rc<TreeNode>)) {
+    for child in &node.children {
+        match child.value.as_str() {
+            "FunctionDeclaration" if is_component_name(&child.label) => {
+                found(&child.label, child);
+                continue;
+            }
+            "VariableDeclarator" if is_component_name(&child.label) => {
+                if let Some(function) = child.children.last().and_then(unwrap_component) {
+                    found(&child.label, &function);
+                    continue;
+                }
+            }
+            "ExportDefaultDeclaration" => {
+                if let Some(function) = child.children.last().and_then(unwrap_component) {
+                    let name = match function.label.as_str() {
+                        "Function" | "ArrowFunction" => "default",
+                        name => name,
+                    };
+                    found(name, &function);
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        collect_components(child, found);
+    }
+}
+
+/// The component function behind an initializer: the function itself or the argument of
+/// `memo(...)`, `React.forwardRef(...)` and nestings of them
+fn unwrap_component(node: &Rc<TreeNode>) -> Option<Rc<TreeNode>> {
+    match node.value.as_str() {
+        "ArrowFunctionExpression" | "FunctionExpression" | "FunctionDeclaration" => {
+            Some(node.clone())
+        }
+        "CallExpression" => {
+            let callee = node.children.first()?;
+            let callee_name = match callee.value.as_str() {
+                "Identifier" => &callee.label,
+                "StaticMemberExpression" => &callee.children.last()?.label,
+                _ => return None,
+            };
+            if !COMPONENT_WRAPPERS.contains(&callee_name.as_str()) {
+                return None;
+            }
+            node.children[1..].iter().find_map(unwrap_component)
+        }
+        _ => None,
+    }
+}
+
+fn is_component_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+fn build_component(
+    name: &str,
+    function: &Rc<TreeNode>,
+    file_path: &str,
+    line_starts: &[u32],
+) -> Option<ComponentDefinition> {
+    let (start, end) = function.span?;
+
+    let mut jsx = Vec::new();
+    collect_jsx(function, name, file_path, line_starts, &mut jsx);
+    if jsx.is_empty() {
+        return None;
+    }
+
+    let mut hooks = Vec::new();
+    collect_hooks(function, &mut hooks);
+
+    Some(ComponentDefinition {
+        name: name.to_string(),
+        file_path: file_path.to_string(),
+        start_line: line_of(start, line_starts),
+        end_line: line_of(end, line_starts),
+        hooks,
+        tree: function.clone(),
+        jsx,
+    })
+}
+
+fn collect_jsx(
+    node: &Rc<TreeNode>,
+    component: &str,
+    file_path: &str,
+    line_starts: &[u32],
+    jsx: &mut Vec<JsxSubtree>,
+) {
+    if let ("JSXElement" | "JSXFragment", Some((start, end))) = (node.value.as_str(), node.span) {
+        let tag = match node.children.first() {
+            Some(name) if node.value == "JSXElement" => jsx_tag(name),
+            _ => "<>".to_string(),
+        };
+        jsx.push(JsxSubtree {
+            component: component.to_string(),
+            file_path: file_path.to_string(),
+            tag,
+            start_line: line_of(start, line_starts),
+            end_line: line_of(end, line_starts),
+            span: (start, end),
+            tree: node.clone(),
+        });
+    }
+    for child in &node.children {
+        collect_jsx(child, component, file_path, line_starts, jsx);
+    }
+}
+
+/// `Foo`, `Foo.Bar` or `svg:path` from the converted element name
+fn jsx_tag(name: &TreeNode) -> String {
+    match name.value.as_str() {
+        "JSXMemberExpression" | "JSXNamespacedName" => {
+            let separator = if name.value == "JSXNamespacedName" { ":" } else { "." };
+            name.children.iter().map(|part| jsx_tag(part)).collect::<Vec<_>>().join(separator)
+        }
+        _ => name.label.clone(),
+    }
+}
+
+fn collect_hooks(node: &TreeNode, hooks: &mut Vec<String>) {
+    if node.value == "CallExpression" {
+        let hook = node.children.first().and_then(|callee| match callee.value.as_str() {
+            "Identifier" => Some(callee.label.as_str()),
+            "StaticMemberExpression" => callee.children.last().map(|name| name.label.as_str()),
+            _ => None,
+        });
+        if let Some(hook) = hook.filter(|name| is_hook_name(name)) {
+            hooks.push(hook.to_string());
+        }
+    }
+    for child in &node.children {
+        collect_hooks(child, hooks);
+    }
+}
+
+/// React's rule of hooks naming: `use` followed by an uppercase letter
+fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use").is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+fn line_starts(source: &str) -> Vec<u32> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(offset, _)| offset as u32 + 1))
+        .collect()
+}
+
+fn line_of(offset: u32, line_starts: &[u32]) -> u32 {
+    line_starts.partition_point(|&start| start <= offset) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_function_arrow_and_wrapped_components() {
+        let code = r#"
+import React, { memo, useState } from "react";
+
+export function UserCard({ user }: Props) {
+  const [open, setOpen] = useState(false);
+  React.useEffect(() => {}, []);
+  return <div onClick={() => setOpen(!open)}><h2>{user.name}</h2></div>;
+}
+
+export const Badge = memo((props: BadgeProps) => <span>{props.label}</span>);
+
+const Field = React.forwardRef<HTMLInputElement, FieldProps>(function Field(props, ref) {
+  return <input ref={ref} {...props} />;
+});
+
+export default function () {
+  return <><UserCard user={me} /></>;
+}
+
+function formatName(user: User) {
+  return user.name;
+}
+
+const Config = { title: "x" };
+"#;
+        let components = extract_components_from_code(code, "components.tsx").unwrap();
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["UserCard", "Badge", "Field", "default"]);
+
+        let card = &components[0];
+        assert_eq!((card.start_line, card.end_line), (4, 8));
+        assert_eq!(card.hooks, ["useState", "useEffect"]);
+        let tags: Vec<&str> = card.jsx.iter().map(|j| j.tag.as_str()).collect();
+        assert_eq!(tags, ["div", "h2"]);
+        assert!(card.jsx[0].contains(&card.jsx[1]));
+        assert!(!card.jsx[1].contains(&card.jsx[0]));
+
+        assert_eq!(components[1].tree.value, "ArrowFunctionExpression");
+        assert_eq!(components[2].tree.value, "FunctionExpression");
+        assert_eq!(components[3].jsx[0].tag, "<>");
+    }
+
+    #[test]
+    fn test_hook_names() {
+        assert!(is_hook_name("useState"));
+        assert!(is_hook_name("useQuery2"));
+        assert!(!is_hook_name("user"));
+        assert!(!is_hook_name("use"));
+        assert!(!is_hook_name("useful"));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::structure_comparator::{
+    ComparisonOptions, SourceLocation, Structure, StructureComparator, StructureComparisonResult,
+    StructureIdentifier, StructureKind, StructureMember, StructureMetadata,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElixirModuleKind {
+    Module,
+    Protocol,
+    Implementation,
+}
+
+impl ElixirModuleKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Module => "module",
+            Self::Protocol => "protocol",
+            Self::Implementation => "implementation",
+        }
+    }
+}
+
+/// A `defmodule`, `defprotocol` or `defimpl` with what shapes its interface
+#[derive(Debug, Clone)]
+pub struct ElixirModuleDef {
+    /// Full name, nested modules included (`MyApp.Accounts.User`); the protocol for `defimpl`
+    pub name: String,
+    pub kind: ElixirModuleKind,
+    /// Type a `defimpl` is for (`for: User`)
+    pub for_type: Option<String>,
+    /// `use` arguments as written, e.g. `GenServer` or `MyAppWeb, :controller`
+    pub uses: Vec<String>,
+    /// Modules pulled in with `import`
+    pub imports: Vec<String>,
+    /// Modules named with `@behaviour`
+    pub behaviours: Vec<String>,
+    /// `defstruct` fields and Ecto `schema` fields and associations
+    pub fields: Vec<ElixirFieldDef>,
+    /// Public functions and macros, one per `name/arity`
+    pub functions: Vec<ElixirFunctionSig>,
+    /// `@callback` declarations of a behaviour
+    pub callbacks: Vec<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub file_path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ElixirFieldDef {
+    pub name: String,
+    /// Ecto type or association (`string`, `belongs_to`); absent for `defstruct` fields
+    pub field_type: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ElixirFunctionSig {
+    /// `handle_call/3`
+    pub name: String,
+    /// `def` or `defmacro`
+    pub kind: String,
+    /// Marked with `@impl`, implementing a behaviour callback
+    pub is_callback: bool,
+}
+
+impl From<ElixirModuleDef> for Structure {
+    fn from(module: ElixirModuleDef) -> Self {
+        let member = |name: String, value_type: &str, modifier: &str| StructureMember {
+            name,
+            value_type: value_type.to_string(),
+            modifiers: vec![modifier.to_string()],
+            nested: None,
+        };
+
+        let mut members = Vec::new();
+        members.extend(module.uses.iter().map(|name| member(format!("use {name}"), "use", "use")));
+        members.extend(
+            module.imports.iter().map(|name| member(format!("import {name}"), "import", "import")),
+        );
+        members.extend(
+            module
+                .behaviours
+                .iter()
+                .map(|name| member(format!("@behaviour {name}"), "behaviour", "behaviour")),
+        );
+        members.extend(module.fields.iter().map(|field| {
+            member(field.name.clone(), field.field_type.as_deref().unwrap_or("any"), "field")
+        }));
+        members.extend(module.functions.iter().map(|function| {
+            let value_type = if function.is_callback { "callback" } else { &function.kind };
+            member(function.name.clone(), value_type, "function")
+        }));
+        members.extend(
+            module.callbacks.iter().map(|name| member(name.clone(), "callback", "@callback")),
+        );
+
+        let kind = match module.kind {
+            ElixirModuleKind::Module => StructureKind::ElixirModule,
+            ElixirModuleKind::Protocol => StructureKind::ElixirProtocol,
+            ElixirModuleKind::Implementation => StructureKind::ElixirImplementation,
+        };
+
+        Structure {
+            identifier: StructureIdentifier { name: module.name, kind, namespace: None },
+            members,
+            metadata: StructureMetadata {
+                location: SourceLocation {
+                    file_path: module.file_path,
+                    start_line: module.start_line,
+                    end_line: module.end_line,
+                },
+                generics: Vec::new(),
+                extends: module.behaviours.into_iter().chain(module.for_type).collect(),
+                visibility: None,
+            },
+        }
+    }
+}
+
+/// Comparison engine for Elixir modules, protocols and protocol implementations
+pub struct ElixirStructureComparator {
+    pub comparator: StructureComparator,
+}
+
+impl Default for ElixirStructureComparator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ElixirStructureComparator {
+    pub fn new() -> Self {
+        // Copies across apps keep their members but not their names
+        // (`Billing.InvoiceController` and `Shop.OrderController`)
+        let options = ComparisonOptions {
+            name_weight: 0.1,
+            structure_weight: 0.9,
+            threshold: 0.7,
+            ..Default::default()
+        };
+        Self::with_options(options)
+    }
+
+    pub fn with_options(options: ComparisonOptions) -> Self {
+        Self { comparator: StructureComparator::new(options) }
+    }
+
+    /// Compare two modules by their `use`/`import` lists, behaviours, struct fields, public
+    /// functions and callbacks
+    pub fn compare_modules(
+        &mut self,
+        module1: &ElixirModuleDef,
+        module2: &ElixirModuleDef,
+    ) -> StructureComparisonResult {
+        self.comparator
+            .compare(&Structure::from(module1.clone()), &Structure::from(module2.clone()))
+    }
+}
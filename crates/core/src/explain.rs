@@ -0,0 +1,383 @@
+use crate::apted::{compute_edit_mapping, EditOperation};
+use crate::function_extractor::FunctionDefinition;
+use crate::parser::parse_and_convert_to_tree;
+use crate::sfc::snippet_file_name;
+use crate::tree::TreeNode;
+use crate::tsed::TSEDOptions;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+
+/// Kind of difference a hunk describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HunkKind {
+    /// An identifier (variable, parameter, function name) was renamed
+    Renamed,
+    /// A string, numeric or boolean literal changed
+    LiteralChanged,
+    /// A node kept its position but changed (e.g. a different operator)
+    Modified,
+    /// A subtree only exists in the second function
+    Inserted,
+    /// A subtree only exists in the first function
+    Deleted,
+}
+
+/// One difference between two functions, mapped back to source lines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHunk {
+    pub kind: HunkKind,
+    /// Node kind the hunk was derived from (e.g. `Identifier`, `ReturnStatement`)
+    pub node_kind: String,
+    /// Inclusive line range in the first file
+    pub old_lines: Option<(u32, u32)>,
+    /// Inclusive line range in the second file
+    pub new_lines: Option<(u32, u32)>,
+    pub old_text: Option<String>,
+    pub new_text: Option<String>,
+}
+
+/// Structural diff between two similar pieces of code
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructuralDiff {
+    pub hunks: Vec<DiffHunk>,
+    /// Number of aligned nodes that are identical
+    pub matched_nodes: usize,
+}
+
+impl StructuralDiff {
+    /// Distinct identifier renames, in order of first appearance
+    pub fn renames(&self) -> Vec<(String, String)> {
+        let mut renames: Vec<(String, String)> = Vec::new();
+        for hunk in self.hunks.iter().filter(|h| h.kind == HunkKind::Renamed) {
+            if let (Some(old), Some(new)) = (&hunk.old_text, &hunk.new_text) {
+                let pair = (old.clone(), new.clone());
+                if !renames.contains(&pair) {
+                    renames.push(pair);
+                }
+            }
+        }
+        renames
+    }
+}
+
+/// Source text a tree was parsed from, and the line its first byte sits on
+#[derive(Debug, Clone, Copy)]
+pub struct SourceRef<'a> {
+    pub text: &'a str,
+    pub first_line: u32,
+}
+
+impl SourceRef<'_> {
+    fn line_of(&self, offset: u32) -> u32 {
+        let end = (offset as usize).min(self.text.len());
+        self.first_line + self.text.as_bytes()[..end].iter().filter(|&&b| b == b'\n').count() as u32
+    }
+
+    fn lines_of(&self, node: &TreeNode) -> Option<(u32, u32)> {
+        node.span.map(|(start, end)| (self.line_of(start), self.line_of(end.saturating_sub(1))))
+    }
+
+    fn snippet(&self, node: &TreeNode) -> Option<String> {
+        let (start, end) = node.span?;
+        self.text.get(start as usize..end as usize).map(|s| s.trim().to_string())
+    }
+}
+
+/// Explain the differences between two trees as a list of hunks
+///
+/// Trees must carry spans relative to the given source texts; nodes without a span
+/// produce hunks without line information.
+pub fn explain_trees(
+    tree1: &Rc<TreeNode>,
+    tree2: &Rc<TreeNode>,
+    source1: SourceRef,
+    source2: SourceRef,
+    options: &TSEDOptions,
+) -> StructuralDiff {
+    let mut diff = StructuralDiff::default();
+
+    for operation in compute_edit_mapping(tree1, tree2, &options.apted_options) {
+        match operation {
+            EditOperation::Match(_, _) => diff.matched_nodes += 1,
+            EditOperation::Rename(node1, node2) => {
+                let kind = classify_rename(&node1, &node2);
+                let (old_text, new_text) = if node1.children.is_empty() && node2.children.is_empty()
+                {
+                    (Some(leaf_text(&node1, source1)), Some(leaf_text(&node2, source2)))
+                } else {
+                    (Some(node_name(&node1)), Some(node_name(&node2)))
+                };
+                diff.hunks.push(DiffHunk {
+                    kind,
+                    node_kind: node_kind(&node2).to_string(),
+                    old_lines: source1.lines_of(&node1).map(|(start, _)| (start, start)),
+                    new_lines: source2.lines_of(&node2).map(|(start, _)| (start, start)),
+                    old_text,
+                    new_text,
+                });
+            }
+            EditOperation::Delete(node) => diff.hunks.push(DiffHunk {
+                kind: HunkKind::Deleted,
+                node_kind: node_kind(&node).to_string(),
+                old_lines: source1.lines_of(&node),
+                new_lines: None,
+                old_text: source1.snippet(&node),
+                new_text: None,
+            }),
+            EditOperation::Insert(node) => diff.hunks.push(DiffHunk {
+                kind: HunkKind::Inserted,
+                node_kind: node_kind(&node).to_string(),
+                old_lines: None,
+                new_lines: source2.lines_of(&node),
+                old_text: None,
+                new_text: source2.snippet(&node),
+            }),
+        }
+    }
+
+    diff
+}
+
+/// Explain the differences between two TypeScript/JavaScript functions
+///
+/// # Errors
+///
+/// Returns an error if a function's body span is not in its source or the body fails to parse
+pub fn explain_functions(
+    func1: &FunctionDefinition,
+    func2: &FunctionDefinition,
+    file1: &str,
+    source1: &str,
+    file2: &str,
+    source2: &str,
+    options: &TSEDOptions,
+) -> Result<StructuralDiff, String> {
+    let body1 = function_body(func1, source1)?;
+    let body2 = function_body(func2, source2)?;
+
+    let tree1 = parse_and_convert_to_tree(&snippet_file_name(file1, source1), body1)?;
+    let tree2 = parse_and_convert_to_tree(&snippet_file_name(file2, source2), body2)?;
+
+    Ok(explain_trees(
+        &tree1,
+        &tree2,
+        SourceRef { text: body1, first_line: func1.start_line },
+        SourceRef { text: body2, first_line: func2.start_line },
+        options,
+    ))
+}
+
+pub(crate) fn function_body<'a>(
+    func: &FunctionDefinition,
+    source: &'a str,
+) -> Result<&'a str, String> {
+    source
+        .get(func.body_span.start as usize..func.body_span.end as usize)
+        .ok_or_else(|| format!("Body of {} is outside the given source", func.name))
+}
+
+/// Render hunks as a unified-style structural diff
+pub fn format_structural_diff(diff: &StructuralDiff, name1: &str, name2: &str) -> String {
+    let mut out = format!("--- {name1}\n+++ {name2}\n");
+
+    if diff.hunks.is_empty() {
+        out.push_str("  (structurally identical)\n");
+        return out;
+    }
+
+    for hunk in &diff.hunks {
+        let old_at = hunk.old_lines.map_or("?".to_string(), |(s, e)| format_range(s, e));
+        let new_at = hunk.new_lines.map_or("?".to_string(), |(s, e)| format_range(s, e));
+        match hunk.kind {
+            HunkKind::Renamed | HunkKind::LiteralChanged | HunkKind::Modified => {
+                let what = match hunk.kind {
+                    HunkKind::Renamed => "renamed",
+                    HunkKind::LiteralChanged => "literal changed",
+                    _ => "modified",
+                };
+                out.push_str(&format!(
+                    "@@ L{old_at} -> L{new_at} {what} ({}) @@\n  {} -> {}\n",
+                    hunk.node_kind,
+                    hunk.old_text.as_deref().unwrap_or(""),
+                    hunk.new_text.as_deref().unwrap_or("")
+                ));
+            }
+            HunkKind::Deleted => {
+                out.push_str(&format!("@@ -L{old_at} deleted ({}) @@\n", hunk.node_kind));
+                for line in hunk.old_text.as_deref().unwrap_or("").lines() {
+                    out.push_str(&format!("- {}\n", line.trim_end()));
+                }
+            }
+            HunkKind::Inserted => {
+                out.push_str(&format!("@@ +L{new_at} inserted ({}) @@\n", hunk.node_kind));
+                for line in hunk.new_text.as_deref().unwrap_or("").lines() {
+                    out.push_str(&format!("+ {}\n", line.trim_end()));
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn format_range(start: u32, end: u32) -> String {
+    if start == end {
+        start.to_string()
+    } else {
+        format!("{start}-{end}")
+    }
+}
+
+// The TypeScript converter stores the node kind in `value` (e.g. `ReturnStatement`),
+// tree-sitter converters store it in `label` and keep source text in `value`
+pub(crate) fn node_kind(node: &TreeNode) -> &str {
+    const KIND_SUFFIXES: &[&str] = &[
+        "Identifier",
+        "Parameter",
+        "Program",
+        "Statement",
+        "Expression",
+        "Literal",
+        "Declaration",
+        "Declarator",
+        "Definition",
+    ];
+    // Named TypeScript kinds without one of the suffixes above
+    const NAMED_KINDS: &[&str] = &[
+        "IdentifierName",
+        "Directive",
+        "JSXText",
+        "AccessorProperty",
+        "ObjectProperty",
+        "TSPropertySignature",
+        "TSMethodSignature",
+        "TSTypeParameter",
+        "TSEnumMember",
+        "TSTemplateLiteralType",
+    ];
+    if KIND_SUFFIXES.iter().any(|suffix| node.value.ends_with(suffix))
+        || NAMED_KINDS.contains(&node.value.as_str())
+    {
+        &node.value
+    } else {
+        &node.label
+    }
+}
+
+pub(crate) fn node_name(node: &TreeNode) -> String {
+    if node_kind(node) == node.value {
+        node.label.clone()
+    } else {
+        node.value.clone()
+    }
+}
+
+fn leaf_text(node: &TreeNode, source: SourceRef) -> String {
+    let name = node_name(node);
+    if name.is_empty() {
+        source.snippet(node).unwrap_or_default()
+    } else {
+        name
+    }
+}
+
+pub(crate) fn classify_rename(node1: &TreeNode, node2: &TreeNode) -> HunkKind {
+    let kind1 = node_kind(node1).to_lowercase();
+    let kind2 = node_kind(node2).to_lowercase();
+    if kind1 != kind2 {
+        return HunkKind::Modified;
+    }
+
+    const LITERAL_KINDS: &[&str] = &["string", "integer", "float", "true", "false", "none"];
+    const NAMED_KINDS: &[&str] =
+        &["identifier", "parameter", "declarator", "declaration", "definition"];
+
+    if kind1.contains("literal") || LITERAL_KINDS.contains(&kind1.as_str()) {
+        HunkKind::LiteralChanged
+    } else if NAMED_KINDS.iter().any(|k| kind1.contains(k)) {
+        HunkKind::Renamed
+    } else {
+        HunkKind::Modified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function_extractor::extract_functions;
+
+    fn explain_pair(code1: &str, code2: &str) -> StructuralDiff {
+        let func1 = extract_functions("a.ts", code1).unwrap().remove(0);
+        let func2 = extract_functions("b.ts", code2).unwrap().remove(0);
+        explain_functions(&func1, &func2, "a.ts", code1, "b.ts", code2, &TSEDOptions::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_identical_functions_have_no_hunks() {
+        let code = "function add(a: number, b: number) {\n  return a + b;\n}\n";
+        let diff = explain_pair(code, code);
+        assert!(diff.hunks.is_empty());
+        assert!(diff.matched_nodes > 0);
+    }
+
+    #[test]
+    fn test_renames_and_literals_are_reported_with_lines() {
+        let code1 =
+            "function load(user) {\n  const id = user;\n  return fetch(\"/users\", id);\n}\n";
+        let code2 =
+            "function load(account) {\n  const id = account;\n  return fetch(\"/accounts\", id);\n}\n";
+        let diff = explain_pair(code1, code2);
+
+        assert!(diff.renames().contains(&("user".to_string(), "account".to_string())));
+
+        let literal = diff.hunks.iter().find(|h| h.kind == HunkKind::LiteralChanged).unwrap();
+        assert_eq!(literal.old_lines, Some((3, 3)));
+        assert_eq!(literal.new_lines, Some((3, 3)));
+        assert_eq!(literal.new_text.as_deref(), Some("\"/accounts\""));
+    }
+
+    #[test]
+    fn test_inserted_statement_is_reported() {
+        let code1 = "function f(a) {\n  log(a);\n  return a;\n}\n";
+        let code2 = "function f(a) {\n  log(a);\n  if (a) {\n    save(a);\n  }\n  return a;\n}\n";
+        let diff = explain_pair(code1, code2);
+
+        let inserted = diff.hunks.iter().find(|h| h.kind == HunkKind::Inserted).unwrap();
+        assert_eq!(inserted.node_kind, "IfStatement");
+        assert_eq!(inserted.new_lines, Some((3, 5)));
+
+        let rendered = format_structural_diff(&diff, "a.ts:f", "b.ts:f");
+        assert!(rendered.contains("+ if (a) {"));
+    }
+
+    #[test]
+    fn test_jsx_bodies_are_parsed_as_jsx() {
+        let code1 = "function Item({ name }) {\n  return <li>{name}</li>;\n}\n";
+        let code2 = "function Row({ label }) {\n  return <li>{label}</li>;\n}\n";
+        let func1 = extract_functions("a.jsx", code1).unwrap().remove(0);
+        let func2 = extract_functions("b.jsx", code2).unwrap().remove(0);
+
+        let diff = explain_functions(
+            &func1,
+            &func2,
+            "a.jsx",
+            code1,
+            "b.jsx",
+            code2,
+            &TSEDOptions::default(),
+        )
+        .unwrap();
+        assert!(diff.renames().contains(&("name".to_string(), "label".to_string())));
+    }
+
+    #[test]
+    fn test_body_outside_source_is_an_error() {
+        let code = "function add(a: number, b: number) {\n  return a + b;\n}\n";
+        let func = extract_functions("a.ts", code).unwrap().remove(0);
+
+        let result =
+            explain_functions(&func, &func, "a.ts", code, "b.ts", "", &TSEDOptions::default());
+        assert!(result.is_err());
+    }
+}
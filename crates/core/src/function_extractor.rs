@@ -1,8 +1,11 @@
 use oxc_ast::ast::*;
-use oxc_span::Span;
+use oxc_span::{GetSpan, Span};
 
 use crate::parser::parse_and_convert_to_tree;
+use crate::test_detection::{test_call_kind, TestCallKind};
+use crate::tree::TreeNode;
 use crate::tsed::{calculate_tsed, TSEDOptions};
+use std::rc::Rc;
 
 type CrossFileSimilarityResult = Vec<(String, SimilarityResult, String)>;
 
@@ -33,6 +36,8 @@ pub struct FunctionDefinition {
     pub class_name: Option<String>,
     pub parent_function: Option<String>,
     pub node_count: Option<u32>,
+    /// Declared inside a test block (`describe`/`it`/`test` callback or hook), or is one
+    pub is_test: bool,
 }
 
 impl FunctionDefinition {
@@ -64,6 +69,8 @@ pub enum FunctionType {
     Method,
     Arrow,
     Constructor,
+    /// Callback of an `it`/`test` case or a hook, named after the test title
+    Test,
 }
 
 /// Extract all functions from TypeScript/JavaScript code
@@ -71,13 +78,13 @@ pub fn extract_functions(
     filename: &str,
     source_text: &str,
 ) -> Result<Vec<FunctionDefinition>, String> {
+    use crate::sfc::script_source;
     use oxc_allocator::Allocator;
     use oxc_parser::Parser;
-    use oxc_span::SourceType;
 
     let allocator = Allocator::default();
-    let source_type = SourceType::from_path(filename).unwrap_or(SourceType::tsx());
-    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let (script, source_type) = script_source(filename, source_text);
+    let ret = Parser::new(&allocator, &script, source_type).parse();
 
     if !ret.errors.is_empty() {
         // Create a more readable error message
@@ -92,6 +99,8 @@ pub fn extract_functions(
         source_text,
         class_name: None,
         parent_function: None,
+        test_suites: Vec::new(),
+        in_test: false,
     };
 
     extract_from_program(&ret.program, &mut context);
@@ -103,6 +112,9 @@ struct ExtractionContext<'a> {
     source_text: &'a str,
     class_name: Option<String>,
     parent_function: Option<String>,
+    /// Titles of the enclosing `describe` blocks
+    test_suites: Vec<String>,
+    in_test: bool,
 }
 
 fn extract_from_program(program: &Program, ctx: &mut ExtractionContext) {
@@ -127,6 +139,7 @@ fn extract_from_statement(stmt: &Statement, ctx: &mut ExtractionContext) {
                     class_name: None,
                     parent_function: ctx.parent_function.clone(),
                     node_count: count_function_nodes(func.span, ctx.source_text),
+                    is_test: ctx.in_test,
                 });
 
                 // Extract nested functions within the function body
@@ -174,6 +187,7 @@ fn extract_from_statement(stmt: &Statement, ctx: &mut ExtractionContext) {
                         class_name: class_name.clone(),
                         parent_function: ctx.parent_function.clone(),
                         node_count: count_function_nodes(method.span, ctx.source_text),
+                        is_test: ctx.in_test,
                     });
 
                     // Extract nested functions within method body
@@ -204,6 +218,7 @@ fn extract_from_statement(stmt: &Statement, ctx: &mut ExtractionContext) {
                             class_name: None,
                             parent_function: ctx.parent_function.clone(),
                             node_count: count_function_nodes(arrow.span, ctx.source_text),
+                            is_test: ctx.in_test,
                         });
 
                         // Extract nested functions within arrow function body
@@ -241,6 +256,7 @@ fn extract_from_statement(stmt: &Statement, ctx: &mut ExtractionContext) {
                     class_name: None,
                     parent_function: ctx.parent_function.clone(),
                     node_count: count_function_nodes(func.span, ctx.source_text),
+                    is_test: ctx.in_test,
                 });
 
                 // Extract nested functions within the function body
@@ -252,6 +268,11 @@ fn extract_from_statement(stmt: &Statement, ctx: &mut ExtractionContext) {
                 }
             }
         }
+        Statement::ExpressionStatement(statement) => {
+            if let Expression::CallExpression(call) = &statement.expression {
+                extract_test_call(call, ctx);
+            }
+        }
         _ => {}
     }
 }
@@ -272,6 +293,7 @@ fn extract_from_declaration(decl: &Declaration, ctx: &mut ExtractionContext) {
                     class_name: None,
                     parent_function: ctx.parent_function.clone(),
                     node_count: count_function_nodes(func.span, ctx.source_text),
+                    is_test: ctx.in_test,
                 });
 
                 // Extract nested functions within the function body
@@ -319,6 +341,7 @@ fn extract_from_declaration(decl: &Declaration, ctx: &mut ExtractionContext) {
                         class_name: class_name.clone(),
                         parent_function: ctx.parent_function.clone(),
                         node_count: count_function_nodes(method.span, ctx.source_text),
+                        is_test: ctx.in_test,
                     });
 
                     // Extract nested functions within method body
@@ -349,6 +372,7 @@ fn extract_from_declaration(decl: &Declaration, ctx: &mut ExtractionContext) {
                             class_name: None,
                             parent_function: ctx.parent_function.clone(),
                             node_count: count_function_nodes(arrow.span, ctx.source_text),
+                            is_test: ctx.in_test,
                         });
 
                         // Extract nested functions within arrow function body
@@ -366,6 +390,109 @@ fn extract_from_declaration(decl: &Declaration, ctx: &mut ExtractionContext) {
     }
 }
 
+/// `describe`/`it`/`test` calls and hooks: test cases and hooks become units named after the
+/// test title (`it: UserService > creates a user`), and suite callbacks are searched for nested
+/// tests and helpers
+fn extract_test_call(call: &CallExpression, ctx: &mut ExtractionContext) {
+    let Some((kind, call_name)) = test_callee(&call.callee) else {
+        return;
+    };
+    let callback = call.arguments.iter().find_map(|argument| match argument {
+        Argument::ArrowFunctionExpression(arrow) => {
+            Some((arrow.span, Some(&*arrow.body), &*arrow.params))
+        }
+        Argument::FunctionExpression(func) => {
+            Some((func.span, func.body.as_deref(), &*func.params))
+        }
+        _ => None,
+    });
+    let Some((span, body, params)) = callback else {
+        return;
+    };
+    let title = match kind {
+        TestCallKind::Hook => None,
+        _ => call.arguments.first().map(|argument| test_title(argument, ctx.source_text)),
+    };
+
+    let saved_parent = ctx.parent_function.clone();
+    let saved_in_test = ctx.in_test;
+    ctx.in_test = true;
+
+    if kind == TestCallKind::Suite {
+        ctx.test_suites.push(title.unwrap_or_default());
+        ctx.parent_function = Some(format!("{call_name}: {}", ctx.test_suites.join(" > ")));
+        if let Some(body) = body {
+            extract_from_function_body(body, ctx);
+        }
+        ctx.test_suites.pop();
+    } else {
+        let mut path = ctx.test_suites.clone();
+        path.extend(title);
+        let name =
+            if path.is_empty() { call_name } else { format!("{call_name}: {}", path.join(" > ")) };
+        ctx.functions.push(FunctionDefinition {
+            name: name.clone(),
+            function_type: FunctionType::Test,
+            parameters: extract_parameters(params),
+            body_span: span,
+            start_line: get_line_number(span.start, ctx.source_text),
+            end_line: get_line_number(span.end, ctx.source_text),
+            class_name: None,
+            parent_function: ctx.parent_function.clone(),
+            node_count: count_function_nodes(span, ctx.source_text),
+            is_test: true,
+        });
+
+        if let Some(body) = body {
+            ctx.parent_function = Some(name);
+            extract_from_function_body(body, ctx);
+        }
+    }
+
+    ctx.parent_function = saved_parent;
+    ctx.in_test = saved_in_test;
+}
+
+/// The kind and name of a test call's callee: `it`, `it.only`, `test.describe.serial` (Playwright)
+/// or `describe.each(table)`. The root identifier must itself be a test function name.
+fn test_callee(callee: &Expression) -> Option<(TestCallKind, String)> {
+    let mut names = Vec::new();
+    let mut current = callee;
+    loop {
+        match current {
+            Expression::Identifier(ident) => {
+                names.push(ident.name.as_str());
+                break;
+            }
+            Expression::StaticMemberExpression(member) => {
+                names.push(member.property.name.as_str());
+                current = &member.object;
+            }
+            Expression::CallExpression(call) => match &call.callee {
+                Expression::StaticMemberExpression(member) if member.property.name == "each" => {
+                    current = &call.callee;
+                }
+                _ => return None,
+            },
+            _ => return None,
+        }
+    }
+
+    test_call_kind(names.last()?)?;
+    names.iter().find_map(|name| test_call_kind(name).map(|kind| (kind, name.to_string())))
+}
+
+/// Title of a test: the string itself, or the source text of a computed title
+fn test_title(argument: &Argument, source_text: &str) -> String {
+    match argument {
+        Argument::StringLiteral(literal) => literal.value.to_string(),
+        argument => {
+            let span = argument.span();
+            source_text[span.start as usize..span.end as usize].trim_matches('`').to_string()
+        }
+    }
+}
+
 fn extract_parameters(params: &oxc_ast::ast::FormalParameters) -> Vec<String> {
     params
         .items
@@ -416,7 +543,18 @@ pub fn compare_functions(
     let tree1 = parse_and_convert_to_tree("func1.ts", &body1)?;
     let tree2 = parse_and_convert_to_tree("func2.ts", &body2)?;
 
-    let mut similarity = calculate_tsed(&tree1, &tree2, options);
+    Ok(compare_function_trees(func1, func2, &tree1, &tree2, options))
+}
+
+/// Compare two functions whose bodies have already been parsed into trees
+pub fn compare_function_trees(
+    func1: &FunctionDefinition,
+    func2: &FunctionDefinition,
+    tree1: &Rc<TreeNode>,
+    tree2: &Rc<TreeNode>,
+    options: &TSEDOptions,
+) -> f64 {
+    let mut similarity = calculate_tsed(tree1, tree2, options);
 
     // Apply size penalty for short functions if enabled
     if options.size_penalty {
@@ -428,7 +566,7 @@ pub fn compare_functions(
         }
     }
 
-    Ok(similarity)
+    similarity
 }
 
 fn extract_body_text(func: &FunctionDefinition, source: &str) -> String {
@@ -832,4 +970,55 @@ mod tests {
         });
         assert!(validate_check.is_some());
     }
+
+    #[test]
+    fn test_extract_test_blocks_as_named_units() {
+        let code = r#"
+import { describe, it, beforeEach, expect } from "vitest";
+
+function buildUser(name: string) {
+    return { name, active: true };
+}
+
+describe("UserService", () => {
+    let service: UserService;
+
+    beforeEach(() => {
+        service = new UserService();
+    });
+
+    const makeAdmin = () => ({ ...buildUser("root"), admin: true });
+
+    describe.each([["a"], ["b"]])("with role %s", (role) => {
+        it.only("creates a user", async () => {
+            expect(await service.create(role)).toBeDefined();
+        });
+    });
+
+    test(`deletes ${"users"}`, function () {
+        expect(service.delete(makeAdmin())).toBe(true);
+    });
+});
+
+expect(buildUser("x")).toEqual({ name: "x", active: true });
+"#;
+
+        let functions = extract_functions("user.test.ts", code).unwrap();
+        let units: Vec<(&str, &FunctionType, bool)> =
+            functions.iter().map(|f| (f.name.as_str(), &f.function_type, f.is_test)).collect();
+        assert_eq!(
+            units,
+            [
+                ("buildUser", &FunctionType::Function, false),
+                ("beforeEach: UserService", &FunctionType::Test, true),
+                ("makeAdmin", &FunctionType::Arrow, true),
+                ("it: UserService > with role %s > creates a user", &FunctionType::Test, true),
+                ("test: UserService > deletes ${\"users\"}", &FunctionType::Test, true),
+            ]
+        );
+
+        let create = &functions[3];
+        assert_eq!((create.start_line, create.end_line), (18, 20));
+        assert_eq!(create.parent_function.as_deref(), Some("describe: UserService > with role %s"));
+    }
 }
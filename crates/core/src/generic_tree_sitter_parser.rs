@@ -62,7 +62,8 @@ impl GenericTreeSitterParser {
             "".to_string()
         };
 
-        let mut tree_node = TreeNode::new(label, value, current_id);
+        let mut tree_node = TreeNode::new(label, value, current_id)
+            .with_span(node.start_byte() as u32, node.end_byte() as u32);
 
         for child in node.children(&mut node.walk()) {
             let child_node = self.convert_node(child, source, id_counter);
@@ -0,0 +1,204 @@
+use crate::apted::{compute_edit_mapping, APTEDOptions, EditOperation};
+use crate::explain::{function_body, node_kind, node_name};
+use crate::function_extractor::FunctionDefinition;
+use crate::parser::parse_and_convert_to_tree;
+use crate::sfc::snippet_file_name;
+use crate::tree::TreeNode;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A renamed identifier and how often the rename was observed
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentifierRename {
+    pub from: String,
+    pub to: String,
+    pub occurrences: usize,
+    /// Whether `from` always maps to `to` and `to` is only reached from `from`
+    pub consistent: bool,
+}
+
+/// Result of comparing identifier usage between two aligned trees
+#[derive(Debug, Clone, Default)]
+pub struct IdentifierMapping {
+    /// Identifier pairs whose names differ, in order of first appearance
+    pub renames: Vec<IdentifierRename>,
+    /// Aligned identifier occurrences with identical names
+    pub unchanged_occurrences: usize,
+    /// Aligned identifier occurrences taking part in a consistent mapping
+    pub consistent_occurrences: usize,
+    /// All aligned identifier occurrences
+    pub total_occurrences: usize,
+}
+
+impl IdentifierMapping {
+    /// Share of aligned identifier occurrences that follow a bijective mapping (1.0 if none)
+    pub fn consistency(&self) -> f64 {
+        if self.total_occurrences == 0 {
+            1.0
+        } else {
+            self.consistent_occurrences as f64 / self.total_occurrences as f64
+        }
+    }
+
+    /// True when every rename is part of a bijective mapping (a pure alpha-renaming)
+    pub fn is_bijective(&self) -> bool {
+        self.renames.iter().all(|r| r.consistent)
+    }
+
+    /// Consistent renames only, e.g. `user -> account`
+    pub fn consistent_renames(&self) -> impl Iterator<Item = &IdentifierRename> {
+        self.renames.iter().filter(|r| r.consistent)
+    }
+}
+
+fn is_identifier(node: &TreeNode) -> bool {
+    let kind = node_kind(node).to_lowercase();
+    (kind.contains("identifier") || kind.contains("parameter") || kind.contains("declarator"))
+        && !node_name(node).is_empty()
+}
+
+/// Detect identifier mappings between two trees from their edit mapping
+pub fn analyze_identifier_mapping(
+    tree1: &Rc<TreeNode>,
+    tree2: &Rc<TreeNode>,
+    options: &APTEDOptions,
+) -> IdentifierMapping {
+    identifier_mapping_from_operations(&compute_edit_mapping(tree1, tree2, options))
+}
+
+/// Detect identifier mappings in an already computed edit mapping
+pub fn identifier_mapping_from_operations(operations: &[EditOperation]) -> IdentifierMapping {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for operation in operations {
+        if let EditOperation::Match(node1, node2) | EditOperation::Rename(node1, node2) = operation
+        {
+            if is_identifier(node1) && is_identifier(node2) {
+                pairs.push((node_name(node1), node_name(node2)));
+            }
+        }
+    }
+
+    let mut forward: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut backward: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (from, to) in &pairs {
+        let targets = forward.entry(from.as_str()).or_default();
+        if !targets.contains(&to.as_str()) {
+            targets.push(to.as_str());
+        }
+        let sources = backward.entry(to.as_str()).or_default();
+        if !sources.contains(&from.as_str()) {
+            sources.push(from.as_str());
+        }
+    }
+    let is_consistent = |from: &str, to: &str| forward[from].len() == 1 && backward[to].len() == 1;
+
+    let mut mapping = IdentifierMapping { total_occurrences: pairs.len(), ..Default::default() };
+    for (from, to) in &pairs {
+        let consistent = is_consistent(from, to);
+        if consistent {
+            mapping.consistent_occurrences += 1;
+        }
+        if from == to {
+            mapping.unchanged_occurrences += 1;
+            continue;
+        }
+        match mapping.renames.iter_mut().find(|r| &r.from == from && &r.to == to) {
+            Some(rename) => rename.occurrences += 1,
+            None => mapping.renames.push(IdentifierRename {
+                from: from.clone(),
+                to: to.clone(),
+                occurrences: 1,
+                consistent,
+            }),
+        }
+    }
+
+    mapping
+}
+
+/// Detect identifier mappings between two TypeScript/JavaScript functions
+///
+/// # Errors
+///
+/// Returns an error if a function's body span is not in its source or the body fails to parse
+pub fn analyze_function_identifiers(
+    func1: &FunctionDefinition,
+    func2: &FunctionDefinition,
+    file1: &str,
+    source1: &str,
+    file2: &str,
+    source2: &str,
+    options: &APTEDOptions,
+) -> Result<IdentifierMapping, String> {
+    let body1 = function_body(func1, source1)?;
+    let body2 = function_body(func2, source2)?;
+    let tree1 = parse_and_convert_to_tree(&snippet_file_name(file1, source1), body1)?;
+    let tree2 = parse_and_convert_to_tree(&snippet_file_name(file2, source2), body2)?;
+    Ok(analyze_identifier_mapping(&tree1, &tree2, options))
+}
+
+/// Adjust a TSED similarity using identifier rename consistency
+///
+/// Consistent renames are refunded their rename cost, since they are what a shared helper
+/// would take as parameters. Inconsistent mappings (one name mapped to several) scale the
+/// similarity down by up to half, as they indicate unrelated code sharing a shape.
+#[allow(clippy::cast_precision_loss)]
+pub fn apply_rename_consistency(
+    similarity: f64,
+    mapping: &IdentifierMapping,
+    max_tree_size: usize,
+    options: &APTEDOptions,
+) -> f64 {
+    if mapping.total_occurrences == 0 || max_tree_size == 0 {
+        return similarity;
+    }
+
+    let consistent_renames: usize = mapping.consistent_renames().map(|r| r.occurrences).sum();
+    let refund = consistent_renames as f64 * options.rename_cost / max_tree_size as f64;
+    let penalty = 1.0 - (1.0 - mapping.consistency()) * 0.5;
+
+    ((similarity + refund).min(1.0) * penalty).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping_for(code1: &str, code2: &str) -> IdentifierMapping {
+        let tree1 = parse_and_convert_to_tree("a.ts", code1).unwrap();
+        let tree2 = parse_and_convert_to_tree("b.ts", code2).unwrap();
+        let options =
+            APTEDOptions { rename_cost: 0.3, compare_values: false, ..Default::default() };
+        analyze_identifier_mapping(&tree1, &tree2, &options)
+    }
+
+    #[test]
+    fn test_consistent_alpha_renaming() {
+        let mapping = mapping_for(
+            "function f(user, userId) { const x = save(user, userId); return user; }",
+            "function f(account, accountId) { const x = save(account, accountId); return account; }",
+        );
+
+        assert!(mapping.is_bijective());
+        assert!((mapping.consistency() - 1.0).abs() < f64::EPSILON);
+        let user = mapping.renames.iter().find(|r| r.from == "user").unwrap();
+        assert_eq!(user.to, "account");
+        assert_eq!(user.occurrences, 3);
+        assert!(mapping.renames.iter().any(|r| r.from == "userId" && r.to == "accountId"));
+    }
+
+    #[test]
+    fn test_inconsistent_mapping_is_penalized() {
+        let mapping = mapping_for(
+            "function f(a, b) { const x = save(a, a); return a; }",
+            "function f(a, b) { const x = save(a, b); return b; }",
+        );
+
+        assert!(!mapping.is_bijective());
+        assert!(mapping.consistency() < 1.0);
+
+        let options = APTEDOptions::default();
+        let adjusted = apply_rename_consistency(0.9, &mapping, 20, &options);
+        assert!(adjusted < 0.9);
+    }
+}
@@ -0,0 +1,392 @@
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Argument, CallExpression, ExportAllDeclaration, ExportNamedDeclaration, Expression,
+    ImportDeclaration, ImportExpression,
+};
+use oxc_ast_visit::{walk, Visit};
+use oxc_parser::Parser;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::sfc::script_source;
+
+/// Extensions tried when resolving an import without one, in order
+const RESOLVE_EXTENSIONS: &[&str] =
+    &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "vue", "svelte", "astro"];
+
+/// Module specifiers a file depends on: static imports, `export ... from` re-exports, dynamic
+/// `import("...")` and `require("...")` with a string argument
+pub fn extract_import_specifiers(file_path: &str, source: &str) -> Result<Vec<String>, String> {
+    let allocator = Allocator::default();
+    let (script, source_type) = script_source(file_path, source);
+    let ret = Parser::new(&allocator, &script, source_type).parse();
+    if !ret.errors.is_empty() {
+        let error_messages: Vec<String> =
+            ret.errors.iter().map(|e| e.message.to_string()).collect();
+        return Err(format!("Parse errors: {}", error_messages.join(", ")));
+    }
+
+    let mut collector = SpecifierCollector { specifiers: Vec::new() };
+    collector.visit_program(&ret.program);
+    Ok(collector.specifiers)
+}
+
+struct SpecifierCollector {
+    specifiers: Vec<String>,
+}
+
+impl<'a> Visit<'a> for SpecifierCollector {
+    fn visit_import_declaration(&mut self, it: &ImportDeclaration<'a>) {
+        self.specifiers.push(it.source.value.to_string());
+    }
+
+    fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+        if let Some(source) = &it.source {
+            self.specifiers.push(source.value.to_string());
+        }
+        walk::walk_export_named_declaration(self, it);
+    }
+
+    fn visit_export_all_declaration(&mut self, it: &ExportAllDeclaration<'a>) {
+        self.specifiers.push(it.source.value.to_string());
+    }
+
+    fn visit_import_expression(&mut self, it: &ImportExpression<'a>) {
+        if let Expression::StringLiteral(source) = &it.source {
+            self.specifiers.push(source.value.to_string());
+        }
+        walk::walk_import_expression(self, it);
+    }
+
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        if it.callee.is_specific_id("require") {
+            if let Some(Argument::StringLiteral(source)) = it.arguments.first() {
+                self.specifiers.push(source.value.to_string());
+            }
+        }
+        walk::walk_call_expression(self, it);
+    }
+}
+
+/// How the two files of a duplicate pair relate in the import graph
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportRelation {
+    /// Both sides of the pair are in the same file
+    pub same_file: bool,
+    /// Name of the package (nearest `package.json`) both files belong to
+    pub same_package: Option<String>,
+    pub first_imports_second: bool,
+    pub second_imports_first: bool,
+    /// Analyzed modules importing each file
+    pub dependents: (usize, usize),
+}
+
+impl ImportRelation {
+    pub fn imports_other(&self) -> bool {
+        self.first_imports_second || self.second_imports_first
+    }
+
+    /// Ranking weight for a duplicate pair: a copy is easiest to consolidate when one module
+    /// already imports the other, and cheaper to move within one package than across packages.
+    /// Copies with more dependents weigh more, growing with the logarithm of their count.
+    pub fn consolidation_weight(&self) -> f64 {
+        let mut weight = 1.0;
+        // Within one file there is nothing to move
+        if self.same_package.is_some() && !self.same_file {
+            weight += 0.25;
+        }
+        if self.imports_other() {
+            weight += 0.5;
+        }
+        let dependents = self.dependents.0 + self.dependents.1;
+        weight + 0.1 * ((dependents + 1) as f64).log2()
+    }
+}
+
+#[derive(Debug)]
+struct ModuleNode {
+    /// Directory of the nearest `package.json`
+    package_dir: Option<PathBuf>,
+    imports: HashSet<usize>,
+    dependents: usize,
+}
+
+/// Dependency graph between the analyzed TypeScript/JavaScript files
+///
+/// Relative specifiers are resolved like TypeScript's bundler resolution (extensions, `index`
+/// files, `.js` written for `.ts`); bare specifiers are resolved when they name a workspace
+/// package found in a `package.json` next to the analyzed files. Imports of anything outside
+/// the analyzed files are ignored.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    modules: Vec<ModuleNode>,
+    index: HashMap<PathBuf, usize>,
+    package_names: HashMap<PathBuf, String>,
+}
+
+impl ModuleGraph {
+    pub fn build<'a>(files: impl IntoIterator<Item = (&'a Path, &'a str)>) -> Self {
+        let mut graph = ModuleGraph::default();
+        let mut specifiers = Vec::new();
+        for (path, source) in files {
+            let key = module_key(path);
+            if graph.index.contains_key(&key) {
+                continue;
+            }
+            let package_dir = graph.find_package(&key);
+            graph.index.insert(key, graph.modules.len());
+            graph.modules.push(ModuleNode { package_dir, imports: HashSet::new(), dependents: 0 });
+            specifiers.push(
+                extract_import_specifiers(&path.to_string_lossy(), source).unwrap_or_default(),
+            );
+        }
+
+        let packages: HashMap<&str, &Path> =
+            graph.package_names.iter().map(|(dir, name)| (name.as_str(), dir.as_path())).collect();
+        let keys: Vec<&PathBuf> = {
+            let mut keys: Vec<(&PathBuf, &usize)> = graph.index.iter().collect();
+            keys.sort_by_key(|(_, &id)| id);
+            keys.into_iter().map(|(key, _)| key).collect()
+        };
+        let edges: Vec<HashSet<usize>> = keys
+            .iter()
+            .zip(&specifiers)
+            .map(|(key, specifiers)| {
+                specifiers
+                    .iter()
+                    .filter_map(|specifier| graph.resolve(key, specifier, &packages))
+                    .collect()
+            })
+            .collect();
+
+        for (id, imports) in edges.into_iter().enumerate() {
+            for &imported in &imports {
+                if imported != id {
+                    graph.modules[imported].dependents += 1;
+                }
+            }
+            graph.modules[id].imports = imports;
+        }
+        graph
+    }
+
+    fn id(&self, path: &Path) -> Option<usize> {
+        self.index.get(&module_key(path)).copied()
+    }
+
+    /// Whether `importer` imports `imported` directly
+    pub fn imports(&self, importer: &Path, imported: &Path) -> bool {
+        match (self.id(importer), self.id(imported)) {
+            (Some(importer), Some(imported)) => self.modules[importer].imports.contains(&imported),
+            _ => false,
+        }
+    }
+
+    /// Number of analyzed modules importing `path`
+    pub fn dependent_count(&self, path: &Path) -> usize {
+        self.id(path).map_or(0, |id| self.modules[id].dependents)
+    }
+
+    /// Name of the package `path` belongs to (its directory when the `package.json` has no name)
+    pub fn package_of(&self, path: &Path) -> Option<String> {
+        let dir = self.modules[self.id(path)?].package_dir.as_ref()?;
+        self.package_names.get(dir).cloned()
+    }
+
+    pub fn relation(&self, file1: &Path, file2: &Path) -> ImportRelation {
+        let package1 = self.package_of(file1);
+        ImportRelation {
+            same_file: self.id(file1).is_some() && self.id(file1) == self.id(file2),
+            same_package: package1
+                .filter(|package| self.package_of(file2).as_ref() == Some(package)),
+            first_imports_second: self.imports(file1, file2),
+            second_imports_first: self.imports(file2, file1),
+            dependents: (self.dependent_count(file1), self.dependent_count(file2)),
+        }
+    }
+
+    /// Directory of the nearest `package.json` above `key`, recording its name
+    fn find_package(&mut self, key: &Path) -> Option<PathBuf> {
+        for dir in key.ancestors().skip(1) {
+            if self.package_names.contains_key(dir) {
+                return Some(dir.to_path_buf());
+            }
+            let Ok(manifest) = fs::read_to_string(dir.join("package.json")) else {
+                continue;
+            };
+            let name = serde_json::from_str::<serde_json::Value>(&manifest)
+                .ok()
+                .and_then(|manifest| manifest.get("name")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| dir.to_string_lossy().to_string());
+            self.package_names.insert(dir.to_path_buf(), name);
+            return Some(dir.to_path_buf());
+        }
+        None
+    }
+
+    fn resolve(
+        &self,
+        importer: &Path,
+        specifier: &str,
+        packages: &HashMap<&str, &Path>,
+    ) -> Option<usize> {
+        if specifier.starts_with("./") || specifier.starts_with("../") {
+            let base = normalize(&importer.parent()?.join(specifier));
+            return self.resolve_file(&base);
+        }
+
+        // `@scope/name/sub/path` or `name/sub/path`
+        let mut parts = specifier.splitn(if specifier.starts_with('@') { 3 } else { 2 }, '/');
+        let name = if specifier.starts_with('@') {
+            format!("{}/{}", parts.next()?, parts.next()?)
+        } else {
+            parts.next()?.to_string()
+        };
+        let subpath = parts.next().unwrap_or("");
+        let package_dir = packages.get(name.as_str())?;
+
+        if subpath.is_empty() {
+            ["src/index", "index"]
+                .iter()
+                .find_map(|entry| self.resolve_file(&package_dir.join(entry)))
+        } else {
+            [package_dir.join(subpath), package_dir.join("src").join(subpath)]
+                .iter()
+                .find_map(|base| self.resolve_file(base))
+        }
+    }
+
+    /// The analyzed file `base` refers to: itself, with an extension added or swapped from
+    /// `.js` to `.ts`, or its `index` file
+    fn resolve_file(&self, base: &Path) -> Option<usize> {
+        if let Some(&id) = self.index.get(base) {
+            return Some(id);
+        }
+
+        let mut candidates = Vec::new();
+        if let Some(ext) = base.extension().and_then(|ext| ext.to_str()) {
+            let typescript: &[&str] = match ext {
+                "js" => &["ts", "tsx"],
+                "jsx" => &["tsx"],
+                "mjs" => &["mts"],
+                "cjs" => &["cts"],
+                _ => &[],
+            };
+            candidates.extend(typescript.iter().map(|ext| base.with_extension(ext)));
+        }
+        let base = base.to_string_lossy();
+        candidates
+            .extend(RESOLVE_EXTENSIONS.iter().map(|ext| PathBuf::from(format!("{base}.{ext}"))));
+        candidates.extend(
+            RESOLVE_EXTENSIONS.iter().map(|ext| Path::new(&*base).join(format!("index.{ext}"))),
+        );
+
+        candidates.iter().find_map(|candidate| self.index.get(candidate).copied())
+    }
+}
+
+/// Absolute, symlink-free path used to identify a module; lexically normalized when the file
+/// can't be canonicalized
+fn module_key(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| {
+        let absolute =
+            std::env::current_dir().map_or_else(|_| path.to_path_buf(), |dir| dir.join(path));
+        normalize(&absolute)
+    })
+}
+
+/// Resolve `.` and `..` components without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_import_specifiers() {
+        let source = r#"
+import { a } from "./a";
+import type { B } from "../types/b";
+export { c } from "./c.js";
+export * from "@app/shared";
+const lazy = () => import("./lazy");
+const legacy = require("./legacy");
+"#;
+        assert_eq!(
+            extract_import_specifiers("mod.ts", source).unwrap(),
+            ["./a", "../types/b", "./c.js", "@app/shared", "./lazy", "./legacy"]
+        );
+    }
+
+    #[test]
+    fn test_module_graph_resolves_relative_and_workspace_imports() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let files = [
+            ("packages/shared/package.json", r#"{ "name": "@app/shared" }"#),
+            ("packages/shared/src/index.ts", "export * from \"./format.js\";"),
+            ("packages/shared/src/format.ts", "export const format = (x: string) => x;"),
+            ("packages/web/package.json", r#"{ "name": "web" }"#),
+            (
+                "packages/web/src/page.ts",
+                "import { format } from \"@app/shared\";\nimport \"./util\";",
+            ),
+            ("packages/web/src/util/index.ts", "import \"@app/shared/format\";"),
+        ];
+        for (path, content) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let path = |name: &str| dir.join(name);
+        let sources: Vec<(PathBuf, &str)> = files
+            .iter()
+            .filter(|(name, _)| name.ends_with(".ts"))
+            .map(|(name, content)| (path(name), *content))
+            .collect();
+        let graph = ModuleGraph::build(sources.iter().map(|(p, s)| (p.as_path(), *s)));
+
+        let index = path("packages/shared/src/index.ts");
+        let format = path("packages/shared/src/format.ts");
+        let page = path("packages/web/src/page.ts");
+        let util = path("packages/web/src/util/index.ts");
+        assert!(graph.imports(&index, &format));
+        assert!(graph.imports(&page, &index));
+        assert!(graph.imports(&page, &util));
+        assert!(graph.imports(&util, &format));
+        assert!(!graph.imports(&format, &index));
+        assert_eq!(graph.dependent_count(&format), 2);
+        assert_eq!(graph.package_of(&format).as_deref(), Some("@app/shared"));
+
+        let relation = graph.relation(&util, &format);
+        assert_eq!(relation.same_package, None);
+        assert!(relation.first_imports_second && !relation.second_imports_first);
+        assert_eq!(relation.dependents, (1, 2));
+        assert_eq!(graph.relation(&index, &format).same_package.as_deref(), Some("@app/shared"));
+        assert!(
+            relation.consolidation_weight() > graph.relation(&page, &format).consolidation_weight()
+        );
+
+        // No package bonus within one file, and dependents add to the weight
+        let same_file = graph.relation(&format, &format);
+        assert!(same_file.same_file);
+        assert_eq!(same_file.dependents, (2, 2));
+        assert!((same_file.consolidation_weight() - (1.0 + 0.1 * 5f64.log2())).abs() < 1e-9);
+        let no_dependents = graph.relation(&page, &page);
+        assert_eq!(no_dependents.consolidation_weight(), 1.0);
+    }
+}
@@ -3,21 +3,32 @@
 pub mod apted;
 pub mod ast_exchange;
 pub mod ast_fingerprint;
+pub mod changed_lines;
 pub mod class_comparator;
 pub mod class_extractor;
+pub mod component_comparator;
+pub mod component_extractor;
 pub mod enhanced_similarity;
+pub mod explain;
 pub mod fast_similarity;
 pub mod function_extractor;
 pub mod generic_overlap_detector;
 pub mod generic_parser_config;
 pub mod generic_tree_sitter_parser;
+pub mod identifier_mapping;
+pub mod import_graph;
 pub mod language_parser;
+pub mod module_comparator;
 pub mod overlap_detector;
 pub mod parser;
+pub mod refactor_suggestion;
+pub mod sfc;
 pub mod subtree_fingerprint;
+pub mod test_detection;
 pub mod tree;
 pub mod tsed;
 pub mod type_comparator;
+pub mod type_derivation;
 pub mod type_extractor;
 pub mod type_fingerprint;
 pub mod type_normalizer;
@@ -25,22 +36,44 @@ pub mod unified_type_comparator;
 pub mod structure_comparator;
 pub mod typescript_structure_adapter;
 pub mod rust_structure_adapter;
+pub mod python_structure_adapter;
+pub mod php_structure_adapter;
+pub mod elixir_structure_adapter;
 pub mod css_structure_adapter;
 
 // CLI utilities
 pub mod cli_file_utils;
 pub mod cli_output;
 pub mod cli_parallel;
+pub mod cli_structures;
 
-pub use apted::{compute_edit_distance, APTEDOptions};
+pub use apted::{
+    compute_edit_distance, compute_edit_distance_with_mapping, compute_edit_mapping, APTEDOptions,
+    EditOperation,
+};
+pub use changed_lines::ChangedLines;
 pub use enhanced_similarity::{
     calculate_enhanced_similarity, calculate_semantic_similarity, EnhancedSimilarityOptions,
 };
+pub use explain::{
+    explain_functions, explain_trees, format_structural_diff, DiffHunk, HunkKind, SourceRef,
+    StructuralDiff,
+};
 pub use function_extractor::{
-    compare_functions, extract_functions, find_similar_functions_across_files,
-    find_similar_functions_in_file, FunctionDefinition, FunctionType, SimilarityResult,
+    compare_function_trees, compare_functions, extract_functions,
+    find_similar_functions_across_files, find_similar_functions_in_file, FunctionDefinition,
+    FunctionType, SimilarityResult,
+};
+pub use identifier_mapping::{
+    analyze_function_identifiers, analyze_identifier_mapping, apply_rename_consistency,
+    identifier_mapping_from_operations, IdentifierMapping, IdentifierRename,
 };
 pub use parser::{ast_to_tree_node, parse_and_convert_to_tree};
+pub use refactor_suggestion::{
+    dedent, format_suggestion, group_into_clusters, suggest_parameterized_extraction, CallSite,
+    CloneMember, ExtractionParameter, ParameterKind, RefactoringSuggestion, SuggestionLanguage,
+};
+pub use test_detection::{is_test_file, TestCallKind, TestCodeMode};
 pub use tree::TreeNode;
 pub use tsed::{calculate_tsed, calculate_tsed_from_code, TSEDOptions};
 
@@ -51,6 +84,10 @@ pub use type_comparator::{
     group_similar_types, MatchedProperty, SimilarTypePair, TypeComparisonOptions,
     TypeComparisonResult, TypeDifferences, TypeLiteralComparisonPair, TypeMismatch,
 };
+pub use type_derivation::{
+    detect_type_derivation, find_type_derivations, TypeDerivation, TypeDerivationOptions,
+    UtilityModifier,
+};
 pub use type_extractor::{
     extract_type_literals_from_code, extract_type_literals_from_files, extract_types_from_code,
     extract_types_from_files, PropertyDefinition, TypeDefinition, TypeKind, TypeLiteralContext,
@@ -58,7 +95,7 @@ pub use type_extractor::{
 };
 pub use type_normalizer::{
     calculate_property_similarity, calculate_type_similarity, find_property_matches,
-    normalize_type, NormalizationOptions, NormalizedType, PropertyMatch,
+    normalize_type, resolve_type_hierarchy, NormalizationOptions, NormalizedType, PropertyMatch,
 };
 pub use unified_type_comparator::{
     find_similar_unified_types, find_similar_unified_types_structured,
@@ -70,13 +107,28 @@ pub use structure_comparator::{
     Structure, StructureIdentifier, StructureKind, StructureMember, StructureMetadata,
     SourceLocation, StructureComparator, ComparisonOptions, StructureComparisonResult,
     MemberMatch, StructureDifferences, MemberComparisonStrategy, compute_structure_fingerprint,
-    should_compare_fingerprints,
+    should_compare_fingerprints, blend_method_bodies, MethodBodyComparison,
 };
 pub use typescript_structure_adapter::{
     TypeScriptStructureComparator, BatchComparator,
 };
 pub use rust_structure_adapter::{
     RustStructureComparator, RustStructDef, RustFieldDef, RustEnumDef, RustVariantDef, RustVariantType,
+    RustTypeDef,
+    compare_impl_blocks, RustImplComparison, RustImplDef, RustImplMethodDef, RustMethodMatch,
+};
+pub use python_structure_adapter::{
+    resolve_inherited_kinds, PythonClassComparison, PythonClassDef, PythonClassKind,
+    PythonFieldDef, PythonMethodDef, PythonStructureComparator,
+};
+pub use php_structure_adapter::{
+    compare_php_method_bodies, find_trait_candidates, PhpMethodDef, PhpPropertyDef,
+    PhpSharedMethod, PhpStructureComparator, PhpTraitCandidate, PhpTypeComparison, PhpTypeDef,
+    PhpTypeKind,
+};
+pub use elixir_structure_adapter::{
+    ElixirFieldDef, ElixirFunctionSig, ElixirModuleDef, ElixirModuleKind,
+    ElixirStructureComparator,
 };
 pub use css_structure_adapter::{
     CssStructureComparator, CssStructDef, CssBatchComparator,
@@ -109,14 +161,31 @@ pub use generic_overlap_detector::{
 
 // Class-related exports
 pub use class_comparator::{
-    compare_classes, find_similar_classes, find_similar_classes_across_files, normalize_class,
-    ClassComparisonResult, ClassDifferences, MethodMismatch, NormalizedClass, PropertyMismatch,
+    compare_classes, compare_method_bodies, find_inherited_duplicates, find_shared_methods,
+    find_similar_classes, find_similar_classes_across_files, normalize_class,
+    ClassComparisonResult, ClassDifferences, DuplicateMethod, InheritedDuplicate, MethodMismatch,
+    NormalizedClass, PropertyMismatch, SharedMethodsPair,
     SimilarClassPair,
 };
 pub use class_extractor::{
-    extract_classes_from_code, extract_classes_from_files, ClassDefinition, ClassMethod,
+    extract_classes_from_code, extract_classes_from_files,
+    extract_classes_with_method_bodies_from_code, ClassDefinition, ClassMethod,
     ClassProperty, MethodKind,
 };
 
+// React component exports
+pub use component_comparator::{
+    compare_components, find_duplicate_jsx, find_similar_components, DuplicateJsxPair,
+    SimilarComponentPair,
+};
+pub use component_extractor::{extract_components_from_code, ComponentDefinition, JsxSubtree};
+
+// File-level exports
+pub use module_comparator::{
+    compare_modules, extract_module, find_similar_modules, ModuleComparisonOptions,
+    ModuleDefinition, SimilarModulePair,
+};
+pub use import_graph::{extract_import_specifiers, ImportRelation, ModuleGraph};
+
 #[cfg(test)]
 mod structure_comparator_tests;
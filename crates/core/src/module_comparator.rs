@@ -0,0 +1,396 @@
+use crate::function_extractor::{
+    compare_functions, extract_functions, FunctionDefinition, SimilarityResult,
+};
+use crate::parser::parse_and_convert_to_tree;
+use crate::tree::TreeNode;
+use crate::tsed::{calculate_tsed, TSEDOptions};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// A whole TypeScript/JavaScript file, compared as one unit
+#[derive(Debug, Clone)]
+pub struct ModuleDefinition {
+    pub file_path: String,
+    pub source: String,
+    pub line_count: u32,
+    /// Comparison tree of the full program
+    pub tree: Rc<TreeNode>,
+    /// Node count per kind (`TreeNode::value`), a fingerprint for skipping hopeless pairs
+    /// before the tree edit distance
+    pub node_kinds: HashMap<String, usize>,
+    /// Module specifiers of imports and `export ... from` re-exports
+    pub imports: Vec<String>,
+    /// Exported names: `default` for the default export, `* from "./x"` for star re-exports
+    pub exports: Vec<String>,
+    pub functions: Vec<FunctionDefinition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimilarModulePair {
+    pub file1: String,
+    pub file2: String,
+    /// Weighted combination of the tree, import and export similarities
+    pub similarity: f64,
+    pub tree_similarity: f64,
+    pub import_similarity: f64,
+    pub export_similarity: f64,
+    /// Share of the functions in both files that have a near-identical counterpart in the other
+    pub shared_functions: f64,
+    pub function_pairs: Vec<SimilarityResult>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleComparisonOptions {
+    pub tree_weight: f64,
+    pub import_weight: f64,
+    pub export_weight: f64,
+    /// Files with a larger tree are skipped: a full-program edit distance is quadratic in memory
+    pub max_tree_nodes: usize,
+    pub tsed_options: TSEDOptions,
+}
+
+impl Default for ModuleComparisonOptions {
+    fn default() -> Self {
+        Self {
+            tree_weight: 0.8,
+            import_weight: 0.1,
+            export_weight: 0.1,
+            max_tree_nodes: 5000,
+            tsed_options: TSEDOptions::default(),
+        }
+    }
+}
+
+impl ModuleComparisonOptions {
+    pub fn is_comparable(&self, module: &ModuleDefinition) -> bool {
+        module.tree.get_subtree_size() <= self.max_tree_nodes
+            && module.line_count >= self.tsed_options.min_lines
+    }
+}
+
+/// Parse a file into a module definition
+pub fn extract_module(file_path: &str, source: &str) -> Result<ModuleDefinition, String> {
+    let tree = parse_and_convert_to_tree(file_path, source)?;
+    let functions = extract_functions(file_path, source)?;
+
+    let mut imports = Vec::new();
+    let mut exports = Vec::new();
+    for statement in &tree.children {
+        let specifier = statement
+            .children
+            .last()
+            .filter(|child| child.value == "StringLiteral")
+            .map(|literal| literal.label.trim_matches(|c| c == '"' || c == '\'').to_string());
+
+        match statement.value.as_str() {
+            "ImportDeclaration" => imports.extend(specifier),
+            "ExportAllDeclaration" => {
+                if let Some(specifier) = specifier {
+                    exports.push(format!("* from \"{specifier}\""));
+                    imports.push(specifier);
+                }
+            }
+            "ExportDefaultDeclaration" => exports.push("default".to_string()),
+            "ExportNamedDeclaration" => {
+                for child in &statement.children {
+                    match child.value.as_str() {
+                        // `export { a as b }`: the last name is the exported one
+                        "ExportSpecifier" => {
+                            exports.extend(child.children.last().map(|name| name.label.clone()))
+                        }
+                        "VariableDeclaration" => exports.extend(
+                            child
+                                .children
+                                .iter()
+                                .filter(|declarator| declarator.value == "VariableDeclarator")
+                                .map(|declarator| declarator.label.clone()),
+                        ),
+                        "StringLiteral" => {}
+                        _ => exports.push(child.label.clone()),
+                    }
+                }
+                imports.extend(specifier);
+            }
+            _ => {}
+        }
+    }
+
+    let mut node_kinds = HashMap::new();
+    count_node_kinds(&tree, &mut node_kinds);
+
+    Ok(ModuleDefinition {
+        file_path: file_path.to_string(),
+        source: source.to_string(),
+        line_count: source.lines().count() as u32,
+        tree,
+        node_kinds,
+        imports,
+        exports,
+        functions,
+    })
+}
+
+fn count_node_kinds(node: &TreeNode, counts: &mut HashMap<String, usize>) {
+    *counts.entry(node.value.clone()).or_insert(0) += 1;
+    for child in &node.children {
+        count_node_kinds(child, counts);
+    }
+}
+
+/// Upper bound of the tree similarity from the node kind counts alone
+///
+/// Nodes of a kind one file has more of than the other cannot be matched to a node of the same
+/// kind, so each of them is at least deleted, inserted or relabelled.
+fn tree_similarity_bound(
+    module1: &ModuleDefinition,
+    module2: &ModuleDefinition,
+    options: &TSEDOptions,
+) -> f64 {
+    let shared: usize = module1
+        .node_kinds
+        .iter()
+        .map(|(kind, &count)| count.min(module2.node_kinds.get(kind).copied().unwrap_or(0)))
+        .sum();
+    let size1 = module1.tree.get_subtree_size();
+    let size2 = module2.tree.get_subtree_size();
+    let max_size = size1.max(size2);
+    if max_size == 0 {
+        return 1.0;
+    }
+
+    let apted = &options.apted_options;
+    let mut node_cost = apted.delete_cost.min(apted.insert_cost);
+    if apted.compare_values {
+        // Relabelling within a kind is charged as a plain rename
+        node_cost = node_cost.min(apted.rename_cost);
+    }
+    (1.0 - (max_size - shared) as f64 * node_cost / max_size as f64).max(0.0)
+}
+
+/// Jaccard similarity of two name sets; two empty sets are identical
+fn set_similarity(names1: &[String], names2: &[String]) -> f64 {
+    let set1: HashSet<&String> = names1.iter().collect();
+    let set2: HashSet<&String> = names2.iter().collect();
+    let union = set1.union(&set2).count();
+    if union == 0 {
+        return 1.0;
+    }
+    set1.intersection(&set2).count() as f64 / union as f64
+}
+
+/// Compare two files by their full-program trees and import/export sets
+///
+/// Returns `(similarity, tree_similarity, import_similarity, export_similarity)`.
+pub fn compare_modules(
+    module1: &ModuleDefinition,
+    module2: &ModuleDefinition,
+    options: &ModuleComparisonOptions,
+) -> (f64, f64, f64, f64) {
+    // Whole files are never "short functions"
+    let tsed_options = TSEDOptions { size_penalty: false, ..options.tsed_options.clone() };
+    let tree_similarity = calculate_tsed(&module1.tree, &module2.tree, &tsed_options);
+    let import_similarity = set_similarity(&module1.imports, &module2.imports);
+    let export_similarity = set_similarity(&module1.exports, &module2.exports);
+
+    let total_weight = options.tree_weight + options.import_weight + options.export_weight;
+    let similarity = (tree_similarity * options.tree_weight
+        + import_similarity * options.import_weight
+        + export_similarity * options.export_weight)
+        / total_weight;
+
+    (similarity, tree_similarity, import_similarity, export_similarity)
+}
+
+/// Near-identical function pairs between two files, and the share of functions covered
+fn shared_functions(
+    module1: &ModuleDefinition,
+    module2: &ModuleDefinition,
+    threshold: f64,
+    options: &TSEDOptions,
+) -> (f64, Vec<SimilarityResult>) {
+    let long_enough = |func: &&FunctionDefinition| func.line_count() >= options.min_lines;
+    let functions1: Vec<&FunctionDefinition> =
+        module1.functions.iter().filter(long_enough).collect();
+    let functions2: Vec<&FunctionDefinition> =
+        module2.functions.iter().filter(long_enough).collect();
+    if functions1.is_empty() && functions2.is_empty() {
+        return (0.0, Vec::new());
+    }
+
+    let mut matched1 = vec![false; functions1.len()];
+    let mut matched2 = vec![false; functions2.len()];
+    let mut pairs = Vec::new();
+    for (i, func1) in functions1.iter().enumerate() {
+        for (j, func2) in functions2.iter().enumerate() {
+            let Ok(similarity) =
+                compare_functions(func1, func2, &module1.source, &module2.source, options)
+            else {
+                continue;
+            };
+            if similarity >= threshold {
+                matched1[i] = true;
+                matched2[j] = true;
+                pairs.push(SimilarityResult::new((*func1).clone(), (*func2).clone(), similarity));
+            }
+        }
+    }
+
+    let matched = matched1.iter().chain(&matched2).filter(|&&matched| matched).count();
+    (matched as f64 / (functions1.len() + functions2.len()) as f64, pairs)
+}
+
+/// Find pairs of near-identical files
+///
+/// Files over `max_tree_nodes` or under `min_lines` are skipped. For each reported pair the
+/// near-identical functions between the two files are listed as well.
+pub fn find_similar_modules(
+    modules: &[ModuleDefinition],
+    threshold: f64,
+    options: &ModuleComparisonOptions,
+) -> Vec<SimilarModulePair> {
+    let comparable: Vec<&ModuleDefinition> =
+        modules.iter().filter(|module| options.is_comparable(module)).collect();
+    let total_weight = options.tree_weight + options.import_weight + options.export_weight;
+
+    let mut pairs = Vec::new();
+    for i in 0..comparable.len() {
+        for j in i + 1..comparable.len() {
+            let (module1, module2) = (comparable[i], comparable[j]);
+
+            // Bound the best score from the node kind counts and the cheap set similarities
+            // before running APTED on the full programs
+            let tree_bound = tree_similarity_bound(module1, module2, &options.tsed_options);
+            let bound = (tree_bound * options.tree_weight
+                + set_similarity(&module1.imports, &module2.imports) * options.import_weight
+                + set_similarity(&module1.exports, &module2.exports) * options.export_weight)
+                / total_weight;
+            if bound < threshold {
+                continue;
+            }
+
+            let (similarity, tree_similarity, import_similarity, export_similarity) =
+                compare_modules(module1, module2, options);
+            if similarity < threshold {
+                continue;
+            }
+
+            let (shared_functions, function_pairs) =
+                shared_functions(module1, module2, threshold, &options.tsed_options);
+            pairs.push(SimilarModulePair {
+                file1: module1.file_path.clone(),
+                file2: module2.file_path.clone(),
+                similarity,
+                tree_similarity,
+                import_similarity,
+                export_similarity,
+                shared_functions,
+                function_pairs,
+            });
+        }
+    }
+
+    pairs.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: &str = r#"
+import axios from "axios";
+import { Config } from "./config";
+
+export async function fetchUsers(config: Config) {
+    const response = await axios.get(`${config.baseUrl}/users`);
+    if (response.status !== 200) {
+        throw new Error("Failed to fetch users");
+    }
+    return response.data;
+}
+
+export async function fetchUser(config: Config, id: string) {
+    const response = await axios.get(`${config.baseUrl}/users/${id}`);
+    if (response.status !== 200) {
+        throw new Error("Failed to fetch user");
+    }
+    return response.data;
+}
+
+export default { fetchUsers, fetchUser };
+"#;
+
+    #[test]
+    fn test_extract_module_imports_and_exports() {
+        let source = r#"
+import { a } from "./a";
+import * as lib from 'lib';
+export { a as b } from "./c";
+export * from "./d";
+export const x = 1, y = 2;
+export function f() {}
+export interface Shape {}
+export default class Foo {}
+"#;
+        let module = extract_module("mod.ts", source).unwrap();
+        assert_eq!(module.imports, ["./a", "lib", "./c", "./d"]);
+        assert_eq!(module.exports, ["b", "* from \"./d\"", "x", "y", "f", "Shape", "default"]);
+    }
+
+    #[test]
+    fn test_find_similar_modules() {
+        let copy = CLIENT
+            .replace("Failed to fetch users", "Could not load users")
+            .replace("Failed to fetch user\"", "Could not load user\"");
+        let other = r#"
+import { readFile } from "fs/promises";
+
+export function parseConfig(text: string) {
+    const lines = text.split("\n").filter((line) => !line.startsWith(";"));
+    return Object.fromEntries(lines.map((line) => line.split("=")));
+}
+
+export async function loadConfig(path: string) {
+    return parseConfig(await readFile(path, "utf8"));
+}
+"#;
+        let modules = vec![
+            extract_module("a/client.ts", CLIENT).unwrap(),
+            extract_module("b/client.ts", &copy).unwrap(),
+            extract_module("config.ts", other).unwrap(),
+        ];
+        // The fixture functions are short enough for the short-function penalty to hide them
+        let mut options = ModuleComparisonOptions::default();
+        options.tsed_options.size_penalty = false;
+
+        let pairs = find_similar_modules(&modules, 0.8, &options);
+        assert_eq!(pairs.len(), 1);
+        let pair = &pairs[0];
+        assert_eq!((pair.file1.as_str(), pair.file2.as_str()), ("a/client.ts", "b/client.ts"));
+        assert_eq!(pair.import_similarity, 1.0);
+        assert_eq!(pair.export_similarity, 1.0);
+        assert!(pair.tree_similarity > 0.9);
+        assert_eq!(pair.shared_functions, 1.0);
+        assert!(pair.function_pairs.len() >= 2);
+    }
+
+    #[test]
+    fn test_tree_similarity_bound_never_undercuts_apted() {
+        let options = ModuleComparisonOptions::default();
+        let tsed_options = TSEDOptions { size_penalty: false, ..options.tsed_options.clone() };
+        let client = extract_module("a/client.ts", CLIENT).unwrap();
+        let renamed =
+            extract_module("b/client.ts", &CLIENT.replace("fetchUser", "loadUser")).unwrap();
+        let first_function = &CLIENT[..CLIENT.find("export async function fetchUser(").unwrap()];
+        let trimmed = extract_module("c/client.ts", first_function).unwrap();
+
+        assert_eq!(tree_similarity_bound(&client, &client, &tsed_options), 1.0);
+        for other in [&renamed, &trimmed] {
+            let bound = tree_similarity_bound(&client, other, &tsed_options);
+            let actual = calculate_tsed(&client.tree, &other.tree, &tsed_options);
+            assert!(bound >= actual, "{} < {}", bound, actual);
+        }
+        // A file cut to one function cannot score above the share of node kinds it keeps
+        assert!(tree_similarity_bound(&client, &trimmed, &tsed_options) < 0.6);
+    }
+}
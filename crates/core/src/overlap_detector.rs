@@ -1,6 +1,7 @@
 use crate::{
     function_extractor::{extract_functions, FunctionDefinition},
     parser::parse_and_convert_to_tree,
+    sfc::snippet_file_name,
     subtree_fingerprint::{
         detect_partial_overlaps, generate_subtree_fingerprints, IndexedFunction, OverlapOptions,
         PartialOverlap,
@@ -14,9 +15,20 @@ pub fn find_function_overlaps(
     source_code: &str,
     target_code: &str,
     options: &OverlapOptions,
+) -> Result<Vec<PartialOverlap>, anyhow::Error> {
+    find_file_overlaps("source.ts", source_code, "target.ts", target_code, options)
+}
+
+/// Overlaps between the functions of two files, parsed as their file type
+fn find_file_overlaps(
+    source_file: &str,
+    source_code: &str,
+    target_file: &str,
+    target_code: &str,
+    options: &OverlapOptions,
 ) -> Result<Vec<PartialOverlap>, anyhow::Error> {
     // Extract functions from both files
-    let source_functions = match extract_functions("source.ts", source_code) {
+    let source_functions = match extract_functions(source_file, source_code) {
         Ok(funcs) => funcs,
         Err(e) if e.contains("Parse errors:") => {
             // Skip files with parse errors silently
@@ -25,7 +37,7 @@ pub fn find_function_overlaps(
         Err(e) => return Err(anyhow::anyhow!(e)),
     };
 
-    let target_functions = match extract_functions("target.ts", target_code) {
+    let target_functions = match extract_functions(target_file, target_code) {
         Ok(funcs) => funcs,
         Err(e) if e.contains("Parse errors:") => {
             // Skip files with parse errors silently
@@ -38,7 +50,7 @@ pub fn find_function_overlaps(
     let mut all_overlaps = Vec::new();
 
     for source_func in &source_functions {
-        let source_indexed = index_function(source_func, source_code, "source.ts")?;
+        let source_indexed = index_function(source_func, source_code, source_file)?;
 
         for target_func in &target_functions {
             // Skip if comparing the same function in the same file
@@ -47,7 +59,7 @@ pub fn find_function_overlaps(
                 continue;
             }
 
-            let target_indexed = index_function(target_func, target_code, "target.ts")?;
+            let target_indexed = index_function(target_func, target_code, target_file)?;
 
             // Debug output
             #[cfg(test)]
@@ -84,7 +96,8 @@ pub fn find_overlaps_across_files(
             let target_code = &file_contents[target_file];
 
             // Find overlaps between these files
-            let overlaps = find_function_overlaps(source_code, target_code, options)?;
+            let overlaps =
+                find_file_overlaps(source_file, source_code, target_file, target_code, options)?;
 
             // Add file information to overlaps
             for overlap in overlaps {
@@ -134,8 +147,10 @@ fn index_function(
         eprintln!("First 100 chars: {}", &func_code.chars().take(100).collect::<String>());
     }
 
-    // Parse the function
-    let tree = parse_and_convert_to_tree(file_name, &func_code).map_err(|e| anyhow::anyhow!(e))?;
+    // Parse the function; a snippet cut out of a component file is script in the block's `lang`
+    let parse_name = snippet_file_name(file_name, full_code);
+    let tree =
+        parse_and_convert_to_tree(&parse_name, &func_code).map_err(|e| anyhow::anyhow!(e))?;
 
     // Generate fingerprints for all subtrees
     let (root_fp, subtrees) = generate_subtree_fingerprints(&tree, 0, func.start_line);
@@ -298,6 +313,40 @@ function utilityFunction() {
         // Check that we found overlaps (may not always detect For specifically due to windowing)
     }
 
+    #[test]
+    fn test_overlaps_in_tsx_components() {
+        let component = |name: &str, items: &str| {
+            format!(
+                r#"<script setup lang="tsx">
+function {name}({items}) {{
+    const rows = [];
+    for (let i = 0; i < {items}.length; i++) {{
+        if ({items}[i].value > 10) {{
+            rows.push(<li key={{i}}>{{{items}[i].value * 2}}</li>);
+        }}
+    }}
+    return <ul>{{rows}}</ul>;
+}}
+</script>
+"#
+            )
+        };
+        let files = HashMap::from([
+            ("List.vue".to_string(), component("renderItems", "items")),
+            ("Table.vue".to_string(), component("renderRows", "entries")),
+        ]);
+        let options = OverlapOptions {
+            min_window_size: 3,
+            max_window_size: 20,
+            threshold: 0.5,
+            size_tolerance: 0.5,
+        };
+
+        // Function bodies hold JSX, so they only parse as the `lang="tsx"` of their block
+        let overlaps = find_overlaps_across_files(&files, &options).unwrap();
+        assert!(overlaps.iter().any(|o| o.source_file != o.target_file));
+    }
+
     #[test]
     fn test_extract_code_segment() {
         let code = "line1\nline2\nline3\nline4\nline5";
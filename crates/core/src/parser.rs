@@ -1,12 +1,17 @@
 use oxc_allocator::Allocator;
 use oxc_ast::ast::{
-    BindingPatternKind, BlockStatement, ClassElement, Expression, FormalParameter, FunctionBody,
-    Program, PropertyKey, Statement, VariableDeclarator,
+    BindingIdentifier, BindingPattern, BindingPatternKind, BindingProperty, Hashbang,
+    JSXClosingElement, JSXClosingFragment, JSXOpeningFragment, MemberExpression, Program,
+    PropertyKey, Statement, TSEnumMemberName, TSModuleDeclarationName, TemplateElement,
 };
+use oxc_ast::{AstKind, AstType};
+use oxc_ast_visit::{walk, Visit};
 use oxc_parser::Parser;
-use oxc_span::SourceType;
+use oxc_span::{GetSpan, Span};
+use std::collections::HashSet;
 use std::rc::Rc;
 
+use crate::sfc::script_source;
 use crate::tree::TreeNode;
 
 /// Parse TypeScript code and convert to `TreeNode` structure
@@ -19,8 +24,8 @@ pub fn parse_and_convert_to_tree(
     source_text: &str,
 ) -> Result<Rc<TreeNode>, String> {
     let allocator = Allocator::default();
-    let source_type = SourceType::from_path(filename).unwrap_or(SourceType::tsx());
-    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let (script, source_type) = script_source(filename, source_text);
+    let ret = Parser::new(&allocator, &script, source_type).parse();
 
     if !ret.errors.is_empty() {
         // Create a more readable error message
@@ -34,336 +39,306 @@ pub fn parse_and_convert_to_tree(
 }
 
 pub fn ast_to_tree_node(program: &Program, id_counter: &mut usize) -> Rc<TreeNode> {
-    let mut root = TreeNode::new("Program".to_string(), "Program".to_string(), *id_counter);
-    *id_counter += 1;
-
-    for stmt in &program.body {
-        if let Some(child) = statement_to_tree_node(stmt, id_counter) {
-            root.add_child(child);
-        }
-    }
-
-    Rc::new(root)
+    let mut builder = TreeBuilder::new(program.source_text, id_counter);
+    builder.visit_program(program);
+    builder.root.unwrap_or_else(|| Rc::new(TreeNode::new("Program".into(), "Program".into(), 0)))
 }
 
-fn statement_to_tree_node(stmt: &Statement, id_counter: &mut usize) -> Option<Rc<TreeNode>> {
-    match stmt {
-        Statement::FunctionDeclaration(func) => {
-            let label = func.id.as_ref().map_or("Function", |id| id.name.as_str()).to_string();
-            let mut node = TreeNode::new(label, "FunctionDeclaration".to_string(), *id_counter);
-            *id_counter += 1;
-
-            // Add parameters
-            for param in &func.params.items {
-                if let Some(param_node) = formal_parameter_to_tree_node(param, id_counter) {
-                    node.add_child(param_node);
-                }
-            }
-
-            // Add body
-            if let Some(body) = &func.body {
-                if let Some(body_node) = function_body_to_tree_node(body, id_counter) {
-                    node.add_child(body_node);
-                }
-            }
+/// Builds a `TreeNode` for every AST node oxc visits
+///
+/// Nodes are labelled with their name, operator or literal value where they have one and
+/// with their kind otherwise; the kind goes into `value`. Pure wrappers (expression
+/// statements, parentheses, argument and parameter lists, ...) are skipped so their
+/// children attach to the enclosing node, and names already used as a label (a function's
+/// identifier, a property key, ...) are not repeated as children.
+struct TreeBuilder<'a, 'c> {
+    source: &'a str,
+    id_counter: &'c mut usize,
+    /// Open nodes from the root down; `None` for skipped wrappers
+    stack: Vec<Option<TreeNode>>,
+    /// Nodes folded into their parent's label, skipped when visited
+    folded: HashSet<(AstType, Span)>,
+    /// Expression bodies of arrow functions, converted as `return` statements
+    implicit_returns: HashSet<Span>,
+    root: Option<Rc<TreeNode>>,
+}
 
-            Some(Rc::new(node))
+impl<'a, 'c> TreeBuilder<'a, 'c> {
+    fn new(source: &'a str, id_counter: &'c mut usize) -> Self {
+        Self {
+            source,
+            id_counter,
+            stack: Vec::new(),
+            folded: HashSet::new(),
+            implicit_returns: HashSet::new(),
+            root: None,
         }
-        Statement::ClassDeclaration(class) => {
-            let label = class.id.as_ref().map_or("Class", |id| id.name.as_str()).to_string();
-            let mut node = TreeNode::new(label, "ClassDeclaration".to_string(), *id_counter);
-            *id_counter += 1;
-
-            // Add class body elements
-            for element in &class.body.body {
-                if let Some(elem_node) = class_element_to_tree_node(element, id_counter) {
-                    node.add_child(elem_node);
-                }
-            }
+    }
 
-            Some(Rc::new(node))
-        }
-        Statement::VariableDeclaration(var_decl) => {
-            let mut node = TreeNode::new(
-                "VariableDeclaration".to_string(),
-                "VariableDeclaration".to_string(),
-                *id_counter,
-            );
-            *id_counter += 1;
+    fn fold(&mut self, ty: AstType, span: Span) {
+        self.folded.insert((ty, span));
+    }
 
-            for decl in &var_decl.declarations {
-                if let Some(decl_node) = variable_declarator_to_tree_node(decl, id_counter) {
-                    node.add_child(decl_node);
-                }
-            }
+    fn fold_binding(&mut self, pattern: &BindingPattern) -> Option<String> {
+        let BindingPatternKind::BindingIdentifier(ident) = &pattern.kind else {
+            return None;
+        };
+        self.fold(AstType::BindingIdentifier, ident.span);
+        Some(ident.name.to_string())
+    }
 
-            Some(Rc::new(node))
+    fn fold_key(&mut self, key: &PropertyKey, computed: bool) -> Option<String> {
+        if computed {
+            return None;
         }
-        Statement::ExpressionStatement(expr_stmt) => {
-            expression_to_tree_node(&expr_stmt.expression, id_counter)
-        }
-        Statement::BlockStatement(block) => block_statement_to_tree_node(block, id_counter),
-        Statement::IfStatement(if_stmt) => {
-            let mut node =
-                TreeNode::new("IfStatement".to_string(), "IfStatement".to_string(), *id_counter);
-            *id_counter += 1;
-
-            // Add test expression
-            if let Some(test_node) = expression_to_tree_node(&if_stmt.test, id_counter) {
-                node.add_child(test_node);
+        let (label, ty, span) = match key {
+            PropertyKey::StaticIdentifier(ident) => {
+                (ident.name.to_string(), AstType::IdentifierName, ident.span)
             }
-
-            // Add consequent
-            if let Some(cons_node) = statement_to_tree_node(&if_stmt.consequent, id_counter) {
-                node.add_child(cons_node);
+            PropertyKey::PrivateIdentifier(ident) => {
+                (format!("#{}", ident.name), AstType::PrivateIdentifier, ident.span)
             }
-
-            // Add alternate if exists
-            if let Some(alt) = &if_stmt.alternate {
-                if let Some(alt_node) = statement_to_tree_node(alt, id_counter) {
-                    node.add_child(alt_node);
-                }
+            PropertyKey::StringLiteral(lit) => {
+                (format!("\"{}\"", lit.value), AstType::StringLiteral, lit.span)
             }
-
-            Some(Rc::new(node))
-        }
-        Statement::ReturnStatement(ret_stmt) => {
-            let mut node = TreeNode::new(
-                "ReturnStatement".to_string(),
-                "ReturnStatement".to_string(),
-                *id_counter,
-            );
-            *id_counter += 1;
-
-            if let Some(arg) = &ret_stmt.argument {
-                if let Some(arg_node) = expression_to_tree_node(arg, id_counter) {
-                    node.add_child(arg_node);
-                }
+            PropertyKey::NumericLiteral(lit) => {
+                (lit.value.to_string(), AstType::NumericLiteral, lit.span)
             }
+            _ => return None,
+        };
+        self.fold(ty, span);
+        Some(label)
+    }
 
-            Some(Rc::new(node))
-        }
-        _ => {
-            // For other statement types, create a generic node
-            let node = TreeNode::new("Statement".to_string(), "Statement".to_string(), *id_counter);
-            *id_counter += 1;
-            Some(Rc::new(node))
-        }
+    fn fold_identifier(&mut self, ident: Option<&BindingIdentifier>) -> Option<String> {
+        let ident = ident?;
+        self.fold(AstType::BindingIdentifier, ident.span);
+        Some(ident.name.to_string())
     }
-}
 
-fn expression_to_tree_node(expr: &Expression, id_counter: &mut usize) -> Option<Rc<TreeNode>> {
-    match expr {
-        Expression::Identifier(ident) => {
-            let node = TreeNode::new(
-                ident.name.as_str().to_string(),
-                "Identifier".to_string(),
-                *id_counter,
-            );
-            *id_counter += 1;
-            Some(Rc::new(node))
-        }
-        Expression::StringLiteral(str_lit) => {
-            let label = format!("\"{}\"", str_lit.value.as_str());
-            let node = TreeNode::new(label, "StringLiteral".to_string(), *id_counter);
-            *id_counter += 1;
-            Some(Rc::new(node))
-        }
-        Expression::NumericLiteral(num_lit) => {
-            let label = num_lit.value.to_string();
-            let node = TreeNode::new(label, "NumericLiteral".to_string(), *id_counter);
-            *id_counter += 1;
-            Some(Rc::new(node))
-        }
-        Expression::BooleanLiteral(bool_lit) => {
-            let label = bool_lit.value.to_string();
-            let node = TreeNode::new(label, "BooleanLiteral".to_string(), *id_counter);
-            *id_counter += 1;
-            Some(Rc::new(node))
-        }
-        Expression::BinaryExpression(bin_expr) => {
-            let mut node = TreeNode::new(
-                format!("{:?}", bin_expr.operator),
-                "BinaryExpression".to_string(),
-                *id_counter,
-            );
-            *id_counter += 1;
+    fn source_text(&self, span: Span) -> String {
+        self.source.get(span.start as usize..span.end as usize).unwrap_or_default().to_string()
+    }
 
-            if let Some(left_node) = expression_to_tree_node(&bin_expr.left, id_counter) {
-                node.add_child(left_node);
+    /// `(label, value)` of the node for `kind`
+    fn describe(&mut self, kind: AstKind<'a>) -> (String, String) {
+        let kind_name = format!("{:?}", kind.ty());
+        let named = |label: Option<String>, fallback: &str| {
+            (label.unwrap_or_else(|| fallback.to_string()), kind_name.clone())
+        };
+        match kind {
+            AstKind::IdentifierReference(ident) => (ident.name.to_string(), "Identifier".into()),
+            AstKind::IdentifierName(ident) => named(Some(ident.name.to_string()), ""),
+            AstKind::BindingIdentifier(ident) => named(Some(ident.name.to_string()), ""),
+            AstKind::LabelIdentifier(ident) => named(Some(ident.name.to_string()), ""),
+            AstKind::PrivateIdentifier(ident) => named(Some(format!("#{}", ident.name)), ""),
+            AstKind::JSXIdentifier(ident) => named(Some(ident.name.to_string()), ""),
+
+            AstKind::StringLiteral(lit) => named(Some(format!("\"{}\"", lit.value)), ""),
+            AstKind::NumericLiteral(lit) => named(Some(lit.value.to_string()), ""),
+            AstKind::BooleanLiteral(lit) => named(Some(lit.value.to_string()), ""),
+            AstKind::NullLiteral(_) => named(Some("null".into()), ""),
+            AstKind::BigIntLiteral(lit) => named(Some(self.source_text(lit.span)), ""),
+            AstKind::RegExpLiteral(lit) => named(Some(self.source_text(lit.span)), ""),
+            AstKind::TemplateLiteral(lit) => named(Some(template_label(&lit.quasis)), ""),
+            AstKind::TSTemplateLiteralType(lit) => named(Some(template_label(&lit.quasis)), ""),
+            AstKind::Directive(directive) => {
+                named(Some(format!("\"{}\"", directive.directive)), "")
             }
-
-            if let Some(right_node) = expression_to_tree_node(&bin_expr.right, id_counter) {
-                node.add_child(right_node);
+            AstKind::JSXText(text) => named(Some(text.value.trim().to_string()), ""),
+
+            AstKind::BinaryExpression(expr) => named(Some(format!("{:?}", expr.operator)), ""),
+            AstKind::LogicalExpression(expr) => named(Some(format!("{:?}", expr.operator)), ""),
+            AstKind::UnaryExpression(expr) => named(Some(format!("{:?}", expr.operator)), ""),
+            AstKind::UpdateExpression(expr) => named(Some(format!("{:?}", expr.operator)), ""),
+            AstKind::AssignmentExpression(expr) => named(Some(format!("{:?}", expr.operator)), ""),
+            AstKind::MemberExpression(expr) => {
+                let variant = match expr {
+                    MemberExpression::ComputedMemberExpression(_) => "ComputedMemberExpression",
+                    MemberExpression::StaticMemberExpression(_) => "StaticMemberExpression",
+                    MemberExpression::PrivateFieldExpression(_) => "PrivateFieldExpression",
+                };
+                (variant.to_string(), variant.to_string())
             }
 
-            Some(Rc::new(node))
-        }
-        Expression::CallExpression(call_expr) => {
-            let mut node = TreeNode::new(
-                "CallExpression".to_string(),
-                "CallExpression".to_string(),
-                *id_counter,
-            );
-            *id_counter += 1;
-
-            if let Some(callee_node) = expression_to_tree_node(&call_expr.callee, id_counter) {
-                node.add_child(callee_node);
+            AstKind::Function(func) => {
+                let label = self.fold_identifier(func.id.as_ref());
+                (label.unwrap_or_else(|| "Function".into()), format!("{:?}", func.r#type))
             }
-
-            for arg in &call_expr.arguments {
-                if let Some(expr) = arg.as_expression() {
-                    if let Some(arg_node) = expression_to_tree_node(expr, id_counter) {
-                        node.add_child(arg_node);
+            AstKind::Class(class) => {
+                let label = self.fold_identifier(class.id.as_ref());
+                (label.unwrap_or_else(|| "Class".into()), format!("{:?}", class.r#type))
+            }
+            AstKind::ArrowFunctionExpression(arrow) => {
+                // `x => x + 1` compares like `x => { return x + 1; }`
+                if arrow.expression {
+                    if let Some(Statement::ExpressionStatement(stmt)) =
+                        arrow.body.statements.first()
+                    {
+                        self.implicit_returns.insert(stmt.span);
                     }
                 }
+                ("ArrowFunction".into(), kind_name)
+            }
+            AstKind::FunctionBody(_) => ("BlockStatement".into(), "BlockStatement".into()),
+            AstKind::FormalParameter(param) => {
+                let label = self.fold_binding(&param.pattern);
+                (label.unwrap_or_else(|| "Parameter".into()), "Parameter".into())
+            }
+            AstKind::VariableDeclaration(_) => (kind_name.clone(), kind_name),
+            AstKind::VariableDeclarator(decl) => {
+                let label = self.fold_binding(&decl.id);
+                named(label, "Variable")
             }
 
-            Some(Rc::new(node))
-        }
-        Expression::ArrowFunctionExpression(arrow) => {
-            let mut node = TreeNode::new(
-                "ArrowFunction".to_string(),
-                "ArrowFunctionExpression".to_string(),
-                *id_counter,
-            );
-            *id_counter += 1;
-
-            // Add parameters
-            for param in &arrow.params.items {
-                if let Some(param_node) = formal_parameter_to_tree_node(param, id_counter) {
-                    node.add_child(param_node);
-                }
+            AstKind::MethodDefinition(method) => {
+                // Parameters and body sit directly under the method
+                self.fold(AstType::Function, method.value.span);
+                let label = self.fold_key(&method.key, method.computed);
+                named(label, "Method")
+            }
+            AstKind::PropertyDefinition(prop) => {
+                let label = self.fold_key(&prop.key, prop.computed);
+                named(label, "Property")
+            }
+            AstKind::AccessorProperty(prop) => {
+                let label = self.fold_key(&prop.key, prop.computed);
+                named(label, "Accessor")
+            }
+            AstKind::ObjectProperty(prop) => {
+                let label = self.fold_key(&prop.key, prop.computed);
+                named(label, "Property")
+            }
+            AstKind::TSPropertySignature(sig) => {
+                let label = self.fold_key(&sig.key, sig.computed);
+                named(label, "Property")
+            }
+            AstKind::TSMethodSignature(sig) => {
+                let label = self.fold_key(&sig.key, sig.computed);
+                named(label, "Method")
             }
 
-            // Add body
-            if arrow.expression {
-                // Expression body (e.g., => x + 1)
-                if let Some(Statement::ExpressionStatement(expr_stmt)) =
-                    arrow.body.statements.first()
-                {
-                    if let Some(expr_node) =
-                        expression_to_tree_node(&expr_stmt.expression, id_counter)
-                    {
-                        node.add_child(expr_node);
+            AstKind::TSTypeAliasDeclaration(decl) => {
+                let label = self.fold_identifier(Some(&decl.id));
+                named(label, "")
+            }
+            AstKind::TSInterfaceDeclaration(decl) => {
+                let label = self.fold_identifier(Some(&decl.id));
+                named(label, "")
+            }
+            AstKind::TSEnumDeclaration(decl) => {
+                let label = self.fold_identifier(Some(&decl.id));
+                named(label, "")
+            }
+            AstKind::TSTypeParameter(param) => {
+                let label = self.fold_identifier(Some(&param.name));
+                named(label, "")
+            }
+            AstKind::TSModuleDeclaration(decl) => {
+                let label = match &decl.id {
+                    TSModuleDeclarationName::Identifier(ident) => self.fold_identifier(Some(ident)),
+                    TSModuleDeclarationName::StringLiteral(lit) => {
+                        self.fold(AstType::StringLiteral, lit.span);
+                        Some(format!("\"{}\"", lit.value))
                     }
-                }
-            } else {
-                // Block body (e.g., => { return x + 1; })
-                if let Some(body_node) = function_body_to_tree_node(&arrow.body, id_counter) {
-                    node.add_child(body_node);
-                }
+                };
+                named(label, "Module")
+            }
+            AstKind::TSEnumMember(member) => {
+                let label = match &member.id {
+                    TSEnumMemberName::Identifier(ident) => {
+                        self.fold(AstType::IdentifierName, ident.span);
+                        Some(ident.name.to_string())
+                    }
+                    TSEnumMemberName::String(lit) => {
+                        self.fold(AstType::StringLiteral, lit.span);
+                        Some(format!("\"{}\"", lit.value))
+                    }
+                    _ => None,
+                };
+                named(label, "Member")
             }
 
-            Some(Rc::new(node))
-        }
-        _ => {
-            // For other expression types, create a generic node
-            let node =
-                TreeNode::new("Expression".to_string(), "Expression".to_string(), *id_counter);
-            *id_counter += 1;
-            Some(Rc::new(node))
+            _ => (kind_name.clone(), kind_name),
         }
     }
 }
 
-fn formal_parameter_to_tree_node(
-    param: &FormalParameter,
-    id_counter: &mut usize,
-) -> Option<Rc<TreeNode>> {
-    let label = match &param.pattern.kind {
-        BindingPatternKind::BindingIdentifier(ident) => ident.name.as_str().to_string(),
-        _ => "Parameter".to_string(),
-    };
-    let node = TreeNode::new(label, "Parameter".to_string(), *id_counter);
-    *id_counter += 1;
-    Some(Rc::new(node))
+/// Wrapper kinds that add no structure of their own
+fn is_transparent(ty: AstType) -> bool {
+    matches!(
+        ty,
+        AstType::ExpressionStatement
+            | AstType::ParenthesizedExpression
+            | AstType::Argument
+            | AstType::FormalParameters
+            | AstType::ClassBody
+            | AstType::PropertyKey
+            | AstType::AssignmentTarget
+            | AstType::SimpleAssignmentTarget
+            | AstType::AssignmentTargetPattern
+            | AstType::CatchParameter
+            | AstType::ModuleDeclaration
+            | AstType::TSTypeAnnotation
+            | AstType::TSTypeName
+            | AstType::TSEnumBody
+            | AstType::JSXOpeningElement
+    )
 }
 
-fn function_body_to_tree_node(body: &FunctionBody, id_counter: &mut usize) -> Option<Rc<TreeNode>> {
-    let mut node =
-        TreeNode::new("BlockStatement".to_string(), "BlockStatement".to_string(), *id_counter);
-    *id_counter += 1;
+// `` `Hello ${name}!` `` -> "`Hello ${}!`", so differing text compares like string literals
+fn template_label(quasis: &[TemplateElement]) -> String {
+    let parts: Vec<&str> = quasis.iter().map(|q| q.value.raw.as_str()).collect();
+    format!("`{}`", parts.join("${}"))
+}
 
-    for stmt in &body.statements {
-        if let Some(stmt_node) = statement_to_tree_node(stmt, id_counter) {
-            node.add_child(stmt_node);
+impl<'a> Visit<'a> for TreeBuilder<'a, '_> {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        let span = kind.span();
+        if kind.ty() == AstType::ExpressionStatement && self.implicit_returns.remove(&span) {
+            let node =
+                TreeNode::new("ReturnStatement".into(), "ReturnStatement".into(), *self.id_counter)
+                    .with_span(span.start, span.end);
+            *self.id_counter += 1;
+            self.stack.push(Some(node));
+            return;
+        }
+        if self.folded.remove(&(kind.ty(), span)) || is_transparent(kind.ty()) {
+            self.stack.push(None);
+            return;
         }
+        let (label, value) = self.describe(kind);
+        let node = TreeNode::new(label, value, *self.id_counter).with_span(span.start, span.end);
+        *self.id_counter += 1;
+        self.stack.push(Some(node));
     }
 
-    Some(Rc::new(node))
-}
-
-fn block_statement_to_tree_node(
-    block: &BlockStatement,
-    id_counter: &mut usize,
-) -> Option<Rc<TreeNode>> {
-    let mut node =
-        TreeNode::new("BlockStatement".to_string(), "BlockStatement".to_string(), *id_counter);
-    *id_counter += 1;
-
-    for stmt in &block.body {
-        if let Some(stmt_node) = statement_to_tree_node(stmt, id_counter) {
-            node.add_child(stmt_node);
+    fn leave_node(&mut self, _kind: AstKind<'a>) {
+        let Some(Some(node)) = self.stack.pop() else {
+            return;
+        };
+        let node = Rc::new(node);
+        match self.stack.iter_mut().rev().find_map(Option::as_mut) {
+            Some(parent) => parent.add_child(node),
+            None => self.root = Some(node),
         }
     }
 
-    Some(Rc::new(node))
-}
-
-fn variable_declarator_to_tree_node(
-    decl: &VariableDeclarator,
-    id_counter: &mut usize,
-) -> Option<Rc<TreeNode>> {
-    let label = match &decl.id.kind {
-        BindingPatternKind::BindingIdentifier(ident) => ident.name.as_str().to_string(),
-        _ => "Variable".to_string(),
-    };
-    let mut node = TreeNode::new(label, "VariableDeclarator".to_string(), *id_counter);
-    *id_counter += 1;
-
-    if let Some(init) = &decl.init {
-        if let Some(init_node) = expression_to_tree_node(init, id_counter) {
-            node.add_child(init_node);
+    // `{ a }` binds `a` once; skip the key that repeats it
+    fn visit_binding_property(&mut self, it: &BindingProperty<'a>) {
+        if it.shorthand {
+            self.visit_binding_pattern(&it.value);
+        } else {
+            walk::walk_binding_property(self, it);
         }
     }
 
-    Some(Rc::new(node))
-}
+    // The closing tag repeats the opening one
+    fn visit_jsx_closing_element(&mut self, _it: &JSXClosingElement<'a>) {}
 
-fn class_element_to_tree_node(
-    element: &ClassElement,
-    id_counter: &mut usize,
-) -> Option<Rc<TreeNode>> {
-    match element {
-        ClassElement::MethodDefinition(method) => {
-            let label = match &method.key {
-                PropertyKey::StaticIdentifier(ident) => ident.name.as_str().to_string(),
-                PropertyKey::PrivateIdentifier(ident) => format!("#{}", ident.name.as_str()),
-                _ => "Method".to_string(),
-            };
-            let mut node = TreeNode::new(label, "MethodDefinition".to_string(), *id_counter);
-            *id_counter += 1;
+    fn visit_jsx_opening_fragment(&mut self, _it: &JSXOpeningFragment) {}
 
-            // Add method body
-            if let Some(body) = &method.value.body {
-                if let Some(body_node) = function_body_to_tree_node(body, id_counter) {
-                    node.add_child(body_node);
-                }
-            }
+    fn visit_jsx_closing_fragment(&mut self, _it: &JSXClosingFragment) {}
 
-            Some(Rc::new(node))
-        }
-        ClassElement::PropertyDefinition(prop) => {
-            let label = match &prop.key {
-                PropertyKey::StaticIdentifier(ident) => ident.name.as_str().to_string(),
-                PropertyKey::PrivateIdentifier(ident) => format!("#{}", ident.name.as_str()),
-                _ => "Property".to_string(),
-            };
-            let node = TreeNode::new(label, "PropertyDefinition".to_string(), *id_counter);
-            *id_counter += 1;
-            Some(Rc::new(node))
-        }
-        _ => None,
-    }
+    fn visit_hashbang(&mut self, _it: &Hashbang<'a>) {}
 }
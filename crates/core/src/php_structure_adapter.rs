@@ -0,0 +1,300 @@
+use crate::structure_comparator::{
+    blend_method_bodies, ComparisonOptions, MethodBodyComparison, SourceLocation, Structure,
+    StructureComparator, StructureIdentifier, StructureKind, StructureMember, StructureMetadata,
+};
+use crate::tree::TreeNode;
+use crate::tsed::{calculate_tsed, TSEDOptions};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhpTypeKind {
+    Class,
+    Interface,
+    Trait,
+}
+
+impl PhpTypeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Class => "class",
+            Self::Interface => "interface",
+            Self::Trait => "trait",
+        }
+    }
+}
+
+/// A PHP class, interface or trait with its properties and methods
+#[derive(Debug, Clone)]
+pub struct PhpTypeDef {
+    pub name: String,
+    pub kind: PhpTypeKind,
+    pub namespace: Option<String>,
+    /// Parent class, or parent interfaces of an interface
+    pub extends: Vec<String>,
+    pub implements: Vec<String>,
+    /// Traits pulled in with `use`
+    pub traits: Vec<String>,
+    pub properties: Vec<PhpPropertyDef>,
+    pub methods: Vec<PhpMethodDef>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub file_path: String,
+}
+
+impl PhpTypeDef {
+    /// Whether `other` is named as this type's parent class or interface, short names included
+    /// (`\App\Models\Model` matches `Model`)
+    pub fn inherits_from(&self, other: &PhpTypeDef) -> bool {
+        self.extends
+            .iter()
+            .chain(&self.implements)
+            .chain(&self.traits)
+            .any(|name| name.rsplit('\\').next() == Some(other.name.as_str()))
+    }
+}
+
+/// A declared property (`private ?string $name = null`) or a promoted constructor parameter
+#[derive(Debug, Clone)]
+pub struct PhpPropertyDef {
+    /// Name without the `$`
+    pub name: String,
+    pub type_hint: Option<String>,
+    /// `public`, `static`, `readonly`
+    pub modifiers: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PhpMethodDef {
+    pub name: String,
+    /// Parameters as written without defaults, e.g. `?int $age` or `...$rest`
+    pub parameters: Vec<String>,
+    pub return_type: Option<String>,
+    /// `public`, `static`, `abstract`, `final`
+    pub modifiers: Vec<String>,
+    /// Absent for abstract and interface methods
+    pub body: Option<Rc<TreeNode>>,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+impl PhpMethodDef {
+    /// `(string $email, ?int $age): void`
+    pub fn signature(&self) -> String {
+        match &self.return_type {
+            Some(return_type) => format!("({}): {}", self.parameters.join(", "), return_type),
+            None => format!("({})", self.parameters.join(", ")),
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.end_line.saturating_sub(self.start_line) + 1
+    }
+}
+
+impl From<PhpTypeDef> for Structure {
+    fn from(type_def: PhpTypeDef) -> Self {
+        let kind = match type_def.kind {
+            PhpTypeKind::Class => StructureKind::PhpClass,
+            PhpTypeKind::Interface => StructureKind::PhpInterface,
+            PhpTypeKind::Trait => StructureKind::PhpTrait,
+        };
+        let properties = type_def.properties.into_iter().map(|property| StructureMember {
+            name: property.name,
+            value_type: property.type_hint.unwrap_or_else(|| "mixed".to_string()),
+            modifiers: std::iter::once("property".to_string()).chain(property.modifiers).collect(),
+            nested: None,
+        });
+        let methods = type_def.methods.iter().map(|method| StructureMember {
+            name: method.name.clone(),
+            value_type: method.signature(),
+            modifiers: std::iter::once("method".to_string())
+                .chain(method.modifiers.iter().cloned())
+                .collect(),
+            nested: None,
+        });
+
+        Structure {
+            identifier: StructureIdentifier {
+                name: type_def.name,
+                kind,
+                namespace: type_def.namespace,
+            },
+            members: properties.chain(methods).collect(),
+            metadata: StructureMetadata {
+                location: SourceLocation {
+                    file_path: type_def.file_path,
+                    start_line: type_def.start_line,
+                    end_line: type_def.end_line,
+                },
+                generics: Vec::new(),
+                extends: type_def.extends.into_iter().chain(type_def.implements).collect(),
+                visibility: None,
+            },
+        }
+    }
+}
+
+pub type PhpTypeComparison = MethodBodyComparison;
+
+/// A method of one class matched to a near-identical method of another, under any name
+#[derive(Debug, Clone)]
+pub struct PhpSharedMethod {
+    pub method1: PhpMethodDef,
+    pub method2: PhpMethodDef,
+    pub similarity: f64,
+}
+
+/// Two unrelated classes sharing enough method implementations to extract a trait
+#[derive(Debug, Clone)]
+pub struct PhpTraitCandidate {
+    /// Indices into the analyzed types
+    pub index1: usize,
+    pub index2: usize,
+    pub methods: Vec<PhpSharedMethod>,
+}
+
+/// Comparison engine for PHP classes, interfaces and traits
+pub struct PhpStructureComparator {
+    pub comparator: StructureComparator,
+    pub tsed_options: TSEDOptions,
+    /// Weight of the method bodies, see [`blend_method_bodies`]
+    pub method_body_weight: f64,
+}
+
+impl Default for PhpStructureComparator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhpStructureComparator {
+    pub fn new() -> Self {
+        // Copies are usually renamed (`UserRepository` and `CustomerRepository`), so the members
+        // decide
+        let options = ComparisonOptions {
+            name_weight: 0.1,
+            structure_weight: 0.9,
+            threshold: 0.7,
+            ..Default::default()
+        };
+        Self::with_options(options)
+    }
+
+    pub fn with_options(options: ComparisonOptions) -> Self {
+        Self {
+            comparator: StructureComparator::new(options),
+            tsed_options: TSEDOptions { size_penalty: false, ..TSEDOptions::default() },
+            method_body_weight: 0.5,
+        }
+    }
+
+    /// Compare two types by their properties, method signatures and method bodies
+    pub fn compare_types(&mut self, type1: &PhpTypeDef, type2: &PhpTypeDef) -> PhpTypeComparison {
+        let structure = self
+            .comparator
+            .compare(&Structure::from(type1.clone()), &Structure::from(type2.clone()));
+
+        blend_method_bodies(
+            structure,
+            |member_match| {
+                let method1 = type1.methods.iter().find(|m| m.name == member_match.member1)?;
+                let method2 = type2.methods.iter().find(|m| m.name == member_match.member2)?;
+                Some((method1.body.as_ref()?, method2.body.as_ref()?))
+            },
+            self.method_body_weight,
+            &self.tsed_options,
+        )
+    }
+}
+
+/// Compare two method implementations with TSED
+///
+/// Returns 0.0 when either method has no body. Short methods get the same size penalty as short
+/// functions, so that getters and setters don't make every pair of entities a trait candidate.
+pub fn compare_php_method_bodies(
+    method1: &PhpMethodDef,
+    method2: &PhpMethodDef,
+    options: &TSEDOptions,
+) -> f64 {
+    let (Some(body1), Some(body2)) = (&method1.body, &method2.body) else {
+        return 0.0;
+    };
+    let mut similarity = calculate_tsed(body1, body2, options);
+
+    if options.size_penalty {
+        let avg_lines = (method1.line_count() + method2.line_count()) as f64 / 2.0;
+        if avg_lines < 10.0 {
+            similarity *= avg_lines / 10.0;
+        }
+    }
+
+    similarity
+}
+
+/// Find pairs of classes sharing at least `min_shared` near-identical methods, under any name
+///
+/// Interfaces and traits are skipped, as are classes that extend one another.
+pub fn find_trait_candidates(
+    types: &[PhpTypeDef],
+    threshold: f64,
+    min_shared: usize,
+    options: &TSEDOptions,
+) -> Vec<PhpTraitCandidate> {
+    let mut candidates = Vec::new();
+
+    for i in 0..types.len() {
+        for j in i + 1..types.len() {
+            let (class1, class2) = (&types[i], &types[j]);
+            if class1.kind != PhpTypeKind::Class || class2.kind != PhpTypeKind::Class {
+                continue;
+            }
+            if class1.inherits_from(class2) || class2.inherits_from(class1) {
+                continue;
+            }
+
+            let methods = match_methods(class1, class2, threshold, options);
+            if !methods.is_empty() && methods.len() >= min_shared {
+                candidates.push(PhpTraitCandidate { index1: i, index2: j, methods });
+            }
+        }
+    }
+
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.methods.len()));
+    candidates
+}
+
+/// Match the methods of two classes one-to-one, regardless of their names
+fn match_methods(
+    class1: &PhpTypeDef,
+    class2: &PhpTypeDef,
+    threshold: f64,
+    options: &TSEDOptions,
+) -> Vec<PhpSharedMethod> {
+    let mut candidates = Vec::new();
+    for (i, method1) in class1.methods.iter().enumerate() {
+        for (j, method2) in class2.methods.iter().enumerate() {
+            let similarity = compare_php_method_bodies(method1, method2, options);
+            if similarity >= threshold {
+                candidates.push((i, j, similarity));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut used1 = vec![false; class1.methods.len()];
+    let mut used2 = vec![false; class2.methods.len()];
+    let mut matches = Vec::new();
+    for (i, j, similarity) in candidates {
+        if used1[i] || used2[j] {
+            continue;
+        }
+        used1[i] = true;
+        used2[j] = true;
+        matches.push(PhpSharedMethod {
+            method1: class1.methods[i].clone(),
+            method2: class2.methods[j].clone(),
+            similarity,
+        });
+    }
+    matches
+}
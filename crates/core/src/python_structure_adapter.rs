@@ -0,0 +1,310 @@
+use crate::structure_comparator::{
+    blend_method_bodies, ComparisonOptions, MethodBodyComparison, SourceLocation, Structure,
+    StructureComparator, StructureIdentifier, StructureKind, StructureMember, StructureMetadata,
+};
+use crate::tree::TreeNode;
+use crate::tsed::TSEDOptions;
+use std::rc::Rc;
+
+/// The kind of a Python class: a plain class or one of the common data model styles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PythonClassKind {
+    Class,
+    /// `@dataclass`, `@dataclasses.dataclass(frozen=True)`
+    Dataclass,
+    /// `class Point(NamedTuple)` or `namedtuple("Point", ...)`
+    NamedTuple,
+    /// `class Movie(TypedDict, total=False)` or `TypedDict("Movie", {...})`
+    TypedDict,
+    /// Pydantic `BaseModel` and `BaseSettings` subclasses
+    Pydantic,
+    /// `@attr.s`, `@attrs.define`, `@define`, `@frozen`
+    Attrs,
+}
+
+impl PythonClassKind {
+    /// Detect the kind from a class's base classes and decorators, as written in the source
+    pub fn detect(bases: &[String], decorators: &[String]) -> Self {
+        for decorator in decorators {
+            // `dataclass(frozen=True)` → `dataclass`
+            let name = decorator.split('(').next().unwrap_or("").trim();
+            match name {
+                "dataclass" | "dataclasses.dataclass" | "pydantic.dataclasses.dataclass" => {
+                    return Self::Dataclass;
+                }
+                "attr.s" | "attr.attrs" | "attr.define" | "attr.frozen" | "attr.mutable"
+                | "attrs.define" | "attrs.frozen" | "attrs.mutable" | "define" | "frozen"
+                | "mutable" => return Self::Attrs,
+                _ => {}
+            }
+        }
+
+        for base in bases {
+            let name = base.split('[').next().unwrap_or("").trim();
+            match name.rsplit('.').next().unwrap_or(name) {
+                "NamedTuple" => return Self::NamedTuple,
+                "TypedDict" => return Self::TypedDict,
+                "BaseModel" | "BaseSettings" => return Self::Pydantic,
+                _ => {}
+            }
+        }
+
+        Self::Class
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Class => "class",
+            Self::Dataclass => "dataclass",
+            Self::NamedTuple => "NamedTuple",
+            Self::TypedDict => "TypedDict",
+            Self::Pydantic => "pydantic",
+            Self::Attrs => "attrs",
+        }
+    }
+
+    pub fn is_data_model(self) -> bool {
+        self != Self::Class
+    }
+}
+
+/// A Python class or data model with its fields and methods
+#[derive(Debug, Clone)]
+pub struct PythonClassDef {
+    pub name: String,
+    pub kind: PythonClassKind,
+    pub bases: Vec<String>,
+    pub decorators: Vec<String>,
+    pub fields: Vec<PythonFieldDef>,
+    pub methods: Vec<PythonMethodDef>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub file_path: String,
+}
+
+/// A class-level field (`id: int = 0`, `name = models.CharField()`) or a `self.x` assignment
+/// in `__init__`
+#[derive(Debug, Clone)]
+pub struct PythonFieldDef {
+    pub name: String,
+    /// The type annotation, or the field constructor for `attr.ib()`/`models.CharField()` style
+    /// fields
+    pub annotation: Option<String>,
+    /// Whether the field can be left out: it has a default, is `NotRequired`, or belongs to a
+    /// `total=False` TypedDict
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PythonMethodDef {
+    pub name: String,
+    /// Parameters without `self`/`cls`, as `name` or `name: type`
+    pub parameters: Vec<String>,
+    pub return_type: Option<String>,
+    pub decorators: Vec<String>,
+    pub body: Option<Rc<TreeNode>>,
+}
+
+impl PythonMethodDef {
+    /// `(name: str, *args) -> bool`
+    pub fn signature(&self) -> String {
+        match &self.return_type {
+            Some(return_type) => format!("({}) -> {}", self.parameters.join(", "), return_type),
+            None => format!("({})", self.parameters.join(", ")),
+        }
+    }
+}
+
+/// Resolve the kind of classes that inherit from a data model defined in the same set, e.g.
+/// `class User(Base)` where `class Base(BaseModel)`
+pub fn resolve_inherited_kinds(classes: &mut [PythonClassDef]) {
+    loop {
+        let mut changed = false;
+        for i in 0..classes.len() {
+            if classes[i].kind.is_data_model() {
+                continue;
+            }
+            let inherited = classes[i].bases.iter().find_map(|base| {
+                classes.iter().find(|other| &other.name == base && other.kind.is_data_model())
+            });
+            if let Some(kind) = inherited.map(|base| base.kind) {
+                classes[i].kind = kind;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+impl From<PythonClassDef> for Structure {
+    fn from(class_def: PythonClassDef) -> Self {
+        let fields = class_def.fields.into_iter().map(|field| StructureMember {
+            name: field.name,
+            value_type: field.annotation.unwrap_or_else(|| "Any".to_string()),
+            modifiers: if field.optional {
+                vec!["field".to_string(), "optional".to_string()]
+            } else {
+                vec!["field".to_string()]
+            },
+            nested: None,
+        });
+        let methods = class_def.methods.iter().map(|method| StructureMember {
+            name: method.name.clone(),
+            value_type: method.signature(),
+            modifiers: std::iter::once("method".to_string())
+                .chain(method.decorators.iter().cloned())
+                .collect(),
+            nested: None,
+        });
+
+        Structure {
+            identifier: StructureIdentifier {
+                name: class_def.name,
+                // Every style shares one kind so that a dataclass and a Pydantic model with the
+                // same fields are compared like two dataclasses
+                kind: StructureKind::PythonClass,
+                namespace: Some(class_def.file_path.clone()),
+            },
+            members: fields.chain(methods).collect(),
+            metadata: StructureMetadata {
+                location: SourceLocation {
+                    file_path: class_def.file_path,
+                    start_line: class_def.start_line,
+                    end_line: class_def.end_line,
+                },
+                generics: Vec::new(),
+                extends: class_def.bases,
+                visibility: None,
+            },
+        }
+    }
+}
+
+pub type PythonClassComparison = MethodBodyComparison;
+
+/// Comparison engine for Python classes
+pub struct PythonStructureComparator {
+    pub comparator: StructureComparator,
+    pub tsed_options: TSEDOptions,
+    /// Weight of the method bodies, see [`blend_method_bodies`]
+    pub method_body_weight: f64,
+}
+
+impl Default for PythonStructureComparator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PythonStructureComparator {
+    pub fn new() -> Self {
+        // Duplicated models rarely share a name (`User` and `Customer`), so the members decide
+        let options = ComparisonOptions {
+            name_weight: 0.1,
+            structure_weight: 0.9,
+            threshold: 0.7,
+            ..Default::default()
+        };
+        Self::with_options(options)
+    }
+
+    pub fn with_options(options: ComparisonOptions) -> Self {
+        Self {
+            comparator: StructureComparator::new(options),
+            // Method bodies are often a line or two long
+            tsed_options: TSEDOptions { size_penalty: false, ..TSEDOptions::default() },
+            method_body_weight: 0.5,
+        }
+    }
+
+    /// Compare two classes by their fields, method signatures and method bodies
+    pub fn compare_classes(
+        &mut self,
+        class1: &PythonClassDef,
+        class2: &PythonClassDef,
+    ) -> PythonClassComparison {
+        let structure = self
+            .comparator
+            .compare(&Structure::from(class1.clone()), &Structure::from(class2.clone()));
+
+        blend_method_bodies(
+            structure,
+            |member_match| {
+                let method1 = class1.methods.iter().find(|m| m.name == member_match.member1)?;
+                let method2 = class2.methods.iter().find(|m| m.name == member_match.member2)?;
+                Some((method1.body.as_ref()?, method2.body.as_ref()?))
+            },
+            self.method_body_weight,
+            &self.tsed_options,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, annotation: &str) -> PythonFieldDef {
+        PythonFieldDef {
+            name: name.to_string(),
+            annotation: Some(annotation.to_string()),
+            optional: false,
+        }
+    }
+
+    fn model(name: &str, kind: PythonClassKind, fields: Vec<PythonFieldDef>) -> PythonClassDef {
+        PythonClassDef {
+            name: name.to_string(),
+            kind,
+            bases: Vec::new(),
+            decorators: Vec::new(),
+            fields,
+            methods: Vec::new(),
+            start_line: 1,
+            end_line: 5,
+            file_path: "models.py".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_detect_kind() {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let detect = |bases: &[&str], decorators: &[&str]| {
+            PythonClassKind::detect(&strings(bases), &strings(decorators))
+        };
+        assert_eq!(detect(&[], &["dataclass(frozen=True)"]), PythonClassKind::Dataclass);
+        assert_eq!(detect(&[], &["attr.s(auto_attribs=True)"]), PythonClassKind::Attrs);
+        assert_eq!(detect(&["typing.NamedTuple"], &[]), PythonClassKind::NamedTuple);
+        assert_eq!(detect(&["TypedDict"], &[]), PythonClassKind::TypedDict);
+        assert_eq!(detect(&["pydantic.BaseModel"], &[]), PythonClassKind::Pydantic);
+        assert_eq!(detect(&["Exception"], &["total_ordering"]), PythonClassKind::Class);
+
+        let mut classes = vec![
+            model("Admin", PythonClassKind::Class, Vec::new()),
+            model("Base", PythonClassKind::Pydantic, Vec::new()),
+        ];
+        classes[0].bases = strings(&["Base"]);
+        resolve_inherited_kinds(&mut classes);
+        assert_eq!(classes[0].kind, PythonClassKind::Pydantic);
+    }
+
+    #[test]
+    fn test_models_of_different_styles_with_the_same_fields_are_similar() {
+        let fields = || vec![field("id", "int"), field("name", "str"), field("email", "str")];
+        let user = model("User", PythonClassKind::Dataclass, fields());
+        let customer = model("Customer", PythonClassKind::Pydantic, fields());
+        let mut retyped = model("Account", PythonClassKind::Class, fields());
+        retyped.fields[0].annotation = Some("str".to_string());
+        retyped.fields[2].annotation = Some("bool".to_string());
+
+        let mut comparator = PythonStructureComparator::new();
+        let same = comparator.compare_classes(&user, &customer);
+        assert!(same.similarity > 0.9, "similarity: {}", same.similarity);
+        assert_eq!(same.method_body_similarity, None);
+
+        let different = comparator.compare_classes(&user, &retyped);
+        assert!(different.similarity < same.similarity);
+    }
+}
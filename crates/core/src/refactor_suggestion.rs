@@ -0,0 +1,559 @@
+use crate::apted::{compute_edit_mapping, APTEDOptions, EditOperation};
+use crate::explain::{classify_rename, node_kind, node_name, HunkKind};
+use crate::tree::TreeNode;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Language used to render suggested signatures and call sites
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestionLanguage {
+    TypeScript,
+    Python,
+}
+
+/// One function of a cluster of similar functions
+#[derive(Debug, Clone)]
+pub struct CloneMember<'a> {
+    pub name: String,
+    /// Human readable location, e.g. `src/api.ts:10-24`
+    pub location: String,
+    pub parameters: Vec<String>,
+    /// Tree parsed from `source`, with spans relative to it
+    pub tree: Rc<TreeNode>,
+    pub source: &'a str,
+}
+
+/// Kind of sub-expression that varies between cluster members
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParameterKind {
+    Identifier,
+    Literal,
+}
+
+/// A varying sub-expression turned into a parameter of the shared function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionParameter {
+    pub name: String,
+    pub kind: ParameterKind,
+    /// Value passed by each member, in member order
+    pub values: Vec<String>,
+}
+
+/// How an existing function would call the shared function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallSite {
+    pub function: String,
+    pub location: String,
+    pub call: String,
+}
+
+/// Suggested extraction of a shared, parameterized function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefactoringSuggestion {
+    pub function_name: String,
+    pub signature: String,
+    pub parameters: Vec<ExtractionParameter>,
+    /// Body of the first member with varying parts replaced by parameter names
+    pub extracted: String,
+    pub call_sites: Vec<CallSite>,
+    /// Inserted, deleted or reshaped nodes that parameters cannot express
+    pub structural_differences: usize,
+}
+
+/// A varying position in the reference tree and the text each member has there
+struct Hole {
+    node: Rc<TreeNode>,
+    kind: ParameterKind,
+    values: Vec<String>,
+}
+
+/// Suggest a shared function for a cluster of similar functions
+///
+/// The first member is the reference: every other member is aligned to it, and leaves that
+/// differ (non-local identifiers and literals) become parameters of the shared function.
+/// Renamed locals and parameters are ignored, since the shared function can pick one name.
+/// Returns `None` for clusters with fewer than two members.
+pub fn suggest_parameterized_extraction(
+    members: &[CloneMember],
+    language: SuggestionLanguage,
+    options: &APTEDOptions,
+) -> Option<RefactoringSuggestion> {
+    let (reference, others) = members.split_first()?;
+    if others.is_empty() {
+        return None;
+    }
+
+    let mut locals = local_names(reference);
+    for member in others {
+        locals.extend(local_names(member));
+    }
+
+    // Tree-sitter trees keep identifier and literal text in `value`, so values must be compared
+    let options = APTEDOptions { compare_values: true, ..options.clone() };
+
+    let mut holes: Vec<Hole> = Vec::new();
+    let mut hole_index: HashMap<usize, usize> = HashMap::new();
+    let mut structural_differences = 0;
+
+    for (position, member) in others.iter().enumerate() {
+        for operation in compute_edit_mapping(&reference.tree, &member.tree, &options) {
+            let (node1, node2) = match operation {
+                EditOperation::Rename(node1, node2) => (node1, node2),
+                EditOperation::Delete(_) | EditOperation::Insert(_) => {
+                    structural_differences += 1;
+                    continue;
+                }
+                EditOperation::Match(_, _) => continue,
+            };
+
+            let kind = match classify_rename(&node1, &node2) {
+                HunkKind::LiteralChanged => ParameterKind::Literal,
+                HunkKind::Renamed if node1.children.is_empty() && node2.children.is_empty() => {
+                    if locals.contains(&node_name(&node1)) || locals.contains(&node_name(&node2)) {
+                        continue;
+                    }
+                    ParameterKind::Identifier
+                }
+                // Declarations keep their shape; only the bound name differs
+                HunkKind::Renamed => continue,
+                _ => {
+                    structural_differences += 1;
+                    continue;
+                }
+            };
+
+            let index = *hole_index.entry(node1.id).or_insert_with(|| {
+                let reference_text = hole_text(&node1, kind, reference.source);
+                holes.push(Hole {
+                    node: node1.clone(),
+                    kind,
+                    values: vec![reference_text; members.len()],
+                });
+                holes.len() - 1
+            });
+            holes[index].values[position + 1] = hole_text(&node2, kind, member.source);
+        }
+    }
+
+    // Occurrences with the same values across all members share one parameter
+    let mut parameters: Vec<ExtractionParameter> = Vec::new();
+    let mut hole_parameter: Vec<usize> = Vec::with_capacity(holes.len());
+    let mut taken: HashSet<String> = reference.parameters.iter().cloned().collect();
+    for hole in &holes {
+        if let Some(existing) =
+            parameters.iter().position(|p| p.kind == hole.kind && p.values == hole.values)
+        {
+            hole_parameter.push(existing);
+            continue;
+        }
+        let same_kind = parameters.iter().filter(|p| p.kind == hole.kind).count();
+        let name = parameter_name(hole, same_kind, language, &mut taken);
+        parameters.push(ExtractionParameter { name, kind: hole.kind, values: hole.values.clone() });
+        hole_parameter.push(parameters.len() - 1);
+    }
+
+    let function_name = shared_function_name(members, language);
+    let all_parameters: Vec<&str> = reference
+        .parameters
+        .iter()
+        .map(String::as_str)
+        .chain(parameters.iter().map(|p| p.name.as_str()))
+        .collect();
+    let signature = match language {
+        SuggestionLanguage::TypeScript => {
+            format!("function {}({})", function_name, all_parameters.join(", "))
+        }
+        SuggestionLanguage::Python => {
+            format!("def {}({}):", function_name, all_parameters.join(", "))
+        }
+    };
+
+    let replacements: Vec<(u32, u32, &str)> = holes
+        .iter()
+        .zip(&hole_parameter)
+        .filter_map(|(hole, &param)| {
+            hole.node.span.map(|(start, end)| (start, end, parameters[param].name.as_str()))
+        })
+        .collect();
+    let extracted = render_extracted(reference, &signature, &replacements, language);
+
+    let call_sites = members
+        .iter()
+        .enumerate()
+        .map(|(position, member)| {
+            let arguments: Vec<&str> = member
+                .parameters
+                .iter()
+                .map(String::as_str)
+                .chain(parameters.iter().map(|p| p.values[position].as_str()))
+                .collect();
+            CallSite {
+                function: member.name.clone(),
+                location: member.location.clone(),
+                call: format!("{}({})", function_name, arguments.join(", ")),
+            }
+        })
+        .collect();
+
+    Some(RefactoringSuggestion {
+        function_name,
+        signature,
+        parameters,
+        extracted,
+        call_sites,
+        structural_differences,
+    })
+}
+
+/// Group similar pairs (indices into a list of functions) into clusters
+///
+/// Pairs are joined transitively; every returned cluster has at least two members and
+/// lists its indices in ascending order.
+pub fn group_into_clusters(pairs: &[(usize, usize)], count: usize) -> Vec<Vec<usize>> {
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut parent: Vec<usize> = (0..count).collect();
+    for &(a, b) in pairs {
+        if a >= count || b >= count {
+            continue;
+        }
+        let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+        if root_a != root_b {
+            parent[root_b.max(root_a)] = root_a.min(root_b);
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut cluster_of_root: HashMap<usize, usize> = HashMap::new();
+    for i in 0..count {
+        let root = find(&mut parent, i);
+        let index = *cluster_of_root.entry(root).or_insert_with(|| {
+            clusters.push(Vec::new());
+            clusters.len() - 1
+        });
+        clusters[index].push(i);
+    }
+    clusters.retain(|c| c.len() > 1);
+    clusters
+}
+
+/// Render a suggestion as human readable text
+pub fn format_suggestion(suggestion: &RefactoringSuggestion) -> String {
+    let mut out = format!("Extract shared function: {}\n", suggestion.signature);
+    if suggestion.parameters.is_empty() {
+        out.push_str("  (members are identical up to local renames)\n");
+    }
+    for parameter in &suggestion.parameters {
+        let kind = match parameter.kind {
+            ParameterKind::Identifier => "identifier",
+            ParameterKind::Literal => "literal",
+        };
+        out.push_str(&format!(
+            "  {} ({}): {}\n",
+            parameter.name,
+            kind,
+            parameter.values.join(" | ")
+        ));
+    }
+    if suggestion.structural_differences > 0 {
+        out.push_str(&format!(
+            "  note: {} structural difference(s) need a callback or flag\n",
+            suggestion.structural_differences
+        ));
+    }
+    out.push('\n');
+    for line in suggestion.extracted.lines() {
+        out.push_str(&format!("  {}\n", line));
+    }
+    out.push_str("\nCall sites:\n");
+    for site in &suggestion.call_sites {
+        out.push_str(&format!("  {} {} -> {}\n", site.location, site.function, site.call));
+    }
+    out
+}
+
+// Names bound inside a member: its parameters, variable declarators (TypeScript) and
+// assignment / loop targets (tree-sitter grammars)
+fn local_names(member: &CloneMember) -> HashSet<String> {
+    fn visit(node: &TreeNode, names: &mut HashSet<String>) {
+        match node_kind(node) {
+            "Parameter" | "VariableDeclarator" | "BindingIdentifier" => {
+                names.insert(node_name(node));
+            }
+            "assignment" | "augmented_assignment" | "for_statement" | "for_in_clause" => {
+                if let Some(target) = node.children.iter().find(|c| c.label != "for") {
+                    if target.children.is_empty() {
+                        names.insert(node_name(target));
+                    }
+                }
+            }
+            _ => {}
+        }
+        for child in &node.children {
+            visit(child, names);
+        }
+    }
+
+    let mut names: HashSet<String> = member.parameters.iter().cloned().collect();
+    visit(&member.tree, &mut names);
+    names.remove("");
+    names
+}
+
+fn hole_text(node: &TreeNode, kind: ParameterKind, source: &str) -> String {
+    let snippet = node
+        .span
+        .and_then(|(start, end)| source.get(start as usize..end as usize))
+        .map(|s| s.trim().to_string());
+    match kind {
+        ParameterKind::Literal => snippet.unwrap_or_else(|| node_name(node)),
+        ParameterKind::Identifier => {
+            let name = node_name(node);
+            if name.is_empty() {
+                snippet.unwrap_or_default()
+            } else {
+                name
+            }
+        }
+    }
+}
+
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in name.chars() {
+        if c == '_' || c == '-' || !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else if c.is_uppercase() && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+            current.extend(c.to_lowercase());
+        } else {
+            current.extend(c.to_lowercase());
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn join_words(words: &[String], language: SuggestionLanguage) -> String {
+    match language {
+        SuggestionLanguage::Python => words.join("_"),
+        SuggestionLanguage::TypeScript => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                if i == 0 {
+                    w.clone()
+                } else {
+                    let mut chars = w.chars();
+                    chars
+                        .next()
+                        .map(|f| f.to_uppercase().chain(chars).collect())
+                        .unwrap_or_default()
+                }
+            })
+            .collect(),
+    }
+}
+
+// Longest run of words shared by every name, preferring a common prefix (`loadUsers`,
+// `loadAccounts` -> `load`) over a common suffix (`userCount`, `itemCount` -> `count`)
+fn common_words(names: &[&str]) -> Vec<String> {
+    let split: Vec<Vec<String>> = names.iter().map(|n| split_words(n)).collect();
+    let Some((first, rest)) = split.split_first() else {
+        return Vec::new();
+    };
+
+    let prefix_len = (0..first.len())
+        .take_while(|&i| rest.iter().all(|words| words.get(i) == Some(&first[i])))
+        .count();
+    if prefix_len > 0 {
+        return first[..prefix_len].to_vec();
+    }
+
+    let suffix_len = (1..=first.len())
+        .take_while(|&i| {
+            rest.iter()
+                .all(|words| words.len() >= i && words[words.len() - i] == first[first.len() - i])
+        })
+        .count();
+    first[first.len() - suffix_len..].to_vec()
+}
+
+fn parameter_name(
+    hole: &Hole,
+    index: usize,
+    language: SuggestionLanguage,
+    taken: &mut HashSet<String>,
+) -> String {
+    let base = match hole.kind {
+        ParameterKind::Identifier => {
+            let values: Vec<&str> = hole.values.iter().map(String::as_str).collect();
+            let words = common_words(&values);
+            if words.is_empty() || values.iter().any(|v| split_words(v) == words) {
+                format!("arg{}", index + 1)
+            } else {
+                join_words(&words, language)
+            }
+        }
+        ParameterKind::Literal => format!("value{}", index + 1),
+    };
+
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while taken.contains(&name) {
+        name = format!("{}{}", base, suffix);
+        suffix += 1;
+    }
+    taken.insert(name.clone());
+    name
+}
+
+fn shared_function_name(members: &[CloneMember], language: SuggestionLanguage) -> String {
+    let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+    let words = common_words(&names);
+    if !words.is_empty() && !names.iter().any(|n| split_words(n) == words) {
+        return join_words(&words, language);
+    }
+
+    let mut words = split_words(&members[0].name);
+    words.push("shared".to_string());
+    join_words(&words, language)
+}
+
+fn substitute(source: &str, offset: u32, replacements: &[(u32, u32, &str)]) -> String {
+    let mut sorted: Vec<&(u32, u32, &str)> = replacements.iter().collect();
+    sorted.sort_by_key(|(start, _, _)| *start);
+
+    let mut out = String::new();
+    let mut cursor = offset as usize;
+    for &&(start, end, name) in &sorted {
+        let (start, end) = (start as usize, end as usize);
+        if start < cursor || end > source.len() {
+            continue;
+        }
+        out.push_str(&source[cursor..start]);
+        out.push_str(name);
+        cursor = end;
+    }
+    out.push_str(&source[cursor..]);
+    out
+}
+
+fn render_extracted(
+    reference: &CloneMember,
+    signature: &str,
+    replacements: &[(u32, u32, &str)],
+    language: SuggestionLanguage,
+) -> String {
+    match language {
+        SuggestionLanguage::TypeScript => {
+            // Keep the function body and swap the original header for the new signature
+            let Some((start, end)) =
+                find_node(&reference.tree, "BlockStatement").and_then(|n| n.span)
+            else {
+                return substitute(reference.source, 0, replacements);
+            };
+            let inside: Vec<(u32, u32, &str)> =
+                replacements.iter().copied().filter(|(s, e, _)| *s >= start && *e <= end).collect();
+            let body = substitute(&reference.source[..end as usize], start, &inside);
+            format!("{} {}", signature, body)
+        }
+        SuggestionLanguage::Python => {
+            let body = substitute(reference.source, 0, replacements);
+            let mut out = signature.to_string();
+            for line in dedent(&body).lines() {
+                out.push('\n');
+                if !line.trim().is_empty() {
+                    out.push_str("    ");
+                    out.push_str(line);
+                }
+            }
+            out
+        }
+    }
+}
+
+fn find_node<'t>(node: &'t Rc<TreeNode>, kind: &str) -> Option<&'t Rc<TreeNode>> {
+    if node.value == kind {
+        return Some(node);
+    }
+    node.children.iter().find_map(|child| find_node(child, kind))
+}
+
+/// Strip the indentation common to all non-blank lines, e.g. so a body parses as top-level code
+pub fn dedent(text: &str) -> String {
+    let indent = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    text.lines().map(|l| l.get(indent..).unwrap_or(l.trim_start())).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_and_convert_to_tree;
+
+    fn member<'a>(name: &str, parameters: &[&str], source: &'a str) -> CloneMember<'a> {
+        CloneMember {
+            name: name.to_string(),
+            location: format!("a.ts:{}", name),
+            parameters: parameters.iter().map(|p| p.to_string()).collect(),
+            tree: parse_and_convert_to_tree("a.ts", source).unwrap(),
+            source,
+        }
+    }
+
+    fn options() -> APTEDOptions {
+        APTEDOptions { rename_cost: 0.3, compare_values: false, ..Default::default() }
+    }
+
+    #[test]
+    fn test_varying_callee_and_literal_become_parameters() {
+        let code1 = "function loadUsers(id) {\n  const data = fetchUsers(\"/users\", id);\n  return data;\n}";
+        let code2 = "function loadAccounts(key) {\n  const data = fetchAccounts(\"/accounts\", key);\n  return data;\n}";
+        let members =
+            vec![member("loadUsers", &["id"], code1), member("loadAccounts", &["key"], code2)];
+
+        let suggestion =
+            suggest_parameterized_extraction(&members, SuggestionLanguage::TypeScript, &options())
+                .unwrap();
+
+        assert_eq!(suggestion.function_name, "load");
+        assert_eq!(suggestion.signature, "function load(id, fetch, value1)");
+        assert_eq!(suggestion.parameters.len(), 2);
+        assert_eq!(suggestion.parameters[0].values, vec!["fetchUsers", "fetchAccounts"]);
+        assert_eq!(suggestion.parameters[1].values, vec!["\"/users\"", "\"/accounts\""]);
+        assert!(suggestion.extracted.starts_with("function load(id, fetch, value1) {"));
+        assert!(suggestion.extracted.contains("fetch(value1, id)"));
+        assert_eq!(suggestion.call_sites[1].call, "load(key, fetchAccounts, \"/accounts\")");
+        assert_eq!(suggestion.structural_differences, 0);
+    }
+
+    #[test]
+    fn test_group_into_clusters_is_transitive() {
+        let clusters = group_into_clusters(&[(0, 1), (3, 4), (1, 2)], 6);
+        assert_eq!(clusters, vec![vec![0, 1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn test_common_words() {
+        assert_eq!(common_words(&["load_users", "loadAccounts"]), vec!["load"]);
+        assert_eq!(common_words(&["userCount", "itemCount"]), vec!["count"]);
+        assert!(common_words(&["alpha", "beta"]).is_empty());
+    }
+}
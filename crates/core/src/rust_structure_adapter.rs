@@ -1,8 +1,12 @@
 use crate::structure_comparator::{
-    Structure, StructureIdentifier, StructureKind, StructureMember, StructureMetadata,
-    SourceLocation, StructureComparator, ComparisonOptions, StructureComparisonResult,
+    calculate_string_similarity, Structure, StructureIdentifier, StructureKind, StructureMember,
+    StructureMetadata, SourceLocation, StructureComparator, ComparisonOptions,
+    StructureComparisonResult, MemberMatch, StructureDifferences,
 };
 use crate::language_parser::GenericTypeDef;
+use crate::tree::TreeNode;
+use crate::tsed::{calculate_tsed, TSEDOptions};
+use std::rc::Rc;
 
 /// Rustの型定義を一般構造に変換
 impl From<GenericTypeDef> for Structure {
@@ -97,6 +101,8 @@ pub struct RustEnumDef {
 pub struct RustVariantDef {
     pub name: String,
     pub variant_type: RustVariantType,
+    /// Explicit discriminant as written (`= 1`, `= b'a' as isize`)
+    pub discriminant: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -106,6 +112,53 @@ pub enum RustVariantType {
     Struct(Vec<RustFieldDef>),
 }
 
+impl RustVariantType {
+    /// Payload as written: `unit`, `(u32, String)` or `{ x: f64, y: f64 }`
+    pub fn describe(&self) -> String {
+        match self {
+            RustVariantType::Unit => "unit".to_string(),
+            RustVariantType::Tuple(types) => format!("({})", types.join(", ")),
+            RustVariantType::Struct(fields) => {
+                let field_strs: Vec<String> =
+                    fields.iter().map(|f| format!("{}: {}", f.name, f.field_type)).collect();
+                format!("{{ {} }}", field_strs.join(", "))
+            }
+        }
+    }
+}
+
+/// A struct or an enum, as compared by `RustStructureComparator::compare_type_defs`
+#[derive(Debug, Clone)]
+pub enum RustTypeDef {
+    Struct(RustStructDef),
+    Enum(RustEnumDef),
+}
+
+impl RustTypeDef {
+    pub fn name(&self) -> &str {
+        match self {
+            RustTypeDef::Struct(struct_def) => &struct_def.name,
+            RustTypeDef::Enum(enum_def) => &enum_def.name,
+        }
+    }
+
+    pub fn start_line(&self) -> usize {
+        match self {
+            RustTypeDef::Struct(struct_def) => struct_def.start_line,
+            RustTypeDef::Enum(enum_def) => enum_def.start_line,
+        }
+    }
+
+    /// `struct`, `tuple struct` or `enum`
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            RustTypeDef::Struct(struct_def) if struct_def.is_tuple_struct => "tuple struct",
+            RustTypeDef::Struct(_) => "struct",
+            RustTypeDef::Enum(_) => "enum",
+        }
+    }
+}
+
 /// Rust構造体を一般構造に変換
 impl From<RustStructDef> for Structure {
     fn from(struct_def: RustStructDef) -> Self {
@@ -166,16 +219,11 @@ impl From<RustEnumDef> for Structure {
         let mut members: Vec<StructureMember> = enum_def.variants
             .into_iter()
             .map(|variant| {
-                let value_type = match variant.variant_type {
-                    RustVariantType::Unit => "unit".to_string(),
-                    RustVariantType::Tuple(ref types) => format!("({})", types.join(", ")),
-                    RustVariantType::Struct(ref fields) => {
-                        let field_strs: Vec<String> = fields
-                            .iter()
-                            .map(|f| format!("{}: {}", f.name, f.field_type))
-                            .collect();
-                        format!("{{ {} }}", field_strs.join(", "))
+                let value_type = match &variant.discriminant {
+                    Some(discriminant) => {
+                        format!("{} = {}", variant.variant_type.describe(), discriminant)
                     }
+                    None => variant.variant_type.describe(),
                 };
                 
                 StructureMember {
@@ -231,6 +279,7 @@ impl From<RustEnumDef> for Structure {
 /// Rust用の比較エンジン
 pub struct RustStructureComparator {
     pub comparator: StructureComparator,
+    options: ComparisonOptions,
 }
 
 impl RustStructureComparator {
@@ -242,29 +291,155 @@ impl RustStructureComparator {
             ..Default::default()
         };
         
-        Self {
-            comparator: StructureComparator::new(options),
-        }
+        Self::with_options(options)
     }
     
     pub fn with_options(options: ComparisonOptions) -> Self {
         Self {
-            comparator: StructureComparator::new(options),
+            comparator: StructureComparator::new(options.clone()),
+            options,
         }
     }
     
     /// 構造体を比較
+    ///
+    /// Tuple structs are compared field by field in order, with each other and with named
+    /// structs (`struct Point(f64, f64)` and `struct Point { x: f64, y: f64 }`).
     pub fn compare_structs(&mut self, struct1: &RustStructDef, struct2: &RustStructDef) -> StructureComparisonResult {
-        let s1 = Structure::from(struct1.clone());
-        let s2 = Structure::from(struct2.clone());
-        self.comparator.compare(&s1, &s2)
+        if !struct1.is_tuple_struct && !struct2.is_tuple_struct {
+            let s1 = Structure::from(struct1.clone());
+            let s2 = Structure::from(struct2.clone());
+            return self.comparator.compare(&s1, &s2);
+        }
+
+        let types1: Vec<String> = struct1.fields.iter().map(|f| f.field_type.clone()).collect();
+        let types2: Vec<String> = struct2.fields.iter().map(|f| f.field_type.clone()).collect();
+        let matches = (0..types1.len().min(types2.len()))
+            .map(|i| (i, i, rust_type_similarity(&types1[i], &types2[i])))
+            .filter(|&(_, _, similarity)| similarity > 0.0)
+            .collect();
+        let fields_similarity = positional_similarity(&types1, &types2);
+
+        self.type_comparison_result(
+            (&struct1.name, &struct2.name),
+            fields_similarity,
+            derive_similarity(&struct1.derives, &struct2.derives),
+            (&struct1.fields, &struct2.fields),
+            matches,
+        )
     }
     
     /// Enumを比較
+    ///
+    /// Variants are paired one to one by payload shape first (unit, tuple types in order,
+    /// struct fields by type), then by name and discriminant, so enums that differ only in
+    /// naming score high; derives count for a tenth of the structure score.
     pub fn compare_enums(&mut self, enum1: &RustEnumDef, enum2: &RustEnumDef) -> StructureComparisonResult {
-        let s1 = Structure::from(enum1.clone());
-        let s2 = Structure::from(enum2.clone());
-        self.comparator.compare(&s1, &s2)
+        let (variants_similarity, matches) = match_greedily(
+            &enum1.variants,
+            &enum2.variants,
+            variant_similarity,
+        );
+
+        self.type_comparison_result(
+            (&enum1.name, &enum2.name),
+            variants_similarity,
+            derive_similarity(&enum1.derives, &enum2.derives),
+            (&enum1.variants, &enum2.variants),
+            matches,
+        )
+    }
+
+    /// Compare an enum with a struct holding one `Option` per variant
+    /// (`enum Shape { Circle(f64), Square(f64) }` and
+    /// `struct Shape { circle: Option<f64>, square: Option<f64> }`)
+    pub fn compare_enum_with_struct(&mut self, enum_def: &RustEnumDef, struct_def: &RustStructDef) -> StructureComparisonResult {
+        let (similarity, matches) = match_greedily(
+            &enum_def.variants,
+            &struct_def.fields,
+            variant_field_similarity,
+        );
+
+        let mut result = self.type_comparison_result(
+            (&enum_def.name, &struct_def.name),
+            similarity,
+            derive_similarity(&enum_def.derives, &struct_def.derives),
+            (&enum_def.variants, &struct_def.fields),
+            matches,
+        );
+        // Payloads and `Option` fields never read the same
+        result.differences.type_mismatches.clear();
+        result
+    }
+
+    /// Compare two structs, two enums, or an enum with a struct of `Option`s
+    pub fn compare_type_defs(&mut self, type1: &RustTypeDef, type2: &RustTypeDef) -> StructureComparisonResult {
+        match (type1, type2) {
+            (RustTypeDef::Struct(s1), RustTypeDef::Struct(s2)) => self.compare_structs(s1, s2),
+            (RustTypeDef::Enum(e1), RustTypeDef::Enum(e2)) => self.compare_enums(e1, e2),
+            (RustTypeDef::Enum(e), RustTypeDef::Struct(s)) => self.compare_enum_with_struct(e, s),
+            (RustTypeDef::Struct(s), RustTypeDef::Enum(e)) => self.compare_enum_with_struct(e, s),
+        }
+    }
+
+    /// Combine member and derive scores the way `StructureComparator` combines name and
+    /// structure, listing the members each side lacks
+    fn type_comparison_result<A, B>(
+        &self,
+        (name1, name2): (&str, &str),
+        members_similarity: f64,
+        derives_similarity: f64,
+        (members1, members2): (&[A], &[B]),
+        matches: Vec<(usize, usize, f64)>,
+    ) -> StructureComparisonResult
+    where
+        A: DescribedMember,
+        B: DescribedMember,
+    {
+        let identifier_similarity = calculate_string_similarity(name1, name2);
+        let member_similarity = 0.9 * members_similarity + 0.1 * derives_similarity;
+        let overall_similarity = self.options.name_weight * identifier_similarity
+            + self.options.structure_weight * member_similarity;
+
+        let described1: Vec<(String, String)> = members1.iter().map(|m| m.describe()).collect();
+        let described2: Vec<(String, String)> = members2.iter().map(|m| m.describe()).collect();
+        let mut matched1 = vec![false; members1.len()];
+        let mut matched2 = vec![false; members2.len()];
+        let mut member_matches = Vec::new();
+        let mut type_mismatches = Vec::new();
+        for (i, j, similarity) in matches {
+            matched1[i] = true;
+            matched2[j] = true;
+            let ((name1, type1), (name2, type2)) = (&described1[i], &described2[j]);
+            member_matches.push(MemberMatch {
+                member1: name1.clone(),
+                member2: name2.clone(),
+                similarity,
+            });
+            if type1 != type2 {
+                type_mismatches.push((name1.clone(), type1.clone(), type2.clone()));
+            }
+        }
+        let unmatched = |described: &[(String, String)], matched: &[bool]| {
+            described
+                .iter()
+                .zip(matched)
+                .filter(|(_, &matched)| !matched)
+                .map(|((name, _), _)| name.clone())
+                .collect()
+        };
+
+        StructureComparisonResult {
+            overall_similarity,
+            identifier_similarity,
+            member_similarity,
+            member_matches,
+            differences: StructureDifferences {
+                missing_members: unmatched(&described1, &matched1),
+                extra_members: unmatched(&described2, &matched2),
+                type_mismatches,
+            },
+        }
     }
     
     /// 汎用型定義を比較
@@ -275,6 +450,317 @@ impl RustStructureComparator {
     }
 }
 
+/// Fields and variants as `(name, type or payload)` for comparison results
+trait DescribedMember {
+    fn describe(&self) -> (String, String);
+}
+
+impl DescribedMember for RustFieldDef {
+    fn describe(&self) -> (String, String) {
+        (self.name.clone(), self.field_type.clone())
+    }
+}
+
+impl DescribedMember for RustVariantDef {
+    fn describe(&self) -> (String, String) {
+        (self.name.clone(), self.variant_type.describe())
+    }
+}
+
+const NUMERIC_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    "f32", "f64",
+];
+const STRING_TYPES: &[&str] = &["String", "&str", "&'static str", "str", "Cow<str>", "Box<str>"];
+
+/// Similarity of two Rust types as written: equal, both numbers, both strings, or the same
+/// outer type (`Vec<u8>` and `Vec<String>`)
+fn rust_type_similarity(t1: &str, t2: &str) -> f64 {
+    let t1: String = t1.chars().filter(|c| !c.is_whitespace()).collect();
+    let t2: String = t2.chars().filter(|c| !c.is_whitespace()).collect();
+    if t1 == t2 {
+        return 1.0;
+    }
+    let both_in = |types: &[&str]| types.contains(&t1.as_str()) && types.contains(&t2.as_str());
+    if both_in(NUMERIC_TYPES) {
+        0.7
+    } else if both_in(STRING_TYPES) {
+        0.8
+    } else {
+        match (t1.split_once('<'), t2.split_once('<')) {
+            (Some((outer1, _)), Some((outer2, _))) if outer1 == outer2 => 0.5,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Types compared position by position, over the longer list
+fn positional_similarity(types1: &[String], types2: &[String]) -> f64 {
+    let max_len = types1.len().max(types2.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    let sum: f64 = types1.iter().zip(types2).map(|(t1, t2)| rust_type_similarity(t1, t2)).sum();
+    sum / max_len as f64
+}
+
+/// Names compared case- and underscore-insensitively, so `LastLogin` matches `last_login`
+fn member_name_similarity(name1: &str, name2: &str) -> f64 {
+    let normalize = |name: &str| name.replace('_', "").to_lowercase();
+    calculate_string_similarity(&normalize(name1), &normalize(name2))
+}
+
+fn derive_similarity(derives1: &[String], derives2: &[String]) -> f64 {
+    if derives1.is_empty() && derives2.is_empty() {
+        return 1.0;
+    }
+    let shared = derives1.iter().filter(|d| derives2.contains(d)).count();
+    shared as f64 / (derives1.len() + derives2.len() - shared) as f64
+}
+
+/// Pair members one to one, best scores first; the score is the sum of the pairs over the
+/// member count of the larger side
+fn match_greedily<A, B>(
+    members1: &[A],
+    members2: &[B],
+    similarity: impl Fn(&A, &B) -> f64,
+) -> (f64, Vec<(usize, usize, f64)>) {
+    let max_len = members1.len().max(members2.len());
+    if max_len == 0 {
+        return (1.0, Vec::new());
+    }
+
+    let mut candidates = Vec::new();
+    for (i, member1) in members1.iter().enumerate() {
+        for (j, member2) in members2.iter().enumerate() {
+            let score = similarity(member1, member2);
+            if score > 0.0 {
+                candidates.push((i, j, score));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut used1 = vec![false; members1.len()];
+    let mut used2 = vec![false; members2.len()];
+    let mut matches = Vec::new();
+    for (i, j, score) in candidates {
+        if !used1[i] && !used2[j] {
+            used1[i] = true;
+            used2[j] = true;
+            matches.push((i, j, score));
+        }
+    }
+    matches.sort_by_key(|&(i, _, _)| i);
+
+    let sum: f64 = matches.iter().map(|&(_, _, score)| score).sum();
+    (sum / max_len as f64, matches)
+}
+
+fn payload_similarity(payload1: &RustVariantType, payload2: &RustVariantType) -> f64 {
+    let field_types =
+        |fields: &[RustFieldDef]| fields.iter().map(|f| f.field_type.clone()).collect::<Vec<_>>();
+    match (payload1, payload2) {
+        (RustVariantType::Unit, RustVariantType::Unit) => 1.0,
+        (RustVariantType::Tuple(types1), RustVariantType::Tuple(types2)) => {
+            positional_similarity(types1, types2)
+        }
+        (RustVariantType::Struct(fields1), RustVariantType::Struct(fields2)) => {
+            match_greedily(fields1, fields2, |f1, f2| {
+                let type_similarity = rust_type_similarity(&f1.field_type, &f2.field_type);
+                if type_similarity == 0.0 {
+                    return 0.0;
+                }
+                0.7 * type_similarity + 0.3 * member_name_similarity(&f1.name, &f2.name)
+            })
+            .0
+        }
+        // `Move(i32, i32)` and `Move { x: i32, y: i32 }`
+        (RustVariantType::Tuple(types), RustVariantType::Struct(fields))
+        | (RustVariantType::Struct(fields), RustVariantType::Tuple(types)) => {
+            0.9 * positional_similarity(types, &field_types(fields))
+        }
+        _ => 0.0,
+    }
+}
+
+fn variant_similarity(variant1: &RustVariantDef, variant2: &RustVariantDef) -> f64 {
+    let payload = payload_similarity(&variant1.variant_type, &variant2.variant_type);
+    if payload == 0.0 {
+        return 0.0;
+    }
+    let discriminant = match (&variant1.discriminant, &variant2.discriminant) {
+        (None, None) => 1.0,
+        (Some(d1), Some(d2)) if d1 == d2 => 1.0,
+        (Some(_), Some(_)) => 0.5,
+        _ => 0.0,
+    };
+    0.7 * payload + 0.2 * member_name_similarity(&variant1.name, &variant2.name) + 0.1 * discriminant
+}
+
+/// Split a comma-separated type list at its top-level commas only, keeping
+/// `HashMap<String,u32>` and `(u8,u8)` whole
+fn split_top_level(types: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in types.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(types[start..i].to_string());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(types[start..].to_string());
+    parts
+}
+
+/// A variant against the `Option` field standing in for it: `Circle(f64)` and
+/// `circle: Option<f64>`, `Empty` and `empty: Option<()>`
+fn variant_field_similarity(variant: &RustVariantDef, field: &RustFieldDef) -> f64 {
+    let field_type: String = field.field_type.chars().filter(|c| !c.is_whitespace()).collect();
+    let Some(inner) = field_type.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) else {
+        return 0.0;
+    };
+
+    let payload = match &variant.variant_type {
+        RustVariantType::Unit => {
+            if inner == "()" { 1.0 } else { 0.0 }
+        }
+        RustVariantType::Tuple(types) if types.len() == 1 => rust_type_similarity(&types[0], inner),
+        RustVariantType::Tuple(types) => {
+            let inner_types: Vec<String> = inner
+                .strip_prefix('(')
+                .and_then(|t| t.strip_suffix(')'))
+                .map(split_top_level)
+                .unwrap_or_default();
+            positional_similarity(types, &inner_types)
+        }
+        // A struct payload would be a named type of its own
+        RustVariantType::Struct(_) => 0.5,
+    };
+    if payload == 0.0 {
+        return 0.0;
+    }
+    0.6 * payload + 0.4 * member_name_similarity(&variant.name, &field.name)
+}
+
+/// An `impl Type` or `impl Trait for Type` block
+#[derive(Debug, Clone)]
+pub struct RustImplDef {
+    pub self_type: String,
+    /// Trait as written, path and generic arguments included (`From<u8>`)
+    pub trait_name: Option<String>,
+    pub generics: Vec<String>,
+    pub methods: Vec<RustImplMethodDef>,
+    /// Associated types and constants (`type Output`, `const ZERO`)
+    pub associated_items: Vec<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub file_path: String,
+}
+
+impl RustImplDef {
+    /// `impl Display for Meters` or `impl Meters`
+    pub fn header(&self) -> String {
+        match &self.trait_name {
+            Some(trait_name) => format!("impl {} for {}", trait_name, self.self_type),
+            None => format!("impl {}", self.self_type),
+        }
+    }
+
+    /// Trait name without its path and generic arguments (`From` for `std::convert::From<u8>`)
+    pub fn trait_base_name(&self) -> Option<&str> {
+        let trait_name = self.trait_name.as_deref()?;
+        let without_generics = trait_name.split('<').next().unwrap_or(trait_name);
+        without_generics.rsplit("::").next()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RustImplMethodDef {
+    pub name: String,
+    /// `(&self, f: &mut fmt::Formatter) -> fmt::Result`
+    pub signature: String,
+    /// The whole method, signature included
+    pub tree: Rc<TreeNode>,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// A method of one impl block paired with a method of another
+#[derive(Debug, Clone)]
+pub struct RustMethodMatch {
+    /// Indices into the methods of each impl block
+    pub method1: usize,
+    pub method2: usize,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RustImplComparison {
+    /// Sum of the matched method similarities over the method count of the larger block
+    pub similarity: f64,
+    pub matches: Vec<RustMethodMatch>,
+    pub unmatched1: Vec<usize>,
+    pub unmatched2: Vec<usize>,
+}
+
+/// Compare two impl blocks method by method with TSED
+///
+/// Methods of trait implementations are paired by name; methods of inherent impls are paired
+/// one-to-one by similarity, whatever their names.
+pub fn compare_impl_blocks(
+    impl1: &RustImplDef,
+    impl2: &RustImplDef,
+    options: &TSEDOptions,
+) -> RustImplComparison {
+    let by_name = impl1.trait_name.is_some() && impl2.trait_name.is_some();
+
+    let mut candidates = Vec::new();
+    for (i, method1) in impl1.methods.iter().enumerate() {
+        for (j, method2) in impl2.methods.iter().enumerate() {
+            if by_name && method1.name != method2.name {
+                continue;
+            }
+            candidates.push((i, j, calculate_tsed(&method1.tree, &method2.tree, options)));
+        }
+    }
+    candidates.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut used1 = vec![false; impl1.methods.len()];
+    let mut used2 = vec![false; impl2.methods.len()];
+    let mut matches = Vec::new();
+    for (i, j, similarity) in candidates {
+        if used1[i] || used2[j] {
+            continue;
+        }
+        used1[i] = true;
+        used2[j] = true;
+        matches.push(RustMethodMatch { method1: i, method2: j, similarity });
+    }
+    matches.sort_by_key(|m| m.method1);
+
+    let method_count = impl1.methods.len().max(impl2.methods.len());
+    let similarity = if method_count == 0 {
+        0.0
+    } else {
+        matches.iter().map(|m| m.similarity).sum::<f64>() / method_count as f64
+    };
+    let unmatched = |used: &[bool]| (0..used.len()).filter(|&i| !used[i]).collect();
+
+    RustImplComparison {
+        similarity,
+        matches,
+        unmatched1: unmatched(&used1),
+        unmatched2: unmatched(&used2),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,10 +811,12 @@ mod tests {
                 RustVariantDef {
                     name: "Ok".to_string(),
                     variant_type: RustVariantType::Tuple(vec!["T".to_string()]),
+                    discriminant: None,
                 },
                 RustVariantDef {
                     name: "Err".to_string(),
                     variant_type: RustVariantType::Tuple(vec!["E".to_string()]),
+                    discriminant: None,
                 },
             ],
             generics: vec!["T".to_string(), "E".to_string()],
@@ -502,4 +990,38 @@ mod tests {
         assert_eq!(result2.member_matches.len(), 2);
         assert_eq!(result1.member_matches.len(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_trait_impls_are_compared_method_by_method() {
+        let leaf = |label: &str| Rc::new(TreeNode::new(label.to_string(), String::new(), 0));
+        let method = |name: &str, label: &str| RustImplMethodDef {
+            name: name.to_string(),
+            signature: "(&self)".to_string(),
+            tree: leaf(label),
+            start_line: 1,
+            end_line: 3,
+        };
+        let display = |self_type: &str, methods: Vec<RustImplMethodDef>| RustImplDef {
+            self_type: self_type.to_string(),
+            trait_name: Some("fmt::Display".to_string()),
+            generics: vec![],
+            methods,
+            associated_items: vec![],
+            start_line: 1,
+            end_line: 5,
+            file_path: "src/units.rs".to_string(),
+        };
+
+        let meters = display("Meters", vec![method("fmt", "block"), method("extra", "block")]);
+        let feet = display("Feet", vec![method("fmt", "block")]);
+        assert_eq!(meters.header(), "impl fmt::Display for Meters");
+        assert_eq!(meters.trait_base_name(), Some("Display"));
+
+        let options = TSEDOptions { size_penalty: false, ..TSEDOptions::default() };
+        let comparison = compare_impl_blocks(&meters, &feet, &options);
+        assert_eq!(comparison.matches.len(), 1);
+        assert_eq!((comparison.matches[0].method1, comparison.matches[0].method2), (0, 0));
+        assert_eq!(comparison.unmatched1, vec![1]);
+        assert!((comparison.similarity - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,263 @@
+use oxc_span::SourceType;
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Extensions of single-file component formats whose scripts are analyzed
+pub const SFC_EXTENSIONS: &[&str] = &["vue", "svelte", "astro"];
+
+/// A script inside a component file: the byte range of its content and its `lang` attribute
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptBlock {
+    pub start: usize,
+    pub end: usize,
+    pub lang: Option<String>,
+}
+
+pub fn is_sfc_path(file_path: &str) -> bool {
+    Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SFC_EXTENSIONS.contains(&ext))
+}
+
+/// Find the script blocks of a `.vue`, `.svelte` or `.astro` file
+///
+/// Covers `<script>` elements (Vue's `<script setup>`, Svelte's `context="module"`) and Astro's
+/// `---` frontmatter, which is always TypeScript. External (`src=`) and non-JavaScript
+/// (`type="application/json"`) scripts are skipped.
+pub fn find_script_blocks(file_path: &str, source: &str) -> Vec<ScriptBlock> {
+    let mut blocks = Vec::new();
+
+    if file_path.ends_with(".astro") {
+        if let Some(block) = astro_frontmatter(source) {
+            blocks.push(block);
+        }
+    }
+
+    let lower = source.to_ascii_lowercase();
+    let mut search_from = blocks.last().map_or(0, |block| block.end);
+    while let Some(found) = lower[search_from..].find("<script") {
+        let tag_start = search_from + found;
+        let after_name = tag_start + "<script".len();
+        // `<scripts>` or `<script-foo>` are other elements
+        if !lower[after_name..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace()) {
+            search_from = after_name;
+            continue;
+        }
+
+        let Some(tag_len) = lower[after_name..].find('>') else { break };
+        let attributes = &source[after_name..after_name + tag_len];
+        let content_start = after_name + tag_len + 1;
+        if attributes.trim_end().ends_with('/') {
+            search_from = content_start;
+            continue;
+        }
+
+        let Some(content_len) = lower[content_start..].find("</script") else { break };
+        let content_end = content_start + content_len;
+        search_from = content_end;
+
+        let is_javascript = match attribute(attributes, "type").as_deref() {
+            None
+            | Some("module" | "text/javascript" | "text/typescript" | "application/javascript") => {
+                true
+            }
+            Some(_) => false,
+        };
+        if is_javascript && attribute(attributes, "src").is_none() {
+            blocks.push(ScriptBlock {
+                start: content_start,
+                end: content_end,
+                lang: attribute(attributes, "lang"),
+            });
+        }
+    }
+
+    blocks
+}
+
+/// `---\n ... \n---` at the top of an Astro component
+fn astro_frontmatter(source: &str) -> Option<ScriptBlock> {
+    let fence = source.len() - source.trim_start().len();
+    let after_fence = source[fence..].strip_prefix("---")?;
+    let start = source.len() - after_fence.len() + after_fence.find('\n')? + 1;
+
+    let mut offset = start;
+    for line in source[start..].split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some(ScriptBlock { start, end: offset, lang: Some("ts".to_string()) });
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Value of `name="value"`, `name='value'` or `name=value` in a tag's attribute text
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes;
+    while let Some(position) = rest.find(name) {
+        let before = rest[..position].chars().last();
+        let after = rest[position + name.len()..].trim_start();
+        rest = &rest[position + name.len()..];
+        if before.is_some_and(|c| !c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or(""),
+            _ => value.split(|c: char| c.is_ascii_whitespace() || c == '/').next().unwrap_or(""),
+        };
+        return Some(value.to_ascii_lowercase());
+    }
+    None
+}
+
+/// The component file with everything outside its script blocks blanked out
+///
+/// Newlines are kept and every other byte becomes a space, so byte offsets and line numbers in
+/// the result are those of the component file.
+pub fn mask_to_scripts(source: &str, blocks: &[ScriptBlock]) -> String {
+    let mut masked = String::with_capacity(source.len());
+    let mut position = 0;
+    for block in blocks {
+        blank_out(&source[position..block.start], &mut masked);
+        masked.push_str(&source[block.start..block.end]);
+        position = block.end;
+    }
+    blank_out(&source[position..], &mut masked);
+    masked
+}
+
+fn blank_out(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '\n' | '\r' => out.push(c),
+            _ => out.extend(std::iter::repeat_n(' ', c.len_utf8())),
+        }
+    }
+}
+
+/// Source text and source type to hand to the oxc parser for `file_path`
+///
+/// Regular JavaScript/TypeScript files are returned unchanged. Component files are reduced to
+/// their scripts with [`mask_to_scripts`], parsed as TypeScript when a block has `lang="ts"`
+/// (or `tsx`/`jsx` accordingly) and as JavaScript modules otherwise.
+pub fn script_source<'a>(file_path: &str, source: &'a str) -> (Cow<'a, str>, SourceType) {
+    if !is_sfc_path(file_path) {
+        let source_type = SourceType::from_path(file_path).unwrap_or(SourceType::tsx());
+        return (Cow::Borrowed(source), source_type);
+    }
+
+    let blocks = find_script_blocks(file_path, source);
+    let source_type = match script_extension(&blocks) {
+        "tsx" => SourceType::tsx(),
+        "ts" => SourceType::ts(),
+        "jsx" => SourceType::jsx(),
+        _ => SourceType::mjs(),
+    };
+
+    (Cow::Owned(mask_to_scripts(source, &blocks)), source_type)
+}
+
+/// File name to parse code cut out of `file_path` (e.g. a function body) under
+///
+/// Regular files keep their own name so JSX and TypeScript parse as they do in the file.
+/// Component files are named after the language of their scripts, as in [`script_source`].
+pub fn snippet_file_name(file_path: &str, source: &str) -> String {
+    if !is_sfc_path(file_path) {
+        return file_path.to_string();
+    }
+    format!("component.{}", script_extension(&find_script_blocks(file_path, source)))
+}
+
+fn script_extension(blocks: &[ScriptBlock]) -> &'static str {
+    let langs: Vec<&str> = blocks.iter().filter_map(|block| block.lang.as_deref()).collect();
+    if langs.contains(&"tsx") {
+        "tsx"
+    } else if langs.contains(&"ts") {
+        "ts"
+    } else if langs.contains(&"jsx") {
+        "jsx"
+    } else {
+        "mjs"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vue_script_blocks_keep_line_numbers() {
+        let source = r#"<template>
+  <button @click="save">Save</button>
+</template>
+
+<script>
+export default { name: "SaveButton" };
+</script>
+
+<script setup lang="ts">
+const save = (): void => {};
+</script>
+
+<style scoped>
+button { color: red; }
+</style>
+"#;
+        let blocks = find_script_blocks("SaveButton.vue", source);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].lang, None);
+        assert_eq!(blocks[1].lang.as_deref(), Some("ts"));
+
+        let (masked, source_type) = script_source("SaveButton.vue", source);
+        assert!(source_type.is_typescript());
+        assert_eq!(masked.len(), source.len());
+        let lines: Vec<&str> = masked.lines().collect();
+        assert_eq!(lines.len(), source.lines().count());
+        assert_eq!(lines[5], r#"export default { name: "SaveButton" };"#);
+        assert_eq!(lines[9], "const save = (): void => {};");
+        assert!(lines[1].trim().is_empty());
+        assert!(lines[13].trim().is_empty());
+    }
+
+    #[test]
+    fn test_svelte_and_astro_scripts() {
+        let svelte = r#"<script context="module" lang="ts">
+  export const prerender = true;
+</script>
+<script type="application/ld+json">{"@type": "Thing"}</script>
+<script src="/analytics.js"></script>
+<h1>{title}</h1>
+"#;
+        let blocks = find_script_blocks("Page.svelte", svelte);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(&svelte[blocks[0].start..blocks[0].end], "\n  export const prerender = true;\n");
+
+        let astro = r#"---
+import Layout from "../layouts/Layout.astro";
+const { title } = Astro.props;
+---
+<Layout title={title}>
+  <script>
+    document.querySelector("h1")?.addEventListener("click", () => {});
+  </script>
+</Layout>
+"#;
+        let blocks = find_script_blocks("index.astro", astro);
+        assert_eq!(blocks.len(), 2);
+        assert!(astro[blocks[0].start..blocks[0].end].contains("Astro.props"));
+        assert!(astro[blocks[1].start..blocks[1].end].contains("addEventListener"));
+        assert_eq!(script_source("index.astro", astro).1, SourceType::ts());
+    }
+
+    #[test]
+    fn test_regular_files_are_unchanged() {
+        let (source, source_type) = script_source("util.js", "const a = 1;");
+        assert!(matches!(source, Cow::Borrowed("const a = 1;")));
+        assert_eq!(source_type, SourceType::from_path("util.js").unwrap());
+    }
+}
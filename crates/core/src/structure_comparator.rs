@@ -1,4 +1,7 @@
+use crate::tree::TreeNode;
+use crate::tsed::{calculate_tsed, TSEDOptions};
 use std::collections::HashMap;
+use std::rc::Rc;
 
 /// 一般化された構造定義
 #[derive(Debug, Clone)]
@@ -28,6 +31,13 @@ pub enum StructureKind {
     TypeScriptClass,
     RustStruct,
     RustEnum,
+    PythonClass,
+    PhpClass,
+    PhpInterface,
+    PhpTrait,
+    ElixirModule,
+    ElixirProtocol,
+    ElixirImplementation,
     CssRule,
     CssClass,
     Generic(String),
@@ -426,6 +436,56 @@ fn size_category_distance(cat1: &str, cat2: &str) -> usize {
     if pos1 > pos2 { pos1 - pos2 } else { pos2 - pos1 }
 }
 
+/// A structure comparison blended with the similarity of the matched method bodies
+#[derive(Debug, Clone)]
+pub struct MethodBodyComparison {
+    /// Structure similarity blended with the method body similarity
+    pub similarity: f64,
+    /// Comparison of the fields and method signatures
+    pub structure: StructureComparisonResult,
+    /// Average TSED of the bodies of methods matched by the structure comparison
+    pub method_body_similarity: Option<f64>,
+}
+
+/// Blend a structure comparison with the TSED of the bodies of its matched methods
+///
+/// `bodies` looks up the bodies of two matched members, returning `None` for fields and methods
+/// without a body. The bodies weigh `method_body_weight` when every matched member is a method;
+/// the weight shrinks with the share of matched methods, so the body of a model's one `__str__`
+/// hardly counts.
+pub fn blend_method_bodies<'a>(
+    structure: StructureComparisonResult,
+    bodies: impl Fn(&MemberMatch) -> Option<(&'a Rc<TreeNode>, &'a Rc<TreeNode>)>,
+    method_body_weight: f64,
+    options: &TSEDOptions,
+) -> MethodBodyComparison {
+    let body_similarities: Vec<f64> = structure
+        .member_matches
+        .iter()
+        .filter_map(|member_match| {
+            let (body1, body2) = bodies(member_match)?;
+            Some(calculate_tsed(body1, body2, options))
+        })
+        .collect();
+
+    if body_similarities.is_empty() {
+        return MethodBodyComparison {
+            similarity: structure.overall_similarity,
+            structure,
+            method_body_similarity: None,
+        };
+    }
+
+    let body_similarity = body_similarities.iter().sum::<f64>() / body_similarities.len() as f64;
+    let weight = method_body_weight * body_similarities.len() as f64
+        / structure.member_matches.len() as f64;
+    MethodBodyComparison {
+        similarity: structure.overall_similarity * (1.0 - weight) + body_similarity * weight,
+        structure,
+        method_body_similarity: Some(body_similarity),
+    }
+}
+
 /// 型を正規化
 fn normalize_type(type_str: &str) -> String {
     // Check for array patterns first (before checking for the base type)
@@ -433,7 +493,16 @@ fn normalize_type(type_str: &str) -> String {
         return "array".to_string();
     }
     
+    // Pythonの型ヒント
+    if type_str.starts_with("list[") || type_str.starts_with("List[") || type_str.starts_with("tuple[") || type_str.starts_with("Tuple[") {
+        return "array".to_string();
+    }
+    
     match type_str {
+        "str" | "bytes" => "string".to_string(),
+        "int" | "float" | "Decimal" => "number".to_string(),
+        "bool" => "boolean".to_string(),
+        s if s.starts_with("dict[") || s.starts_with("Dict[") => "object".to_string(),
         s if s.contains("string") => "string".to_string(),
         s if s.contains("number") => "number".to_string(),
         s if s.contains("boolean") => "boolean".to_string(),
@@ -443,7 +512,7 @@ fn normalize_type(type_str: &str) -> String {
 }
 
 /// 文字列の類似性を計算
-fn calculate_string_similarity(s1: &str, s2: &str) -> f64 {
+pub(crate) fn calculate_string_similarity(s1: &str, s2: &str) -> f64 {
     if s1 == s2 {
         return 1.0;
     }
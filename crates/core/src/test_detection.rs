@@ -0,0 +1,125 @@
+use std::path::Path;
+
+/// What a Jest/Vitest/Mocha/Playwright call declares
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TestCallKind {
+    /// `describe`, `context`, `suite`
+    Suite,
+    /// `it`, `test`, `specify`
+    Case,
+    /// `beforeEach`, `afterAll`, Mocha's `before`/`after`
+    Hook,
+}
+
+pub fn test_call_kind(name: &str) -> Option<TestCallKind> {
+    match name {
+        "describe" | "fdescribe" | "xdescribe" | "context" | "suite" => Some(TestCallKind::Suite),
+        "it" | "fit" | "xit" | "test" | "xtest" | "specify" => Some(TestCallKind::Case),
+        "beforeEach" | "afterEach" | "beforeAll" | "afterAll" | "before" | "after" => {
+            Some(TestCallKind::Hook)
+        }
+        _ => None,
+    }
+}
+
+/// Whether a file is test code by its path: `*.test.ts`, `*.spec.tsx`, `*.e2e.js`, or any file
+/// under a `__tests__`, `__mocks__`, `test` or `tests` directory inside the scanned `roots`
+///
+/// Directories above a root don't count, so a project checked out under `~/tests/` isn't all test
+/// code.
+pub fn is_test_file(file_path: &str, roots: &[String]) -> bool {
+    let path = Path::new(file_path);
+    let in_project = roots
+        .iter()
+        .filter_map(|root| path.strip_prefix(root).ok())
+        .min_by_key(|relative| relative.components().count())
+        .unwrap_or(path);
+    let in_test_dir = in_project.parent().is_some_and(|dir| {
+        dir.components().any(|component| {
+            matches!(
+                component.as_os_str().to_str(),
+                Some("__tests__" | "__mocks__" | "test" | "tests")
+            )
+        })
+    });
+    let has_test_suffix = path.file_name().and_then(|name| name.to_str()).is_some_and(|name| {
+        // `user.test.ts` and `user.spec.d.ts`, but not `test.ts` or `latest.ts`
+        name.split('.').skip(1).any(|part| matches!(part, "test" | "spec" | "e2e"))
+    });
+    in_test_dir || has_test_suffix
+}
+
+/// How test code takes part in duplicate detection
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TestCodeMode {
+    /// Tests are compared like any other code
+    #[default]
+    Include,
+    /// Pairs involving test code are dropped
+    Skip,
+    /// Only pairs of two pieces of test code are kept
+    Only,
+    /// Test code is compared with test code and production code with production code
+    Separate,
+}
+
+impl TestCodeMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "include" => Some(Self::Include),
+            "skip" => Some(Self::Skip),
+            "only" => Some(Self::Only),
+            "separate" => Some(Self::Separate),
+            _ => None,
+        }
+    }
+
+    /// Whether a pair is reported, given whether each side is test code
+    pub fn keeps(self, is_test1: bool, is_test2: bool) -> bool {
+        match self {
+            Self::Include => true,
+            Self::Skip => !is_test1 && !is_test2,
+            Self::Only => is_test1 && is_test2,
+            Self::Separate => is_test1 == is_test2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_test_file() {
+        let roots = [".".to_string()];
+        assert!(is_test_file("src/user.test.ts", &roots));
+        assert!(is_test_file("src/user.spec.tsx", &roots));
+        assert!(is_test_file("e2e/login.e2e.js", &roots));
+        assert!(is_test_file("./src/__tests__/user.ts", &roots));
+        assert!(is_test_file("packages/api/tests/fixtures.ts", &roots));
+        assert!(!is_test_file("src/test.ts", &roots));
+        assert!(!is_test_file("src/latest.ts", &roots));
+        assert!(!is_test_file("src/testing/helpers.ts", &roots));
+    }
+
+    #[test]
+    fn test_directories_above_the_root_are_not_test_dirs() {
+        let roots = ["/tmp/tests/proj".to_string()];
+        assert!(!is_test_file("/tmp/tests/proj/src/user.ts", &roots));
+        assert!(is_test_file("/tmp/tests/proj/tests/user.ts", &roots));
+        assert!(is_test_file("/tmp/tests/proj/src/user.test.ts", &roots));
+        // A file named on the command line is its own root
+        let roots = ["/tmp/tests/proj/src/user.ts".to_string()];
+        assert!(!is_test_file("/tmp/tests/proj/src/user.ts", &roots));
+    }
+
+    #[test]
+    fn test_modes() {
+        let pairs = [(false, false), (true, false), (true, true)];
+        let kept = |mode: TestCodeMode| pairs.map(|(a, b)| mode.keeps(a, b));
+        assert_eq!(kept(TestCodeMode::Include), [true, true, true]);
+        assert_eq!(kept(TestCodeMode::Skip), [true, false, false]);
+        assert_eq!(kept(TestCodeMode::Only), [false, false, true]);
+        assert_eq!(kept(TestCodeMode::Separate), [true, false, true]);
+    }
+}
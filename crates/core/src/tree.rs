@@ -7,12 +7,21 @@ pub struct TreeNode {
     pub children: Vec<Rc<TreeNode>>,
     pub id: usize,
     pub subtree_size: Option<usize>,
+    /// Byte range `(start, end)` of this node in the parsed source, when known
+    pub span: Option<(u32, u32)>,
 }
 
 impl TreeNode {
     #[must_use]
     pub fn new(label: String, value: String, id: usize) -> Self {
-        TreeNode { label, value, children: Vec::new(), id, subtree_size: None }
+        TreeNode { label, value, children: Vec::new(), id, subtree_size: None, span: None }
+    }
+
+    /// Attach the source byte range this node was converted from
+    #[must_use]
+    pub fn with_span(mut self, start: u32, end: u32) -> Self {
+        self.span = Some((start, end));
+        self
     }
 
     pub fn add_child(&mut self, child: Rc<TreeNode>) {
@@ -1,4 +1,5 @@
-use crate::apted::{compute_edit_distance, APTEDOptions};
+use crate::apted::{compute_edit_distance, compute_edit_distance_with_mapping, APTEDOptions};
+use crate::identifier_mapping::{apply_rename_consistency, identifier_mapping_from_operations};
 use crate::tree::TreeNode;
 use std::rc::Rc;
 
@@ -9,6 +10,7 @@ pub struct TSEDOptions {
     pub min_tokens: Option<u32>, // Minimum number of tokens (AST nodes) for a function to be considered
     pub size_penalty: bool,      // Apply penalty for short functions
     pub skip_test: bool,         // Skip test functions (language-specific)
+    pub rename_consistency: bool, // Reward consistent identifier renames, penalize conflicting ones
 }
 
 impl Default for TSEDOptions {
@@ -24,6 +26,7 @@ impl Default for TSEDOptions {
             min_tokens: None,   // No token limit by default
             size_penalty: true, // Enable size penalty by default
             skip_test: false,   // Don't skip test functions by default
+            rename_consistency: false,
         }
     }
 }
@@ -33,7 +36,14 @@ impl Default for TSEDOptions {
 #[must_use]
 #[allow(clippy::cast_precision_loss)]
 pub fn calculate_tsed(tree1: &Rc<TreeNode>, tree2: &Rc<TreeNode>, options: &TSEDOptions) -> f64 {
-    let distance = compute_edit_distance(tree1, tree2, &options.apted_options);
+    // Rename consistency reads the node mapping of the same APTED run
+    let (distance, operations) = if options.rename_consistency {
+        let (distance, operations) =
+            compute_edit_distance_with_mapping(tree1, tree2, &options.apted_options);
+        (distance, Some(operations))
+    } else {
+        (compute_edit_distance(tree1, tree2, &options.apted_options), None)
+    };
 
     let size1 = tree1.get_subtree_size() as f64;
     let size2 = tree2.get_subtree_size() as f64;
@@ -111,6 +121,16 @@ pub fn calculate_tsed(tree1: &Rc<TreeNode>, tree2: &Rc<TreeNode>, options: &TSED
         }
     }
 
+    if let Some(operations) = operations {
+        let mapping = identifier_mapping_from_operations(&operations);
+        similarity = apply_rename_consistency(
+            similarity,
+            &mapping,
+            max_size as usize,
+            &options.apted_options,
+        );
+    }
+
     similarity
 }
 
@@ -177,4 +197,19 @@ mod tests {
         // Should have lower similarity due to structural differences
         assert!(similarity < 0.7);
     }
+
+    #[test]
+    fn test_rename_consistency_refunds_alpha_renaming() {
+        let code1 = "function add(a: number, b: number) { return a + b; }";
+        let code2 = "function add(x: number, y: number) { return x + y; }";
+        let options = TSEDOptions { size_penalty: false, ..Default::default() };
+        let consistent = TSEDOptions { rename_consistency: true, ..options.clone() };
+
+        let plain =
+            calculate_tsed_from_code(code1, code2, "test1.ts", "test2.ts", &options).unwrap();
+        let refunded =
+            calculate_tsed_from_code(code1, code2, "test1.ts", "test2.ts", &consistent).unwrap();
+        assert!(plain < 1.0);
+        assert!((refunded - 1.0).abs() < 1e-9, "refunded similarity is {refunded}");
+    }
 }
@@ -1,7 +1,7 @@
-use crate::type_extractor::{TypeDefinition, TypeLiteralDefinition};
+use crate::type_extractor::{PropertyDefinition, TypeDefinition, TypeKind, TypeLiteralDefinition};
 use crate::type_normalizer::{
-    calculate_property_similarity, find_property_matches, normalize_type, NormalizationOptions,
-    NormalizedType, PropertyMatch,
+    calculate_property_similarity, calculate_type_similarity, find_property_matches,
+    normalize_type, NormalizationOptions, NormalizedType, PropertyMatch,
 };
 use std::collections::HashSet;
 
@@ -68,6 +68,19 @@ pub fn compare_types(
         return create_empty_comparison_result();
     }
 
+    // Enums, unions, tuples and function types are compared by their members, not by name
+    if !type1.kind.is_object_shaped() || !type2.kind.is_object_shaped() {
+        return match (&type1.kind, &type2.kind) {
+            (TypeKind::Enum | TypeKind::Union, TypeKind::Enum | TypeKind::Union) => {
+                compare_member_sets(type1, type2, options)
+            }
+            (TypeKind::Tuple, TypeKind::Tuple) | (TypeKind::Function, TypeKind::Function) => {
+                compare_positional_members(type1, type2, options)
+            }
+            _ => create_empty_comparison_result(),
+        };
+    }
+
     // Normalize both types
     let normalized1 = normalize_type(type1, &options.normalization_options);
     let normalized2 = normalize_type(type2, &options.normalization_options);
@@ -227,6 +240,190 @@ fn identify_differences(
     }
 }
 
+/// What an enum or union member stands for: the string value of `A = "a"` and of `'a'`, or
+/// the member name of other enum members and the type text of other union members
+fn member_key(kind: &TypeKind, member: &PropertyDefinition) -> String {
+    let value = match kind {
+        TypeKind::Enum => &member.type_annotation,
+        _ => &member.name,
+    };
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(string_value) => string_value.to_lowercase(),
+        None if *kind == TypeKind::Enum => member.name.to_lowercase(),
+        None => value.to_lowercase(),
+    }
+}
+
+/// Display name of an enum or union member (`Active`, `active`, `string`)
+fn member_name(member: &PropertyDefinition) -> &str {
+    member.name.trim_matches('"')
+}
+
+/// Member-set similarity of enums and unions, so that `enum Status { Active = "active" }`
+/// matches `type Status = "active"`
+fn compare_member_sets(
+    type1: &TypeDefinition,
+    type2: &TypeDefinition,
+    options: &TypeComparisonOptions,
+) -> TypeComparisonResult {
+    let members1 = &type1.properties;
+    let members2 = &type2.properties;
+    if members1.is_empty() || members2.is_empty() {
+        return create_empty_comparison_result();
+    }
+
+    let mut matched = Vec::new();
+    let mut used2 = HashSet::new();
+    for member1 in members1 {
+        let key = member_key(&type1.kind, member1);
+        if let Some((index, member2)) = members2.iter().enumerate().find(|(index, member2)| {
+            !used2.contains(index) && member_key(&type2.kind, member2) == key
+        }) {
+            used2.insert(index);
+            matched.push((member1, member2));
+        }
+    }
+
+    let structural_similarity =
+        (matched.len() * 2) as f64 / (members1.len() + members2.len()) as f64;
+    let member_naming = if matched.is_empty() {
+        0.0
+    } else {
+        matched
+            .iter()
+            .map(|(m1, m2)| calculate_property_similarity(member_name(m1), member_name(m2)))
+            .sum::<f64>()
+            / matched.len() as f64
+    };
+    let naming_similarity =
+        member_naming * 0.8 + calculate_property_similarity(&type1.name, &type2.name) * 0.2;
+
+    let mut type_mismatches = Vec::new();
+    if type1.kind == TypeKind::Enum && type2.kind == TypeKind::Enum {
+        for (m1, m2) in &matched {
+            if m1.type_annotation != m2.type_annotation {
+                type_mismatches.push(TypeMismatch {
+                    property: format!("{} -> {}", m1.name, m2.name),
+                    type1: m1.type_annotation.clone(),
+                    type2: m2.type_annotation.clone(),
+                });
+            }
+        }
+    }
+
+    TypeComparisonResult {
+        similarity: structural_similarity * options.structural_weight
+            + naming_similarity * options.naming_weight,
+        structural_similarity,
+        naming_similarity,
+        differences: TypeDifferences {
+            missing_properties: members1
+                .iter()
+                .filter(|m| !matched.iter().any(|(m1, _)| std::ptr::eq(*m1, *m)))
+                .map(|m| member_name(m).to_string())
+                .collect(),
+            extra_properties: members2
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !used2.contains(index))
+                .map(|(_, m)| member_name(m).to_string())
+                .collect(),
+            type_mismatches,
+            optionality_differences: Vec::new(),
+        },
+        matched_properties: matched
+            .iter()
+            .map(|(m1, m2)| MatchedProperty {
+                prop1: member_name(m1).to_string(),
+                prop2: member_name(m2).to_string(),
+                similarity: 1.0,
+            })
+            .collect(),
+    }
+}
+
+/// Position-by-position similarity of tuple elements and function parameters; the `return`
+/// entry of function types is compared separately
+fn compare_positional_members(
+    type1: &TypeDefinition,
+    type2: &TypeDefinition,
+    options: &TypeComparisonOptions,
+) -> TypeComparisonResult {
+    let split = |type_def: &TypeDefinition| -> (Vec<PropertyDefinition>, Option<String>) {
+        let mut members = type_def.properties.clone();
+        let return_type = if type_def.kind == TypeKind::Function {
+            members.pop().map(|ret| ret.type_annotation)
+        } else {
+            None
+        };
+        (members, return_type)
+    };
+    let (members1, return1) = split(type1);
+    let (members2, return2) = split(type2);
+
+    let mut type_mismatches = Vec::new();
+    let mut optionality_differences = Vec::new();
+    let mut matched_properties = Vec::new();
+    let mut type_score = 0.0;
+    let mut name_score = 0.0;
+
+    for (member1, member2) in members1.iter().zip(&members2) {
+        let similarity =
+            calculate_type_similarity(&member1.type_annotation, &member2.type_annotation);
+        type_score += similarity;
+        name_score += calculate_property_similarity(&member1.name, &member2.name);
+        if similarity < 1.0 {
+            type_mismatches.push(TypeMismatch {
+                property: format!("{} -> {}", member1.name, member2.name),
+                type1: member1.type_annotation.clone(),
+                type2: member2.type_annotation.clone(),
+            });
+        }
+        if member1.optional != member2.optional {
+            optionality_differences.push(format!("{} -> {}", member1.name, member2.name));
+        }
+        matched_properties.push(MatchedProperty {
+            prop1: member1.name.clone(),
+            prop2: member2.name.clone(),
+            similarity,
+        });
+    }
+
+    let aligned = members1.len().min(members2.len());
+    let mut positions = members1.len().max(members2.len());
+    if let (Some(return1), Some(return2)) = (&return1, &return2) {
+        let similarity = calculate_type_similarity(return1, return2);
+        type_score += similarity;
+        positions += 1;
+        if similarity < 1.0 {
+            type_mismatches.push(TypeMismatch {
+                property: "return".to_string(),
+                type1: return1.clone(),
+                type2: return2.clone(),
+            });
+        }
+    }
+
+    let structural_similarity = if positions == 0 { 1.0 } else { type_score / positions as f64 };
+    let element_naming = if aligned == 0 { 1.0 } else { name_score / aligned as f64 };
+    let naming_similarity =
+        element_naming * 0.8 + calculate_property_similarity(&type1.name, &type2.name) * 0.2;
+
+    TypeComparisonResult {
+        similarity: structural_similarity * options.structural_weight
+            + naming_similarity * options.naming_weight,
+        structural_similarity,
+        naming_similarity,
+        differences: TypeDifferences {
+            missing_properties: members1[aligned..].iter().map(|m| m.name.clone()).collect(),
+            extra_properties: members2[aligned..].iter().map(|m| m.name.clone()).collect(),
+            type_mismatches,
+            optionality_differences,
+        },
+        matched_properties,
+    }
+}
+
 /// Create an empty comparison result for cases where comparison is not possible
 fn create_empty_comparison_result() -> TypeComparisonResult {
     TypeComparisonResult {
@@ -371,7 +568,7 @@ pub fn compare_type_literal_with_type(
     // Convert type literal to TypeDefinition for comparison
     let temp_type_def = TypeDefinition {
         name: type_literal.name.clone(),
-        kind: crate::type_extractor::TypeKind::TypeLiteral,
+        kind: TypeKind::TypeLiteral,
         properties: type_literal.properties.clone(),
         generics: Vec::new(),
         extends: Vec::new(),
@@ -449,7 +646,7 @@ pub fn find_similar_type_literals_pairs(
                 type_literal1,
                 &TypeDefinition {
                     name: type_literal2.name.clone(),
-                    kind: crate::type_extractor::TypeKind::TypeLiteral,
+                    kind: TypeKind::TypeLiteral,
                     properties: type_literal2.properties.clone(),
                     generics: Vec::new(),
                     extends: Vec::new(),
@@ -475,7 +672,7 @@ pub fn find_similar_type_literals_pairs(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::type_extractor::{PropertyDefinition, TypeDefinition, TypeKind};
+    use crate::type_extractor::extract_types_from_code;
 
     fn create_test_type(name: &str, properties: Vec<(&str, &str, bool, bool)>) -> TypeDefinition {
         TypeDefinition {
@@ -576,4 +773,83 @@ mod tests {
         assert_eq!(similar_pairs[0].type1.name, "User");
         assert_eq!(similar_pairs[0].type2.name, "Person");
     }
+
+    fn compare_source(source: &str) -> TypeComparisonResult {
+        let types = extract_types_from_code(source, "test.ts").unwrap();
+        assert_eq!(types.len(), 2);
+        compare_types(&types[0], &types[1], &TypeComparisonOptions::default())
+    }
+
+    #[test]
+    fn test_enum_duplicated_by_string_literal_union() {
+        let result = compare_source(
+            r#"
+enum OrderStatus { Pending = "pending", Shipped = "shipped", Delivered = "delivered" }
+type OrderState = "pending" | "shipped" | "delivered";
+"#,
+        );
+        assert!(result.similarity > 0.9, "{}", result.similarity);
+        assert_eq!(result.matched_properties.len(), 3);
+
+        let result = compare_source(
+            r#"
+enum OrderStatus { Pending = "pending", Shipped = "shipped", Delivered = "delivered" }
+type Color = "red" | "green" | "blue";
+"#,
+        );
+        assert!(result.similarity < 0.3, "{}", result.similarity);
+    }
+
+    #[test]
+    fn test_enum_member_sets() {
+        let result = compare_source(
+            r#"
+enum Direction { Up, Down, Left, Right }
+enum Heading { Up, Down, Left }
+"#,
+        );
+        assert!((result.structural_similarity - 6.0 / 7.0).abs() < 1e-9);
+        assert_eq!(result.differences.missing_properties, ["Right"]);
+        assert!(result.differences.extra_properties.is_empty());
+    }
+
+    #[test]
+    fn test_tuples_compare_by_position() {
+        let same = compare_source(
+            r#"
+type Point = [x: number, y: number, label?: string];
+type Coordinate = [x: number, y: number, name?: string];
+"#,
+        );
+        let swapped = compare_source(
+            r#"
+type Entry = [string, number];
+type Pair = [number, string];
+"#,
+        );
+        assert!(same.structural_similarity > 0.99);
+        assert!(swapped.structural_similarity < 0.5, "{}", swapped.structural_similarity);
+        assert_eq!(swapped.differences.type_mismatches.len(), 2);
+    }
+
+    #[test]
+    fn test_function_types_compare_parameters_and_return() {
+        let result = compare_source(
+            r#"
+type UserLoader = (id: string, force?: boolean) => User;
+type AccountLoader = (id: string, force?: boolean) => Account;
+"#,
+        );
+        assert!(result.similarity > 0.7, "{}", result.similarity);
+        assert_eq!(result.differences.type_mismatches.len(), 1);
+        assert_eq!(result.differences.type_mismatches[0].property, "return");
+
+        let result = compare_source(
+            r#"
+type UserLoader = (id: string) => Promise<User>;
+interface Loader { id: string; return: Promise<User> }
+"#,
+        );
+        assert_eq!(result.similarity, 0.0);
+    }
 }
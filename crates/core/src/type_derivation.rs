@@ -0,0 +1,277 @@
+use crate::type_extractor::TypeDefinition;
+use crate::type_normalizer::normalize_type_name;
+
+/// Modifier wrapped around a derivation: every property made optional or required
+#[derive(Debug, Clone, PartialEq)]
+pub enum UtilityModifier {
+    Partial,
+    Required,
+}
+
+/// A type whose properties are a subset of another type's, possibly with every property
+/// made optional or required, so it can be written with `Pick`/`Omit`/`Partial`/`Required`
+#[derive(Debug, Clone)]
+pub struct TypeDerivation {
+    pub derived: TypeDefinition,
+    pub base: TypeDefinition,
+    /// Base properties the derived type keeps, in base declaration order
+    pub picked: Vec<String>,
+    /// Base properties the derived type leaves out
+    pub omitted: Vec<String>,
+    pub modifier: Option<UtilityModifier>,
+}
+
+impl TypeDerivation {
+    /// The equivalent utility type expression, e.g. `Partial<Pick<User, 'id' | 'name'>>`
+    ///
+    /// Uses `Pick` or `Omit`, whichever lists fewer keys.
+    pub fn suggestion(&self) -> String {
+        let keys = |names: &[String]| {
+            names.iter().map(|name| format!("'{name}'")).collect::<Vec<_>>().join(" | ")
+        };
+        let selection = if self.omitted.is_empty() {
+            self.base.name.clone()
+        } else if self.omitted.len() < self.picked.len() {
+            format!("Omit<{}, {}>", self.base.name, keys(&self.omitted))
+        } else {
+            format!("Pick<{}, {}>", self.base.name, keys(&self.picked))
+        };
+
+        match self.modifier {
+            Some(UtilityModifier::Partial) => format!("Partial<{selection}>"),
+            Some(UtilityModifier::Required) => format!("Required<{selection}>"),
+            None => selection,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeDerivationOptions {
+    /// Derived types with fewer properties are not reported (`{ id: string }` is a subset of
+    /// almost everything)
+    pub min_properties: usize,
+}
+
+impl Default for TypeDerivationOptions {
+    fn default() -> Self {
+        Self { min_properties: 2 }
+    }
+}
+
+/// Check whether `derived` can be written as a utility type of `base`
+///
+/// Every property of `derived` must exist in `base` with the same type. Optional modifiers
+/// must either match `base` or all be made optional (`Partial`) or required (`Required`).
+/// Exact copies are not derivations; they are reported by the similarity check.
+pub fn detect_type_derivation(
+    derived: &TypeDefinition,
+    base: &TypeDefinition,
+    options: &TypeDerivationOptions,
+) -> Option<TypeDerivation> {
+    if !derived.kind.is_object_shaped() || !base.kind.is_object_shaped() {
+        return None;
+    }
+    // Generic types would need their parameters mapped onto the base's
+    if !derived.generics.is_empty() || !base.generics.is_empty() {
+        return None;
+    }
+    if derived.properties.len() < options.min_properties
+        || derived.properties.len() > base.properties.len()
+    {
+        return None;
+    }
+
+    let mut matched = Vec::with_capacity(derived.properties.len());
+    for prop in &derived.properties {
+        let base_prop = base.properties.iter().find(|base_prop| base_prop.name == prop.name)?;
+        if normalize_type_name(&prop.type_annotation)
+            != normalize_type_name(&base_prop.type_annotation)
+        {
+            return None;
+        }
+        matched.push((prop, base_prop));
+    }
+
+    let modifier = if matched.iter().all(|(prop, base_prop)| prop.optional == base_prop.optional) {
+        None
+    } else if matched.iter().all(|(prop, _)| prop.optional) {
+        Some(UtilityModifier::Partial)
+    } else if matched.iter().all(|(prop, _)| !prop.optional) {
+        Some(UtilityModifier::Required)
+    } else {
+        return None;
+    };
+
+    let (picked, omitted): (Vec<String>, Vec<String>) = base
+        .properties
+        .iter()
+        .map(|base_prop| base_prop.name.clone())
+        .partition(|name| derived.properties.iter().any(|prop| &prop.name == name));
+
+    if omitted.is_empty() && modifier.is_none() {
+        return None;
+    }
+
+    Some(TypeDerivation { derived: derived.clone(), base: base.clone(), picked, omitted, modifier })
+}
+
+/// Find types that restate a subset of another type
+///
+/// Each derived type is reported once. Bases that can't be derived from anything themselves
+/// are preferred, then the base with the fewest left-out properties, then one in the same file.
+/// Of two types derivable from each other, only one is reported, based on the type whose
+/// properties are all required.
+pub fn find_type_derivations(
+    types: &[TypeDefinition],
+    options: &TypeDerivationOptions,
+) -> Vec<TypeDerivation> {
+    let candidates: Vec<Vec<(usize, TypeDerivation)>> = types
+        .iter()
+        .enumerate()
+        .map(|(i, derived)| {
+            types
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .filter_map(|(j, base)| {
+                    detect_type_derivation(derived, base, options).map(|derivation| (j, derivation))
+                })
+                .collect()
+        })
+        .collect();
+
+    let chosen: Vec<Option<&(usize, TypeDerivation)>> = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, derivations)| {
+            derivations.iter().min_by_key(|(base, derivation)| {
+                (
+                    !candidates[*base].is_empty(),
+                    derivation.omitted.len(),
+                    types[*base].file_path != types[i].file_path,
+                )
+            })
+        })
+        .collect();
+
+    // `Draft = Partial<Post>` and `Post = Required<Draft>` can't both be applied
+    let all_required = |index: usize| types[index].properties.iter().all(|prop| !prop.optional);
+    chosen
+        .iter()
+        .enumerate()
+        .filter_map(|(i, choice)| {
+            let &(base, ref derivation) = (*choice)?;
+            let mutual = chosen[base].is_some_and(|&(back, _)| back == i);
+            if mutual && (all_required(i), base) > (all_required(base), i) {
+                return None;
+            }
+            Some(derivation.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_extractor::extract_types_from_code;
+
+    const SOURCE: &str = r#"
+interface User {
+    id: string;
+    name: string;
+    email: string;
+    avatar?: string;
+    createdAt: Date;
+}
+
+interface UserSummary {
+    id: string;
+    name: string;
+}
+
+interface UserUpdate {
+    name?: string;
+    email?: string;
+}
+
+type UserDraft = {
+    id: string;
+    name: string;
+    email: string;
+    avatar?: string;
+};
+
+interface Settings {
+    theme: string;
+    language?: string;
+}
+
+interface SettingsPatch {
+    theme?: string;
+    language?: string;
+}
+
+interface StrictSettings {
+    theme: string;
+    language: string;
+}
+
+interface Mismatch {
+    id: number;
+    name: string;
+}
+"#;
+
+    fn suggestions() -> Vec<(String, String)> {
+        let types = extract_types_from_code(SOURCE, "types.ts").unwrap();
+        find_type_derivations(&types, &TypeDerivationOptions::default())
+            .into_iter()
+            .map(|d| (d.derived.name.clone(), d.suggestion()))
+            .collect()
+    }
+
+    #[test]
+    fn test_suggests_pick_omit_partial_and_required() {
+        assert_eq!(
+            suggestions(),
+            [
+                ("UserSummary".to_string(), "Pick<User, 'id' | 'name'>".to_string()),
+                ("UserUpdate".to_string(), "Partial<Pick<User, 'name' | 'email'>>".to_string()),
+                ("UserDraft".to_string(), "Omit<User, 'createdAt'>".to_string()),
+                ("SettingsPatch".to_string(), "Partial<Settings>".to_string()),
+                ("StrictSettings".to_string(), "Required<Settings>".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_types_derivable_from_each_other_are_reported_once() {
+        let source = r#"
+interface Draft {
+    title?: string;
+    body?: string;
+}
+
+interface Post {
+    title: string;
+    body: string;
+}
+"#;
+        let types = extract_types_from_code(source, "posts.ts").unwrap();
+        let derivations = find_type_derivations(&types, &TypeDerivationOptions::default());
+        let suggestions: Vec<(&str, String)> =
+            derivations.iter().map(|d| (d.derived.name.as_str(), d.suggestion())).collect();
+        assert_eq!(suggestions, [("Draft", "Partial<Post>".to_string())]);
+    }
+
+    #[test]
+    fn test_exact_copies_and_type_mismatches_are_not_derivations() {
+        let types = extract_types_from_code(SOURCE, "types.ts").unwrap();
+        let find = |name: &str| types.iter().find(|t| t.name == name).unwrap();
+        let options = TypeDerivationOptions::default();
+
+        assert!(detect_type_derivation(find("User"), find("User"), &options).is_none());
+        assert!(detect_type_derivation(find("Mismatch"), find("User"), &options).is_none());
+        assert!(detect_type_derivation(find("User"), find("UserSummary"), &options).is_none());
+    }
+}
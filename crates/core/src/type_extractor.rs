@@ -1,12 +1,15 @@
 use oxc_allocator::Allocator;
 use oxc_ast::ast::{
-    Expression, PropertyKey, Statement, TSInterfaceDeclaration, TSPropertySignature, TSType,
-    TSTypeAliasDeclaration, VariableDeclarator,
+    Expression, PropertyKey, Statement, TSEnumDeclaration, TSEnumMemberName,
+    TSInterfaceDeclaration, TSPropertySignature, TSTupleElement, TSType, TSTypeAliasDeclaration,
+    VariableDeclarator,
 };
 use oxc_parser::Parser;
-use oxc_span::SourceType;
+use oxc_span::GetSpan;
 use std::collections::HashMap;
 
+use crate::sfc::script_source;
+
 #[derive(Debug, Clone)]
 pub struct TypeDefinition {
     pub name: String,
@@ -18,11 +21,35 @@ pub struct TypeDefinition {
     pub end_line: usize,
     pub file_path: String,
 }
+/// Kind of a type definition
+///
+/// Object-shaped kinds keep their fields in `properties`. The other kinds store their members
+/// there too, as described on each variant.
 #[derive(Debug, Clone, PartialEq)]
 pub enum TypeKind {
     Interface,
     TypeAlias,
     TypeLiteral,
+    /// `enum`: one property per member, with its initializer (or implicit index) as the type
+    Enum,
+    /// Type alias of a union: one property per member type, named by the member itself
+    Union,
+    /// Type alias of a tuple: one property per element, named by its label or position
+    Tuple,
+    /// Type alias of a function type: the parameters, then a `return` property
+    Function,
+}
+
+impl TypeKind {
+    /// Whether the type is object-shaped and compared property by property
+    pub fn is_object_shaped(&self) -> bool {
+        matches!(self, TypeKind::Interface | TypeKind::TypeAlias | TypeKind::TypeLiteral)
+    }
+
+    /// Whether the definition is written with `type X = ...`
+    pub fn is_type_alias(&self) -> bool {
+        matches!(self, TypeKind::TypeAlias | TypeKind::Union | TypeKind::Tuple | TypeKind::Function)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -82,8 +109,8 @@ impl TypeExtractor {
 
     pub fn extract_types(&self) -> Result<Vec<TypeDefinition>, String> {
         let allocator = Allocator::default();
-        let source_type = SourceType::from_path(&self.file_path).unwrap_or(SourceType::tsx());
-        let ret = Parser::new(&allocator, &self.source_text, source_type).parse();
+        let (script, source_type) = script_source(&self.file_path, &self.source_text);
+        let ret = Parser::new(&allocator, &script, source_type).parse();
 
         if !ret.errors.is_empty() {
             // Create a more readable error message
@@ -106,6 +133,9 @@ impl TypeExtractor {
                         types.push(type_def);
                     }
                 }
+                Statement::TSEnumDeclaration(enum_decl) => {
+                    types.push(self.extract_enum(enum_decl));
+                }
                 _ => {}
             }
         }
@@ -115,8 +145,8 @@ impl TypeExtractor {
 
     pub fn extract_type_literals(&self) -> Result<Vec<TypeLiteralDefinition>, String> {
         let allocator = Allocator::default();
-        let source_type = SourceType::from_path(&self.file_path).unwrap_or(SourceType::tsx());
-        let ret = Parser::new(&allocator, &self.source_text, source_type).parse();
+        let (script, source_type) = script_source(&self.file_path, &self.source_text);
+        let ret = Parser::new(&allocator, &script, source_type).parse();
 
         if !ret.errors.is_empty() {
             // Create a more readable error message
@@ -160,21 +190,238 @@ impl TypeExtractor {
         let start_line = self.get_line_number(type_alias.span.start as usize);
         let end_line = self.get_line_number(type_alias.span.end as usize);
 
-        let properties = self.extract_type_properties(&type_alias.type_annotation);
+        let (kind, properties) = match &type_alias.type_annotation {
+            TSType::TSUnionType(union_type) => (
+                TypeKind::Union,
+                union_type
+                    .types
+                    .iter()
+                    .map(|member| {
+                        let member = self.extract_member_type_string(member);
+                        PropertyDefinition {
+                            name: member.clone(),
+                            type_annotation: member,
+                            optional: false,
+                            readonly: false,
+                        }
+                    })
+                    .collect(),
+            ),
+            TSType::TSTupleType(tuple_type) => (
+                TypeKind::Tuple,
+                tuple_type
+                    .element_types
+                    .iter()
+                    .enumerate()
+                    .map(|(index, element)| self.extract_tuple_element(index, element))
+                    .collect(),
+            ),
+            TSType::TSFunctionType(func_type) => {
+                let mut properties = self.extract_function_param_properties(&func_type.params);
+                properties.push(PropertyDefinition {
+                    name: "return".to_string(),
+                    type_annotation: self
+                        .extract_type_string(&func_type.return_type.type_annotation),
+                    optional: false,
+                    readonly: false,
+                });
+                (TypeKind::Function, properties)
+            }
+            annotation => (TypeKind::TypeAlias, self.extract_type_properties(annotation)),
+        };
         let generics = self.extract_generics(type_alias.type_parameters.as_ref());
 
+        // `A & B & { x: T }` keeps the literal members as properties and the referenced
+        // types as parents, the same way `interface X extends A, B { x: T }` is recorded
+        let (properties, extends) = match &type_alias.type_annotation {
+            TSType::TSIntersectionType(intersection) => {
+                let mut properties = Vec::new();
+                let mut extends = Vec::new();
+                for member in &intersection.types {
+                    match member {
+                        TSType::TSTypeLiteral(_) => {
+                            properties.extend(self.extract_type_properties(member))
+                        }
+                        TSType::TSTypeReference(_) => {
+                            extends.push(self.extract_type_string(member))
+                        }
+                        _ => {}
+                    }
+                }
+                (properties, extends)
+            }
+            _ => (properties, Vec::new()),
+        };
+
         Some(TypeDefinition {
             name,
-            kind: TypeKind::TypeAlias,
+            kind,
             properties,
             generics,
-            extends: Vec::new(), // Type aliases don't have extends
+            extends,
             start_line,
             end_line,
             file_path: self.file_path.clone(),
         })
     }
 
+    fn extract_enum(&self, enum_decl: &TSEnumDeclaration) -> TypeDefinition {
+        let mut next_index = 0.0;
+        let properties = enum_decl
+            .body
+            .members
+            .iter()
+            .map(|member| {
+                let name = match &member.id {
+                    TSEnumMemberName::Identifier(ident) => ident.name.to_string(),
+                    TSEnumMemberName::String(str_lit)
+                    | TSEnumMemberName::ComputedString(str_lit) => str_lit.value.to_string(),
+                    TSEnumMemberName::ComputedTemplateString(template) => self.source_text
+                        [template.span.start as usize..template.span.end as usize]
+                        .to_string(),
+                };
+                // Members without an initializer continue numbering from the previous one
+                let value = match &member.initializer {
+                    Some(Expression::StringLiteral(str_lit)) => format!("\"{}\"", str_lit.value),
+                    Some(Expression::NumericLiteral(num_lit)) => {
+                        next_index = num_lit.value + 1.0;
+                        num_lit.value.to_string()
+                    }
+                    Some(init) => self.source_text
+                        [init.span().start as usize..init.span().end as usize]
+                        .to_string(),
+                    None => {
+                        next_index += 1.0;
+                        (next_index - 1.0).to_string()
+                    }
+                };
+                PropertyDefinition { name, type_annotation: value, optional: false, readonly: true }
+            })
+            .collect();
+
+        TypeDefinition {
+            name: enum_decl.id.name.to_string(),
+            kind: TypeKind::Enum,
+            properties,
+            generics: Vec::new(),
+            extends: Vec::new(),
+            start_line: self.get_line_number(enum_decl.span.start as usize),
+            end_line: self.get_line_number(enum_decl.span.end as usize),
+            file_path: self.file_path.clone(),
+        }
+    }
+
+    fn extract_tuple_element(&self, index: usize, element: &TSTupleElement) -> PropertyDefinition {
+        match element {
+            TSTupleElement::TSNamedTupleMember(member) => {
+                let element = self.extract_tuple_element(index, &member.element_type);
+                PropertyDefinition {
+                    name: member.label.name.to_string(),
+                    optional: element.optional || member.optional,
+                    ..element
+                }
+            }
+            TSTupleElement::TSOptionalType(optional_type) => PropertyDefinition {
+                name: index.to_string(),
+                type_annotation: self.extract_type_string(&optional_type.type_annotation),
+                optional: true,
+                readonly: false,
+            },
+            TSTupleElement::TSRestType(rest_type) => {
+                // `...rest: T[]` is a rest type wrapping a labeled member
+                let element = match &rest_type.type_annotation {
+                    TSType::TSNamedTupleMember(member) => {
+                        let element = self.extract_tuple_element(index, &member.element_type);
+                        PropertyDefinition { name: member.label.name.to_string(), ..element }
+                    }
+                    ts_type => PropertyDefinition {
+                        name: index.to_string(),
+                        type_annotation: self.extract_type_string(ts_type),
+                        optional: false,
+                        readonly: false,
+                    },
+                };
+                PropertyDefinition {
+                    type_annotation: format!("...{}", element.type_annotation),
+                    ..element
+                }
+            }
+            element => PropertyDefinition {
+                name: index.to_string(),
+                type_annotation: element
+                    .as_ts_type()
+                    .map(|ts_type| self.extract_type_string(ts_type))
+                    .unwrap_or_else(|| "unknown".to_string()),
+                optional: false,
+                readonly: false,
+            },
+        }
+    }
+
+    /// Union members keep object literals spelled out, so that two different object members
+    /// don't both collapse to `object`
+    fn extract_member_type_string(&self, ts_type: &TSType) -> String {
+        match ts_type {
+            TSType::TSTypeLiteral(type_literal) => {
+                let fields: Vec<String> = self
+                    .extract_interface_properties(&type_literal.members)
+                    .iter()
+                    .map(|prop| {
+                        let optional = if prop.optional { "?" } else { "" };
+                        format!("{}{}: {}", prop.name, optional, prop.type_annotation)
+                    })
+                    .collect();
+                format!("{{ {} }}", fields.join("; "))
+            }
+            ts_type => self.extract_type_string(ts_type),
+        }
+    }
+
+    fn extract_function_param_properties(
+        &self,
+        params: &oxc_ast::ast::FormalParameters,
+    ) -> Vec<PropertyDefinition> {
+        let mut properties: Vec<PropertyDefinition> = params
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, param)| PropertyDefinition {
+                name: self.get_parameter_name(param).unwrap_or_else(|| index.to_string()),
+                type_annotation: param
+                    .pattern
+                    .type_annotation
+                    .as_ref()
+                    .map(|ta| self.extract_type_string(&ta.type_annotation))
+                    .unwrap_or_else(|| "any".to_string()),
+                optional: param.pattern.optional,
+                readonly: false,
+            })
+            .collect();
+
+        if let Some(rest) = &params.rest {
+            let name = match &rest.argument.kind {
+                oxc_ast::ast::BindingPatternKind::BindingIdentifier(ident) => {
+                    ident.name.to_string()
+                }
+                _ => properties.len().to_string(),
+            };
+            let type_annotation = rest
+                .argument
+                .type_annotation
+                .as_ref()
+                .map(|ta| self.extract_type_string(&ta.type_annotation))
+                .unwrap_or_else(|| "any[]".to_string());
+            properties.push(PropertyDefinition {
+                name,
+                type_annotation: format!("...{type_annotation}"),
+                optional: false,
+                readonly: false,
+            });
+        }
+
+        properties
+    }
+
     fn extract_interface_properties(
         &self,
         signatures: &[oxc_ast::ast::TSSignature],
@@ -272,12 +519,13 @@ impl TypeExtractor {
             TSType::TSVoidKeyword(_) => "void".to_string(),
             TSType::TSNullKeyword(_) => "null".to_string(),
             TSType::TSUndefinedKeyword(_) => "undefined".to_string(),
-            TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
-                oxc_ast::ast::TSTypeName::IdentifierReference(ident) => {
-                    ident.name.as_str().to_string()
+            TSType::TSTypeReference(type_ref) => {
+                let name = type_name_string(&type_ref.type_name);
+                match &type_ref.type_arguments {
+                    Some(args) => format!("{name}<{}>", self.extract_type_arguments(args)),
+                    None => name,
                 }
-                _ => "unknown".to_string(),
-            },
+            }
             TSType::TSArrayType(array_type) => {
                 let element_type = self.extract_type_string(&array_type.element_type);
                 format!("{element_type}[]")
@@ -310,6 +558,10 @@ impl TypeExtractor {
         }
     }
 
+    fn extract_type_arguments(&self, args: &oxc_ast::ast::TSTypeParameterInstantiation) -> String {
+        args.params.iter().map(|t| self.extract_type_string(t)).collect::<Vec<_>>().join(", ")
+    }
+
     fn extract_function_params(&self, params: &oxc_ast::ast::FormalParameters) -> String {
         let param_strings: Vec<String> = params
             .items
@@ -354,11 +606,16 @@ impl TypeExtractor {
         if let Some(heritage_clauses) = extends {
             heritage_clauses
                 .iter()
-                .filter_map(|heritage| match &heritage.expression {
-                    oxc_ast::ast::Expression::Identifier(ident) => {
-                        Some(ident.name.as_str().to_string())
-                    }
-                    _ => None,
+                .filter_map(|heritage| {
+                    let name = match &heritage.expression {
+                        oxc_ast::ast::Expression::Identifier(ident) => ident.name.to_string(),
+                        _ => return None,
+                    };
+                    // Keep type arguments so `extends Base<string>` can be resolved later
+                    Some(match &heritage.type_arguments {
+                        Some(args) => format!("{name}<{}>", self.extract_type_arguments(args)),
+                        None => name,
+                    })
                 })
                 .collect()
         } else {
@@ -502,6 +759,16 @@ impl TypeExtractor {
     }
 }
 
+/// `Foo` or `ns.Foo`
+fn type_name_string(type_name: &oxc_ast::ast::TSTypeName) -> String {
+    match type_name {
+        oxc_ast::ast::TSTypeName::IdentifierReference(ident) => ident.name.to_string(),
+        oxc_ast::ast::TSTypeName::QualifiedName(qualified) => {
+            format!("{}.{}", type_name_string(&qualified.left), qualified.right.name)
+        }
+    }
+}
+
 /// Extract types from source code
 pub fn extract_types_from_code(
     source_text: &str,
@@ -617,7 +884,9 @@ type User = {
 
         let status_type = &types[0];
         assert_eq!(status_type.name, "Status");
-        assert_eq!(status_type.kind, TypeKind::TypeAlias);
+        assert_eq!(status_type.kind, TypeKind::Union);
+        let members: Vec<&str> = status_type.properties.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(members, ["\"active\"", "\"inactive\"", "\"pending\""]);
 
         let user_type = &types[1];
         assert_eq!(user_type.name, "User");
@@ -660,4 +929,68 @@ interface User extends BaseUser {
         assert_eq!(user_type.name, "User");
         assert_eq!(user_type.extends, vec!["BaseUser"]);
     }
+
+    #[test]
+    fn test_extract_enums_tuples_and_function_types() {
+        let source = r#"
+enum Level { Low = 1, Medium, High = "high" }
+type Pair = [key: string, value?: number, ...rest: boolean[]];
+type Handler = (event: Event, ...args: string[]) => void;
+type Id = string | { raw: number };
+"#;
+
+        let types = extract_types_from_code(source, "test.ts").unwrap();
+        let kinds: Vec<&TypeKind> = types.iter().map(|t| &t.kind).collect();
+        assert_eq!(
+            kinds,
+            [&TypeKind::Enum, &TypeKind::Tuple, &TypeKind::Function, &TypeKind::Union]
+        );
+
+        let values: Vec<(&str, &str)> = types[0]
+            .properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_str()))
+            .collect();
+        assert_eq!(values, [("Low", "1"), ("Medium", "2"), ("High", "\"high\"")]);
+
+        let elements: Vec<(&str, &str, bool)> = types[1]
+            .properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_str(), p.optional))
+            .collect();
+        assert_eq!(
+            elements,
+            [("key", "string", false), ("value", "number", true), ("rest", "...boolean[]", false)]
+        );
+
+        let signature: Vec<(&str, &str)> = types[2]
+            .properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_str()))
+            .collect();
+        assert_eq!(signature, [("event", "Event"), ("args", "...string[]"), ("return", "void")]);
+
+        assert_eq!(types[3].properties[1].name, "{ raw: number }");
+    }
+
+    #[test]
+    fn test_extract_intersection_alias_and_generic_parents() {
+        let source = r#"
+interface Paged extends Page<User, Cursor> {}
+type Admin = User & Auditable & { role: string; permissions?: Map<string, boolean> };
+"#;
+
+        let types = extract_types_from_code(source, "test.ts").unwrap();
+        assert_eq!(types[0].extends, vec!["Page<User, Cursor>"]);
+
+        let admin = &types[1];
+        assert_eq!(admin.kind, TypeKind::TypeAlias);
+        assert_eq!(admin.extends, vec!["User", "Auditable"]);
+        let properties: Vec<(&str, &str)> = admin
+            .properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_str()))
+            .collect();
+        assert_eq!(properties, [("role", "string"), ("permissions", "Map<string, boolean>")]);
+    }
 }
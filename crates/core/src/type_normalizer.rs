@@ -1,4 +1,4 @@
-use crate::type_extractor::{TypeDefinition, TypeKind};
+use crate::type_extractor::{PropertyDefinition, TypeDefinition, TypeKind};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone)]
@@ -17,6 +17,9 @@ pub struct NormalizationOptions {
     pub ignore_optional_modifiers: bool,
     pub ignore_readonly_modifiers: bool,
     pub normalize_type_names: bool,
+    /// Rename type parameters by position (`T0`, `T1`, ...) so `Box<T>` and `Box<U>` match;
+    /// off by default so existing scores are unchanged
+    pub normalize_type_parameters: bool,
 }
 
 impl Default for NormalizationOptions {
@@ -26,6 +29,7 @@ impl Default for NormalizationOptions {
             ignore_optional_modifiers: false,
             ignore_readonly_modifiers: true,
             normalize_type_names: true,
+            normalize_type_parameters: false,
         }
     }
 }
@@ -36,13 +40,25 @@ pub fn normalize_type(type_def: &TypeDefinition, options: &NormalizationOptions)
     let mut optional_properties = HashSet::new();
     let mut readonly_properties = HashSet::new();
 
+    let type_parameters: HashMap<&str, String> = if options.normalize_type_parameters {
+        type_def
+            .generics
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.as_str(), format!("T{index}")))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
     // Process each property
     for prop in &type_def.properties {
         let normalized_prop_name = prop.name.to_lowercase().trim().to_string();
+        let type_annotation = rename_type_identifiers(&prop.type_annotation, &type_parameters);
         let normalized_type = if options.normalize_type_names {
-            normalize_type_name(&prop.type_annotation)
+            normalize_type_name(&type_annotation)
         } else {
-            prop.type_annotation.clone()
+            type_annotation
         };
 
         properties.insert(normalized_prop_name.clone(), normalized_type);
@@ -384,6 +400,147 @@ pub fn find_property_matches(
     matches
 }
 
+/// Replace whole identifiers in a type string, leaving string literal types untouched
+fn rename_type_identifiers<S: AsRef<str>>(type_str: &str, renames: &HashMap<&str, S>) -> String {
+    if renames.is_empty() {
+        return type_str.to_string();
+    }
+
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut result = String::with_capacity(type_str.len());
+    let mut quote: Option<char> = None;
+    let mut chars = type_str.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+                result.push(c);
+            }
+            None if c == '"' || c == '\'' || c == '`' => {
+                quote = Some(c);
+                result.push(c);
+            }
+            None if is_ident_char(c) => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(offset, next)) = chars.peek() {
+                    if !is_ident_char(next) {
+                        break;
+                    }
+                    end = offset + next.len_utf8();
+                    chars.next();
+                }
+                let ident = &type_str[start..end];
+                result.push_str(renames.get(ident).map_or(ident, |renamed| renamed.as_ref()));
+            }
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+/// Split `Base<A, Map<K, V>>` into `Base` and its top-level type arguments
+fn split_type_reference(reference: &str) -> (&str, Vec<&str>) {
+    let Some(open) = reference.find('<') else {
+        return (reference.trim(), Vec::new());
+    };
+    let inner = reference[open + 1..].strip_suffix('>').unwrap_or(&reference[open + 1..]);
+
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut arg_start = 0;
+    for (offset, c) in inner.char_indices() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(inner[arg_start..offset].trim());
+                arg_start = offset + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(inner[arg_start..].trim());
+    (reference[..open].trim(), args)
+}
+
+/// Flatten `extends` clauses and intersection parents into each type's own properties
+///
+/// Parents are looked up by name among `types`, preferring a declaration in the same file and
+/// otherwise requiring the name to be unique. Inherited property types are instantiated with
+/// the type arguments of the reference (`extends Page<User>`), and properties declared on the
+/// type itself win over inherited ones. Parents outside the analyzed set are skipped.
+pub fn resolve_type_hierarchy(types: &[TypeDefinition]) -> Vec<TypeDefinition> {
+    let mut resolved = vec![None; types.len()];
+    (0..types.len())
+        .map(|index| TypeDefinition {
+            properties: flattened_properties(types, index, &mut resolved, &mut Vec::new()),
+            ..types[index].clone()
+        })
+        .collect()
+}
+
+fn flattened_properties(
+    types: &[TypeDefinition],
+    index: usize,
+    resolved: &mut Vec<Option<Vec<PropertyDefinition>>>,
+    visiting: &mut Vec<usize>,
+) -> Vec<PropertyDefinition> {
+    if let Some(properties) = &resolved[index] {
+        return properties.clone();
+    }
+
+    let type_def = &types[index];
+    let mut properties = type_def.properties.clone();
+    visiting.push(index);
+    for reference in &type_def.extends {
+        let (name, args) = split_type_reference(reference);
+        let Some(parent_index) = find_parent_type(types, name, &type_def.file_path) else {
+            continue;
+        };
+        // Circular inheritance is a TypeScript error; just stop following it
+        if visiting.contains(&parent_index) {
+            continue;
+        }
+
+        let parent = &types[parent_index];
+        let type_arguments: HashMap<&str, &str> =
+            parent.generics.iter().map(String::as_str).zip(args.iter().copied()).collect();
+        for inherited in flattened_properties(types, parent_index, resolved, visiting) {
+            if properties.iter().any(|prop| prop.name == inherited.name) {
+                continue;
+            }
+            properties.push(PropertyDefinition {
+                type_annotation: rename_type_identifiers(
+                    &inherited.type_annotation,
+                    &type_arguments,
+                ),
+                ..inherited
+            });
+        }
+    }
+    visiting.pop();
+
+    resolved[index] = Some(properties.clone());
+    properties
+}
+
+fn find_parent_type(types: &[TypeDefinition], name: &str, file_path: &str) -> Option<usize> {
+    let candidates: Vec<usize> = types
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.name == name && t.kind.is_object_shaped())
+        .map(|(index, _)| index)
+        .collect();
+
+    match candidates.iter().find(|&&index| types[index].file_path == file_path) {
+        Some(&index) => Some(index),
+        None if candidates.len() == 1 => Some(candidates[0]),
+        None => None,
+    }
+}
+
 /// Calculate Levenshtein distance between two strings
 fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     let len1 = s1.chars().count();
@@ -501,6 +658,77 @@ mod tests {
         assert!(calculate_union_type_similarity("string | number", "string | boolean") < 1.0);
     }
 
+    #[test]
+    fn test_type_parameters_are_alpha_normalized() {
+        let mut boxed = create_test_type("Box", vec![("value", "T", false, false)]);
+        boxed.generics = vec!["T".to_string()];
+        let mut wrapper = create_test_type("Wrapper", vec![("value", "Item", false, false)]);
+        wrapper.generics = vec!["Item".to_string()];
+
+        let options = NormalizationOptions::default();
+        assert_eq!(normalize_type(&wrapper, &options).properties["value"], "Item");
+
+        let options = NormalizationOptions { normalize_type_parameters: true, ..options };
+        assert_eq!(normalize_type(&boxed, &options).properties["value"], "T0");
+        assert_eq!(normalize_type(&wrapper, &options).properties["value"], "T0");
+    }
+
+    #[test]
+    fn test_rename_type_identifiers() {
+        let renames = HashMap::from([("T", "string"), ("K", "T")]);
+        assert_eq!(
+            rename_type_identifiers("Map<K, T[]> | \"T\" | Type", &renames),
+            "Map<T, string[]> | \"T\" | Type"
+        );
+        assert_eq!(
+            split_type_reference("Page<Map<K, V>, string>"),
+            ("Page", vec!["Map<K, V>", "string"])
+        );
+        assert_eq!(split_type_reference("Base"), ("Base", vec![]));
+    }
+
+    #[test]
+    fn test_resolve_type_hierarchy() {
+        let mut entity = create_test_type("Entity", vec![("id", "string", false, false)]);
+        entity.extends = vec!["User".to_string()]; // cycle through User
+        let mut page = create_test_type(
+            "Page",
+            vec![("items", "T[]", false, false), ("total", "number", false, false)],
+        );
+        page.generics = vec!["T".to_string()];
+        let mut user = create_test_type(
+            "User",
+            vec![("id", "number", false, false), ("name", "string", false, false)],
+        );
+        user.extends = vec!["Entity".to_string(), "Unknown".to_string()];
+        let mut users = create_test_type("UserPage", vec![("query", "string", false, false)]);
+        users.extends = vec!["Page<User>".to_string()];
+
+        let resolved = resolve_type_hierarchy(&[entity, page, user, users]);
+        let properties = |index: usize| -> Vec<(String, String)> {
+            resolved[index]
+                .properties
+                .iter()
+                .map(|p| (p.name.clone(), p.type_annotation.clone()))
+                .collect()
+        };
+
+        // Own properties win over inherited ones
+        assert_eq!(
+            properties(2),
+            [("id".to_string(), "number".to_string()), ("name".to_string(), "string".to_string())]
+        );
+        assert_eq!(
+            properties(3),
+            [
+                ("query".to_string(), "string".to_string()),
+                ("items".to_string(), "User[]".to_string()),
+                ("total".to_string(), "number".to_string()),
+            ]
+        );
+        assert_eq!(resolved[3].extends, vec!["Page<User>"]);
+    }
+
     #[test]
     fn test_levenshtein_distance() {
         assert_eq!(levenshtein_distance("", ""), 0);
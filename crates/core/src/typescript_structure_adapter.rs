@@ -12,6 +12,10 @@ impl From<TypeDefinition> for Structure {
             TypeKind::Interface => StructureKind::TypeScriptInterface,
             TypeKind::TypeAlias => StructureKind::TypeScriptTypeAlias,
             TypeKind::TypeLiteral => StructureKind::TypeScriptTypeLiteral,
+            TypeKind::Enum => StructureKind::Generic("TypeScriptEnum".to_string()),
+            TypeKind::Union => StructureKind::Generic("TypeScriptUnion".to_string()),
+            TypeKind::Tuple => StructureKind::Generic("TypeScriptTuple".to_string()),
+            TypeKind::Function => StructureKind::Generic("TypeScriptFunctionType".to_string()),
         };
         
         Structure {
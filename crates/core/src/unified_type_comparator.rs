@@ -46,6 +46,10 @@ impl UnifiedType {
                 TypeKind::Interface => "interface",
                 TypeKind::TypeAlias => "type",
                 TypeKind::TypeLiteral => "type-literal",
+                TypeKind::Enum => "enum",
+                TypeKind::Union => "union",
+                TypeKind::Tuple => "tuple",
+                TypeKind::Function => "function-type",
             }
             .to_string(),
             UnifiedType::TypeLiteral(_) => "type-literal".to_string(),
@@ -1,4 +1,7 @@
-use similarity_core::{compare_functions, extract_functions, TSEDOptions};
+use similarity_core::{
+    compare_functions, extract_functions, parse_and_convert_to_tree, TSEDOptions, TreeNode,
+};
+use std::collections::HashSet;
 
 #[test]
 fn test_different_functions_should_not_have_high_similarity() {
@@ -250,3 +253,181 @@ function countOccurrences(text: string, char: string): number {
         similarity * 100.0
     );
 }
+
+#[test]
+fn test_loop_and_try_block_are_not_equal() {
+    // Statements other than if/return used to become opaque `Statement` leaves, so a `for`
+    // loop and a `try` block compared as equal and these scored 88%
+    let code1 = r#"
+function collectNames(users: User[]): string[] {
+    const names: string[] = [];
+    for (const user of users) {
+        names.push(user.name);
+    }
+    return names;
+}
+"#;
+
+    let code2 = r#"
+function loadConfig(path: string): Config {
+    const config: Config = {};
+    try {
+        Object.assign(config, readJson(path));
+    } catch (error) {
+        logger.warn(error);
+    }
+    return config;
+}
+"#;
+
+    let options = TSEDOptions { size_penalty: false, ..Default::default() };
+
+    let funcs1 = extract_functions("test1.ts", code1).unwrap();
+    let funcs2 = extract_functions("test2.ts", code2).unwrap();
+
+    let similarity = compare_functions(&funcs1[0], &funcs2[0], code1, code2, &options).unwrap();
+
+    assert!(
+        similarity < 0.60,
+        "collectNames vs loadConfig similarity is {:.2}%, expected < 60%",
+        similarity * 100.0
+    );
+}
+
+#[test]
+fn test_renamed_clone_with_switch_and_await_is_detected() {
+    // With switch, await, member access and templates as opaque leaves these trees had 9
+    // nodes, and the short-function penalty pushed a renamed copy down to 19%
+    let code1 = r#"
+async function fetchStatus(client: Client, id: string): Promise<string> {
+    const response = await client.get(`/jobs/${id}`);
+    switch (response.status) {
+        case 200:
+            return response.body.state ?? "unknown";
+        case 404:
+            throw new Error(`Job ${id} not found`);
+        default:
+            return response.ok ? "pending" : "failed";
+    }
+}
+"#;
+
+    let code2 = r#"
+async function fetchOrderState(api: Api, orderId: string): Promise<string> {
+    const result = await api.get(`/orders/${orderId}`);
+    switch (result.status) {
+        case 200:
+            return result.body.state ?? "unknown";
+        case 404:
+            throw new Error(`Order ${orderId} not found`);
+        default:
+            return result.ok ? "pending" : "failed";
+    }
+}
+"#;
+
+    let options = TSEDOptions { size_penalty: true, ..Default::default() };
+
+    let funcs1 = extract_functions("test1.ts", code1).unwrap();
+    let funcs2 = extract_functions("test2.ts", code2).unwrap();
+
+    let similarity = compare_functions(&funcs1[0], &funcs2[0], code1, code2, &options).unwrap();
+
+    assert!(
+        similarity > 0.85,
+        "fetchStatus vs fetchOrderState similarity is {:.2}%, expected > 85%",
+        similarity * 100.0
+    );
+}
+
+fn collect_kinds(node: &TreeNode, kinds: &mut HashSet<String>) {
+    kinds.insert(node.value.clone());
+    for child in &node.children {
+        collect_kinds(child, kinds);
+    }
+}
+
+#[test]
+fn test_converter_labels_every_statement_and_expression() {
+    let code = r#"
+export class Queue<T> {
+    #items: T[] = [];
+
+    async drain(limit = 10): Promise<T[]> {
+        const { length } = this.#items;
+        const taken = [...this.#items.slice(0, limit)];
+        let i = 0;
+        outer: while (i < length) {
+            do {
+                i++;
+            } while (i % 2 === 0);
+            for (const key in { a: 1 }) {
+                if (!key) continue outer;
+            }
+            break;
+        }
+        try {
+            await Promise.resolve(new Map<string, T>());
+        } catch (error) {
+            throw error instanceof Error ? error : new Error(`failed: ${error}`);
+        } finally {
+            this.#items = [];
+        }
+        return taken as T[];
+    }
+}
+
+type Handler = (event: { type: "click" | "key" }) => void;
+"#;
+
+    let tree = parse_and_convert_to_tree("queue.ts", code).unwrap();
+    let mut kinds = HashSet::new();
+    collect_kinds(&tree, &mut kinds);
+
+    assert!(!kinds.contains("Statement"), "no opaque statement leaves");
+    assert!(!kinds.contains("Expression"), "no opaque expression leaves");
+    for kind in [
+        "ClassDeclaration",
+        "MethodDefinition",
+        "PropertyDefinition",
+        "ObjectPattern",
+        "AssignmentPattern",
+        "ArrayExpression",
+        "SpreadElement",
+        "ObjectExpression",
+        "LabeledStatement",
+        "WhileStatement",
+        "DoWhileStatement",
+        "ForInStatement",
+        "ContinueStatement",
+        "BreakStatement",
+        "TryStatement",
+        "CatchClause",
+        "ThrowStatement",
+        "AwaitExpression",
+        "NewExpression",
+        "ConditionalExpression",
+        "TemplateLiteral",
+        "StaticMemberExpression",
+        "PrivateFieldExpression",
+        "UpdateExpression",
+        "TSAsExpression",
+        "TSTypeAliasDeclaration",
+        "TSUnionType",
+        "TSLiteralType",
+        "TSTypeParameter",
+    ] {
+        assert!(kinds.contains(kind), "missing {kind} in {kinds:?}");
+    }
+
+    let jsx = parse_and_convert_to_tree(
+        "view.tsx",
+        "const view = <List items={items}>{items.map((item) => <Item key={item.id} />)}</List>;",
+    )
+    .unwrap();
+    let mut kinds = HashSet::new();
+    collect_kinds(&jsx, &mut kinds);
+    for kind in ["JSXElement", "JSXAttribute", "JSXExpressionContainer", "JSXIdentifier"] {
+        assert!(kinds.contains(kind), "missing {kind} in {kinds:?}");
+    }
+}
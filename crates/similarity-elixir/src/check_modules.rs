@@ -0,0 +1,107 @@
+use crate::elixir_parser::ElixirParser;
+use crate::get_relative_path;
+use similarity_core::cli_file_utils::collect_files;
+use similarity_core::cli_structures::{
+    find_similar_pairs, print_differences, ExtractedDefinitions, MIN_MEMBERS,
+};
+use similarity_core::{ElixirModuleDef, ElixirModuleKind, ElixirStructureComparator};
+
+fn describe_module(module: &ElixirModuleDef) -> String {
+    let name = match &module.for_type {
+        Some(for_type) => format!("{}, for: {}", module.name, for_type),
+        None => module.name.clone(),
+    };
+    format!(
+        "{} {} | {}:{}-{}",
+        module.kind.label(),
+        name,
+        get_relative_path(&module.file_path),
+        module.start_line,
+        module.end_line
+    )
+}
+
+fn member_count(module: &ElixirModuleDef) -> usize {
+    module.uses.len()
+        + module.imports.len()
+        + module.behaviours.len()
+        + module.fields.len()
+        + module.functions.len()
+        + module.callbacks.len()
+}
+
+/// Check for similar modules, protocols and protocol implementations across files
+pub fn check_modules(
+    paths: Vec<String>,
+    threshold: f64,
+    extensions: Option<&Vec<String>>,
+    print: bool,
+) -> anyhow::Result<usize> {
+    let default_extensions = vec!["ex", "exs"];
+    let exts: Vec<&str> =
+        extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
+
+    let files = collect_files(&paths, &exts)?;
+    if files.is_empty() {
+        println!("No Elixir files found in the specified paths.");
+        return Ok(0);
+    }
+
+    println!("Checking {} files for similar modules...", files.len());
+
+    let mut parser =
+        ElixirParser::new().map_err(|e| anyhow::anyhow!("Failed to create parser: {}", e))?;
+    let extracted = ExtractedDefinitions::extract(&files, |content, file_path| {
+        parser.extract_module_defs(content, file_path)
+    });
+    let modules = &extracted.definitions;
+
+    let count = |kind: ElixirModuleKind| modules.iter().filter(|m| m.kind == kind).count();
+    println!(
+        "Found {} modules, {} protocols and {} implementations\n",
+        count(ElixirModuleKind::Module),
+        count(ElixirModuleKind::Protocol),
+        count(ElixirModuleKind::Implementation)
+    );
+
+    let comparable = |module1: &ElixirModuleDef, module2: &ElixirModuleDef| {
+        module1.kind == module2.kind
+            && member_count(module1) >= MIN_MEMBERS
+            && member_count(module2) >= MIN_MEMBERS
+    };
+
+    let mut comparator = ElixirStructureComparator::new();
+    let pairs = find_similar_pairs(modules, threshold, comparable, |module1, module2| {
+        let comparison = comparator.compare_modules(module1, module2);
+        (comparison.overall_similarity, comparison)
+    });
+
+    if pairs.is_empty() {
+        println!("No similar modules found with threshold {:.0}%", threshold * 100.0);
+        return Ok(0);
+    }
+
+    println!("Similar modules:");
+    println!("{}", "-".repeat(60));
+
+    for pair in &pairs {
+        let module1 = &modules[pair.index1];
+        let module2 = &modules[pair.index2];
+
+        println!("\nSimilarity: {:.2}%", pair.similarity * 100.0);
+        println!("  {}", describe_module(module1));
+        println!("  {}", describe_module(module2));
+        print_differences(&module1.name, &module2.name, &pair.comparison.differences);
+
+        if print {
+            for (index, module) in [(pair.index1, module1), (pair.index2, module2)] {
+                extracted.print_code(index, &module.name, module.start_line, module.end_line);
+            }
+        }
+    }
+
+    println!("\n{}", "-".repeat(60));
+    println!("Total similar module pairs found: {}", pairs.len());
+
+    Ok(pairs.len())
+}
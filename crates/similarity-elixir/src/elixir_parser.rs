@@ -2,6 +2,7 @@ use similarity_core::language_parser::{
     GenericFunctionDef, GenericTypeDef, Language, LanguageParser,
 };
 use similarity_core::tree::TreeNode;
+use similarity_core::{ElixirFieldDef, ElixirFunctionSig, ElixirModuleDef, ElixirModuleKind};
 use std::error::Error;
 use std::rc::Rc;
 use tree_sitter::{Node, Parser};
@@ -25,6 +26,7 @@ impl ElixirParser {
         source: &str,
         functions: &mut Vec<GenericFunctionDef>,
         module_name: Option<&str>,
+        group_clauses: bool,
     ) {
         let node_kind = node.kind();
 
@@ -38,18 +40,23 @@ impl ElixirParser {
                             if let Some(func_def) =
                                 self.extract_function_definition(node, source, module_name)
                             {
-                                functions.push(func_def);
+                                // Clauses of one `name/arity` follow each other and form one
+                                // function
+                                let previous = functions.last_mut().filter(|previous| {
+                                    group_clauses
+                                        && previous.name == func_def.name
+                                        && previous.class_name == func_def.class_name
+                                });
+                                match previous {
+                                    Some(previous) => merge_clause(previous, func_def),
+                                    None => functions.push(func_def),
+                                }
                             }
                             return; // Don't traverse children
                         }
                         // Module definitions
                         "defmodule" | "defprotocol" | "defimpl" => {
-                            // Extract module name
-                            let new_module_name = node
-                                .child_by_field_name("arguments")
-                                .and_then(|args| args.child(0))
-                                .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                                .unwrap_or("");
+                            let new_module_name = module_name_of(node, source, module_name);
 
                             // Process do_block
                             let do_block = node.child(2).filter(|n| n.kind() == "do_block");
@@ -59,7 +66,8 @@ impl ElixirParser {
                                         child,
                                         source,
                                         functions,
-                                        Some(new_module_name),
+                                        Some(&new_module_name),
+                                        group_clauses,
                                     );
                                 }
                             }
@@ -73,7 +81,7 @@ impl ElixirParser {
 
         // Continue searching in children
         for child in node.children(&mut node.walk()) {
-            self.extract_functions_from_node(child, source, functions, module_name);
+            self.extract_functions_from_node(child, source, functions, module_name, group_clauses);
         }
     }
 
@@ -83,46 +91,19 @@ impl ElixirParser {
         source: &str,
         module_name: Option<&str>,
     ) -> Option<GenericFunctionDef> {
-        // Extract function name from arguments -> first call -> target
-        let name_string = node
-            .child(1)
-            .filter(|n| n.kind() == "arguments")
-            .and_then(|args| args.child(0))
-            .and_then(|call_node| {
-                if call_node.kind() == "call" {
-                    call_node.child_by_field_name("target")
-                } else {
-                    None
-                }
-            })
-            .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-            .map(String::from)?;
-
-        // Extract parameters
-        let params_node = node
-            .child(1)
-            .filter(|n| n.kind() == "arguments")
-            .and_then(|args| args.child(0))
-            .and_then(|call_node| {
-                if call_node.kind() == "call" {
-                    call_node.child(1).filter(|n| n.kind() == "arguments")
-                } else {
-                    None
-                }
-            });
-
-        // Extract do_block (may not exist for one-liner functions)
-        let body_node = node.child(2).filter(|n| n.kind() == "do_block");
+        let clause = Clause::from_definition(node, source)?;
 
-        let params = self.extract_parameters(params_node, source);
+        // The whole clause is compared, so that its head and guard take part
+        let start_line = node.start_position().row as u32 + 1;
+        let end_line = node.end_position().row as u32 + 1;
 
         Some(GenericFunctionDef {
-            name: name_string,
-            start_line: node.start_position().row as u32 + 1,
-            end_line: node.end_position().row as u32 + 1,
-            body_start_line: body_node.map(|n| n.start_position().row as u32 + 1).unwrap_or(0),
-            body_end_line: body_node.map(|n| n.end_position().row as u32 + 1).unwrap_or(0),
-            parameters: params,
+            name: format!("{}/{}", clause.name, clause.patterns.len()),
+            start_line,
+            end_line,
+            body_start_line: start_line,
+            body_end_line: end_line,
+            parameters: self.extract_parameters(&clause.patterns, source),
             is_method: module_name.is_some(),
             class_name: module_name.map(String::from),
             is_async: false,
@@ -131,23 +112,61 @@ impl ElixirParser {
         })
     }
 
-    fn extract_parameters(&self, params_node: Option<Node>, source: &str) -> Vec<String> {
-        let Some(node) = params_node else {
-            return Vec::new();
-        };
+    /// Parameter names by position: the variable a pattern binds (`%{} = opts`), or `arg1`,
+    /// `arg2`... for positions that only match a pattern
+    fn extract_parameters(&self, patterns: &[Node], source: &str) -> Vec<String> {
+        patterns
+            .iter()
+            .enumerate()
+            .map(|(i, pattern)| {
+                let bound = match pattern.kind() {
+                    "identifier" => Some(*pattern),
+                    "binary_operator" if operator(*pattern, source) == "=" => {
+                        [pattern.child_by_field_name("right"), pattern.child_by_field_name("left")]
+                            .into_iter()
+                            .flatten()
+                            .find(|side| side.kind() == "identifier")
+                    }
+                    _ => None,
+                };
+                bound
+                    .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                    .filter(|name| !name.starts_with('_'))
+                    .map_or_else(|| placeholder_parameter(i), String::from)
+            })
+            .collect()
+    }
 
-        let mut params = Vec::new();
-        for child in node.children(&mut node.walk()) {
-            if child.kind() == "identifier" {
-                if let Ok(param_text) = child.utf8_text(source.as_bytes()) {
-                    params.push(param_text.to_string());
-                }
-            }
-        }
-        params
+    /// Extract every `def` clause as a function of its own; clauses of one function share its
+    /// `name/arity`
+    pub fn extract_clauses(
+        &mut self,
+        source: &str,
+    ) -> Result<Vec<GenericFunctionDef>, Box<dyn Error + Send + Sync>> {
+        let tree = self.parser.parse(source, None).ok_or("Failed to parse Elixir code")?;
+
+        let mut functions = Vec::new();
+        self.extract_functions_from_node(tree.root_node(), source, &mut functions, None, false);
+        Ok(functions)
     }
 
+    /// Convert a node to a comparison tree
+    ///
+    /// `def` clauses become `clause(head(patterns), guard(expr), body(statements))` whether
+    /// written with `do ... end` or `, do:`, and pipes are rewritten into nested calls
+    /// (`x |> f(y)` as `f(x, y)`), so the two styles compare equal.
     fn build_tree_from_node(node: Node, source: &str, id: &mut usize) -> TreeNode {
+        if node.kind() == "call" {
+            if let Some(clause) = Clause::from_definition(node, source) {
+                return Self::build_clause_tree(node, &clause, source, id);
+            }
+        }
+        if node.kind() == "binary_operator" && operator(node, source) == "|>" {
+            if let Some(tree) = Self::build_pipe_tree(node, source, id) {
+                return tree;
+            }
+        }
+
         let label = node.kind().to_string();
         let value = if node.child_count() == 0 {
             node.utf8_text(source.as_bytes()).ok().unwrap_or_default().to_string()
@@ -155,18 +174,179 @@ impl ElixirParser {
             String::new()
         };
 
-        let current_id = *id;
-        *id += 1;
-
-        let mut tree_node = TreeNode::new(label, value, current_id);
+        let mut tree_node = Self::new_tree_node(&label, value, node, id);
 
         for child in node.children(&mut node.walk()) {
+            // Argument punctuation would set apart `f(x, y)` and its piped form
+            if node.kind() == "arguments" && !child.is_named() {
+                continue;
+            }
             let child_node = Self::build_tree_from_node(child, source, id);
             tree_node.add_child(Rc::new(child_node));
         }
 
         tree_node
     }
+
+    fn new_tree_node(label: &str, value: String, node: Node, id: &mut usize) -> TreeNode {
+        let current_id = *id;
+        *id += 1;
+        TreeNode::new(label.to_string(), value, current_id)
+            .with_span(node.start_byte() as u32, node.end_byte() as u32)
+    }
+
+    fn build_clause_tree(node: Node, clause: &Clause, source: &str, id: &mut usize) -> TreeNode {
+        let mut tree_node = Self::new_tree_node("clause", String::new(), node, id);
+
+        let mut head = Self::new_tree_node("head", String::new(), node, id);
+        for pattern in &clause.patterns {
+            head.add_child(Rc::new(Self::build_tree_from_node(*pattern, source, id)));
+        }
+        tree_node.add_child(Rc::new(head));
+
+        if let Some(guard) = clause.guard {
+            let mut guard_node = Self::new_tree_node("guard", String::new(), guard, id);
+            guard_node.add_child(Rc::new(Self::build_tree_from_node(guard, source, id)));
+            tree_node.add_child(Rc::new(guard_node));
+        }
+
+        let mut body = Self::new_tree_node("body", String::new(), node, id);
+        for statement in &clause.body {
+            body.add_child(Rc::new(Self::build_tree_from_node(*statement, source, id)));
+        }
+        tree_node.add_child(Rc::new(body));
+
+        tree_node
+    }
+
+    fn build_pipe_tree(node: Node, source: &str, id: &mut usize) -> Option<TreeNode> {
+        let left = node.child_by_field_name("left")?;
+        let right = node.child_by_field_name("right")?;
+
+        let mut call = Self::new_tree_node("call", String::new(), node, id);
+        let arguments_node = right
+            .children(&mut right.walk())
+            .find(|child| child.kind() == "arguments")
+            .filter(|_| right.kind() == "call");
+        match right.child_by_field_name("target").filter(|_| right.kind() == "call") {
+            Some(target) => call.add_child(Rc::new(Self::build_tree_from_node(target, source, id))),
+            // `x |> then_fun` without parentheses
+            None => call.add_child(Rc::new(Self::build_tree_from_node(right, source, id))),
+        }
+
+        let mut arguments =
+            Self::new_tree_node("arguments", String::new(), arguments_node.unwrap_or(right), id);
+        arguments.add_child(Rc::new(Self::build_tree_from_node(left, source, id)));
+        if let Some(arguments_node) = arguments_node {
+            for argument in arguments_node.named_children(&mut arguments_node.walk()) {
+                arguments.add_child(Rc::new(Self::build_tree_from_node(argument, source, id)));
+            }
+        }
+        call.add_child(Rc::new(arguments));
+
+        // `|> Enum.map do ... end` and similar trailing blocks
+        if right.kind() == "call" {
+            for child in right.named_children(&mut right.walk()) {
+                if child.kind() == "do_block" {
+                    call.add_child(Rc::new(Self::build_tree_from_node(child, source, id)));
+                }
+            }
+        }
+
+        Some(call)
+    }
+}
+
+/// The parts of a `def` clause: `def name(patterns) when guard do body end`
+struct Clause<'a> {
+    name: String,
+    patterns: Vec<Node<'a>>,
+    guard: Option<Node<'a>>,
+    body: Vec<Node<'a>>,
+}
+
+impl<'a> Clause<'a> {
+    fn from_definition(node: Node<'a>, source: &str) -> Option<Self> {
+        let target = node.child_by_field_name("target")?.utf8_text(source.as_bytes()).ok()?;
+        if !matches!(target, "def" | "defp" | "defmacro" | "defmacrop") {
+            return None;
+        }
+
+        let arguments = node.child(1).filter(|n| n.kind() == "arguments")?;
+        let mut cursor = arguments.walk();
+        let mut arguments: Vec<Node<'a>> = arguments.named_children(&mut cursor).collect();
+        let keywords =
+            arguments.iter().position(|n| n.kind() == "keywords").map(|i| arguments.remove(i));
+        let head = *arguments.first()?;
+
+        // `def f(x) when x > 0`
+        let (call, guard) = if head.kind() == "binary_operator" && operator(head, source) == "when"
+        {
+            (head.child_by_field_name("left")?, head.child_by_field_name("right"))
+        } else {
+            (head, None)
+        };
+
+        let (name, patterns) = match call.kind() {
+            "call" => {
+                let name = call.child_by_field_name("target")?.utf8_text(source.as_bytes()).ok()?;
+                let patterns = call
+                    .children(&mut call.walk())
+                    .find(|child| child.kind() == "arguments")
+                    .map(|args| args.named_children(&mut args.walk()).collect())
+                    .unwrap_or_default();
+                (name.to_string(), patterns)
+            }
+            // `def name, do: ...` and `def name do`
+            "identifier" => (call.utf8_text(source.as_bytes()).ok()?.to_string(), Vec::new()),
+            _ => return None,
+        };
+
+        let body = match node.child(2).filter(|n| n.kind() == "do_block") {
+            Some(do_block) => do_block.named_children(&mut do_block.walk()).collect(),
+            // `def f(x), do: x`
+            None => keywords
+                .into_iter()
+                .flat_map(|keywords| {
+                    keywords.named_children(&mut keywords.walk()).collect::<Vec<_>>()
+                })
+                .filter(|pair| {
+                    pair.child_by_field_name("key")
+                        .and_then(|key| key.utf8_text(source.as_bytes()).ok())
+                        .is_some_and(|key| key.trim().trim_end_matches(':') == "do")
+                })
+                .filter_map(|pair| pair.child_by_field_name("value"))
+                .collect(),
+        };
+
+        Some(Self { name, patterns, guard, body })
+    }
+}
+
+/// The operator of a `binary_operator` node (`|>`, `when`, `=`)
+fn operator<'a>(node: Node, source: &'a str) -> &'a str {
+    node.child_by_field_name("operator")
+        .and_then(|op| op.utf8_text(source.as_bytes()).ok())
+        .unwrap_or("")
+}
+
+/// Name of a parameter position that only matches a pattern: `arg1`, `arg2`...
+fn placeholder_parameter(position: usize) -> String {
+    format!("arg{}", position + 1)
+}
+
+/// Extend a function with its next clause, naming parameters the clauses so far only matched
+fn merge_clause(function: &mut GenericFunctionDef, clause: GenericFunctionDef) {
+    function.end_line = clause.end_line;
+    function.body_end_line = clause.body_end_line;
+    for (position, (parameter, name)) in
+        function.parameters.iter_mut().zip(clause.parameters).enumerate()
+    {
+        let placeholder = placeholder_parameter(position);
+        if *parameter == placeholder && name != placeholder {
+            *parameter = name;
+        }
+    }
 }
 
 impl LanguageParser for ElixirParser {
@@ -192,57 +372,248 @@ impl LanguageParser for ElixirParser {
         let tree = self.parser.parse(source, None).ok_or("Failed to parse Elixir code")?;
 
         let mut functions = Vec::new();
-        self.extract_functions_from_node(tree.root_node(), source, &mut functions, None);
+        self.extract_functions_from_node(tree.root_node(), source, &mut functions, None, true);
         Ok(functions)
     }
 
     fn extract_types(
         &mut self,
         source: &str,
-        _path: &str,
+        path: &str,
     ) -> Result<Vec<GenericTypeDef>, Box<dyn Error + Send + Sync>> {
+        let modules = self.extract_module_defs(source, path)?;
+        Ok(modules
+            .into_iter()
+            .map(|module| GenericTypeDef {
+                name: module.name,
+                start_line: module.start_line as u32,
+                end_line: module.end_line as u32,
+                kind: module.kind.label().to_string(),
+                fields: module.fields.into_iter().map(|field| field.name).collect(),
+            })
+            .collect())
+    }
+}
+
+impl ElixirParser {
+    /// Extract modules, protocols and protocol implementations with their `use` and `import`
+    /// lists, behaviours, struct fields, public functions and callbacks
+    pub fn extract_module_defs(
+        &mut self,
+        source: &str,
+        file_path: &str,
+    ) -> Result<Vec<ElixirModuleDef>, Box<dyn Error + Send + Sync>> {
         let tree = self.parser.parse(source, None).ok_or("Failed to parse Elixir code")?;
 
-        let mut types = Vec::new();
-        Self::extract_types_from_node(tree.root_node(), source, &mut types);
-        Ok(types)
+        let mut modules = Vec::new();
+        visit_for_module_defs(tree.root_node(), source, file_path, None, &mut modules);
+        Ok(modules)
     }
 }
 
-impl ElixirParser {
-    fn extract_types_from_node(node: Node, source: &str, types: &mut Vec<GenericTypeDef>) {
-        if node.kind() == "call" {
-            if let Some(target_node) = node.child_by_field_name("target") {
-                if let Ok(target_text) = target_node.utf8_text(source.as_bytes()) {
-                    if matches!(target_text, "defmodule" | "defprotocol" | "defimpl") {
-                        // Extract type name
-                        let name = node
-                            .child_by_field_name("arguments")
-                            .and_then(|args| args.child(0))
-                            .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                            .unwrap_or("");
-
-                        types.push(GenericTypeDef {
-                            name: name.to_string(),
-                            start_line: node.start_position().row as u32 + 1,
-                            end_line: node.end_position().row as u32 + 1,
-                            kind: match target_text {
-                                "defmodule" => "module",
-                                "defprotocol" => "protocol",
-                                "defimpl" => "implementation",
-                                _ => "unknown",
-                            }
-                            .to_string(),
-                            fields: Vec::new(),
-                        });
-                    }
+fn visit_for_module_defs(
+    node: Node,
+    source: &str,
+    file_path: &str,
+    parent: Option<&str>,
+    modules: &mut Vec<ElixirModuleDef>,
+) {
+    let kind = match call_target(node, source) {
+        Some("defmodule") => Some(ElixirModuleKind::Module),
+        Some("defprotocol") => Some(ElixirModuleKind::Protocol),
+        Some("defimpl") => Some(ElixirModuleKind::Implementation),
+        _ => None,
+    };
+    let Some(kind) = kind else {
+        for child in node.named_children(&mut node.walk()) {
+            visit_for_module_defs(child, source, file_path, parent, modules);
+        }
+        return;
+    };
+
+    let name = module_name_of(node, source, parent);
+    let mut module = ElixirModuleDef {
+        name: name.clone(),
+        kind,
+        // `defimpl Jason.Encoder, for: User`
+        for_type: keyword_value(&call_arguments(node), "for", source),
+        uses: Vec::new(),
+        imports: Vec::new(),
+        behaviours: Vec::new(),
+        fields: Vec::new(),
+        functions: Vec::new(),
+        callbacks: Vec::new(),
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        file_path: file_path.to_string(),
+    };
+
+    let mut nested = Vec::new();
+    let mut impl_pending = false;
+    for statement in do_block_statements(node) {
+        // Module attributes: `@behaviour Plug`, `@impl true`, `@callback init(term) :: term`
+        if statement.kind() == "unary_operator" {
+            let Some(attribute) = statement.child_by_field_name("operand") else { continue };
+            let arguments = call_arguments(attribute);
+            match call_target(attribute, source) {
+                Some("behaviour") => {
+                    module.behaviours.extend(arguments.first().map(|n| text(*n, source)))
+                }
+                Some("impl") => {
+                    impl_pending = arguments.first().is_none_or(|n| text(*n, source) != "false")
                 }
+                Some("callback" | "macrocallback") => {
+                    module
+                        .callbacks
+                        .extend(arguments.first().and_then(|n| callback_name(*n, source)));
+                }
+                _ => {}
             }
+            continue;
         }
 
-        // Continue searching in children
-        for child in node.children(&mut node.walk()) {
-            Self::extract_types_from_node(child, source, types);
+        let arguments = call_arguments(statement);
+        match call_target(statement, source) {
+            Some("use") => {
+                let used: Vec<String> = arguments.iter().map(|n| text(*n, source)).collect();
+                module.uses.push(used.join(", "));
+            }
+            Some("import") => module.imports.extend(arguments.first().map(|n| text(*n, source))),
+            Some("defstruct") => module.fields.extend(struct_fields(&arguments, source)),
+            Some("schema" | "embedded_schema") => {
+                module.fields.extend(schema_fields(statement, source))
+            }
+            Some(target @ ("def" | "defmacro" | "defp" | "defmacrop")) => {
+                let is_callback = std::mem::take(&mut impl_pending);
+                let Some(clause) = Clause::from_definition(statement, source) else { continue };
+                let name = format!("{}/{}", clause.name, clause.patterns.len());
+                if target.ends_with('p') || module.functions.iter().any(|f| f.name == name) {
+                    continue;
+                }
+                module.functions.push(ElixirFunctionSig {
+                    name,
+                    kind: target.to_string(),
+                    is_callback,
+                });
+            }
+            Some("defmodule" | "defprotocol" | "defimpl") => nested.push(statement),
+            _ => {}
         }
     }
+
+    modules.push(module);
+    for child in nested {
+        visit_for_module_defs(child, source, file_path, Some(&name), modules);
+    }
+}
+
+/// `defstruct [:name, age: 0]` and `defstruct name: nil, age: 0`
+fn struct_fields(arguments: &[Node], source: &str) -> Vec<ElixirFieldDef> {
+    let mut names = Vec::new();
+    for argument in arguments {
+        let entries: Vec<Node> = match argument.kind() {
+            "list" => argument.named_children(&mut argument.walk()).collect(),
+            _ => vec![*argument],
+        };
+        for entry in entries {
+            match entry.kind() {
+                "atom" => names.push(text(entry, source).trim_start_matches(':').to_string()),
+                "keywords" => names.extend(
+                    entry
+                        .named_children(&mut entry.walk())
+                        .filter_map(|pair| pair.child_by_field_name("key"))
+                        .map(|key| text(key, source).trim_end_matches(':').to_string()),
+                ),
+                _ => {}
+            }
+        }
+    }
+    names.into_iter().map(|name| ElixirFieldDef { name, field_type: None }).collect()
+}
+
+/// Ecto `field :email, :string` and associations (`belongs_to :org, Org`) of a `schema` block
+fn schema_fields(schema: Node, source: &str) -> Vec<ElixirFieldDef> {
+    do_block_statements(schema)
+        .into_iter()
+        .filter_map(|statement| {
+            let macro_name = call_target(statement, source)?;
+            let arguments = call_arguments(statement);
+            let name = text(*arguments.first()?, source).trim_start_matches(':').to_string();
+            let field_type = match macro_name {
+                "field" => arguments
+                    .get(1)
+                    .filter(|n| n.kind() != "keywords")
+                    .map_or("any".to_string(), |n| {
+                        text(*n, source).trim_start_matches(':').to_string()
+                    }),
+                "belongs_to" | "has_one" | "has_many" | "many_to_many" | "embeds_one"
+                | "embeds_many" => macro_name.to_string(),
+                _ => return None,
+            };
+            Some(ElixirFieldDef { name, field_type: Some(field_type) })
+        })
+        .collect()
+}
+
+/// `init/1` for `@callback init(args :: term) :: {:ok, state}`
+fn callback_name(spec: Node, source: &str) -> Option<String> {
+    let head = match spec.kind() {
+        "binary_operator" => spec.child_by_field_name("left")?,
+        _ => spec,
+    };
+    match head.kind() {
+        "call" => Some(format!("{}/{}", call_target(head, source)?, call_arguments(head).len())),
+        "identifier" => Some(format!("{}/0", text(head, source))),
+        _ => None,
+    }
+}
+
+/// Name of a `defmodule`, qualified by the enclosing module; the protocol name for `defimpl`
+fn module_name_of(node: Node, source: &str, parent: Option<&str>) -> String {
+    let name = call_arguments(node).first().map(|n| text(*n, source)).unwrap_or_default();
+    match parent {
+        Some(parent) if call_target(node, source) == Some("defmodule") => {
+            format!("{parent}.{name}")
+        }
+        _ => name,
+    }
+}
+
+/// `X` of a `key: X` keyword argument
+fn keyword_value(arguments: &[Node], key: &str, source: &str) -> Option<String> {
+    arguments
+        .iter()
+        .filter(|n| n.kind() == "keywords")
+        .flat_map(|keywords| keywords.named_children(&mut keywords.walk()).collect::<Vec<_>>())
+        .find(|pair| {
+            pair.child_by_field_name("key")
+                .is_some_and(|k| text(k, source).trim_end_matches(':') == key)
+        })
+        .and_then(|pair| pair.child_by_field_name("value"))
+        .map(|value| text(value, source))
+}
+
+fn call_target<'a>(node: Node, source: &'a str) -> Option<&'a str> {
+    if node.kind() != "call" {
+        return None;
+    }
+    node.child_by_field_name("target")?.utf8_text(source.as_bytes()).ok()
+}
+
+fn call_arguments(node: Node) -> Vec<Node> {
+    node.children(&mut node.walk())
+        .find(|child| child.kind() == "arguments")
+        .map(|arguments| arguments.named_children(&mut arguments.walk()).collect())
+        .unwrap_or_default()
+}
+
+fn do_block_statements(node: Node) -> Vec<Node> {
+    node.children(&mut node.walk())
+        .filter(|child| child.kind() == "do_block")
+        .flat_map(|do_block| do_block.named_children(&mut do_block.walk()).collect::<Vec<_>>())
+        .collect()
+}
+
+fn text(node: Node, source: &str) -> String {
+    node.utf8_text(source.as_bytes()).unwrap_or_default().trim().to_string()
 }
@@ -1,7 +1,9 @@
 use anyhow::Result;
 use clap::Parser;
+use similarity_core::ChangedLines;
 
 mod check;
+mod check_modules;
 mod elixir_parser;
 mod parallel;
 
@@ -42,6 +44,10 @@ struct Cli {
     #[arg(long)]
     no_size_penalty: bool,
 
+    /// Reward consistent identifier renames (a→x everywhere) and penalize conflicting ones
+    #[arg(long)]
+    rename_consistency: bool,
+
     /// Filter functions by name (substring match)
     #[arg(long)]
     filter_function: Option<String>,
@@ -69,20 +75,52 @@ struct Cli {
     /// Size tolerance for overlap detection (0.0-1.0)
     #[arg(long, default_value = "0.25")]
     overlap_size_tolerance: f64,
+
+    /// Check for similar modules, protocols and protocol implementations (public functions,
+    /// `use`/`import` lists, behaviours, struct fields and callbacks)
+    #[arg(long)]
+    modules: bool,
+
+    /// Disable function similarity checking
+    #[arg(long = "no-functions")]
+    no_functions: bool,
+
+    /// Compare the clauses of multi-clause functions one by one instead of whole functions
+    #[arg(long)]
+    clauses: bool,
+
+    /// Only report duplicates involving lines changed since this git revision (e.g. origin/main)
+    #[arg(long, conflicts_with = "diff_from")]
+    changed_since: Option<String>,
+
+    /// Only report duplicates involving lines changed by this unified diff (e.g. a .patch file)
+    #[arg(long)]
+    diff_from: Option<std::path::PathBuf>,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let functions_enabled = true; // Elixir always has functions enabled
+    let changed =
+        ChangedLines::from_cli_options(cli.changed_since.as_deref(), cli.diff_from.as_deref())
+            .map_err(anyhow::Error::msg)?;
+
+    let functions_enabled = !cli.no_functions;
+    let modules_enabled = cli.modules;
     let overlap_enabled = cli.overlap;
 
+    // Validate that at least one analyzer is enabled
+    if !functions_enabled && !modules_enabled && !overlap_enabled {
+        eprintln!("Error: At least one analyzer must be enabled. Use --modules to enable module checking, --experimental-overlap for overlap detection, or remove --no-functions.");
+        return Err(anyhow::anyhow!("No analyzer enabled"));
+    }
+
     println!("Analyzing Elixir code similarity...\n");
 
     let separator = "-".repeat(60);
 
     // Run functions analysis
-    if !overlap_enabled || functions_enabled {
+    if functions_enabled {
         println!("=== Function Similarity ===");
         check::check_paths(
             cli.paths.clone(),
@@ -92,15 +130,32 @@ fn main() -> Result<()> {
             cli.min_lines.unwrap_or(3),
             cli.min_tokens,
             cli.no_size_penalty,
+            cli.rename_consistency,
             cli.print,
             !cli.no_fast,
             cli.filter_function.as_ref(),
             cli.filter_function_body.as_ref(),
+            cli.clauses,
+            changed.as_ref(),
+        )?;
+    }
+
+    // Run modules analysis if enabled
+    if modules_enabled {
+        if functions_enabled {
+            println!("\n{separator}");
+        }
+        println!("=== Module Similarity ===");
+        check_modules::check_modules(
+            cli.paths.clone(),
+            cli.threshold,
+            cli.extensions.as_ref(),
+            cli.print,
         )?;
     }
 
     // Run overlap analysis if enabled
-    if overlap_enabled && functions_enabled {
+    if overlap_enabled && (functions_enabled || modules_enabled) {
         println!("\n{separator}\n");
     }
 
@@ -3,6 +3,7 @@
 use crate::elixir_parser::ElixirParser;
 use rayon::prelude::*;
 use similarity_core::{
+    changed_lines::ChangedLines,
     cli_parallel::{FileData, SimilarityResult},
     language_parser::{GenericFunctionDef, LanguageParser},
     tsed::{calculate_tsed, TSEDOptions},
@@ -53,13 +54,19 @@ pub fn load_files_parallel(files: &[PathBuf]) -> Vec<ElixirFileData> {
 }
 
 /// Check for duplicates within Elixir files in parallel
+///
+/// With `clauses`, each clause of a multi-clause function is compared on its own, but never with
+/// another clause of the same function.
 pub fn check_within_file_duplicates_parallel(
     files: &[PathBuf],
     threshold: f64,
     options: &TSEDOptions,
+    clauses: bool,
+    changed: Option<&ChangedLines>,
 ) -> Vec<(PathBuf, Vec<SimilarityResult<GenericFunctionDef>>)> {
     files
         .par_iter()
+        .filter(|file| changed.is_none_or(|c| c.touches_file(file)))
         .filter_map(|file| match fs::read_to_string(file) {
             Ok(code) => {
                 let file_str = file.to_string_lossy();
@@ -68,16 +75,51 @@ pub fn check_within_file_duplicates_parallel(
                 match ElixirParser::new() {
                     Ok(mut parser) => {
                         // Extract functions
-                        match parser.extract_functions(&code, &file_str) {
+                        let functions = if clauses {
+                            parser.extract_clauses(&code)
+                        } else {
+                            parser.extract_functions(&code, &file_str)
+                        };
+                        match functions {
                             Ok(functions) => {
+                                // Only pairs with a side in changed code are compared
+                                let touched: Vec<bool> = functions
+                                    .iter()
+                                    .map(|f| {
+                                        changed.is_none_or(|c| {
+                                            c.overlaps(file, f.start_line, f.end_line)
+                                        })
+                                    })
+                                    .collect();
+
+                                // Parse each function once; all its clauses are compared together
+                                let lines: Vec<&str> = code.lines().collect();
+                                let trees: Vec<_> = functions
+                                    .iter()
+                                    .map(|func| {
+                                        let body = extract_function_body(&lines, func);
+                                        parser.parse(&body, &format!("{}:{}", file_str, func.name))
+                                    })
+                                    .collect();
                                 let mut similar_pairs = Vec::new();
 
                                 // Compare all pairs within the file
                                 for i in 0..functions.len() {
                                     for j in (i + 1)..functions.len() {
+                                        if !touched[i] && !touched[j] {
+                                            continue;
+                                        }
+
                                         let func1 = &functions[i];
                                         let func2 = &functions[j];
 
+                                        // Clauses of one function are expected to look alike
+                                        if func1.name == func2.name
+                                            && func1.class_name == func2.class_name
+                                        {
+                                            continue;
+                                        }
+
                                         // Skip if functions don't meet minimum requirements
                                         if func1.end_line - func1.start_line + 1 < options.min_lines
                                             || func2.end_line - func2.start_line + 1
@@ -86,19 +128,11 @@ pub fn check_within_file_duplicates_parallel(
                                             continue;
                                         }
 
-                                        // Extract function bodies
-                                        let lines: Vec<&str> = code.lines().collect();
-                                        let body1 = extract_function_body(&lines, func1);
-                                        let body2 = extract_function_body(&lines, func2);
-
                                         // Calculate similarity using Elixir parser
-                                        let similarity = match (
-                                            parser.parse(&body1, &format!("{}:func1", file_str)),
-                                            parser.parse(&body2, &format!("{}:func2", file_str)),
-                                        ) {
+                                        let similarity = match (&trees[i], &trees[j]) {
                                             (Ok(tree1), Ok(tree2)) => {
                                                 // Use calculate_tsed to apply size_penalty and other options
-                                                calculate_tsed(&tree1, &tree2, options)
+                                                calculate_tsed(tree1, tree2, options)
                                             }
                                             _ => 0.0,
                                         };
@@ -154,3 +154,278 @@ end
         .stdout(predicate::str::contains("map_example"))
         .stdout(predicate::str::contains("pipeline"));
 }
+
+#[test]
+fn test_elixir_multi_clause_functions() {
+    let (_dir, file_path) = create_elixir_file(
+        r#"
+defmodule Events do
+  def handle(%{type: :created} = event) do
+    event
+    |> Map.get(:payload)
+    |> normalize()
+    |> store(:created)
+  end
+
+  def handle(%{type: :deleted} = event) do
+    event
+    |> Map.get(:payload)
+    |> normalize()
+    |> store(:deleted)
+  end
+
+  def archive(%{type: :archived} = event) do
+    event
+    |> Map.get(:payload)
+    |> normalize()
+    |> store(:archived)
+  end
+end
+"#,
+    );
+
+    // Clauses of one function are grouped and never reported against each other
+    let mut cmd = Command::cargo_bin("similarity-elixir").unwrap();
+    cmd.arg(&file_path);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Found 2 functions"))
+        .stdout(predicate::str::contains("handle/1"))
+        .stdout(predicate::str::is_match(r"handle/1 <-> \S+ handle/1").unwrap().not());
+
+    // Clause by clause, a clause copied into another function is found
+    let mut cmd = Command::cargo_bin("similarity-elixir").unwrap();
+    cmd.arg(&file_path).arg("--clauses");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_match(r"handle/1 <-> \S+ archive/1").unwrap())
+        .stdout(predicate::str::is_match(r"handle/1 <-> \S+ handle/1").unwrap().not());
+}
+
+#[test]
+fn test_elixir_clause_parameters_named_like_placeholders() {
+    use similarity_core::language_parser::LanguageParser;
+    use similarity_elixir::ElixirParser;
+
+    let source = r#"
+defmodule Cli do
+  def run(args, []), do: args
+  def run(_, [opt | _] = opts), do: opts
+  def run(list, config), do: {list, config}
+
+  def parse(:help, _), do: :help
+  def parse(_, args), do: args
+  def parse(argv, _), do: argv
+end
+"#;
+
+    let mut parser = ElixirParser::new().unwrap();
+    let functions = parser.extract_functions(source, "lib/cli.ex").unwrap();
+    let parameters: Vec<(&str, Vec<&str>)> = functions
+        .iter()
+        .map(|f| (f.name.as_str(), f.parameters.iter().map(String::as_str).collect()))
+        .collect();
+    assert_eq!(parameters, [("run/2", vec!["args", "opts"]), ("parse/2", vec!["argv", "args"])]);
+}
+
+#[test]
+fn test_elixir_pipe_and_nested_calls() {
+    let (_dir, file_path) = create_elixir_file(
+        r#"
+defmodule Prices do
+  def total(items) when is_list(items) do
+    items
+    |> Enum.map(&price/1)
+    |> Enum.filter(&positive?/1)
+    |> Enum.sum()
+  end
+
+  def sum_prices(items) when is_list(items) do
+    Enum.sum(
+      Enum.filter(
+        Enum.map(items, &price/1),
+        &positive?/1
+      )
+    )
+  end
+end
+"#,
+    );
+
+    let mut cmd = Command::cargo_bin("similarity-elixir").unwrap();
+    cmd.arg(&file_path);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("total/1"))
+        .stdout(predicate::str::contains("sum_prices/1"))
+        .stdout(predicate::str::contains("Similarity: 100."));
+}
+
+#[test]
+fn test_elixir_module_defs() {
+    use similarity_elixir::ElixirParser;
+
+    let source = r#"
+defmodule Shop.Order do
+  use Ecto.Schema
+  import Ecto.Changeset
+  @behaviour Shop.Exportable
+
+  schema "orders" do
+    field :number, :string
+    field :notes
+    belongs_to :customer, Shop.Customer
+  end
+
+  @impl true
+  def export(order), do: order.number
+  def changeset(order, attrs), do: cast(order, attrs, [:number])
+  def changeset(order), do: changeset(order, %{})
+  defp secret(order), do: order
+
+  defimpl Jason.Encoder, for: Shop.Order do
+    def encode(order, opts), do: Jason.Encode.map(%{number: order.number}, opts)
+  end
+
+  defmodule Line do
+    defstruct [:sku, quantity: 1]
+  end
+end
+
+defmodule Shop.Exportable do
+  @callback export(term) :: String.t()
+end
+"#;
+
+    let mut parser = ElixirParser::new().unwrap();
+    let modules = parser.extract_module_defs(source, "lib/order.ex").unwrap();
+    let names: Vec<&str> = modules.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, ["Shop.Order", "Jason.Encoder", "Shop.Order.Line", "Shop.Exportable"]);
+
+    let order = &modules[0];
+    assert_eq!(order.uses, ["Ecto.Schema"]);
+    assert_eq!(order.imports, ["Ecto.Changeset"]);
+    assert_eq!(order.behaviours, ["Shop.Exportable"]);
+    let fields: Vec<(&str, Option<&str>)> =
+        order.fields.iter().map(|f| (f.name.as_str(), f.field_type.as_deref())).collect();
+    assert_eq!(
+        fields,
+        [("number", Some("string")), ("notes", Some("any")), ("customer", Some("belongs_to"))]
+    );
+    let functions: Vec<(&str, bool)> =
+        order.functions.iter().map(|f| (f.name.as_str(), f.is_callback)).collect();
+    assert_eq!(functions, [("export/1", true), ("changeset/2", false), ("changeset/1", false)]);
+
+    assert_eq!(modules[1].for_type.as_deref(), Some("Shop.Order"));
+    let line_fields: Vec<&str> = modules[2].fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(line_fields, ["sku", "quantity"]);
+    assert_eq!(modules[3].callbacks, ["export/1"]);
+}
+
+#[test]
+fn test_elixir_modules_with_the_same_interface_are_similar() {
+    use similarity_core::ElixirStructureComparator;
+    use similarity_elixir::ElixirParser;
+
+    let source = r#"
+defmodule Billing.Cache do
+  use GenServer
+
+  def start_link(opts), do: GenServer.start_link(__MODULE__, opts)
+
+  @impl true
+  def init(state), do: {:ok, state}
+
+  @impl true
+  def handle_call({:get, key}, _from, state), do: {:reply, Map.get(state, key), state}
+
+  @impl true
+  def handle_cast({:put, key, value}, state), do: {:noreply, Map.put(state, key, value)}
+end
+
+defmodule Shop.Store do
+  use GenServer
+
+  def start_link(opts), do: GenServer.start_link(__MODULE__, opts, name: __MODULE__)
+
+  @impl true
+  def init(items), do: {:ok, items}
+
+  @impl true
+  def handle_call({:fetch, sku}, _from, items), do: {:reply, Map.fetch(items, sku), items}
+
+  @impl true
+  def handle_cast({:store, sku, item}, items), do: {:noreply, Map.put(items, sku, item)}
+end
+
+defmodule Shop.Mailer do
+  use Supervisor
+
+  def start_link(opts), do: Supervisor.start_link(__MODULE__, opts)
+
+  @impl true
+  def init(children), do: Supervisor.init(children, strategy: :one_for_one)
+end
+"#;
+
+    let mut parser = ElixirParser::new().unwrap();
+    let modules = parser.extract_module_defs(source, "lib/workers.ex").unwrap();
+    let mut comparator = ElixirStructureComparator::new();
+
+    let same = comparator.compare_modules(&modules[0], &modules[1]);
+    assert!(same.overall_similarity > 0.9, "similarity: {}", same.overall_similarity);
+
+    let different = comparator.compare_modules(&modules[0], &modules[2]);
+    assert!(different.overall_similarity < 0.7, "similarity: {}", different.overall_similarity);
+    assert!(different.differences.missing_members.contains(&"handle_call/3".to_string()));
+}
+
+#[test]
+fn test_elixir_module_similarity() {
+    let (_dir, file_path) = create_elixir_file(
+        r#"
+defmodule Billing.Cache do
+  use GenServer
+
+  def start_link(opts), do: GenServer.start_link(__MODULE__, opts)
+  def get(key), do: GenServer.call(__MODULE__, {:get, key})
+
+  @impl true
+  def init(state), do: {:ok, state}
+
+  @impl true
+  def handle_call({:get, key}, _from, state), do: {:reply, Map.get(state, key), state}
+end
+
+defmodule Shop.Store do
+  use GenServer
+
+  def start_link(opts), do: GenServer.start_link(__MODULE__, opts)
+  def get(key), do: GenServer.call(__MODULE__, {:get, key})
+
+  @impl true
+  def init(state), do: {:ok, state}
+
+  @impl true
+  def handle_call({:get, key}, _from, state), do: {:reply, Map.get(state, key), state}
+end
+
+defmodule Shop.Point do
+  defstruct [:x, :y]
+end
+"#,
+    );
+
+    let mut cmd = Command::cargo_bin("similarity-elixir").unwrap();
+    cmd.arg(&file_path).arg("--modules").arg("--no-functions");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("=== Module Similarity ==="))
+        .stdout(predicate::str::contains("Found 3 modules, 0 protocols and 0 implementations"))
+        .stdout(predicate::str::contains("module Billing.Cache"))
+        .stdout(predicate::str::contains("module Shop.Store"))
+        .stdout(predicate::str::contains("Shop.Point").not())
+        .stdout(predicate::str::contains("Total similar module pairs found: 1"));
+}
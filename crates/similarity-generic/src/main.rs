@@ -43,6 +43,10 @@ struct Cli {
     #[arg(long, value_name = "LANGUAGE", conflicts_with_all = ["path", "config", "language", "show_functions", "supported"])]
     show_config: Option<String>,
 
+    /// Reward consistent identifier renames (a→x everywhere) and penalize conflicting ones
+    #[arg(long)]
+    rename_consistency: bool,
+
     /// Enable experimental overlap detection mode
     #[arg(long = "experimental-overlap")]
     overlap: bool,
@@ -217,6 +221,7 @@ fn main() -> Result<()> {
                 min_tokens: None,
                 size_penalty: false,
                 skip_test: false,
+                rename_consistency: cli.rename_consistency,
             };
 
             for i in 0..functions.len() {
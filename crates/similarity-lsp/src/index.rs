@@ -0,0 +1,444 @@
+use ignore::WalkBuilder;
+use similarity_core::{
+    calculate_tsed, compare_function_trees, compare_types, extract_functions,
+    extract_types_from_code,
+    language_parser::{GenericFunctionDef, LanguageParser},
+    parse_and_convert_to_tree,
+    sfc::snippet_file_name,
+    FunctionDefinition, TSEDOptions, TreeNode, TypeComparisonOptions, TypeDefinition,
+};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Languages the index understands, keyed by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceLanguage {
+    TypeScript,
+    Python,
+    Rust,
+    Php,
+    Elixir,
+}
+
+impl SourceLanguage {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" | "mts" | "cts" | "vue" | "svelte"
+            | "astro" => Some(Self::TypeScript),
+            "py" => Some(Self::Python),
+            "rs" => Some(Self::Rust),
+            "php" => Some(Self::Php),
+            "ex" | "exs" => Some(Self::Elixir),
+            _ => None,
+        }
+    }
+
+    fn parser(self) -> Option<Box<dyn LanguageParser>> {
+        let parser: Box<dyn LanguageParser> = match self {
+            Self::TypeScript => return None,
+            Self::Python => Box::new(similarity_py::python_parser::PythonParser::new().ok()?),
+            Self::Rust => Box::new(similarity_rs::rust_parser::RustParser::new().ok()?),
+            Self::Php => Box::new(similarity_php::php_parser::PhpParser::new().ok()?),
+            Self::Elixir => Box::new(similarity_elixir::ElixirParser::new().ok()?),
+        };
+        Some(parser)
+    }
+}
+
+/// Options controlling what counts as a duplicate
+#[derive(Debug, Clone)]
+pub struct IndexOptions {
+    pub threshold: f64,
+    pub min_lines: u32,
+    pub types: bool,
+    pub tsed_options: TSEDOptions,
+    pub type_options: TypeComparisonOptions,
+}
+
+impl Default for IndexOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.87,
+            min_lines: 3,
+            types: true,
+            tsed_options: TSEDOptions::default(),
+            type_options: TypeComparisonOptions::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitKind {
+    Function,
+    Type,
+}
+
+/// A function or type as stored in the index
+#[derive(Debug, Clone)]
+pub struct CodeUnit {
+    pub name: String,
+    pub kind: UnitKind,
+    pub path: PathBuf,
+    /// 1-based inclusive line range
+    pub start_line: u32,
+    pub end_line: u32,
+    shape: Shape,
+}
+
+#[derive(Debug, Clone)]
+enum Shape {
+    TypeScriptFunction(FunctionDefinition, Rc<TreeNode>),
+    Function(SourceLanguage, Rc<TreeNode>),
+    Type(TypeDefinition),
+}
+
+/// Another unit similar to the one being reported
+#[derive(Debug, Clone)]
+pub struct SimilarMatch {
+    pub name: String,
+    pub path: PathBuf,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub similarity: f64,
+}
+
+/// A unit in a file together with everything similar to it, best match first
+#[derive(Debug, Clone)]
+pub struct Duplicate {
+    pub name: String,
+    pub kind: UnitKind,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub similar: Vec<SimilarMatch>,
+}
+
+/// In-memory index of every function and type in a workspace
+pub struct WorkspaceIndex {
+    options: IndexOptions,
+    files: HashMap<PathBuf, Vec<CodeUnit>>,
+    /// Duplicates per file for the current contents of the workspace; any edit can change
+    /// cross-file results, so every update clears it
+    duplicates: RefCell<HashMap<PathBuf, Rc<[Duplicate]>>>,
+}
+
+impl WorkspaceIndex {
+    pub fn new(options: IndexOptions) -> Self {
+        Self { options, files: HashMap::new(), duplicates: RefCell::default() }
+    }
+
+    pub fn options(&self) -> &IndexOptions {
+        &self.options
+    }
+
+    pub fn is_supported(path: &Path) -> bool {
+        SourceLanguage::from_path(path).is_some()
+    }
+
+    /// Index every supported file below `root`, respecting .gitignore; returns the file count
+    pub fn index_directory(&mut self, root: &Path) -> usize {
+        let mut count = 0;
+        for entry in WalkBuilder::new(root).follow_links(false).build().flatten() {
+            let path = entry.path();
+            if !path.is_file() || !Self::is_supported(path) {
+                continue;
+            }
+            if let Ok(text) = fs::read_to_string(path) {
+                self.update_file(path, &text);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Replace the units of a file with those extracted from `text`
+    pub fn update_file(&mut self, path: &Path, text: &str) {
+        let Some(language) = SourceLanguage::from_path(path) else {
+            return;
+        };
+        let units = match language {
+            SourceLanguage::TypeScript => self.extract_typescript(path, text),
+            _ => self.extract_generic(language, path, text),
+        };
+        self.files.insert(path.to_path_buf(), units);
+        self.duplicates.get_mut().clear();
+    }
+
+    pub fn remove_file(&mut self, path: &Path) {
+        self.files.remove(path);
+        self.duplicates.get_mut().clear();
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Paths of all indexed files
+    pub fn files(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.keys()
+    }
+
+    pub fn units(&self, path: &Path) -> &[CodeUnit] {
+        self.files.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Units of `path` that have similar units anywhere in the workspace, cached until the
+    /// next update
+    pub fn duplicates_in_file(&self, path: &Path) -> Rc<[Duplicate]> {
+        if let Some(duplicates) = self.duplicates.borrow().get(path) {
+            return Rc::clone(duplicates);
+        }
+        let duplicates: Rc<[Duplicate]> = self.find_duplicates(path).into();
+        self.duplicates.borrow_mut().insert(path.to_path_buf(), Rc::clone(&duplicates));
+        duplicates
+    }
+
+    fn find_duplicates(&self, path: &Path) -> Vec<Duplicate> {
+        let mut duplicates = Vec::new();
+        for unit in self.units(path) {
+            let mut similar: Vec<SimilarMatch> = self
+                .files
+                .values()
+                .flatten()
+                .filter(|other| !std::ptr::eq(*other, unit))
+                .filter_map(|other| {
+                    let similarity = self.similarity(unit, other)?;
+                    (similarity >= self.options.threshold).then(|| SimilarMatch {
+                        name: other.name.clone(),
+                        path: other.path.clone(),
+                        start_line: other.start_line,
+                        end_line: other.end_line,
+                        similarity,
+                    })
+                })
+                .collect();
+            if similar.is_empty() {
+                continue;
+            }
+            similar.sort_by(|a, b| {
+                b.similarity
+                    .partial_cmp(&a.similarity)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.path.cmp(&b.path))
+                    .then_with(|| a.start_line.cmp(&b.start_line))
+            });
+            duplicates.push(Duplicate {
+                name: unit.name.clone(),
+                kind: unit.kind,
+                start_line: unit.start_line,
+                end_line: unit.end_line,
+                similar,
+            });
+        }
+        duplicates
+    }
+
+    /// Files (other than `path`) containing units similar to units of `path`
+    pub fn related_files(&self, path: &Path) -> BTreeSet<PathBuf> {
+        self.duplicates_in_file(path)
+            .iter()
+            .flat_map(|d| d.similar.iter().map(|m| &m.path))
+            .filter(|p| p.as_path() != path)
+            .cloned()
+            .collect()
+    }
+
+    fn similarity(&self, a: &CodeUnit, b: &CodeUnit) -> Option<f64> {
+        match (&a.shape, &b.shape) {
+            (Shape::TypeScriptFunction(f1, t1), Shape::TypeScriptFunction(f2, t2)) => {
+                // Parent and nested functions overlap by construction
+                if a.path == b.path && f1.is_parent_child_relationship(f2) {
+                    return None;
+                }
+                Some(compare_function_trees(f1, f2, t1, t2, &self.options.tsed_options))
+            }
+            (Shape::Function(l1, t1), Shape::Function(l2, t2)) if l1 == l2 => {
+                Some(calculate_tsed(t1, t2, &self.tsed_options_for(*l1)))
+            }
+            (Shape::Type(t1), Shape::Type(t2)) => {
+                Some(compare_types(t1, t2, &self.options.type_options).similarity)
+            }
+            _ => None,
+        }
+    }
+
+    fn tsed_options_for(&self, language: SourceLanguage) -> TSEDOptions {
+        let mut options = self.options.tsed_options.clone();
+        // Rust trees carry identifiers in node values, like similarity-rs compares them
+        if language == SourceLanguage::Rust {
+            options.apted_options.compare_values = true;
+        }
+        options
+    }
+
+    fn long_enough(&self, start_line: u32, end_line: u32) -> bool {
+        end_line.saturating_sub(start_line) + 1 >= self.options.min_lines
+    }
+
+    fn extract_typescript(&self, path: &Path, text: &str) -> Vec<CodeUnit> {
+        let filename = path.to_string_lossy();
+        // Function bodies cut out of a component file are script in the block's `lang`
+        let body_filename = snippet_file_name(&filename, text);
+        let mut units = Vec::new();
+
+        for func in extract_functions(&filename, text).unwrap_or_default() {
+            if !self.long_enough(func.start_line, func.end_line) {
+                continue;
+            }
+            let Some(body) = text.get(func.body_span.start as usize..func.body_span.end as usize)
+            else {
+                continue;
+            };
+            let Ok(tree) = parse_and_convert_to_tree(&body_filename, body) else {
+                continue;
+            };
+            units.push(CodeUnit {
+                name: func.name.clone(),
+                kind: UnitKind::Function,
+                path: path.to_path_buf(),
+                start_line: func.start_line,
+                end_line: func.end_line,
+                shape: Shape::TypeScriptFunction(func, tree),
+            });
+        }
+
+        if self.options.types {
+            for type_def in extract_types_from_code(text, &filename).unwrap_or_default() {
+                if type_def.properties.is_empty() {
+                    continue;
+                }
+                units.push(CodeUnit {
+                    name: type_def.name.clone(),
+                    kind: UnitKind::Type,
+                    path: path.to_path_buf(),
+                    start_line: type_def.start_line as u32,
+                    end_line: type_def.end_line as u32,
+                    shape: Shape::Type(type_def),
+                });
+            }
+        }
+
+        units
+    }
+
+    fn extract_generic(&self, language: SourceLanguage, path: &Path, text: &str) -> Vec<CodeUnit> {
+        let Some(mut parser) = language.parser() else {
+            return Vec::new();
+        };
+        let filename = path.to_string_lossy();
+        let functions = parser.extract_functions(text, &filename).unwrap_or_default();
+        let lines: Vec<&str> = text.lines().collect();
+
+        functions
+            .into_iter()
+            .filter(|func| self.long_enough(func.start_line, func.end_line))
+            .filter_map(|func| {
+                let body = function_body(&lines, &func);
+                let tree = parser.parse(&body, &filename).ok()?;
+                Some(CodeUnit {
+                    name: func.name.clone(),
+                    kind: UnitKind::Function,
+                    path: path.to_path_buf(),
+                    start_line: func.start_line,
+                    end_line: func.end_line,
+                    shape: Shape::Function(language, tree),
+                })
+            })
+            .collect()
+    }
+}
+
+fn function_body(lines: &[&str], func: &GenericFunctionDef) -> String {
+    let (start, end) = if func.body_start_line > 0 {
+        (func.body_start_line, func.body_end_line)
+    } else {
+        (func.start_line, func.end_line)
+    };
+    let start = start.saturating_sub(1) as usize;
+    let end = (end as usize).min(lines.len());
+    if start >= end {
+        return String::new();
+    }
+    lines[start..end].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUM: &str = "function sumA(values: number[]) {\n  let total = 0;\n  for (const v of values) {\n    total += v;\n  }\n  return total;\n}\n";
+
+    fn options() -> IndexOptions {
+        let mut options = IndexOptions::default();
+        options.tsed_options.size_penalty = false;
+        options
+    }
+
+    #[test]
+    fn test_cross_file_duplicates_and_updates() {
+        let mut index = WorkspaceIndex::new(options());
+        let a = Path::new("/ws/a.ts");
+        let b = Path::new("/ws/b.ts");
+        index.update_file(a, SUM);
+        index.update_file(b, &SUM.replace("sumA", "sumB").replace("values", "items"));
+
+        let duplicates = index.duplicates_in_file(a);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].name, "sumA");
+        assert_eq!(duplicates[0].similar[0].name, "sumB");
+        assert_eq!(duplicates[0].similar[0].path, b);
+        assert!(index.related_files(a).contains(b));
+
+        index.update_file(b, "function other(x: string) {\n  return x.length;\n}\n");
+        assert!(index.duplicates_in_file(a).is_empty());
+    }
+
+    #[test]
+    fn test_duplicates_are_cached_until_an_update() {
+        let mut index = WorkspaceIndex::new(options());
+        let a = Path::new("/ws/a.ts");
+        let b = Path::new("/ws/b.ts");
+        index.update_file(a, SUM);
+        index.update_file(b, &SUM.replace("sumA", "sumB"));
+
+        let first = index.duplicates_in_file(a);
+        assert!(Rc::ptr_eq(&first, &index.duplicates_in_file(a)));
+
+        // Editing another file changes the results for `a`, too
+        index.remove_file(b);
+        let after = index.duplicates_in_file(a);
+        assert!(!Rc::ptr_eq(&first, &after));
+        assert!(after.is_empty());
+    }
+
+    #[test]
+    fn test_tsx_component_bodies_are_indexed() {
+        let component = |name: &str| {
+            format!(
+                "<script setup lang=\"tsx\">\nfunction {name}(items: string[]) {{\n  const rows = items.map((item) => <li>{{item}}</li>);\n  if (rows.length === 0) {{\n    return <p>Empty</p>;\n  }}\n  return <ul>{{rows}}</ul>;\n}}\n</script>\n"
+            )
+        };
+        let mut index = WorkspaceIndex::new(options());
+        let list = Path::new("/ws/List.vue");
+        index.update_file(list, &component("renderList"));
+        index.update_file(Path::new("/ws/Menu.vue"), &component("renderMenu"));
+
+        let duplicates = index.duplicates_in_file(list);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].similar[0].name, "renderMenu");
+    }
+
+    #[test]
+    fn test_python_functions_are_indexed() {
+        let code = "def f(items):\n    out = []\n    for i in items:\n        out.append(i)\n    return out\n\ndef g(values):\n    res = []\n    for v in values:\n        res.append(v)\n    return res\n";
+        let mut index = WorkspaceIndex::new(options());
+        let path = Path::new("/ws/m.py");
+        index.update_file(path, code);
+
+        let duplicates = index.duplicates_in_file(path);
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].similar[0].name, "g");
+    }
+}
@@ -0,0 +1,4 @@
+pub mod index;
+pub mod server;
+
+pub use index::{Duplicate, IndexOptions, SimilarMatch, SourceLanguage, UnitKind, WorkspaceIndex};
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use clap::Parser;
+use similarity_lsp::{server, IndexOptions};
+
+#[derive(Parser)]
+#[command(name = "similarity-lsp")]
+#[command(about = "Language server publishing code duplication diagnostics")]
+#[command(version)]
+struct Cli {
+    /// Similarity threshold (0.0-1.0); `initializationOptions.threshold` overrides it
+    #[arg(short, long, default_value = "0.87")]
+    threshold: f64,
+
+    /// Minimum lines for functions to be considered
+    #[arg(short, long, default_value = "3")]
+    min_lines: u32,
+
+    /// Rename cost for APTED algorithm
+    #[arg(short, long, default_value = "0.3")]
+    rename_cost: f64,
+
+    /// Disable size penalty for very different sized functions
+    #[arg(long)]
+    no_size_penalty: bool,
+
+    /// Do not report similar TypeScript types
+    #[arg(long)]
+    no_types: bool,
+
+    /// Communicate over stdio (the only transport; accepted for editor compatibility)
+    #[arg(long)]
+    stdio: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let mut options = IndexOptions {
+        threshold: cli.threshold,
+        min_lines: cli.min_lines,
+        types: !cli.no_types,
+        ..Default::default()
+    };
+    options.tsed_options.apted_options.rename_cost = cli.rename_cost;
+    options.tsed_options.size_penalty = !cli.no_size_penalty;
+    options.tsed_options.min_lines = cli.min_lines;
+
+    server::run_stdio(options)
+}
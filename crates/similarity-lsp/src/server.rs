@@ -0,0 +1,471 @@
+use crate::index::{Duplicate, IndexOptions, UnitKind, WorkspaceIndex};
+use anyhow::Result;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{
+    CodeLensRequest, ExecuteCommand, HoverRequest, Request as _, ShowDocument,
+};
+use lsp_types::{
+    CodeLens, CodeLensOptions, CodeLensParams, Command, Diagnostic, DiagnosticRelatedInformation,
+    DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, ExecuteCommandOptions,
+    ExecuteCommandParams, Hover, HoverContents, HoverParams, HoverProviderCapability,
+    InitializeParams, Location, MarkupContent, MarkupKind, Position, PublishDiagnosticsParams,
+    Range, ServerCapabilities, ServerInfo, ShowDocumentParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Command attached to code lenses; its arguments are the similar locations, best match first
+pub const GO_TO_SIMILAR_COMMAND: &str = "similarity.goToSimilar";
+
+const DIAGNOSTIC_SOURCE: &str = "similarity";
+
+/// Run the server over stdio until the client sends `exit`
+pub fn run_stdio(options: IndexOptions) -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    run(connection, options)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Run the server on an established connection
+pub fn run(connection: Connection, options: IndexOptions) -> Result<()> {
+    // A malformed initialize is rejected and the client may retry
+    let (id, params) = loop {
+        let (id, params) = connection.initialize_start()?;
+        match serde_json::from_value::<InitializeParams>(params) {
+            Ok(params) => break (id, params),
+            Err(e) => {
+                let response =
+                    Response::new_err(id, ErrorCode::InvalidParams as i32, e.to_string());
+                connection.sender.send(response.into())?;
+            }
+        }
+    };
+
+    let result = serde_json::json!({
+        "capabilities": capabilities(),
+        "serverInfo": ServerInfo {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        },
+    });
+    connection.initialize_finish(id, result)?;
+
+    let mut server = Server::new(connection, initialize_options(options, &params));
+    server.show_document = supports_show_document(&params);
+    if let Some(root) = workspace_root(&params) {
+        server.index_workspace(&root)?;
+    }
+    server.main_loop()
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(false) }),
+        execute_command_provider: Some(ExecuteCommandOptions {
+            commands: vec![GO_TO_SIMILAR_COMMAND.to_string()],
+            work_done_progress_options: Default::default(),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Apply `initializationOptions` such as `{"threshold": 0.9, "minLines": 5}`
+fn initialize_options(mut options: IndexOptions, params: &InitializeParams) -> IndexOptions {
+    let Some(init) = &params.initialization_options else {
+        return options;
+    };
+    if let Some(threshold) = init.get("threshold").and_then(Value::as_f64) {
+        options.threshold = threshold;
+    }
+    if let Some(min_lines) = init.get("minLines").and_then(Value::as_u64) {
+        options.min_lines = min_lines as u32;
+    }
+    if let Some(types) = init.get("types").and_then(Value::as_bool) {
+        options.types = types;
+    }
+    options
+}
+
+fn supports_show_document(params: &InitializeParams) -> bool {
+    params
+        .capabilities
+        .window
+        .as_ref()
+        .and_then(|window| window.show_document.as_ref())
+        .is_some_and(|show_document| show_document.support)
+}
+
+#[allow(deprecated)]
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(params.root_uri.as_ref())
+        .and_then(|uri| uri.to_file_path().ok())
+}
+
+struct Server {
+    connection: Connection,
+    index: WorkspaceIndex,
+    root: Option<PathBuf>,
+    /// Diagnostics last sent per file, so unchanged files are not re-sent and resolved ones get cleared
+    published: HashMap<PathBuf, Vec<Diagnostic>>,
+    /// Whether the client accepts `window/showDocument` requests
+    show_document: bool,
+    next_request_id: i32,
+}
+
+impl Server {
+    fn new(connection: Connection, options: IndexOptions) -> Self {
+        Self {
+            connection,
+            index: WorkspaceIndex::new(options),
+            root: None,
+            published: HashMap::new(),
+            show_document: false,
+            next_request_id: 0,
+        }
+    }
+
+    fn index_workspace(&mut self, root: &Path) -> Result<()> {
+        self.root = Some(root.to_path_buf());
+        self.index.index_directory(root);
+
+        let mut files: Vec<PathBuf> = self.index.files().cloned().collect();
+        files.sort();
+        for file in files {
+            self.publish(&file)?;
+        }
+        Ok(())
+    }
+
+    fn main_loop(&mut self) -> Result<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> Result<()> {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            HoverRequest::METHOD => {
+                let Some(params) = self.request_params::<HoverParams>(&request)? else {
+                    return Ok(());
+                };
+                serde_json::to_value(self.hover(&params))?
+            }
+            CodeLensRequest::METHOD => {
+                let Some(params) = self.request_params::<CodeLensParams>(&request)? else {
+                    return Ok(());
+                };
+                serde_json::to_value(self.code_lenses(&params.text_document.uri))?
+            }
+            ExecuteCommand::METHOD => {
+                let Some(params) = self.request_params::<ExecuteCommandParams>(&request)? else {
+                    return Ok(());
+                };
+                if params.command != GO_TO_SIMILAR_COMMAND {
+                    let message = format!("Unknown command: {}", params.command);
+                    return self.reply_error(id, ErrorCode::InvalidParams, message);
+                }
+                let target = params
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .and_then(|argument| serde_json::from_value::<Location>(argument).ok());
+                let Some(target) = target else {
+                    let message = format!("{} expects a location argument", GO_TO_SIMILAR_COMMAND);
+                    return self.reply_error(id, ErrorCode::InvalidParams, message);
+                };
+                self.show_location(&target)?;
+                serde_json::to_value(target)?
+            }
+            _ => {
+                let message = format!("Unhandled method: {}", request.method);
+                return self.reply_error(id, ErrorCode::MethodNotFound, message);
+            }
+        };
+        self.connection.sender.send(Response::new_ok(id, result).into())?;
+        Ok(())
+    }
+
+    /// Decode request params, answering `InvalidParams` instead when they are malformed
+    fn request_params<P: DeserializeOwned>(&self, request: &Request) -> Result<Option<P>> {
+        match serde_json::from_value(request.params.clone()) {
+            Ok(params) => Ok(Some(params)),
+            Err(e) => {
+                let message = format!("Invalid {} params: {}", request.method, e);
+                self.reply_error(request.id.clone(), ErrorCode::InvalidParams, message)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Ask the client to open the similar code; clients without `window/showDocument`
+    /// support get the location as the command result only
+    fn show_location(&mut self, target: &Location) -> Result<()> {
+        if !self.show_document {
+            return Ok(());
+        }
+        self.next_request_id += 1;
+        let params = ShowDocumentParams {
+            uri: target.uri.clone(),
+            external: Some(false),
+            take_focus: Some(true),
+            selection: Some(target.range),
+        };
+        let request =
+            Request::new(self.next_request_id.into(), ShowDocument::METHOD.to_string(), params);
+        self.connection.sender.send(request.into())?;
+        Ok(())
+    }
+
+    fn reply_error(&self, id: RequestId, code: ErrorCode, message: String) -> Result<()> {
+        let response = Response::new_err(id, code as i32, message);
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Some(params) = notification_params::<DidOpenTextDocumentParams>(notification)
+                else {
+                    return Ok(());
+                };
+                self.update(&params.text_document.uri, Some(params.text_document.text))?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let Some(mut params) =
+                    notification_params::<DidChangeTextDocumentParams>(notification)
+                else {
+                    return Ok(());
+                };
+                // Full sync: the last change holds the whole document
+                if let Some(change) = params.content_changes.pop() {
+                    self.update(&params.text_document.uri, Some(change.text))?;
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                let Some(params) = notification_params::<DidSaveTextDocumentParams>(notification)
+                else {
+                    return Ok(());
+                };
+                self.update(&params.text_document.uri, params.text)?;
+            }
+            DidCloseTextDocument::METHOD => {
+                // Unsaved edits are discarded, so fall back to the file on disk
+                let Some(params) = notification_params::<DidCloseTextDocumentParams>(notification)
+                else {
+                    return Ok(());
+                };
+                self.update(&params.text_document.uri, None)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Re-index one document and refresh diagnostics of every file it pairs with
+    fn update(&mut self, uri: &Url, text: Option<String>) -> Result<()> {
+        let Ok(path) = uri.to_file_path() else {
+            return Ok(());
+        };
+        if !WorkspaceIndex::is_supported(&path) {
+            return Ok(());
+        }
+
+        let mut affected = self.index.related_files(&path);
+        match text.or_else(|| fs::read_to_string(&path).ok()) {
+            Some(text) => self.index.update_file(&path, &text),
+            None => self.index.remove_file(&path),
+        }
+        affected.extend(self.index.related_files(&path));
+        affected.insert(path);
+
+        for file in affected {
+            self.publish(&file)?;
+        }
+        Ok(())
+    }
+
+    fn publish(&mut self, path: &Path) -> Result<()> {
+        let Ok(uri) = Url::from_file_path(path) else {
+            return Ok(());
+        };
+        let diagnostics: Vec<Diagnostic> = self
+            .index
+            .duplicates_in_file(path)
+            .iter()
+            .map(|duplicate| self.diagnostic(duplicate))
+            .collect();
+
+        let previous = self.published.get(path).map_or(&[][..], Vec::as_slice);
+        if previous == diagnostics.as_slice() {
+            return Ok(());
+        }
+        if diagnostics.is_empty() {
+            self.published.remove(path);
+        } else {
+            self.published.insert(path.to_path_buf(), diagnostics.clone());
+        }
+
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+
+    fn diagnostic(&self, duplicate: &Duplicate) -> Diagnostic {
+        let best = &duplicate.similar[0];
+        let mut message = format!(
+            "Similar to `{}` in {}:{}-{} ({:.1}%)",
+            best.name,
+            self.display_path(&best.path),
+            best.start_line,
+            best.end_line,
+            best.similarity * 100.0
+        );
+        if duplicate.similar.len() > 1 {
+            message.push_str(&format!(" and {} more", duplicate.similar.len() - 1));
+        }
+
+        let related = duplicate
+            .similar
+            .iter()
+            .filter_map(|similar| {
+                Some(DiagnosticRelatedInformation {
+                    location: location(&similar.path, similar.start_line, similar.end_line)?,
+                    message: format!("`{}` ({:.1}%)", similar.name, similar.similarity * 100.0),
+                })
+            })
+            .collect();
+
+        Diagnostic {
+            range: header_range(duplicate.start_line),
+            severity: Some(DiagnosticSeverity::WARNING),
+            source: Some(DIAGNOSTIC_SOURCE.to_string()),
+            message,
+            related_information: Some(related),
+            ..Default::default()
+        }
+    }
+
+    fn hover(&self, params: &HoverParams) -> Option<Hover> {
+        let position = &params.text_document_position_params;
+        let path = position.text_document.uri.to_file_path().ok()?;
+        let line = position.position.line + 1;
+
+        // Innermost unit under the cursor, so nested functions win over their parent
+        let duplicates = self.index.duplicates_in_file(&path);
+        let duplicate = duplicates
+            .iter()
+            .filter(|d| d.start_line <= line && line <= d.end_line)
+            .min_by_key(|d| d.end_line - d.start_line)?;
+
+        let kind = match duplicate.kind {
+            UnitKind::Function => "Function",
+            UnitKind::Type => "Type",
+        };
+        let mut value = format!("**{} `{}` has similar code**\n", kind, duplicate.name);
+        for similar in &duplicate.similar {
+            value.push_str(&format!(
+                "\n- `{}` in {}:{}-{}: **{:.1}%**",
+                similar.name,
+                self.display_path(&similar.path),
+                similar.start_line,
+                similar.end_line,
+                similar.similarity * 100.0
+            ));
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value }),
+            range: Some(header_range(duplicate.start_line)),
+        })
+    }
+
+    fn code_lenses(&self, uri: &Url) -> Vec<CodeLens> {
+        let Ok(path) = uri.to_file_path() else {
+            return Vec::new();
+        };
+        self.index
+            .duplicates_in_file(&path)
+            .iter()
+            .map(|duplicate| {
+                let locations: Vec<Value> = duplicate
+                    .similar
+                    .iter()
+                    .filter_map(|s| location(&s.path, s.start_line, s.end_line))
+                    .filter_map(|l| serde_json::to_value(l).ok())
+                    .collect();
+                CodeLens {
+                    range: header_range(duplicate.start_line),
+                    command: Some(Command {
+                        title: format!("Go to similar code ({})", locations.len()),
+                        command: GO_TO_SIMILAR_COMMAND.to_string(),
+                        arguments: Some(locations),
+                    }),
+                    data: None,
+                }
+            })
+            .collect()
+    }
+
+    fn display_path(&self, path: &Path) -> String {
+        self.root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
+
+/// Decode notification params; malformed ones cannot be answered, so they are logged and skipped
+fn notification_params<P: DeserializeOwned>(notification: Notification) -> Option<P> {
+    match serde_json::from_value(notification.params) {
+        Ok(params) => Some(params),
+        Err(e) => {
+            eprintln!("Ignoring {} with invalid params: {}", notification.method, e);
+            None
+        }
+    }
+}
+
+/// The whole first line of a unit (1-based), where the name is declared
+fn header_range(start_line: u32) -> Range {
+    let line = start_line.saturating_sub(1);
+    Range::new(Position::new(line, 0), Position::new(line + 1, 0))
+}
+
+fn location(path: &Path, start_line: u32, end_line: u32) -> Option<Location> {
+    let uri = Url::from_file_path(path).ok()?;
+    let range =
+        Range::new(Position::new(start_line.saturating_sub(1), 0), Position::new(end_line, 0));
+    Some(Location::new(uri, range))
+}
@@ -0,0 +1,314 @@
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const SUM_A: &str = r#"export function sumPrices(items: { price: number }[]): number {
+  let total = 0;
+  for (const item of items) {
+    total += item.price;
+  }
+  return total;
+}
+"#;
+
+const SUM_B: &str = r#"export function sumWeights(parcels: { weight: number }[]): number {
+  let total = 0;
+  for (const parcel of parcels) {
+    total += parcel.weight;
+  }
+  return total;
+}
+"#;
+
+const DISTINCT: &str = r#"export function describe(name: string, tags: string[]): string {
+  if (tags.length === 0) {
+    throw new Error("no tags for " + name);
+  }
+  return tags.map((t) => t.toUpperCase()).join(", ");
+}
+"#;
+
+/// Minimal LSP client speaking Content-Length framed JSON-RPC over the server's stdio
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    messages: Receiver<Value>,
+    next_id: i64,
+}
+
+impl Client {
+    fn spawn() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_similarity-lsp"))
+            .arg("--no-size-penalty")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start similarity-lsp");
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            loop {
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    if reader.read_line(&mut header).unwrap_or(0) == 0 {
+                        return;
+                    }
+                    let header = header.trim();
+                    if header.is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.strip_prefix("Content-Length: ") {
+                        length = value.parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                if sender.send(serde_json::from_slice(&body).unwrap()).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self { child, stdin, messages, next_id: 1 }
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let response = self.raw_request(method, params);
+        assert!(response.get("error").is_none(), "{} failed: {}", method, response);
+        response["result"].clone()
+    }
+
+    /// Send a request and return the whole response, errors included
+    fn raw_request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        // Requests from the server carry a method; responses never do
+        self.wait_for(|m| m["id"] == id && m.get("method").is_none())
+    }
+
+    fn wait_for(&mut self, predicate: impl Fn(&Value) -> bool) -> Value {
+        loop {
+            let message = self
+                .messages
+                .recv_timeout(Duration::from_secs(30))
+                .expect("timed out waiting for server message");
+            if predicate(&message) {
+                return message;
+            }
+        }
+    }
+
+    /// Next diagnostics published for `uri`
+    fn diagnostics_for(&mut self, uri: &str) -> Vec<Value> {
+        let message = self.wait_for(|m| {
+            m["method"] == "textDocument/publishDiagnostics" && m["params"]["uri"] == uri
+        });
+        message["params"]["diagnostics"].as_array().unwrap().clone()
+    }
+
+    fn shutdown(mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        let status = self.child.wait().unwrap();
+        assert!(status.success());
+    }
+}
+
+fn uri(path: &Path) -> String {
+    format!("file://{}", path.display())
+}
+
+fn workspace() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("prices.ts"), SUM_A).unwrap();
+    fs::write(dir.path().join("weights.ts"), SUM_B).unwrap();
+    fs::write(dir.path().join("describe.ts"), DISTINCT).unwrap();
+    dir
+}
+
+fn initialize(client: &mut Client, root: &Path) {
+    initialize_with(client, root, json!({}));
+}
+
+fn initialize_with(client: &mut Client, root: &Path, capabilities: Value) {
+    let result = client.request(
+        "initialize",
+        json!({ "processId": null, "rootUri": uri(root), "capabilities": capabilities }),
+    );
+    assert_eq!(result["capabilities"]["hoverProvider"], true);
+    client.notify("initialized", json!({}));
+}
+
+#[test]
+fn test_publishes_diagnostics_hover_and_similar_locations() {
+    let dir = workspace();
+    let root = dir.path().canonicalize().unwrap();
+    let prices = uri(&root.join("prices.ts"));
+    let weights = uri(&root.join("weights.ts"));
+
+    let mut client = Client::spawn();
+    initialize(&mut client, &root);
+
+    let diagnostics = client.diagnostics_for(&prices);
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic["source"], "similarity");
+    assert_eq!(diagnostic["range"]["start"]["line"], 0);
+    let message = diagnostic["message"].as_str().unwrap();
+    assert!(message.contains("`sumWeights` in weights.ts:1-7"), "{}", message);
+    assert_eq!(diagnostic["relatedInformation"][0]["location"]["uri"], weights.as_str());
+
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": prices }, "position": { "line": 2, "character": 4 } }),
+    );
+    let value = hover["contents"]["value"].as_str().unwrap();
+    assert!(value.contains("sumPrices"), "{}", value);
+    assert!(value.contains("`sumWeights` in weights.ts:1-7: **"), "{}", value);
+    assert!(value.contains("%**"), "{}", value);
+
+    let lenses =
+        client.request("textDocument/codeLens", json!({ "textDocument": { "uri": prices } }));
+    let command = &lenses[0]["command"];
+    assert_eq!(command["title"], "Go to similar code (1)");
+    assert_eq!(command["command"], "similarity.goToSimilar");
+
+    let location = client.request(
+        "workspace/executeCommand",
+        json!({ "command": command["command"], "arguments": command["arguments"] }),
+    );
+    assert_eq!(location["uri"], weights.as_str());
+    assert_eq!(location["range"]["start"]["line"], 0);
+
+    // Unrelated code gets no diagnostics and no hover
+    let describe = uri(&root.join("describe.ts"));
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": describe }, "position": { "line": 1, "character": 0 } }),
+    );
+    assert!(hover.is_null());
+
+    client.shutdown();
+}
+
+#[test]
+fn test_did_change_updates_index_and_clears_diagnostics() {
+    let dir = workspace();
+    let root = dir.path().canonicalize().unwrap();
+    let prices = uri(&root.join("prices.ts"));
+    let weights = uri(&root.join("weights.ts"));
+    let describe = uri(&root.join("describe.ts"));
+
+    let mut client = Client::spawn();
+    initialize(&mut client, &root);
+    assert_eq!(client.diagnostics_for(&weights).len(), 1);
+
+    client.notify(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": weights, "languageId": "typescript", "version": 1, "text": SUM_B } }),
+    );
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": weights, "version": 2 },
+            "contentChanges": [{ "text": DISTINCT.replace("describe", "label") }],
+        }),
+    );
+
+    // Affected files are republished in path order: the edit now duplicates describe.ts,
+    // and both sides of the former pair are updated
+    assert_eq!(client.diagnostics_for(&describe).len(), 1);
+    assert!(client.diagnostics_for(&prices).is_empty());
+    let diagnostics = client.diagnostics_for(&weights);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]["message"].as_str().unwrap().contains("`describe` in describe.ts"));
+
+    client.shutdown();
+}
+
+#[test]
+fn test_go_to_similar_shows_the_document() {
+    let dir = workspace();
+    let root = dir.path().canonicalize().unwrap();
+    let prices = uri(&root.join("prices.ts"));
+    let weights = uri(&root.join("weights.ts"));
+
+    let mut client = Client::spawn();
+    initialize_with(
+        &mut client,
+        &root,
+        json!({ "window": { "showDocument": { "support": true } } }),
+    );
+    client.diagnostics_for(&prices);
+
+    let lenses =
+        client.request("textDocument/codeLens", json!({ "textDocument": { "uri": prices } }));
+    let command = &lenses[0]["command"];
+    client.send(json!({
+        "jsonrpc": "2.0",
+        "id": 100,
+        "method": "workspace/executeCommand",
+        "params": { "command": command["command"], "arguments": command["arguments"] },
+    }));
+
+    // The server asks the client to open the best match before answering the command
+    let show = client.wait_for(|m| m["method"] == "window/showDocument");
+    assert_eq!(show["params"]["uri"], weights.as_str());
+    assert_eq!(show["params"]["takeFocus"], true);
+    assert_eq!(show["params"]["selection"]["start"]["line"], 0);
+    let response = client.wait_for(|m| m["id"] == 100 && m.get("method").is_none());
+    assert_eq!(response["result"]["uri"], weights.as_str());
+
+    client.shutdown();
+}
+
+#[test]
+fn test_malformed_messages_do_not_stop_the_server() {
+    let dir = workspace();
+    let root = dir.path().canonicalize().unwrap();
+    let prices = uri(&root.join("prices.ts"));
+
+    let mut client = Client::spawn();
+    let response = client.raw_request("initialize", json!({ "rootUri": 42 }));
+    assert_eq!(response["error"]["code"], -32602);
+    initialize(&mut client, &root);
+    client.diagnostics_for(&prices);
+
+    let response = client.raw_request("textDocument/hover", json!({ "textDocument": 42 }));
+    assert_eq!(response["error"]["code"], -32602);
+    let response = client.raw_request(
+        "workspace/executeCommand",
+        json!({ "command": "similarity.goToSimilar", "arguments": ["nowhere"] }),
+    );
+    assert_eq!(response["error"]["code"], -32602);
+    client.notify("textDocument/didOpen", json!({ "textDocument": "prices.ts" }));
+
+    // Still serving requests afterwards
+    let lenses =
+        client.request("textDocument/codeLens", json!({ "textDocument": { "uri": prices } }));
+    assert_eq!(lenses.as_array().unwrap().len(), 1);
+
+    client.shutdown();
+}
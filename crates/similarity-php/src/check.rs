@@ -2,6 +2,7 @@
 
 use crate::parallel::check_within_file_duplicates_parallel;
 use similarity_core::{
+    changed_lines::ChangedLines,
     cli_file_utils::collect_files,
     cli_output::{format_function_output, show_function_code},
     cli_parallel::SimilarityResult,
@@ -38,10 +39,12 @@ pub fn check_paths(
     min_lines: u32,
     min_tokens: Option<u32>,
     no_size_penalty: bool,
+    rename_consistency: bool,
     print: bool,
     _fast_mode: bool, // PHP doesn't support fast mode yet
     filter_function: Option<&String>,
     filter_function_body: Option<&String>,
+    changed: Option<&ChangedLines>,
 ) -> anyhow::Result<()> {
     let default_extensions = vec!["php"];
     let exts: Vec<&str> =
@@ -55,17 +58,25 @@ pub fn check_paths(
     }
 
     println!("Checking {} files for duplicates...", files.len());
+    if let Some(changed) = changed {
+        println!(
+            "Reporting only duplicates touching lines changed in {} files",
+            changed.file_count()
+        );
+    }
 
     let mut options = TSEDOptions::default();
     options.apted_options.rename_cost = rename_cost;
     options.min_lines = min_lines;
     options.min_tokens = min_tokens;
     options.size_penalty = !no_size_penalty;
+    options.rename_consistency = rename_consistency;
 
     let mut all_results = Vec::new();
 
     // Check within each file
-    let within_file_results = check_within_file_duplicates_parallel(&files, threshold, &options);
+    let within_file_results =
+        check_within_file_duplicates_parallel(&files, threshold, &options, changed);
 
     // Collect within-file duplicates
     for (file, similar_pairs) in within_file_results {
@@ -0,0 +1,148 @@
+use crate::get_relative_path;
+use crate::template_parser::{extract_fragments, TemplateFragment, TemplateSyntax};
+use similarity_core::{calculate_tsed, cli_file_utils::collect_files, TSEDOptions};
+use std::fs;
+
+struct DuplicateFragmentPair {
+    fragment1: TemplateFragment,
+    fragment2: TemplateFragment,
+    similarity: f64,
+}
+
+fn describe_fragment(fragment: &TemplateFragment) -> String {
+    format!(
+        "{} {} | {}:{}-{}",
+        fragment.kind,
+        fragment.name,
+        get_relative_path(&fragment.file_path),
+        fragment.start_line,
+        fragment.end_line
+    )
+}
+
+/// Find repeated template markup that could become a component or include
+///
+/// Only fragments of at least `min_nodes` nodes are considered. Nested fragments and siblings on
+/// one line (`<li>` items of an inline list) are not paired. Largest fragments are matched first,
+/// and once a pair is reported the fragments nested inside both sides are not reported again.
+fn find_duplicate_fragments(
+    fragments: &[TemplateFragment],
+    threshold: f64,
+    min_nodes: usize,
+    options: &TSEDOptions,
+) -> Vec<DuplicateFragmentPair> {
+    let mut candidates: Vec<&TemplateFragment> =
+        fragments.iter().filter(|fragment| fragment.tree.get_subtree_size() >= min_nodes).collect();
+    candidates.sort_by_key(|fragment| std::cmp::Reverse(fragment.tree.get_subtree_size()));
+
+    let mut duplicates: Vec<DuplicateFragmentPair> = Vec::new();
+    for i in 0..candidates.len() {
+        for j in i + 1..candidates.len() {
+            let (fragment1, fragment2) = (candidates[i], candidates[j]);
+            if fragment1.contains(fragment2)
+                || fragment2.contains(fragment1)
+                || fragment1.on_same_lines(fragment2)
+            {
+                continue;
+            }
+
+            // The edit distance is at least the size difference, so a pair this far apart in
+            // size can't reach the threshold
+            let size1 = fragment1.tree.get_subtree_size() as f64;
+            let size2 = fragment2.tree.get_subtree_size() as f64;
+            if 1.0 - (size1 - size2).abs() / size1.max(size2) < threshold {
+                continue;
+            }
+
+            let covered = duplicates.iter().any(|pair| {
+                (pair.fragment1.contains(fragment1) && pair.fragment2.contains(fragment2))
+                    || (pair.fragment1.contains(fragment2) && pair.fragment2.contains(fragment1))
+            });
+            if covered {
+                continue;
+            }
+
+            let similarity = calculate_tsed(&fragment1.tree, &fragment2.tree, options);
+            if similarity >= threshold {
+                duplicates.push(DuplicateFragmentPair {
+                    fragment1: fragment1.clone(),
+                    fragment2: fragment2.clone(),
+                    similarity,
+                });
+            }
+        }
+    }
+
+    duplicates.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+    duplicates
+}
+
+/// Check Blade and Twig templates for duplicated sections, blocks, partials and markup
+pub fn check_templates(
+    paths: Vec<String>,
+    threshold: f64,
+    rename_cost: f64,
+    min_nodes: usize,
+    print: bool,
+) -> anyhow::Result<usize> {
+    let files: Vec<_> = collect_files(&paths, &["php", "twig"])?
+        .into_iter()
+        .filter_map(|file| TemplateSyntax::from_path(&file).map(|syntax| (file, syntax)))
+        .collect();
+    if files.is_empty() {
+        println!("No Blade or Twig templates found in the specified paths.");
+        return Ok(0);
+    }
+
+    println!("Checking {} templates for duplicate markup...", files.len());
+
+    let mut fragments = Vec::new();
+    for (file, syntax) in &files {
+        let Ok(content) = fs::read_to_string(file) else { continue };
+        fragments.extend(extract_fragments(&content, &file.to_string_lossy(), *syntax));
+    }
+    println!(
+        "Found {} fragments of at least {} nodes\n",
+        fragments.iter().filter(|fragment| fragment.tree.get_subtree_size() >= min_nodes).count(),
+        min_nodes
+    );
+
+    // `min_nodes` already keeps trivial markup out; the short-function penalty would mark every
+    // copy of a small element as different. Copies differ in text, classes and expressions
+    // rather than structure, so values are compared too.
+    let mut options = TSEDOptions::default();
+    options.apted_options.rename_cost = rename_cost;
+    options.apted_options.compare_values = true;
+    options.size_penalty = false;
+    let duplicates = find_duplicate_fragments(&fragments, threshold, min_nodes, &options);
+
+    if duplicates.is_empty() {
+        println!("No duplicate template fragments found with threshold {:.0}%", threshold * 100.0);
+        return Ok(0);
+    }
+
+    println!("Duplicate template fragments (candidates for a component or include):");
+    println!("{}", "-".repeat(60));
+
+    for pair in &duplicates {
+        println!("\nSimilarity: {:.2}%", pair.similarity * 100.0);
+        for fragment in [&pair.fragment1, &pair.fragment2] {
+            println!("  {}", describe_fragment(fragment));
+        }
+
+        if print {
+            for fragment in [&pair.fragment1, &pair.fragment2] {
+                println!("\n\x1b[36m--- {} {} ---\x1b[0m", fragment.kind, fragment.name);
+                if let Ok(content) = fs::read_to_string(&fragment.file_path) {
+                    let (start, end) = fragment.span;
+                    println!("{}", content.get(start..end).unwrap_or("").trim());
+                }
+            }
+        }
+    }
+
+    println!("\n{}", "-".repeat(60));
+    println!("Total duplicate fragment pairs found: {}", duplicates.len());
+
+    Ok(duplicates.len())
+}
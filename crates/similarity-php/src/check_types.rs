@@ -0,0 +1,156 @@
+use crate::get_relative_path;
+use crate::php_parser::PhpParser;
+use similarity_core::cli_file_utils::collect_files;
+use similarity_core::cli_structures::{
+    find_similar_pairs, print_differences, print_method_body_similarity, ExtractedDefinitions,
+    SimilarPair, MIN_MEMBERS,
+};
+use similarity_core::{
+    find_trait_candidates, PhpStructureComparator, PhpTypeComparison, PhpTypeDef, PhpTypeKind,
+    TSEDOptions,
+};
+
+fn describe_type(type_def: &PhpTypeDef) -> String {
+    format!(
+        "{} {} | {}:{}-{}",
+        type_def.kind.label(),
+        type_def.name,
+        get_relative_path(&type_def.file_path),
+        type_def.start_line,
+        type_def.end_line
+    )
+}
+
+/// Check for similar classes, interfaces and traits, near-duplicate traits, and classes sharing
+/// methods that could move into a trait
+#[allow(clippy::too_many_arguments)]
+pub fn check_types(
+    paths: Vec<String>,
+    threshold: f64,
+    rename_cost: f64,
+    extensions: Option<&Vec<String>>,
+    no_size_penalty: bool,
+    min_shared_methods: usize,
+    print: bool,
+) -> anyhow::Result<usize> {
+    let default_extensions = vec!["php"];
+    let exts: Vec<&str> =
+        extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
+
+    let files = collect_files(&paths, &exts)?;
+    if files.is_empty() {
+        println!("No PHP files found in the specified paths.");
+        return Ok(0);
+    }
+
+    println!("Checking {} files for similar types...", files.len());
+
+    let mut parser =
+        PhpParser::new().map_err(|e| anyhow::anyhow!("Failed to create parser: {}", e))?;
+    let extracted = ExtractedDefinitions::extract(&files, |content, file_path| {
+        parser.extract_type_defs(content, file_path)
+    });
+    let type_defs = &extracted.definitions;
+
+    let count = |kind: PhpTypeKind| type_defs.iter().filter(|t| t.kind == kind).count();
+    println!(
+        "Found {} classes, {} interfaces and {} traits\n",
+        count(PhpTypeKind::Class),
+        count(PhpTypeKind::Interface),
+        count(PhpTypeKind::Trait)
+    );
+
+    let large_enough =
+        |type_def: &PhpTypeDef| type_def.properties.len() + type_def.methods.len() >= MIN_MEMBERS;
+    let comparable = |type1: &PhpTypeDef, type2: &PhpTypeDef| {
+        // A subclass restating part of its parent is inheritance, not duplication
+        type1.kind == type2.kind
+            && large_enough(type1)
+            && large_enough(type2)
+            && !type1.inherits_from(type2)
+            && !type2.inherits_from(type1)
+    };
+
+    let mut comparator = PhpStructureComparator::new();
+    let pairs = find_similar_pairs(type_defs, threshold, comparable, |type1, type2| {
+        let comparison = comparator.compare_types(type1, type2);
+        (comparison.similarity, comparison)
+    });
+    type TypePair = SimilarPair<PhpTypeComparison>;
+    let (trait_pairs, type_pairs): (Vec<TypePair>, Vec<TypePair>) =
+        pairs.into_iter().partition(|pair| type_defs[pair.index1].kind == PhpTypeKind::Trait);
+
+    let mut options = TSEDOptions::default();
+    options.apted_options.rename_cost = rename_cost;
+    options.size_penalty = !no_size_penalty;
+    let candidates = find_trait_candidates(type_defs, threshold, min_shared_methods, &options);
+
+    let print_pairs = |title: &str, pairs: &[TypePair]| {
+        println!("{title}");
+        println!("{}", "-".repeat(60));
+
+        for pair in pairs {
+            let type1 = &type_defs[pair.index1];
+            let type2 = &type_defs[pair.index2];
+
+            print_method_body_similarity(&pair.comparison, "properties");
+            println!("  {}", describe_type(type1));
+            println!("  {}", describe_type(type2));
+            print_differences(&type1.name, &type2.name, &pair.comparison.structure.differences);
+
+            if print {
+                for (index, type_def) in [(pair.index1, type1), (pair.index2, type2)] {
+                    extracted.print_code(
+                        index,
+                        &type_def.name,
+                        type_def.start_line,
+                        type_def.end_line,
+                    );
+                }
+            }
+        }
+        println!("\n{}", "-".repeat(60));
+    };
+
+    if type_pairs.is_empty() {
+        println!("No similar classes or interfaces found with threshold {:.0}%", threshold * 100.0);
+    } else {
+        print_pairs("Similar classes and interfaces:", &type_pairs);
+        println!("Total similar type pairs found: {}", type_pairs.len());
+    }
+
+    if !trait_pairs.is_empty() {
+        print_pairs("\nNear-duplicate traits (candidates for merging):", &trait_pairs);
+        println!("Total similar trait pairs found: {}", trait_pairs.len());
+    }
+
+    if !candidates.is_empty() {
+        println!("\nClasses sharing near-identical methods (candidates for a trait):");
+        println!("{}", "-".repeat(60));
+
+        for candidate in &candidates {
+            let class1 = &type_defs[candidate.index1];
+            let class2 = &type_defs[candidate.index2];
+            println!("\n  {}", describe_type(class1));
+            println!("  {}", describe_type(class2));
+            for method in &candidate.methods {
+                println!(
+                    "    {:.2}%  {}::{} (L{}-{}) ~ {}::{} (L{}-{})",
+                    method.similarity * 100.0,
+                    class1.name,
+                    method.method1.name,
+                    method.method1.start_line,
+                    method.method1.end_line,
+                    class2.name,
+                    method.method2.name,
+                    method.method2.start_line,
+                    method.method2.end_line
+                );
+            }
+        }
+
+        println!("\nTotal class pairs sharing methods: {}", candidates.len());
+    }
+
+    Ok(type_pairs.len() + trait_pairs.len() + candidates.len())
+}
@@ -1,9 +1,13 @@
 use anyhow::Result;
 use clap::Parser;
+use similarity_core::ChangedLines;
 
 mod check;
+mod check_templates;
+mod check_types;
 mod parallel;
 mod php_parser;
+mod template_parser;
 
 #[derive(Parser)]
 #[command(name = "similarity-php")]
@@ -42,6 +46,10 @@ struct Cli {
     #[arg(long)]
     no_size_penalty: bool,
 
+    /// Reward consistent identifier renames (a→x everywhere) and penalize conflicting ones
+    #[arg(long)]
+    rename_consistency: bool,
+
     /// Filter functions by name (substring match)
     #[arg(long)]
     filter_function: Option<String>,
@@ -54,6 +62,29 @@ struct Cli {
     #[arg(long)]
     no_fast: bool,
 
+    /// Check for similar classes, interfaces and traits, and classes sharing methods that could
+    /// move into a trait
+    #[arg(long)]
+    types: bool,
+
+    /// Disable function similarity checking
+    #[arg(long = "no-functions")]
+    no_functions: bool,
+
+    /// Minimum number of near-identical methods for two classes to be reported as a trait
+    /// candidate
+    #[arg(long, default_value = "2")]
+    min_shared_methods: usize,
+
+    /// Check Blade (`.blade.php`) and Twig (`.twig`) templates for duplicated sections, blocks,
+    /// partials and markup that could become a component or include
+    #[arg(long)]
+    templates: bool,
+
+    /// Minimum number of nodes for a template fragment to be compared
+    #[arg(long, default_value = "15")]
+    template_min_nodes: usize,
+
     /// Enable experimental overlap detection mode
     #[arg(long = "experimental-overlap")]
     overlap: bool,
@@ -69,20 +100,40 @@ struct Cli {
     /// Size tolerance for overlap detection (0.0-1.0)
     #[arg(long, default_value = "0.25")]
     overlap_size_tolerance: f64,
+
+    /// Only report duplicates involving lines changed since this git revision (e.g. origin/main)
+    #[arg(long, conflicts_with = "diff_from")]
+    changed_since: Option<String>,
+
+    /// Only report duplicates involving lines changed by this unified diff (e.g. a .patch file)
+    #[arg(long)]
+    diff_from: Option<std::path::PathBuf>,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let functions_enabled = true; // PHP always has functions enabled
+    let changed =
+        ChangedLines::from_cli_options(cli.changed_since.as_deref(), cli.diff_from.as_deref())
+            .map_err(anyhow::Error::msg)?;
+
+    let functions_enabled = !cli.no_functions;
+    let types_enabled = cli.types;
+    let templates_enabled = cli.templates;
     let overlap_enabled = cli.overlap;
 
+    // Validate that at least one analyzer is enabled
+    if !functions_enabled && !types_enabled && !templates_enabled && !overlap_enabled {
+        eprintln!("Error: At least one analyzer must be enabled. Use --types to enable type checking, --templates for template checking, --experimental-overlap for overlap detection, or remove --no-functions.");
+        return Err(anyhow::anyhow!("No analyzer enabled"));
+    }
+
     println!("Analyzing PHP code similarity...\n");
 
     let separator = "-".repeat(60);
 
     // Run functions analysis
-    if !overlap_enabled || functions_enabled {
+    if functions_enabled {
         println!("=== Function Similarity ===");
         check::check_paths(
             cli.paths.clone(),
@@ -92,16 +143,50 @@ fn main() -> Result<()> {
             cli.min_lines.unwrap_or(3),
             cli.min_tokens,
             cli.no_size_penalty,
+            cli.rename_consistency,
             cli.print,
             !cli.no_fast,
             cli.filter_function.as_ref(),
             cli.filter_function_body.as_ref(),
+            changed.as_ref(),
+        )?;
+    }
+
+    // Run types analysis if enabled
+    if types_enabled {
+        if functions_enabled {
+            println!("\n{separator}");
+        }
+        println!("=== Type Similarity ===");
+        check_types::check_types(
+            cli.paths.clone(),
+            cli.threshold,
+            cli.rename_cost,
+            cli.extensions.as_ref(),
+            cli.no_size_penalty,
+            cli.min_shared_methods,
+            cli.print,
+        )?;
+    }
+
+    // Run template analysis if enabled
+    if templates_enabled {
+        if functions_enabled || types_enabled {
+            println!("\n{separator}");
+        }
+        println!("=== Template Similarity ===");
+        check_templates::check_templates(
+            cli.paths.clone(),
+            cli.threshold,
+            cli.rename_cost,
+            cli.template_min_nodes,
+            cli.print,
         )?;
     }
 
     // Run overlap detection if enabled
     if overlap_enabled {
-        if functions_enabled {
+        if functions_enabled || types_enabled || templates_enabled {
             println!("\n{separator}");
         }
         println!("=== Overlap Detection (Experimental) ===");
@@ -117,3 +202,16 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Get relative path for display
+fn get_relative_path(file_path: &str) -> String {
+    if let Ok(current_dir) = std::env::current_dir() {
+        std::path::Path::new(file_path)
+            .strip_prefix(&current_dir)
+            .unwrap_or(std::path::Path::new(file_path))
+            .to_string_lossy()
+            .to_string()
+    } else {
+        file_path.to_string()
+    }
+}
@@ -2,6 +2,7 @@
 
 use rayon::prelude::*;
 use similarity_core::{
+    changed_lines::ChangedLines,
     cli_parallel::{FileData, SimilarityResult},
     language_parser::{GenericFunctionDef, LanguageParser},
     tsed::{calculate_tsed, TSEDOptions},
@@ -56,9 +57,11 @@ pub fn check_within_file_duplicates_parallel(
     files: &[PathBuf],
     threshold: f64,
     options: &TSEDOptions,
+    changed: Option<&ChangedLines>,
 ) -> Vec<(PathBuf, Vec<SimilarityResult<GenericFunctionDef>>)> {
     files
         .par_iter()
+        .filter(|file| changed.is_none_or(|c| c.touches_file(file)))
         .filter_map(|file| match fs::read_to_string(file) {
             Ok(code) => {
                 let file_str = file.to_string_lossy();
@@ -69,11 +72,24 @@ pub fn check_within_file_duplicates_parallel(
                         // Extract functions
                         match parser.extract_functions(&code, &file_str) {
                             Ok(functions) => {
+                                // Only pairs with a side in changed code are compared
+                                let touched: Vec<bool> = functions
+                                    .iter()
+                                    .map(|f| {
+                                        changed.is_none_or(|c| {
+                                            c.overlaps(file, f.start_line, f.end_line)
+                                        })
+                                    })
+                                    .collect();
                                 let mut similar_pairs = Vec::new();
 
                                 // Compare all pairs within the file
                                 for i in 0..functions.len() {
                                     for j in (i + 1)..functions.len() {
+                                        if !touched[i] && !touched[j] {
+                                            continue;
+                                        }
+
                                         let func1 = &functions[i];
                                         let func2 = &functions[j];
 
@@ -2,6 +2,7 @@ use similarity_core::language_parser::{
     GenericFunctionDef, GenericTypeDef, Language, LanguageParser,
 };
 use similarity_core::tree::TreeNode;
+use similarity_core::{PhpMethodDef, PhpPropertyDef, PhpTypeDef, PhpTypeKind};
 use std::error::Error;
 use std::rc::Rc;
 use tree_sitter::{Node, Parser};
@@ -31,7 +32,8 @@ impl PhpParser {
             _ => "".to_string(),
         };
 
-        let mut tree_node = TreeNode::new(label, value, current_id);
+        let mut tree_node = TreeNode::new(label, value, current_id)
+            .with_span(node.start_byte() as u32, node.end_byte() as u32);
 
         for child in node.children(&mut node.walk()) {
             let child_node = self.convert_node(child, source, id_counter);
@@ -262,6 +264,215 @@ impl PhpParser {
         visit_node(node, source, &mut functions, class_name, final_namespace);
         functions
     }
+
+    /// Extract classes, interfaces and traits with their properties and methods, keeping method
+    /// bodies as comparison trees
+    pub fn extract_type_defs(
+        &mut self,
+        source: &str,
+        file_path: &str,
+    ) -> Result<Vec<PhpTypeDef>, Box<dyn Error + Send + Sync>> {
+        let tree =
+            self.parser.parse(source, None).ok_or_else(|| -> Box<dyn Error + Send + Sync> {
+                "Failed to parse PHP source".into()
+            })?;
+
+        let mut types = Vec::new();
+        let mut namespace = None;
+        self.visit_for_type_defs(tree.root_node(), source, file_path, &mut namespace, &mut types);
+        Ok(types)
+    }
+
+    fn visit_for_type_defs(
+        &self,
+        node: Node,
+        source: &str,
+        file_path: &str,
+        namespace: &mut Option<String>,
+        types: &mut Vec<PhpTypeDef>,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let kind = match child.kind() {
+                "class_declaration" => PhpTypeKind::Class,
+                "interface_declaration" => PhpTypeKind::Interface,
+                "trait_declaration" => PhpTypeKind::Trait,
+                "namespace_definition" => {
+                    // `namespace App;` applies to the following statements, `namespace App { }`
+                    // to its body
+                    let name = child.child_by_field_name("name").map(|n| node_text(n, source));
+                    match child.child_by_field_name("body") {
+                        Some(body) => {
+                            let mut inner = name;
+                            self.visit_for_type_defs(body, source, file_path, &mut inner, types);
+                        }
+                        None => *namespace = name,
+                    }
+                    continue;
+                }
+                _ => {
+                    self.visit_for_type_defs(child, source, file_path, namespace, types);
+                    continue;
+                }
+            };
+            if let Some(type_def) = self.type_def(child, kind, source, file_path, namespace) {
+                types.push(type_def);
+            }
+        }
+    }
+
+    fn type_def(
+        &self,
+        node: Node,
+        kind: PhpTypeKind,
+        source: &str,
+        file_path: &str,
+        namespace: &Option<String>,
+    ) -> Option<PhpTypeDef> {
+        let name = node_text(node.child_by_field_name("name")?, source);
+        let mut type_def = PhpTypeDef {
+            name,
+            kind,
+            namespace: namespace.clone(),
+            extends: Vec::new(),
+            implements: Vec::new(),
+            traits: Vec::new(),
+            properties: Vec::new(),
+            methods: Vec::new(),
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+            file_path: file_path.to_string(),
+        };
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "base_clause" => type_def.extends = type_names(child, source),
+                "class_interface_clause" => type_def.implements = type_names(child, source),
+                _ => {}
+            }
+        }
+
+        let Some(body) = node.child_by_field_name("body") else {
+            return Some(type_def);
+        };
+        let mut cursor = body.walk();
+        for member in body.children(&mut cursor) {
+            match member.kind() {
+                "use_declaration" => type_def.traits.extend(type_names(member, source)),
+                "property_declaration" => {
+                    let type_hint =
+                        member.child_by_field_name("type").map(|t| node_text(t, source));
+                    let modifiers = modifiers(member, source);
+                    let mut element_cursor = member.walk();
+                    for element in member.children(&mut element_cursor) {
+                        if element.kind() != "property_element" {
+                            continue;
+                        }
+                        if let Some(name) = element.child_by_field_name("name") {
+                            type_def.properties.push(PhpPropertyDef {
+                                name: node_text(name, source).trim_start_matches('$').to_string(),
+                                type_hint: type_hint.clone(),
+                                modifiers: modifiers.clone(),
+                            });
+                        }
+                    }
+                }
+                "method_declaration" => {
+                    if let Some(method) = self.method_def(member, source) {
+                        if method.name == "__construct" {
+                            type_def.properties.extend(promoted_properties(member, source));
+                        }
+                        type_def.methods.push(method);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(type_def)
+    }
+
+    fn method_def(&self, node: Node, source: &str) -> Option<PhpMethodDef> {
+        let name = node_text(node.child_by_field_name("name")?, source);
+        let parameters = node
+            .child_by_field_name("parameters")
+            .map(|parameters| {
+                let mut cursor = parameters.walk();
+                parameters
+                    .named_children(&mut cursor)
+                    .filter_map(|parameter| {
+                        let name = node_text(parameter.child_by_field_name("name")?, source);
+                        let name = if parameter.kind() == "variadic_parameter" {
+                            format!("...{name}")
+                        } else {
+                            name
+                        };
+                        Some(match parameter.child_by_field_name("type") {
+                            Some(type_node) => format!("{} {}", node_text(type_node, source), name),
+                            None => name,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let body = node.child_by_field_name("body").map(|body| {
+            let mut id_counter = 0;
+            Rc::new(self.convert_node(body, source, &mut id_counter))
+        });
+
+        Some(PhpMethodDef {
+            name,
+            parameters,
+            return_type: node.child_by_field_name("return_type").map(|t| node_text(t, source)),
+            modifiers: modifiers(node, source),
+            body,
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+        })
+    }
+}
+
+fn node_text(node: Node, source: &str) -> String {
+    node.utf8_text(source.as_bytes()).unwrap_or("").to_string()
+}
+
+/// `Model`, `\JsonSerializable` from `extends`/`implements` clauses and trait `use` lists
+fn type_names(node: Node, source: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|child| matches!(child.kind(), "name" | "qualified_name"))
+        .map(|child| node_text(child, source))
+        .collect()
+}
+
+/// Visibility, `static`, `abstract`, `final` and `readonly` modifiers of a member
+fn modifiers(node: Node, source: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| child.kind().ends_with("_modifier"))
+        .map(|child| node_text(child, source))
+        .collect()
+}
+
+/// Properties declared by constructor promotion, `__construct(private string $email)`
+fn promoted_properties(constructor: Node, source: &str) -> Vec<PhpPropertyDef> {
+    let Some(parameters) = constructor.child_by_field_name("parameters") else {
+        return Vec::new();
+    };
+    let mut cursor = parameters.walk();
+    parameters
+        .named_children(&mut cursor)
+        .filter(|parameter| parameter.kind() == "property_promotion_parameter")
+        .filter_map(|parameter| {
+            let name = node_text(parameter.child_by_field_name("name")?, source);
+            Some(PhpPropertyDef {
+                name: name.trim_start_matches('$').to_string(),
+                type_hint: parameter.child_by_field_name("type").map(|t| node_text(t, source)),
+                modifiers: modifiers(parameter, source),
+            })
+        })
+        .collect()
 }
 
 impl LanguageParser for PhpParser {
@@ -429,6 +640,7 @@ impl LanguageParser for PhpParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use similarity_core::{find_trait_candidates, PhpStructureComparator, TSEDOptions};
 
     #[test]
     fn test_php_functions() {
@@ -506,6 +718,165 @@ trait Loggable {
         assert_eq!(types[2].kind, "trait");
     }
 
+    #[test]
+    fn test_php_type_defs() {
+        let mut parser = PhpParser::new().unwrap();
+        let source = r#"<?php
+namespace App\Models;
+
+abstract class User extends Model implements \JsonSerializable, Arrayable {
+    use HasFactory, SoftDeletes;
+
+    protected ?string $name = null, $nickname;
+    private static array $cache = [];
+
+    public function __construct(private string $email, ?int $age = null, ...$roles) {}
+
+    abstract protected function boot(): void;
+
+    public static function find(int|string $id): ?static {
+        return null;
+    }
+}
+
+interface Repository extends Countable {
+    public function all(): array;
+}
+
+trait Loggable {
+    public function log($message) {
+        echo $message;
+    }
+}
+"#;
+
+        let types = parser.extract_type_defs(source, "User.php").unwrap();
+        assert_eq!(types.len(), 3);
+
+        let user = &types[0];
+        assert_eq!(user.kind, PhpTypeKind::Class);
+        assert_eq!(user.namespace.as_deref(), Some("App\\Models"));
+        assert_eq!(user.extends, ["Model"]);
+        assert_eq!(user.implements, ["\\JsonSerializable", "Arrayable"]);
+        assert_eq!(user.traits, ["HasFactory", "SoftDeletes"]);
+        let properties: Vec<(&str, Option<&str>)> =
+            user.properties.iter().map(|p| (p.name.as_str(), p.type_hint.as_deref())).collect();
+        assert_eq!(
+            properties,
+            [
+                ("name", Some("?string")),
+                ("nickname", Some("?string")),
+                ("cache", Some("array")),
+                ("email", Some("string"))
+            ]
+        );
+        assert_eq!(user.properties[2].modifiers, ["private", "static"]);
+
+        let signatures: Vec<String> = user.methods.iter().map(|m| m.signature()).collect();
+        assert_eq!(
+            signatures,
+            ["(string $email, ?int $age, ...$roles)", "(): void", "(int|string $id): ?static"]
+        );
+        assert!(user.methods[1].body.is_none());
+        assert!(user.methods[2].body.is_some());
+        assert_eq!((user.methods[2].start_line, user.methods[2].end_line), (14, 16));
+
+        assert_eq!(types[1].kind, PhpTypeKind::Interface);
+        assert_eq!(types[1].extends, ["Countable"]);
+        assert_eq!(types[2].kind, PhpTypeKind::Trait);
+        assert_eq!(types[2].methods[0].name, "log");
+    }
+
+    #[test]
+    fn test_php_types_with_the_same_properties_are_similar() {
+        let mut parser = PhpParser::new().unwrap();
+        let source = r#"<?php
+class User {
+    private int $id;
+    private string $name;
+    private string $email;
+}
+
+class Customer {
+    private int $id;
+    private string $name;
+    private string $email;
+}
+
+class Account {
+    private int $id;
+    private int $name;
+    private bool $email;
+}
+"#;
+
+        let types = parser.extract_type_defs(source, "Models.php").unwrap();
+        let mut comparator = PhpStructureComparator::new();
+        let same = comparator.compare_types(&types[0], &types[1]);
+        assert!(same.similarity > 0.9, "similarity: {}", same.similarity);
+        assert_eq!(same.method_body_similarity, None);
+
+        let different = comparator.compare_types(&types[0], &types[2]);
+        assert!(different.similarity < same.similarity);
+    }
+
+    #[test]
+    fn test_php_trait_candidates_skip_related_types() {
+        let mut parser = PhpParser::new().unwrap();
+        let source = r#"<?php
+class Invoice {
+    public function log(string $message): void {
+        $line = date('c') . ' ' . $message;
+        file_put_contents($this->logFile, $line . PHP_EOL, FILE_APPEND);
+    }
+
+    public function toArray(): array {
+        return ['id' => $this->id, 'total' => $this->total, 'items' => $this->items];
+    }
+}
+
+class Order {
+    public function write(string $message): void {
+        $line = date('c') . ' ' . $message;
+        file_put_contents($this->logFile, $line . PHP_EOL, FILE_APPEND);
+    }
+
+    public function serialize(): array {
+        return ['id' => $this->id, 'total' => $this->total, 'items' => $this->items];
+    }
+}
+
+class DraftInvoice extends \App\Invoice {
+    public function log(string $message): void {
+        $line = date('c') . ' ' . $message;
+        file_put_contents($this->logFile, $line . PHP_EOL, FILE_APPEND);
+    }
+
+    public function toArray(): array {
+        return ['id' => $this->id, 'total' => $this->total, 'items' => $this->items];
+    }
+}
+
+trait Logs {
+    public function log(string $message): void {
+        $line = date('c') . ' ' . $message;
+        file_put_contents($this->logFile, $line . PHP_EOL, FILE_APPEND);
+    }
+
+    public function toArray(): array {
+        return ['id' => $this->id, 'total' => $this->total, 'items' => $this->items];
+    }
+}
+"#;
+
+        let types = parser.extract_type_defs(source, "Billing.php").unwrap();
+        let options = TSEDOptions { size_penalty: false, ..TSEDOptions::default() };
+        let candidates = find_trait_candidates(&types, 0.9, 2, &options);
+        let pairs: Vec<(usize, usize)> = candidates.iter().map(|c| (c.index1, c.index2)).collect();
+        assert_eq!(pairs, [(0, 1), (1, 2)]);
+        assert_eq!(candidates[0].methods.len(), 2);
+    }
+
     #[test]
     fn test_php_namespace() {
         let mut parser = PhpParser::new().unwrap();
@@ -0,0 +1,829 @@
+use similarity_core::tree::TreeNode;
+use std::path::Path;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemplateSyntax {
+    Blade,
+    Twig,
+}
+
+impl TemplateSyntax {
+    /// Blade for `.blade.php`, Twig for `.twig` (`.html.twig`)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        if name.ends_with(".blade.php") {
+            Some(Self::Blade)
+        } else if name.ends_with(".twig") {
+            Some(Self::Twig)
+        } else {
+            None
+        }
+    }
+}
+
+/// A part of a template that could be compared with others: a whole partial, a section or
+/// block, or an HTML element
+#[derive(Debug, Clone)]
+pub struct TemplateFragment {
+    /// `template`, `element`, or the directive defining the fragment (`section`, `push`,
+    /// `component`, `block`, `macro`)
+    pub kind: String,
+    /// File name, section or block name, or element tag with its first class (`div.card`)
+    pub name: String,
+    pub file_path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Byte range in the template, used to tell nested fragments apart
+    pub span: (usize, usize),
+    pub tree: Rc<TreeNode>,
+}
+
+impl TemplateFragment {
+    /// Whether `other` lies inside this fragment (or is this fragment)
+    pub fn contains(&self, other: &TemplateFragment) -> bool {
+        self.file_path == other.file_path
+            && self.span.0 <= other.span.0
+            && other.span.1 <= self.span.1
+    }
+
+    /// Whether both fragments start and end on the same lines of one template, like the items
+    /// of a list written on one line
+    pub fn on_same_lines(&self, other: &TemplateFragment) -> bool {
+        self.file_path == other.file_path
+            && self.start_line == other.start_line
+            && self.end_line == other.end_line
+    }
+}
+
+/// Directives whose blocks are compared as fragments of their own
+const FRAGMENT_DIRECTIVES: &[&str] =
+    &["section", "push", "prepend", "component", "slot", "fragment", "block", "macro", "embed"];
+
+const BLADE_BLOCKS: &[&str] = &[
+    "if",
+    "unless",
+    "isset",
+    "auth",
+    "guest",
+    "env",
+    "production",
+    "foreach",
+    "forelse",
+    "for",
+    "while",
+    "switch",
+    "push",
+    "prepend",
+    "pushOnce",
+    "prependOnce",
+    "once",
+    "component",
+    "can",
+    "cannot",
+    "canany",
+    "error",
+    "hasSection",
+    "sectionMissing",
+    "fragment",
+    "session",
+];
+
+const TWIG_BLOCKS: &[&str] = &[
+    "if",
+    "for",
+    "macro",
+    "embed",
+    "apply",
+    "with",
+    "spaceless",
+    "autoescape",
+    "filter",
+    "sandbox",
+    "cache",
+    "guard",
+    "trans",
+];
+
+/// Elements that never have content or a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// A template node while the tree is being built; nodes are converted to `TreeNode`s once
+/// their children are known
+struct Node {
+    label: String,
+    value: String,
+    children: Vec<Node>,
+    start: usize,
+    end: usize,
+    kind: NodeKind,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum NodeKind {
+    Root,
+    Element,
+    /// An inline directive, echo, text or attribute
+    Leaf,
+    /// A directive with a body, closed by its `end` directive
+    Block,
+    /// `else`, `elseif`, `case`, `empty`: a branch of the enclosing block
+    Branch,
+}
+
+impl Node {
+    fn new(label: &str, value: &str, start: usize, end: usize, kind: NodeKind) -> Self {
+        Self {
+            label: label.to_string(),
+            value: value.to_string(),
+            children: Vec::new(),
+            start,
+            end,
+            kind,
+        }
+    }
+
+    fn to_tree(&self, id_counter: &mut usize) -> TreeNode {
+        let mut tree = TreeNode::new(self.label.clone(), self.value.clone(), *id_counter)
+            .with_span(self.start as u32, self.end as u32);
+        *id_counter += 1;
+        for child in &self.children {
+            tree.add_child(Rc::new(child.to_tree(id_counter)));
+        }
+        tree
+    }
+}
+
+struct TemplateParser<'a> {
+    source: &'a str,
+    syntax: TemplateSyntax,
+    pos: usize,
+    /// Open nodes, the root first
+    stack: Vec<Node>,
+}
+
+impl<'a> TemplateParser<'a> {
+    fn new(source: &'a str, syntax: TemplateSyntax) -> Self {
+        let root = Node::new("template", "", 0, source.len(), NodeKind::Root);
+        Self { source, syntax, pos: 0, stack: vec![root] }
+    }
+
+    fn parse(mut self) -> Node {
+        let bytes = self.source.as_bytes();
+        let mut text_start = 0;
+        while self.pos < bytes.len() {
+            if !self.is_special_at(self.pos) {
+                self.pos += 1;
+                continue;
+            }
+            self.add_text(text_start, self.pos);
+            self.parse_special();
+            text_start = self.pos;
+        }
+        self.add_text(text_start, bytes.len());
+
+        while self.stack.len() > 1 {
+            self.close_top(self.source.len());
+        }
+        self.stack.pop().unwrap()
+    }
+
+    fn is_special_at(&self, i: usize) -> bool {
+        let bytes = self.source.as_bytes();
+        let next = bytes.get(i + 1).copied().unwrap_or(0);
+        match bytes[i] {
+            b'<' => next.is_ascii_alphabetic() || matches!(next, b'/' | b'!' | b'?'),
+            b'{' => match self.syntax {
+                TemplateSyntax::Blade => {
+                    next == b'{' || (next == b'!' && bytes.get(i + 2) == Some(&b'!'))
+                }
+                TemplateSyntax::Twig => matches!(next, b'{' | b'%' | b'#'),
+            },
+            // `user@example.com` is not a directive
+            b'@' => {
+                self.syntax == TemplateSyntax::Blade
+                    && (next.is_ascii_alphabetic() || matches!(next, b'@' | b'{'))
+                    && (i == 0 || (!bytes[i - 1].is_ascii_alphanumeric() && bytes[i - 1] != b'.'))
+            }
+            _ => false,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    /// Byte offset just past the next `marker` at or after `from`, or the end of the source
+    fn skip_past(&self, from: usize, marker: &str) -> usize {
+        self.source[from..].find(marker).map_or(self.source.len(), |i| from + i + marker.len())
+    }
+
+    /// The source between the `open_len` delimiter at `start` and the `close_len` one ending
+    /// at `end`; empty for a delimiter left unclosed at the end of the template
+    fn inner(&self, start: usize, open_len: usize, end: usize, close_len: usize) -> &'a str {
+        self.source.get(start + open_len..end.saturating_sub(close_len)).unwrap_or("")
+    }
+
+    fn parse_special(&mut self) {
+        let start = self.pos;
+        let rest = self.rest();
+        if rest.starts_with("<!--") {
+            self.pos = self.skip_past(start, "-->");
+        } else if rest.starts_with("<?") {
+            let end = self.skip_past(start, "?>");
+            let code = collapse_whitespace(self.inner(start, 2, end, 2));
+            self.add_leaf(Node::new(
+                "php",
+                code.trim_start_matches("php "),
+                start,
+                end,
+                NodeKind::Leaf,
+            ));
+            self.pos = end;
+        } else if rest.starts_with("<!") {
+            // `<!DOCTYPE html>`
+            self.pos = self.skip_past(start, ">");
+        } else if rest.starts_with("</") {
+            let end = self.skip_past(start, ">");
+            let name = self.inner(start, 2, end, 1).trim().to_string();
+            self.pos = end;
+            self.close_element(&name, end);
+        } else if rest.starts_with('<') {
+            self.parse_tag();
+        } else if rest.starts_with("{{--") {
+            self.pos = self.skip_past(start, "--}}");
+        } else if rest.starts_with("{#") {
+            self.pos = self.skip_past(start, "#}");
+        } else if rest.starts_with("{!!") {
+            self.parse_echo("raw-echo", "!!}");
+        } else if rest.starts_with("{{") {
+            self.parse_echo("echo", "}}");
+        } else if rest.starts_with("{%") {
+            self.parse_twig_tag();
+        } else if rest.starts_with("@@") || rest.starts_with("@{{") {
+            // Escaped for a JavaScript framework: `@{{ name }}`, `@@click`
+            self.pos = if rest.starts_with("@{{") {
+                self.skip_past(start, "}}")
+            } else {
+                start + 2 + word_len(&rest[2..])
+            };
+            self.add_text(start + 1, self.pos);
+        } else {
+            self.parse_blade_directive();
+        }
+    }
+
+    fn parse_echo(&mut self, label: &str, close: &str) {
+        let start = self.pos;
+        let end = self.skip_past(start, close);
+        let inner = self.inner(start, 2, end, 2);
+        let expression = inner.trim_start_matches('!').trim_end_matches('!');
+        let expression = expression.trim_matches(|c: char| c == '-' || c == '~').trim();
+        self.add_leaf(Node::new(
+            label,
+            &collapse_whitespace(expression),
+            start,
+            end,
+            NodeKind::Leaf,
+        ));
+        self.pos = end;
+    }
+
+    fn parse_tag(&mut self) {
+        let start = self.pos;
+        let bytes = self.source.as_bytes();
+        let name_len = self.source[start + 1..]
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(self.source.len() - start - 1);
+        let name = &self.source[start + 1..start + 1 + name_len];
+
+        // Find the closing `>` outside quoted attribute values
+        let mut i = start + 1 + name_len;
+        let mut quote = None;
+        while i < bytes.len() {
+            match (quote, bytes[i]) {
+                (Some(q), c) if c == q => quote = None,
+                (None, b'"' | b'\'') => quote = Some(bytes[i]),
+                (None, b'>') => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        let end = (i + 1).min(bytes.len());
+        let attributes_source = &self.source[start + 1 + name_len..i.min(bytes.len())];
+        let self_closing = attributes_source.trim_end().ends_with('/');
+        self.pos = end;
+
+        let mut element = Node::new(name, "", start, end, NodeKind::Element);
+        element.children = parse_attributes(attributes_source.trim_end_matches('/'), start);
+
+        let lower = name.to_ascii_lowercase();
+        if lower == "script" || lower == "style" {
+            // Raw content, compared as text
+            let close = format!("</{lower}");
+            let content_end = self.source[end..]
+                .to_ascii_lowercase()
+                .find(&close)
+                .map_or(bytes.len(), |i| end + i);
+            let content = collapse_whitespace(&self.source[end..content_end]);
+            if !content.is_empty() {
+                element.children.push(Node::new(
+                    "text",
+                    &content,
+                    end,
+                    content_end,
+                    NodeKind::Leaf,
+                ));
+            }
+            self.pos = self.skip_past(content_end, ">");
+            element.end = self.pos;
+            self.add_leaf(element);
+        } else if self_closing || VOID_ELEMENTS.contains(&lower.as_str()) {
+            self.add_leaf(element);
+        } else {
+            self.stack.push(element);
+        }
+    }
+
+    fn parse_blade_directive(&mut self) {
+        let start = self.pos;
+        let name_len = word_len(&self.source[start + 1..]);
+        let name = self.source[start + 1..start + 1 + name_len].to_string();
+        let mut end = start + 1 + name_len;
+
+        // `@foreach ($users as $user)`: spaces or tabs, then balanced parentheses
+        let after_name = &self.source[end..];
+        let spaces = after_name.len() - after_name.trim_start_matches([' ', '\t']).len();
+        let mut args = None;
+        if after_name[spaces..].starts_with('(') {
+            if let Some(len) = balanced_parens_len(&after_name[spaces..]) {
+                let inner = &after_name[spaces + 1..spaces + len - 1];
+                args = Some(collapse_whitespace(inner));
+                end += spaces + len;
+            }
+        }
+        self.pos = end;
+
+        if (name == "php" || name == "verbatim") && args.is_none() {
+            self.parse_raw_block(&name, start, &format!("@end{name}"));
+            return;
+        }
+
+        let args = args.unwrap_or_default();
+        let single_argument = !has_top_level_comma(&args);
+        let is_block = BLADE_BLOCKS.contains(&name.as_str())
+            || (matches!(name.as_str(), "section" | "slot") && single_argument)
+            || (name == "empty" && !args.is_empty());
+        match name.as_str() {
+            "show" | "stop" | "overwrite" | "append" => self.close_directive("section", end),
+            "else" | "elseif" | "case" | "default" => self.open_branch(&name, &args, start, end),
+            "empty" if args.is_empty() => self.open_branch(&name, &args, start, end),
+            _ if is_block => {
+                self.stack.push(Node::new(&name, &args, start, end, NodeKind::Block));
+            }
+            _ => match name.strip_prefix("end").filter(|block| !block.is_empty()) {
+                Some(block) => self.close_directive(block, end),
+                None => self.add_leaf(Node::new(&name, &args, start, end, NodeKind::Leaf)),
+            },
+        }
+    }
+
+    fn parse_twig_tag(&mut self) {
+        let start = self.pos;
+        let end = self.skip_past(start, "%}");
+        self.pos = end;
+        let inner = self.inner(start, 2, end, 2);
+        let inner = inner.trim_matches(|c: char| c == '-' || c == '~' || c.is_whitespace());
+        let (name, args) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+        let args = collapse_whitespace(args);
+
+        if name == "verbatim" || name == "raw" {
+            self.parse_raw_block(name, start, &format!("end{name}"));
+            self.pos = self.skip_past(self.pos, "%}");
+            if let Some(node) = self.stack.last_mut().and_then(|top| top.children.last_mut()) {
+                node.end = self.pos;
+            }
+            return;
+        }
+
+        // `{% block title 'Home' %}` and `{% set x = 1 %}` have no body
+        let is_block = TWIG_BLOCKS.contains(&name)
+            || (name == "block" && !args.contains(char::is_whitespace))
+            || (name == "set" && !args.contains('='));
+        match name {
+            "else" | "elseif" => self.open_branch(name, &args, start, end),
+            _ if is_block => self.stack.push(Node::new(name, &args, start, end, NodeKind::Block)),
+            _ => match name.strip_prefix("end").filter(|block| !block.is_empty()) {
+                Some(block) => self.close_directive(block, end),
+                None => self.add_leaf(Node::new(name, &args, start, end, NodeKind::Leaf)),
+            },
+        }
+    }
+
+    /// `@php ... @endphp`, `{% verbatim %}...{% endverbatim %}`: the content is kept as text
+    fn parse_raw_block(&mut self, name: &str, start: usize, end_marker: &str) {
+        let content_start = self.pos;
+        let content_end =
+            self.source[content_start..].find(end_marker).map_or(self.source.len(), |i| {
+                let i = content_start + i;
+                match self.syntax {
+                    // The end marker sits inside `{% ... %}`
+                    TemplateSyntax::Twig => self.source[content_start..i]
+                        .rfind("{%")
+                        .map_or(i, |open| content_start + open),
+                    TemplateSyntax::Blade => i,
+                }
+            });
+        self.pos = self.skip_past(content_start, end_marker).min(self.source.len());
+
+        let mut node = Node::new(name, "", start, self.pos, NodeKind::Leaf);
+        let content = collapse_whitespace(&self.source[content_start..content_end]);
+        if !content.is_empty() {
+            node.children.push(Node::new(
+                "text",
+                &content,
+                content_start,
+                content_end,
+                NodeKind::Leaf,
+            ));
+        }
+        self.add_leaf(node);
+    }
+
+    fn add_text(&mut self, start: usize, end: usize) {
+        let text = collapse_whitespace(&self.source[start..end]);
+        if !text.is_empty() {
+            self.add_leaf(Node::new("text", &text, start, end, NodeKind::Leaf));
+        }
+    }
+
+    fn add_leaf(&mut self, node: Node) {
+        self.stack.last_mut().unwrap().children.push(node);
+    }
+
+    fn close_top(&mut self, end: usize) {
+        let mut node = self.stack.pop().unwrap();
+        node.end = node.end.max(end);
+        self.add_leaf(node);
+    }
+
+    /// Close the element opened by `</name>`, along with elements left open inside it; a closing
+    /// tag without an open element in the same directive block is ignored
+    fn close_element(&mut self, name: &str, end: usize) {
+        let open = self.stack.iter().rposition(|node| {
+            node.kind != NodeKind::Element || node.label.eq_ignore_ascii_case(name)
+        });
+        if let Some(index) = open.filter(|&i| self.stack[i].kind == NodeKind::Element) {
+            while self.stack.len() > index {
+                self.close_top(end);
+            }
+        }
+    }
+
+    /// Close the block directive `name` and everything left open inside it
+    fn close_directive(&mut self, name: &str, end: usize) {
+        let open =
+            self.stack.iter().rposition(|node| node.kind == NodeKind::Block && node.label == name);
+        if let Some(index) = open {
+            while self.stack.len() > index {
+                self.close_top(end);
+            }
+        }
+    }
+
+    /// Start a branch of the innermost block directive, closing the previous branch
+    fn open_branch(&mut self, name: &str, args: &str, start: usize, end: usize) {
+        let Some(index) = self.stack.iter().rposition(|node| node.kind == NodeKind::Block) else {
+            self.add_leaf(Node::new(name, args, start, end, NodeKind::Leaf));
+            return;
+        };
+        while self.stack.len() > index + 1 {
+            self.close_top(start);
+        }
+        self.stack.push(Node::new(name, args, start, end, NodeKind::Branch));
+    }
+}
+
+/// Extract the comparable fragments of a template: the template itself unless it extends a
+/// layout or is a single element, its sections and blocks, and every HTML element
+///
+/// Elements are labeled with their tag and have their attributes as children; directives and
+/// tags are labeled with their name and keep their arguments as the value, with the blocks of
+/// `@if`/`{% for %}` and friends as children; echoes and text keep their content as the value.
+pub fn extract_fragments(
+    source: &str,
+    file_path: &str,
+    syntax: TemplateSyntax,
+) -> Vec<TemplateFragment> {
+    let root = TemplateParser::new(source, syntax).parse();
+    let line_starts: Vec<usize> =
+        std::iter::once(0).chain(source.match_indices('\n').map(|(i, _)| i + 1)).collect();
+    let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset);
+
+    let mut fragments = Vec::new();
+    let mut visit_stack = vec![&root];
+    let mut extends_layout = false;
+    while let Some(node) = visit_stack.pop() {
+        extends_layout |= node.label == "extends" && node.kind == NodeKind::Leaf;
+        let fragment = match node.kind {
+            NodeKind::Element => Some(("element".to_string(), element_name(node))),
+            NodeKind::Block if FRAGMENT_DIRECTIVES.contains(&node.label.as_str()) => {
+                Some((node.label.clone(), directive_name(&node.value)))
+            }
+            _ => None,
+        };
+        if let Some((kind, name)) = fragment {
+            let mut id_counter = 0;
+            fragments.push(TemplateFragment {
+                kind,
+                name,
+                file_path: file_path.to_string(),
+                start_line: line_of(node.start),
+                end_line: line_of(node.end.saturating_sub(1).max(node.start)),
+                span: (node.start, node.end),
+                tree: Rc::new(node.to_tree(&mut id_counter)),
+            });
+        }
+        visit_stack.extend(node.children.iter().rev());
+    }
+
+    // A partial wrapped in one element is already compared as that element
+    if !extends_layout && root.children.len() > 1 {
+        let name = Path::new(file_path)
+            .file_name()
+            .map_or(file_path.to_string(), |name| name.to_string_lossy().to_string());
+        let mut id_counter = 0;
+        fragments.insert(
+            0,
+            TemplateFragment {
+                kind: "template".to_string(),
+                name,
+                file_path: file_path.to_string(),
+                start_line: 1,
+                end_line: line_of(source.len().saturating_sub(1)),
+                span: (0, source.len()),
+                tree: Rc::new(root.to_tree(&mut id_counter)),
+            },
+        );
+    }
+
+    fragments
+}
+
+/// `div.card` for `<div class="card shadow">`
+fn element_name(element: &Node) -> String {
+    let class = element
+        .children
+        .iter()
+        .find(|child| child.label == "attribute" && child.value == "class")
+        .and_then(|attribute| attribute.children.first())
+        .and_then(|value| value.value.split_whitespace().next())
+        .filter(|class| !class.contains(['{', '@', '$']));
+    match class {
+        Some(class) => format!("{}.{}", element.label, class),
+        None => element.label.clone(),
+    }
+}
+
+/// `content` for `('content')`-style Blade arguments, `input` for a Twig `input(name, value)`
+/// macro, `card.html.twig` for an `embed`
+fn directive_name(args: &str) -> String {
+    let first = args
+        .split(|c: char| c == ',' || c == '(' || c.is_whitespace())
+        .find(|part| !part.is_empty())
+        .unwrap_or("");
+    first.trim_matches(|c: char| c == '\'' || c == '"').to_string()
+}
+
+/// Attributes of a tag as `attribute` nodes named by the attribute, with the value as a child
+fn parse_attributes(source: &str, offset: usize) -> Vec<Node> {
+    let bytes = source.as_bytes();
+    let mut attributes = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let name_start = i;
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'=' {
+            i += 1;
+        }
+        let mut attribute = Node::new(
+            "attribute",
+            &source[name_start..i],
+            offset + name_start,
+            offset + i,
+            NodeKind::Leaf,
+        );
+
+        let after_name = i;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i < bytes.len() && bytes[i] == b'=' {
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            let value_start = i;
+            let value = match bytes.get(i) {
+                Some(&quote @ (b'"' | b'\'')) => {
+                    let close =
+                        source[i + 1..].find(quote as char).map_or(bytes.len(), |j| i + 1 + j);
+                    i = (close + 1).min(bytes.len());
+                    &source[value_start + 1..close]
+                }
+                _ => {
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+                        i += 1;
+                    }
+                    &source[value_start..i]
+                }
+            };
+            attribute.end = offset + i;
+            attribute.children.push(Node::new(
+                "attribute-value",
+                &collapse_whitespace(value),
+                offset + value_start,
+                offset + i,
+                NodeKind::Leaf,
+            ));
+        } else {
+            i = after_name;
+        }
+        if !attribute.value.is_empty() {
+            attributes.push(attribute);
+        }
+    }
+    attributes
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Length of the identifier at the start of `text`
+fn word_len(text: &str) -> usize {
+    text.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(text.len())
+}
+
+/// Length of the parenthesized group at the start of `text`, skipping quoted strings
+fn balanced_parens_len(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+/// Whether directive arguments have more than one argument: `'title', 'Home'`
+fn has_top_level_comma(args: &str) -> bool {
+    let mut depth = 0;
+    let mut quote = None;
+    for c in args.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => return true,
+                _ => {}
+            },
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_template(source: &str, syntax: TemplateSyntax) -> TreeNode {
+        TemplateParser::new(source, syntax).parse().to_tree(&mut 0)
+    }
+
+    fn outline(node: &TreeNode) -> String {
+        let children: Vec<String> = node.children.iter().map(|child| outline(child)).collect();
+        let head = if node.value.is_empty() {
+            node.label.clone()
+        } else {
+            format!("{}[{}]", node.label, node.value)
+        };
+        if children.is_empty() {
+            head
+        } else {
+            format!("{}({})", head, children.join(" "))
+        }
+    }
+
+    #[test]
+    fn test_blade_directives_and_markup() {
+        let source = r#"@extends('layouts.app')
+@section('title', 'Users')
+@section('content')
+    {{-- list of users --}}
+    <ul class="users">
+        @forelse ($users as $user)
+            <li>{{ $user->name }} <a href="mailto:{{ $user->email }}">mail</a></li>
+        @empty
+            <li>No users</li>
+        @endforelse
+    </ul>
+    @if($users->hasPages()) {!! $users->links() !!} @else <br> @endif
+@endsection
+"#;
+        let tree = parse_template(source, TemplateSyntax::Blade);
+        assert_eq!(
+            outline(&tree),
+            "template(extends['layouts.app'] section['title', 'Users'] section['content'](\
+             ul(attribute[class](attribute-value[users]) \
+             forelse[$users as $user](li(echo[$user->name] \
+             a(attribute[href](attribute-value[mailto:{{ $user->email }}]) text[mail])) \
+             empty(li(text[No users])))) \
+             if[$users->hasPages()](raw-echo[$users->links()] else(br))))"
+        );
+
+        let fragments = extract_fragments(source, "users.blade.php", TemplateSyntax::Blade);
+        let names: Vec<String> = fragments
+            .iter()
+            .map(|f| format!("{} {} {}-{}", f.kind, f.name, f.start_line, f.end_line))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "section content 3-13",
+                "element ul.users 5-11",
+                "element li 7-7",
+                "element a 7-7",
+                "element li 9-9",
+                "element br 12-12"
+            ]
+        );
+        assert!(fragments[0].contains(&fragments[1]));
+        assert!(!fragments[1].contains(&fragments[0]));
+    }
+
+    #[test]
+    fn test_twig_tags_and_partials() {
+        let source = r#"<div class="card">
+  {# a card #}
+  {%- if title -%}<h2>{{ title|upper }}</h2>{% endif %}
+  {% for item in items %}<p>{{ item }}</p>{% else %}<p>none</p>{% endfor %}
+  {% block footer %}{% include 'footer.html.twig' %}{% endblock %}
+  {% set label = 'x' %}{% verbatim %}{{ raw }}{% endverbatim %}
+</div>
+{{ include('card_actions.html.twig') }}
+"#;
+        let tree = parse_template(source, TemplateSyntax::Twig);
+        assert_eq!(
+            outline(&tree),
+            "template(div(attribute[class](attribute-value[card]) \
+             if[title](h2(echo[title|upper])) \
+             for[item in items](p(echo[item]) else(p(text[none]))) \
+             block[footer](include['footer.html.twig']) \
+             set[label = 'x'] verbatim(text[{{ raw }}])) \
+             echo[include('card_actions.html.twig')])"
+        );
+
+        let fragments = extract_fragments(source, "views/card.html.twig", TemplateSyntax::Twig);
+        assert_eq!(fragments[0].kind, "template");
+        assert_eq!(fragments[0].name, "card.html.twig");
+        assert_eq!((fragments[0].start_line, fragments[0].end_line), (1, 8));
+        assert_eq!(fragments[1].name, "div.card");
+        assert!(fragments.iter().any(|f| f.kind == "block" && f.name == "footer"));
+
+        assert_eq!(
+            TemplateSyntax::from_path(Path::new("a/b.blade.php")),
+            Some(TemplateSyntax::Blade)
+        );
+        assert_eq!(
+            TemplateSyntax::from_path(Path::new("a/b.html.twig")),
+            Some(TemplateSyntax::Twig)
+        );
+        assert_eq!(TemplateSyntax::from_path(Path::new("a/b.php")), None);
+    }
+}
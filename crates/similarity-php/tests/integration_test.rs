@@ -0,0 +1,76 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+const USERS_BLADE: &str = r#"@extends('layouts.app')
+
+@section('content')
+    <div class="card">
+        <div class="card-header">
+            <h2 class="title">{{ $user->name }}</h2>
+            <span class="badge">{{ $user->role }}</span>
+        </div>
+        <div class="card-body">
+            <p>{{ $user->email }}</p>
+            <a href="{{ route('users.edit', $user) }}" class="btn">Edit</a>
+        </div>
+    </div>
+    <ul class="tags"><li class="tag"><a href="{{ route('tags.show', $tag) }}" class="link">{{ $tag->name }}</a></li><li class="tag"><a href="{{ route('tags.show', $tag) }}" class="link">{{ $tag->label }}</a></li></ul>
+@endsection
+"#;
+
+const PRODUCT_TWIG: &str = r#"{% block content %}
+    <div class="card">
+        <div class="card-header">
+            <h2 class="title">{{ product.name }}</h2>
+            <span class="badge">{{ product.category }}</span>
+        </div>
+        <div class="card-body">
+            <p>{{ product.description }}</p>
+            <a href="{{ path('product_edit', {id: product.id}) }}" class="btn">Edit</a>
+        </div>
+    </div>
+{% endblock %}
+"#;
+
+#[test]
+fn test_templates_report_markup_repeated_across_blade_and_twig() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("users.blade.php"), USERS_BLADE).unwrap();
+    fs::write(dir.path().join("product.twig"), PRODUCT_TWIG).unwrap();
+
+    Command::cargo_bin("similarity-php")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--templates")
+        .arg("--no-functions")
+        .arg("--template-min-nodes")
+        .arg("8")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checking 2 templates"))
+        .stdout(predicate::str::contains("element div.card"))
+        .stdout(predicate::str::contains("product.twig:2-11"))
+        .stdout(predicate::str::contains("users.blade.php:4-13"))
+        // The two `<li>` items share one line: an inline list, not a copy
+        .stdout(predicate::str::contains("li.tag").not())
+        .stdout(predicate::str::contains("Total duplicate fragment pairs found: 1"));
+}
+
+#[test]
+fn test_templates_skip_plain_php_files() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("users.blade.php"), USERS_BLADE).unwrap();
+    fs::write(dir.path().join("User.php"), "<?php\nclass User {}\n").unwrap();
+
+    Command::cargo_bin("similarity-php")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--templates")
+        .arg("--no-functions")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checking 1 templates"))
+        .stdout(predicate::str::contains("No duplicate template fragments found"));
+}
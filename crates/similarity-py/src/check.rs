@@ -1,19 +1,26 @@
 #![allow(clippy::uninlined_format_args)]
 
-use crate::parallel::check_within_file_duplicates_parallel;
+use crate::notebook::{load_notebooks, Notebook};
+use crate::parallel::{
+    check_cross_file_duplicates_parallel, check_within_file_duplicates_parallel, load_source,
+};
+use crate::python_parser::PythonParser;
 use similarity_core::{
+    changed_lines::ChangedLines,
     cli_file_utils::collect_files,
-    cli_output::{format_function_output, show_function_code},
+    cli_output::{extract_lines_from_content, format_function_output},
     cli_parallel::SimilarityResult,
-    language_parser::GenericFunctionDef,
+    dedent, format_suggestion, group_into_clusters,
+    language_parser::{GenericFunctionDef, LanguageParser},
+    suggest_parameterized_extraction, CloneMember, RefactoringSuggestion, SuggestionLanguage,
     TSEDOptions,
 };
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Structure to hold all similarity results
 struct DuplicateResult {
     file1: PathBuf,
-    #[allow(dead_code)]
     file2: PathBuf,
     result: SimilarityResult<GenericFunctionDef>,
 }
@@ -38,34 +45,69 @@ pub fn check_paths(
     min_lines: u32,
     min_tokens: Option<u32>,
     no_size_penalty: bool,
+    rename_consistency: bool,
     print: bool,
+    suggest: Option<&str>,
     _fast_mode: bool, // Python doesn't support fast mode yet
     filter_function: Option<&String>,
     filter_function_body: Option<&String>,
+    changed: Option<&ChangedLines>,
+    module_blocks: bool,
 ) -> anyhow::Result<usize> {
-    let default_extensions = vec!["py"];
+    let default_extensions = vec!["py", "ipynb"];
     let exts: Vec<&str> =
         extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
 
-    let files = collect_files(&paths, &exts)?;
+    let mut files = collect_files(&paths, &exts)?;
+    let notebooks = load_notebooks(&mut files);
+
+    // With JSON suggestions stdout carries only the JSON
+    let json = suggest == Some("json");
+    let status = |message: String| {
+        if json {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    };
 
     if files.is_empty() {
-        println!("No Python files found in the specified paths.");
+        status("No Python files found in the specified paths.".to_string());
+        if json {
+            println!("[]");
+        }
         return Ok(0);
     }
 
-    println!("Checking {} files for duplicates...", files.len());
+    status(format!("Checking {} files for duplicates...", files.len()));
+    if !notebooks.is_empty() {
+        status(format!("Including {} Jupyter notebooks", notebooks.len()));
+    }
+    if let Some(changed) = changed {
+        status(format!(
+            "Reporting only duplicates touching lines changed in {} files",
+            changed.file_count()
+        ));
+    }
 
     let mut options = TSEDOptions::default();
     options.apted_options.rename_cost = rename_cost;
     options.min_lines = min_lines;
     options.min_tokens = min_tokens;
     options.size_penalty = !no_size_penalty;
+    options.rename_consistency = rename_consistency;
 
     let mut all_results = Vec::new();
 
     // Check within each file
-    let within_file_results = check_within_file_duplicates_parallel(&files, threshold, &options);
+    let within_file_results = check_within_file_duplicates_parallel(
+        &files,
+        &notebooks,
+        threshold,
+        &options,
+        changed,
+        module_blocks,
+    );
 
     // Collect within-file duplicates
     for (file, similar_pairs) in within_file_results {
@@ -74,12 +116,28 @@ pub fn check_paths(
         }
     }
 
-    // For now, we only support within-file duplicates for Python
-    // Cross-file support can be added later
+    // Across files, only pairs involving a notebook or two top-level blocks are compared for now
+    let cross_file_results = check_cross_file_duplicates_parallel(
+        &files,
+        &notebooks,
+        threshold,
+        &options,
+        changed,
+        module_blocks,
+    );
+    for (file1, result, file2) in cross_file_results {
+        all_results.push(DuplicateResult { file1, file2, result });
+    }
 
     // Display results
-    let duplicate_count =
-        display_all_results(all_results, print, filter_function, filter_function_body);
+    let duplicate_count = display_all_results(
+        all_results,
+        &notebooks,
+        print,
+        suggest.map(|format| (format, &options)),
+        filter_function,
+        filter_function_body,
+    );
 
     Ok(duplicate_count)
 }
@@ -87,11 +145,14 @@ pub fn check_paths(
 /// Display similarity results
 fn display_all_results(
     mut all_results: Vec<DuplicateResult>,
+    notebooks: &HashMap<PathBuf, Notebook>,
     print: bool,
+    suggest: Option<(&str, &TSEDOptions)>,
     filter_function: Option<&String>,
     filter_function_body: Option<&String>,
 ) -> usize {
-    if all_results.is_empty() {
+    let json = matches!(suggest, Some(("json", _)));
+    if all_results.is_empty() && !json {
         println!("\nNo duplicate functions found!");
         return 0;
     }
@@ -119,6 +180,15 @@ fn display_all_results(
         b.priority().partial_cmp(&a.priority()).unwrap_or(std::cmp::Ordering::Equal)
     });
 
+    let suggestions = suggest
+        .map(|(format, options)| (format, collect_suggestions(&all_results, notebooks, options)));
+
+    // The JSON replaces the text report so it can be piped
+    if let Some(("json", suggestions)) = &suggestions {
+        show_refactoring_suggestions(suggestions, "json");
+        return all_results.len();
+    }
+
     // Group by file
     let mut file_groups = std::collections::HashMap::new();
     for dup in all_results {
@@ -138,26 +208,8 @@ fn display_all_results(
 
             println!(
                 "  {} <-> {}",
-                format_function_output(
-                    &file_path,
-                    &format!(
-                        "{} {}",
-                        if func1.is_method { "method" } else { "function" },
-                        &func1.name
-                    ),
-                    func1.start_line,
-                    func1.end_line
-                ),
-                format_function_output(
-                    &file_path,
-                    &format!(
-                        "{} {}",
-                        if func2.is_method { "method" } else { "function" },
-                        &func2.name
-                    ),
-                    func2.start_line,
-                    func2.end_line
-                )
+                describe_unit(&dup.file1, func1, notebooks),
+                describe_unit(&dup.file2, func2, notebooks)
             );
             println!("  Similarity: {:.2}%", dup.result.similarity * 100.0);
 
@@ -166,8 +218,8 @@ fn display_all_results(
             }
 
             if print {
-                show_function_code(&file_path, &func1.name, func1.start_line, func1.end_line);
-                show_function_code(&file_path, &func2.name, func2.start_line, func2.end_line);
+                show_unit_code(&dup.file1, func1, notebooks);
+                show_unit_code(&dup.file2, func2, notebooks);
                 println!();
             }
 
@@ -177,5 +229,169 @@ fn display_all_results(
 
     println!("\nTotal duplicate pairs found: {}", total_count);
 
+    if let Some((format, suggestions)) = suggestions {
+        show_refactoring_suggestions(&suggestions, format);
+    }
+
     total_count
 }
+
+fn unit_kind(func: &GenericFunctionDef) -> &'static str {
+    if func.name.starts_with('<') {
+        "block"
+    } else if func.is_method {
+        "method"
+    } else {
+        "function"
+    }
+}
+
+/// `path:start-end kind name`, with notebook units located by cell
+fn describe_unit(
+    file: &PathBuf,
+    func: &GenericFunctionDef,
+    notebooks: &HashMap<PathBuf, Notebook>,
+) -> String {
+    let file_path = file.to_string_lossy();
+    let name = format!("{} {}", unit_kind(func), func.name);
+    match notebooks.get(file) {
+        Some(notebook) => format!(
+            "{}:{} {}",
+            file_path,
+            notebook.describe_range(func.start_line, func.end_line),
+            name
+        ),
+        None => format_function_output(&file_path, &name, func.start_line, func.end_line),
+    }
+}
+
+fn show_unit_code(
+    file: &PathBuf,
+    func: &GenericFunctionDef,
+    notebooks: &HashMap<PathBuf, Notebook>,
+) {
+    match load_source(file, notebooks) {
+        Some(content) => {
+            println!("\n\x1b[36m--- {} ---\x1b[0m", describe_unit(file, func, notebooks));
+            println!("{}", extract_lines_from_content(&content, func.start_line, func.end_line));
+        }
+        None => eprintln!("Error reading file {}", file.display()),
+    }
+}
+
+/// Suggest shared functions for clusters of duplicated functions
+fn collect_suggestions(
+    results: &[DuplicateResult],
+    notebooks: &HashMap<PathBuf, Notebook>,
+    options: &TSEDOptions,
+) -> Vec<RefactoringSuggestion> {
+    // Number every distinct function taking part in a duplicate pair
+    let mut functions: Vec<(&PathBuf, &GenericFunctionDef)> = Vec::new();
+    let mut index_of: HashMap<(&PathBuf, &str, u32), usize> = HashMap::new();
+    let mut pairs = Vec::new();
+    for dup in results {
+        let mut pair = [0; 2];
+        let sides = [(&dup.file1, &dup.result.func1), (&dup.file2, &dup.result.func2)];
+        for (slot, (file, func)) in sides.into_iter().enumerate() {
+            pair[slot] =
+                *index_of.entry((file, func.name.as_str(), func.start_line)).or_insert_with(|| {
+                    functions.push((file, func));
+                    functions.len() - 1
+                });
+        }
+        pairs.push((pair[0], pair[1]));
+    }
+
+    let Ok(mut parser) = PythonParser::new() else {
+        return Vec::new();
+    };
+    let mut contents: HashMap<&PathBuf, String> = HashMap::new();
+    let mut suggestions = Vec::new();
+    for cluster in group_into_clusters(&pairs, functions.len()) {
+        // Bodies are dedented so they parse as module-level statements
+        let mut bodies = Vec::new();
+        for &i in &cluster {
+            let (file, func) = functions[i];
+            if !contents.contains_key(file) {
+                match load_source(file, notebooks) {
+                    Some(content) => {
+                        contents.insert(file, content);
+                    }
+                    None => {
+                        eprintln!("Error reading file {}", file.display());
+                        continue;
+                    }
+                }
+            }
+            let lines: Vec<&str> = contents[file].lines().collect();
+            let start = func.body_start_line.saturating_sub(1) as usize;
+            let end = (func.body_end_line as usize).min(lines.len());
+            if start >= end {
+                continue;
+            }
+            bodies.push((file, func, dedent(&lines[start..end].join("\n"))));
+        }
+
+        let members: Vec<CloneMember> = bodies
+            .iter()
+            .filter_map(|(file, func, body)| {
+                let tree = parser.parse(body, &file.to_string_lossy()).ok()?;
+                Some(CloneMember {
+                    name: func.name.clone(),
+                    location: match notebooks.get(*file) {
+                        Some(notebook) => format!(
+                            "{}:{}",
+                            file.display(),
+                            notebook.describe_range(func.start_line, func.end_line)
+                        ),
+                        None => {
+                            format!("{}:{}-{}", file.display(), func.start_line, func.end_line)
+                        }
+                    },
+                    parameters: func
+                        .parameters
+                        .iter()
+                        .filter(|p| p.as_str() != "self" && p.as_str() != "cls")
+                        .cloned()
+                        .collect(),
+                    tree,
+                    source: body,
+                })
+            })
+            .collect();
+
+        if let Some(suggestion) = suggest_parameterized_extraction(
+            &members,
+            SuggestionLanguage::Python,
+            &options.apted_options,
+        ) {
+            suggestions.push(suggestion);
+        }
+    }
+
+    suggestions
+}
+
+/// Print refactoring suggestions as text or JSON
+fn show_refactoring_suggestions(suggestions: &[RefactoringSuggestion], format: &str) {
+    if format == "json" {
+        match serde_json::to_string_pretty(suggestions) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error serializing suggestions: {}", e),
+        }
+        return;
+    }
+
+    if suggestions.is_empty() {
+        return;
+    }
+    println!("\n{}", "=".repeat(60));
+    println!(
+        "💡 Refactoring suggestions for {} cluster(s) of similar functions:",
+        suggestions.len()
+    );
+    for suggestion in suggestions {
+        println!();
+        print!("{}", format_suggestion(suggestion));
+    }
+}
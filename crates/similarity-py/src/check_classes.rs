@@ -0,0 +1,95 @@
+use crate::get_relative_path;
+use crate::python_parser::PythonParser;
+use similarity_core::cli_file_utils::collect_files;
+use similarity_core::cli_structures::{
+    find_similar_pairs, print_differences, print_method_body_similarity, ExtractedDefinitions,
+    MIN_MEMBERS,
+};
+use similarity_core::{resolve_inherited_kinds, PythonClassDef, PythonStructureComparator};
+
+fn describe_class(class: &PythonClassDef) -> String {
+    format!(
+        "{} {} | {}:{}-{}",
+        class.kind.label(),
+        class.name,
+        get_relative_path(&class.file_path),
+        class.start_line,
+        class.end_line
+    )
+}
+
+/// Check for similar classes and data models across files
+pub fn check_classes(
+    paths: Vec<String>,
+    threshold: f64,
+    extensions: Option<&Vec<String>>,
+    print: bool,
+) -> anyhow::Result<usize> {
+    let default_extensions = vec!["py"];
+    let exts: Vec<&str> =
+        extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
+
+    let files = collect_files(&paths, &exts)?;
+    if files.is_empty() {
+        println!("No Python files found in the specified paths.");
+        return Ok(0);
+    }
+
+    println!("Checking {} files for similar classes...", files.len());
+
+    let mut parser =
+        PythonParser::new().map_err(|e| anyhow::anyhow!("Failed to create parser: {}", e))?;
+    let mut extracted = ExtractedDefinitions::extract(&files, |content, file_path| {
+        parser.extract_type_defs(content, file_path)
+    });
+    resolve_inherited_kinds(&mut extracted.definitions);
+    let class_defs = &extracted.definitions;
+
+    let model_count = class_defs.iter().filter(|class| class.kind.is_data_model()).count();
+    println!("Found {} classes ({} data models)\n", class_defs.len(), model_count);
+
+    let large_enough =
+        |class: &PythonClassDef| class.fields.len() + class.methods.len() >= MIN_MEMBERS;
+    let comparable = |class1: &PythonClassDef, class2: &PythonClassDef| {
+        // A subclass restating part of its base is inheritance, not duplication
+        large_enough(class1)
+            && large_enough(class2)
+            && !class1.bases.contains(&class2.name)
+            && !class2.bases.contains(&class1.name)
+    };
+
+    let mut comparator = PythonStructureComparator::new();
+    let pairs = find_similar_pairs(class_defs, threshold, comparable, |class1, class2| {
+        let comparison = comparator.compare_classes(class1, class2);
+        (comparison.similarity, comparison)
+    });
+
+    if pairs.is_empty() {
+        println!("No similar classes found with threshold {:.0}%", threshold * 100.0);
+        return Ok(0);
+    }
+
+    println!("Similar classes found:");
+    println!("{}", "-".repeat(60));
+
+    for pair in &pairs {
+        let class1 = &class_defs[pair.index1];
+        let class2 = &class_defs[pair.index2];
+
+        print_method_body_similarity(&pair.comparison, "fields");
+        println!("  {}", describe_class(class1));
+        println!("  {}", describe_class(class2));
+        print_differences(&class1.name, &class2.name, &pair.comparison.structure.differences);
+
+        if print {
+            for (index, class) in [(pair.index1, class1), (pair.index2, class2)] {
+                extracted.print_code(index, &class.name, class.start_line, class.end_line);
+            }
+        }
+    }
+
+    println!("\n{}", "-".repeat(60));
+    println!("Total similar class pairs found: {}", pairs.len());
+
+    Ok(pairs.len())
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::Parser;
+use similarity_core::ChangedLines;
 
 mod check;
+mod check_classes;
+mod notebook;
 mod parallel;
 mod python_parser;
 
@@ -18,6 +21,14 @@ struct Cli {
     #[arg(short, long)]
     print: bool,
 
+    /// Suggest a shared function (signature, body and call sites) for each cluster of similar functions
+    #[arg(long)]
+    suggest: bool,
+
+    /// Output format for refactoring suggestions (text or json); json writes only the suggestions to stdout
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    suggest_format: String,
+
     /// Similarity threshold (0.0-1.0)
     #[arg(short, long, default_value = "0.85")]
     threshold: f64,
@@ -42,6 +53,10 @@ struct Cli {
     #[arg(long)]
     no_size_penalty: bool,
 
+    /// Reward consistent identifier renames (a→x everywhere) and penalize conflicting ones
+    #[arg(long)]
+    rename_consistency: bool,
+
     /// Filter functions by name (substring match)
     #[arg(long)]
     filter_function: Option<String>,
@@ -54,6 +69,18 @@ struct Cli {
     #[arg(long)]
     no_fast: bool,
 
+    /// Also compare top-level statement blocks (split on blank lines) as `<module:L10-L34>` units
+    #[arg(long)]
+    module_blocks: bool,
+
+    /// Check for similar classes and data models (dataclasses, NamedTuple, TypedDict, Pydantic, attrs)
+    #[arg(long, visible_alias = "types")]
+    classes: bool,
+
+    /// Disable function similarity checking
+    #[arg(long = "no-functions")]
+    no_functions: bool,
+
     /// Enable experimental overlap detection mode
     #[arg(long = "experimental-overlap")]
     overlap: bool,
@@ -70,6 +97,14 @@ struct Cli {
     #[arg(long, default_value = "0.25")]
     overlap_size_tolerance: f64,
 
+    /// Only report duplicates involving lines changed since this git revision (e.g. origin/main)
+    #[arg(long, conflicts_with = "diff_from")]
+    changed_since: Option<String>,
+
+    /// Only report duplicates involving lines changed by this unified diff (e.g. a .patch file)
+    #[arg(long)]
+    diff_from: Option<std::path::PathBuf>,
+
     /// Exit with code 1 if duplicates are found
     #[arg(long)]
     fail_on_duplicates: bool,
@@ -78,17 +113,39 @@ struct Cli {
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let functions_enabled = true; // Python always has functions enabled
+    let changed =
+        ChangedLines::from_cli_options(cli.changed_since.as_deref(), cli.diff_from.as_deref())
+            .map_err(anyhow::Error::msg)?;
+
+    let functions_enabled = !cli.no_functions;
+    let classes_enabled = cli.classes;
     let overlap_enabled = cli.overlap;
 
-    println!("Analyzing Python code similarity...\n");
+    // Validate that at least one analyzer is enabled
+    if !functions_enabled && !classes_enabled && !overlap_enabled {
+        eprintln!("Error: At least one analyzer must be enabled. Use --classes to enable class checking, --experimental-overlap for overlap detection, or remove --no-functions.");
+        return Err(anyhow::anyhow!("No analyzer enabled"));
+    }
+
+    // JSON suggestions are the whole output, so nothing else may write to stdout
+    let json_suggestions = cli.suggest && cli.suggest_format == "json";
+    if json_suggestions && (!functions_enabled || classes_enabled || overlap_enabled) {
+        eprintln!("Error: --suggest-format json only covers function similarity. Run --classes or overlap detection separately.");
+        return Err(anyhow::anyhow!("--suggest-format json used with another analyzer"));
+    }
+
+    if !json_suggestions {
+        println!("Analyzing Python code similarity...\n");
+    }
 
     let separator = "-".repeat(60);
     let mut total_duplicates = 0;
 
     // Run functions analysis
-    if !overlap_enabled || functions_enabled {
-        println!("=== Function Similarity ===");
+    if functions_enabled {
+        if !json_suggestions {
+            println!("=== Function Similarity ===");
+        }
         let duplicate_count = check::check_paths(
             cli.paths.clone(),
             cli.threshold,
@@ -97,16 +154,36 @@ fn main() -> Result<()> {
             cli.min_lines.unwrap_or(3),
             cli.min_tokens,
             cli.no_size_penalty,
+            cli.rename_consistency,
             cli.print,
+            cli.suggest.then_some(cli.suggest_format.as_str()),
             !cli.no_fast,
             cli.filter_function.as_ref(),
             cli.filter_function_body.as_ref(),
+            changed.as_ref(),
+            cli.module_blocks,
         )?;
         total_duplicates += duplicate_count;
     }
 
+    // Run classes analysis if enabled
+    if classes_enabled && functions_enabled {
+        println!("\n{separator}\n");
+    }
+
+    if classes_enabled {
+        println!("=== Class Similarity ===");
+        let class_duplicate_count = check_classes::check_classes(
+            cli.paths.clone(),
+            cli.threshold,
+            cli.extensions.as_ref(),
+            cli.print,
+        )?;
+        total_duplicates += class_duplicate_count;
+    }
+
     // Run overlap analysis if enabled
-    if overlap_enabled && functions_enabled {
+    if overlap_enabled && (functions_enabled || classes_enabled) {
         println!("\n{separator}\n");
     }
 
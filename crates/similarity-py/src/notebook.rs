@@ -0,0 +1,333 @@
+use crate::python_parser::collect_statement_runs;
+use serde_json::Value;
+use similarity_core::language_parser::GenericFunctionDef;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tree_sitter::Parser;
+
+pub fn is_notebook_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "ipynb")
+}
+
+/// Parse the notebooks among `files`; those that can't be read as Python notebooks are
+/// reported and removed from `files`
+pub fn load_notebooks(files: &mut Vec<PathBuf>) -> HashMap<PathBuf, Notebook> {
+    let mut notebooks = HashMap::new();
+    files.retain(|file| {
+        if !is_notebook_path(file) {
+            return true;
+        }
+        let notebook = fs::read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|json| Notebook::parse(&json));
+        match notebook {
+            Ok(notebook) => {
+                notebooks.insert(file.clone(), notebook);
+                true
+            }
+            Err(e) => {
+                eprintln!("Skipping {}: {}", file.display(), e);
+                false
+            }
+        }
+    });
+    notebooks
+}
+
+/// The code cells of a Jupyter notebook joined into one virtual Python module
+#[derive(Debug, Clone)]
+pub struct Notebook {
+    /// Code cells in order; IPython-only lines (magics, shell escapes, `obj?` help) are blanked
+    pub source: String,
+    /// `(cell, line)` of each line of `source`, both 1-based; cells are numbered among all cells,
+    /// markdown included, as shown in the notebook
+    lines: Vec<(usize, u32)>,
+}
+
+impl Notebook {
+    /// Parse `.ipynb` JSON; notebooks of other kernels (R, Julia) are rejected
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let notebook: Value =
+            serde_json::from_str(json).map_err(|e| format!("Invalid notebook JSON: {e}"))?;
+
+        let language = notebook
+            .pointer("/metadata/kernelspec/language")
+            .or_else(|| notebook.pointer("/metadata/language_info/name"))
+            .and_then(Value::as_str);
+        if let Some(language) = language.filter(|language| !language.eq_ignore_ascii_case("python"))
+        {
+            return Err(format!("Not a Python notebook ({language})"));
+        }
+
+        let cells =
+            notebook.get("cells").and_then(Value::as_array).ok_or("Notebook has no cells")?;
+        let mut source = String::new();
+        let mut lines = Vec::new();
+        for (index, cell) in cells.iter().enumerate() {
+            if cell.get("cell_type").and_then(Value::as_str) != Some("code") {
+                continue;
+            }
+            let text: String = match cell.get("source") {
+                Some(Value::String(text)) => text.clone(),
+                Some(Value::Array(parts)) => parts.iter().filter_map(Value::as_str).collect(),
+                _ => continue,
+            };
+
+            // `%%bash`, `%%sql` and friends make the whole cell another language
+            let is_cell_magic = text.trim_start().starts_with("%%");
+            // Magics only start statements: `% b` inside brackets is a modulo
+            let (mut depth, mut continued) = (0, false);
+            for (line_index, line) in text.lines().enumerate() {
+                if !is_cell_magic && (continued || !is_ipython_syntax(line)) {
+                    source.push_str(line);
+                    continued = continues_statement(line, &mut depth);
+                }
+                source.push('\n');
+                lines.push((index + 1, line_index as u32 + 1));
+            }
+        }
+
+        Ok(Self { source, lines })
+    }
+
+    /// Notebook position `(cell, line)` of a line of the virtual module
+    pub fn location(&self, line: u32) -> Option<(usize, u32)> {
+        self.lines.get(line.checked_sub(1)? as usize).copied()
+    }
+
+    /// `cell 3:2-8` for virtual lines `start..=end`, or `cell 3:2-cell 4:5` across cells
+    pub fn describe_range(&self, start: u32, end: u32) -> String {
+        match (self.location(start), self.location(end)) {
+            (Some((cell1, line1)), Some((cell2, line2))) if cell1 == cell2 => {
+                format!("cell {cell1}:{line1}-{line2}")
+            }
+            (Some((cell1, line1)), Some((cell2, line2))) => {
+                format!("cell {cell1}:{line1}-cell {cell2}:{line2}")
+            }
+            _ => format!("{start}-{end}"),
+        }
+    }
+
+    /// Top-level code of each cell as pseudo-units named `<cell N>`
+    ///
+    /// A block is a run of top-level statements within one cell, split as module blocks are
+    /// except that cells rather than blank lines separate them; functions are units of their own.
+    pub fn cell_blocks(&self) -> Vec<GenericFunctionDef> {
+        let mut parser = Parser::new();
+        if parser.set_language(&tree_sitter_python::LANGUAGE.into()).is_err() {
+            return Vec::new();
+        }
+        let Some(tree) = parser.parse(&self.source, None) else {
+            return Vec::new();
+        };
+
+        let cell = |line| self.location(line).map(|(cell, _)| cell);
+        let mut blocks = Vec::new();
+        collect_statement_runs(
+            tree.root_node(),
+            &self.source,
+            &|end, start| cell(end) != cell(start),
+            &mut blocks,
+        );
+
+        blocks
+            .into_iter()
+            .filter_map(|(start, end)| Some((cell(start)?, start, end)))
+            .map(|(cell, start, end)| GenericFunctionDef {
+                name: format!("<cell {cell}>"),
+                start_line: start,
+                end_line: end,
+                body_start_line: start,
+                body_end_line: end,
+                parameters: Vec::new(),
+                is_method: false,
+                class_name: None,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// Statements that are IPython rather than Python: `%matplotlib inline`, `!pip install x`,
+/// `files = !ls`, `df.merge?`, `df.merge??`
+fn is_ipython_syntax(line: &str) -> bool {
+    let code = strip_comment(line).trim();
+    if code.starts_with('%') || code.starts_with('!') {
+        return true;
+    }
+    let help_target = code.strip_suffix("??").or_else(|| code.strip_suffix('?'));
+    if help_target.is_some_and(is_dotted_name) {
+        return true;
+    }
+    code.split_once('=').is_some_and(|(target, value)| {
+        let value = value.trim_start();
+        (value.starts_with('!') || value.starts_with('%'))
+            && !target.trim().is_empty()
+            && target
+                .trim()
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == ',' || c == ' ')
+    })
+}
+
+/// `df`, `df.merge`, `np.linalg.norm`
+fn is_dotted_name(text: &str) -> bool {
+    text.split('.').all(|part| {
+        part.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// Byte offsets and characters of `line` outside string literals
+fn code_chars(line: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut quote = None;
+    let mut escaped = false;
+    line.char_indices().filter(move |&(_, c)| match quote {
+        Some(_) if escaped => {
+            escaped = false;
+            false
+        }
+        Some(_) if c == '\\' => {
+            escaped = true;
+            false
+        }
+        Some(open) => {
+            if c == open {
+                quote = None;
+            }
+            false
+        }
+        None if c == '"' || c == '\'' => {
+            quote = Some(c);
+            false
+        }
+        None => true,
+    })
+}
+
+/// `line` without its trailing `# comment`
+fn strip_comment(line: &str) -> &str {
+    match code_chars(line).find(|&(_, c)| c == '#') {
+        Some((comment, _)) => &line[..comment],
+        None => line,
+    }
+}
+
+/// Whether the statement goes on past `line`: an open bracket or a trailing backslash.
+/// `depth` carries the bracket nesting from line to line.
+fn continues_statement(line: &str, depth: &mut usize) -> bool {
+    for (_, c) in code_chars(line).take_while(|&(_, c)| c != '#') {
+        match c {
+            '(' | '[' | '{' => *depth += 1,
+            ')' | ']' | '}' => *depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    *depth > 0 || strip_comment(line).trim_end().ends_with('\\')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTEBOOK: &str = r##"{
+ "cells": [
+  {"cell_type": "markdown", "metadata": {}, "source": ["# Cleaning\n"]},
+  {"cell_type": "code", "metadata": {}, "outputs": [], "source": [
+   "%matplotlib inline\n",
+   "import pandas as pd\n",
+   "!pip install seaborn\n",
+   "files = !ls data\n"
+  ]},
+  {"cell_type": "code", "metadata": {}, "outputs": [], "source": [
+   "def clean(df):\n",
+   "    return df.dropna()\n",
+   "\n",
+   "df = pd.read_csv(\"a.csv\")\n",
+   "df = clean(df)\n",
+   "df.describe?"
+  ]},
+  {"cell_type": "code", "metadata": {}, "outputs": [], "source": "%%bash\necho hi\n"}
+ ],
+ "metadata": {"kernelspec": {"language": "python", "name": "python3"}},
+ "nbformat": 4,
+ "nbformat_minor": 5
+}"##;
+
+    #[test]
+    fn test_code_cells_become_a_virtual_module() {
+        let notebook = Notebook::parse(NOTEBOOK).unwrap();
+        let lines: Vec<&str> = notebook.source.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "",
+                "import pandas as pd",
+                "",
+                "",
+                "def clean(df):",
+                "    return df.dropna()",
+                "",
+                "df = pd.read_csv(\"a.csv\")",
+                "df = clean(df)",
+                "",
+                "",
+                ""
+            ]
+        );
+        assert_eq!(notebook.location(2), Some((2, 2)));
+        assert_eq!(notebook.location(8), Some((3, 4)));
+        assert_eq!(notebook.location(12), Some((4, 2)));
+        assert_eq!(notebook.location(13), None);
+        assert_eq!(notebook.describe_range(5, 6), "cell 3:1-2");
+        assert_eq!(notebook.describe_range(2, 5), "cell 2:2-cell 3:1");
+
+        let blocks = notebook.cell_blocks();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].name, "<cell 3>");
+        assert_eq!((blocks[0].start_line, blocks[0].end_line), (8, 9));
+    }
+
+    #[test]
+    fn test_python_resembling_ipython_is_kept() {
+        assert!(is_ipython_syntax("df.describe??"));
+        assert!(is_ipython_syntax("%timeit clean(df)  # slow?"));
+        assert!(!is_ipython_syntax("if df is None:  # empty?"));
+        assert!(!is_ipython_syntax("print('why?')"));
+        assert!(!is_ipython_syntax("# what now?"));
+
+        let notebook = Notebook::parse(
+            r#"{"cells": [{"cell_type": "code", "source": [
+                "if df is None:  # empty?\n",
+                "    df = load()\n",
+                "rows = (total\n",
+                "        % width)\n",
+                "%time rows\n"
+            ]}]}"#,
+        )
+        .unwrap();
+        let lines: Vec<&str> = notebook.source.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "if df is None:  # empty?",
+                "    df = load()",
+                "rows = (total",
+                "        % width)",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_other_kernels_are_rejected() {
+        let r_notebook = r#"{"cells": [], "metadata": {"kernelspec": {"language": "R"}}}"#;
+        assert_eq!(Notebook::parse(r_notebook).unwrap_err(), "Not a Python notebook (R)");
+        assert!(Notebook::parse("not json").is_err());
+        assert!(is_notebook_path(Path::new("analysis.ipynb")));
+        assert!(!is_notebook_path(Path::new("analysis.py")));
+    }
+}
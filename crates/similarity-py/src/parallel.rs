@@ -1,12 +1,16 @@
 #![allow(clippy::uninlined_format_args)]
 
+use crate::notebook::Notebook;
 use crate::python_parser::PythonParser;
 use rayon::prelude::*;
 use similarity_core::{
+    changed_lines::ChangedLines,
     cli_parallel::{FileData, SimilarityResult},
+    dedent,
     language_parser::{GenericFunctionDef, LanguageParser},
     tsed::{calculate_tsed, TSEDOptions},
 };
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -52,81 +56,204 @@ pub fn load_files_parallel(files: &[PathBuf]) -> Vec<PythonFileData> {
         .collect()
 }
 
+/// Source of a Python file, or the virtual module of a notebook
+pub fn load_source(file: &PathBuf, notebooks: &HashMap<PathBuf, Notebook>) -> Option<String> {
+    match notebooks.get(file) {
+        Some(notebook) => Some(notebook.source.clone()),
+        None => fs::read_to_string(file).ok(),
+    }
+}
+
+/// Functions of a file, plus the top-level cell blocks of a notebook and, with
+/// `module_blocks`, the top-level statement blocks of a module
+fn extract_units(
+    parser: &mut PythonParser,
+    code: &str,
+    file: &PathBuf,
+    notebooks: &HashMap<PathBuf, Notebook>,
+    module_blocks: bool,
+) -> Option<Vec<GenericFunctionDef>> {
+    let mut units = parser.extract_functions(code, &file.to_string_lossy()).ok()?;
+    if let Some(notebook) = notebooks.get(file) {
+        units.extend(notebook.cell_blocks());
+    } else if module_blocks {
+        units.extend(parser.extract_module_blocks(code).ok()?);
+    }
+    Some(units)
+}
+
+/// Top-level pseudo-units (`<cell 3>`, `<module:L10-L34>`) rather than functions
+fn is_block(func: &GenericFunctionDef) -> bool {
+    func.name.starts_with('<')
+}
+
+/// Whether a unit overlaps changed lines; notebook lines don't map onto the JSON a diff
+/// describes, so any change to a notebook touches all of it
+fn is_touched(
+    changed: Option<&ChangedLines>,
+    file: &PathBuf,
+    func: &GenericFunctionDef,
+    notebooks: &HashMap<PathBuf, Notebook>,
+) -> bool {
+    changed.is_none_or(|c| {
+        if notebooks.contains_key(file) {
+            c.touches_file(file)
+        } else {
+            c.overlaps(file, func.start_line, func.end_line)
+        }
+    })
+}
+
 /// Check for duplicates within Python files in parallel
 pub fn check_within_file_duplicates_parallel(
     files: &[PathBuf],
+    notebooks: &HashMap<PathBuf, Notebook>,
     threshold: f64,
     options: &TSEDOptions,
+    changed: Option<&ChangedLines>,
+    module_blocks: bool,
 ) -> Vec<(PathBuf, Vec<SimilarityResult<GenericFunctionDef>>)> {
     files
         .par_iter()
-        .filter_map(|file| match fs::read_to_string(file) {
-            Ok(code) => {
-                let file_str = file.to_string_lossy();
-
-                // Create Python parser
-                match PythonParser::new() {
-                    Ok(mut parser) => {
-                        // Extract functions
-                        match parser.extract_functions(&code, &file_str) {
-                            Ok(functions) => {
-                                let mut similar_pairs = Vec::new();
-
-                                // Compare all pairs within the file
-                                for i in 0..functions.len() {
-                                    for j in (i + 1)..functions.len() {
-                                        let func1 = &functions[i];
-                                        let func2 = &functions[j];
-
-                                        // Skip if functions don't meet minimum requirements
-                                        if func1.end_line - func1.start_line + 1 < options.min_lines
-                                            || func2.end_line - func2.start_line + 1
-                                                < options.min_lines
-                                        {
-                                            continue;
-                                        }
-
-                                        // Extract function bodies
-                                        let lines: Vec<&str> = code.lines().collect();
-                                        let body1 = extract_function_body(&lines, func1);
-                                        let body2 = extract_function_body(&lines, func2);
-
-                                        // Calculate similarity using Python parser
-                                        let similarity = match (
-                                            parser.parse(&body1, &format!("{}:func1", file_str)),
-                                            parser.parse(&body2, &format!("{}:func2", file_str)),
-                                        ) {
-                                            (Ok(tree1), Ok(tree2)) => {
-                                                // Use calculate_tsed to apply size_penalty and other options
-                                                calculate_tsed(&tree1, &tree2, options)
-                                            }
-                                            _ => 0.0,
-                                        };
-
-                                        if similarity >= threshold {
-                                            similar_pairs.push(SimilarityResult::new(
-                                                func1.clone(),
-                                                func2.clone(),
-                                                similarity,
-                                            ));
-                                        }
-                                    }
-                                }
+        .filter(|file| changed.is_none_or(|c| c.touches_file(file)))
+        .filter_map(|file| {
+            let code = load_source(file, notebooks)?;
+            let file_str = file.to_string_lossy();
 
-                                if similar_pairs.is_empty() {
-                                    None
-                                } else {
-                                    Some((file.clone(), similar_pairs))
-                                }
-                            }
-                            Err(_) => None,
+            // Create Python parser
+            let mut parser = PythonParser::new().ok()?;
+            let functions = extract_units(&mut parser, &code, file, notebooks, module_blocks)?;
+
+            // Only pairs with a side in changed code are compared
+            let touched: Vec<bool> =
+                functions.iter().map(|f| is_touched(changed, file, f, notebooks)).collect();
+            let mut similar_pairs = Vec::new();
+            let lines: Vec<&str> = code.lines().collect();
+
+            // Compare all pairs within the file
+            for i in 0..functions.len() {
+                for j in (i + 1)..functions.len() {
+                    if !touched[i] && !touched[j] {
+                        continue;
+                    }
+
+                    let func1 = &functions[i];
+                    let func2 = &functions[j];
+
+                    // Skip if functions don't meet minimum requirements
+                    if func1.end_line - func1.start_line + 1 < options.min_lines
+                        || func2.end_line - func2.start_line + 1 < options.min_lines
+                    {
+                        continue;
+                    }
+
+                    // Extract function bodies
+                    let body1 = extract_function_body(&lines, func1);
+                    let body2 = extract_function_body(&lines, func2);
+
+                    // Calculate similarity using Python parser
+                    let similarity = match (
+                        parser.parse(&body1, &format!("{}:func1", file_str)),
+                        parser.parse(&body2, &format!("{}:func2", file_str)),
+                    ) {
+                        (Ok(tree1), Ok(tree2)) => {
+                            // Use calculate_tsed to apply size_penalty and other options
+                            calculate_tsed(&tree1, &tree2, options)
                         }
+                        _ => 0.0,
+                    };
+
+                    if similarity >= threshold {
+                        similar_pairs.push(SimilarityResult::new(
+                            func1.clone(),
+                            func2.clone(),
+                            similarity,
+                        ));
                     }
-                    Err(_) => None,
                 }
             }
-            Err(_) => None,
+
+            if similar_pairs.is_empty() {
+                None
+            } else {
+                Some((file.clone(), similar_pairs))
+            }
+        })
+        .collect()
+}
+
+/// Check for duplicates across files in parallel
+///
+/// Compares pairs involving a notebook and pairs of two top-level blocks; functions of two
+/// `.py` modules are only compared within each file.
+pub fn check_cross_file_duplicates_parallel(
+    files: &[PathBuf],
+    notebooks: &HashMap<PathBuf, Notebook>,
+    threshold: f64,
+    options: &TSEDOptions,
+    changed: Option<&ChangedLines>,
+    module_blocks: bool,
+) -> Vec<(PathBuf, SimilarityResult<GenericFunctionDef>, PathBuf)> {
+    if notebooks.is_empty() && !module_blocks {
+        return Vec::new();
+    }
+
+    // Bodies are dedented: a function body and a top-level cell block then parse alike
+    let units: Vec<(usize, GenericFunctionDef, String, bool)> = files
+        .par_iter()
+        .enumerate()
+        .filter_map(|(index, file)| {
+            let code = load_source(file, notebooks)?;
+            let mut parser = PythonParser::new().ok()?;
+            let functions = extract_units(&mut parser, &code, file, notebooks, module_blocks)?;
+            let lines: Vec<&str> = code.lines().collect();
+            Some(
+                functions
+                    .into_iter()
+                    .filter(|f| f.end_line - f.start_line + 1 >= options.min_lines)
+                    .map(|f| {
+                        let body = extract_function_body(&lines, &f);
+                        let touched = is_touched(changed, file, &f, notebooks);
+                        (index, f, body, touched)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect();
+
+    let mut pairs_to_check = Vec::new();
+    for i in 0..units.len() {
+        for j in (i + 1)..units.len() {
+            let (file1, func1, _, touched1) = &units[i];
+            let (file2, func2, _, touched2) = &units[j];
+            let involves_notebook =
+                notebooks.contains_key(&files[*file1]) || notebooks.contains_key(&files[*file2]);
+            let both_blocks = is_block(func1) && is_block(func2);
+            if file1 != file2 && (involves_notebook || both_blocks) && (*touched1 || *touched2) {
+                pairs_to_check.push((i, j));
+            }
+        }
+    }
+
+    pairs_to_check
+        .into_par_iter()
+        .map_init(PythonParser::new, |parser, (i, j)| {
+            let parser = parser.as_mut().ok()?;
+            let (file1, func1, body1, _) = &units[i];
+            let (file2, func2, body2, _) = &units[j];
+            let tree1 = parser.parse(body1, "func1").ok()?;
+            let tree2 = parser.parse(body2, "func2").ok()?;
+            let similarity = calculate_tsed(&tree1, &tree2, options);
+            (similarity >= threshold).then(|| {
+                (
+                    files[*file1].clone(),
+                    SimilarityResult::new(func1.clone(), func2.clone(), similarity),
+                    files[*file2].clone(),
+                )
+            })
         })
+        .flatten()
         .collect()
 }
 
@@ -135,9 +262,9 @@ fn extract_function_body(lines: &[&str], func: &GenericFunctionDef) -> String {
     let start_idx = (func.body_start_line.saturating_sub(1)) as usize;
     let end_idx = std::cmp::min(func.body_end_line as usize, lines.len());
 
-    if start_idx >= lines.len() {
+    if start_idx >= end_idx {
         return String::new();
     }
 
-    lines[start_idx..end_idx].join("\n")
+    dedent(&lines[start_idx..end_idx].join("\n"))
 }
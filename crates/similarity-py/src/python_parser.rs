@@ -4,6 +4,7 @@ use similarity_core::language_parser::{
     GenericFunctionDef, GenericTypeDef, Language, LanguageParser,
 };
 use similarity_core::tree::TreeNode;
+use similarity_core::{PythonClassDef, PythonClassKind, PythonFieldDef, PythonMethodDef};
 use std::error::Error;
 use std::rc::Rc;
 use tree_sitter::{Node, Parser};
@@ -38,7 +39,8 @@ impl PythonParser {
             _ => "".to_string(),
         };
 
-        let mut tree_node = TreeNode::new(label, value, current_id);
+        let mut tree_node = TreeNode::new(label, value, current_id)
+            .with_span(node.start_byte() as u32, node.end_byte() as u32);
 
         for child in node.children(&mut node.walk()) {
             let child_node = self.convert_node(child, source, id_counter);
@@ -48,6 +50,206 @@ impl PythonParser {
         tree_node
     }
 
+    /// Top-level statement sequences as pseudo-units named `<module:L10-L34>`
+    ///
+    /// Module-level statements are split into blocks on blank lines; imports and function and
+    /// class definitions end a block. The body of `if __name__ == "__main__":` counts as module
+    /// level.
+    pub fn extract_module_blocks(
+        &mut self,
+        source: &str,
+    ) -> Result<Vec<GenericFunctionDef>, Box<dyn Error + Send + Sync>> {
+        let tree = self.parser.parse(source, None).ok_or_else(|| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Failed to parse Python source",
+            )) as Box<dyn Error + Send + Sync>
+        })?;
+
+        let lines: Vec<&str> = source.lines().collect();
+        let mut spans = Vec::new();
+        let ends_block = |end, start| has_blank_line(&lines, end, start);
+        collect_statement_runs(tree.root_node(), source, &ends_block, &mut spans);
+
+        Ok(spans
+            .into_iter()
+            .map(|(start, end)| GenericFunctionDef {
+                name: format!("<module:L{start}-L{end}>"),
+                start_line: start,
+                end_line: end,
+                body_start_line: start,
+                body_end_line: end,
+                parameters: Vec::new(),
+                is_method: false,
+                class_name: None,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+            })
+            .collect())
+    }
+
+    /// Extract classes and data models with their fields and methods
+    ///
+    /// Class-level annotations (`id: int = 0`) are fields of every class. Plain classes also get
+    /// their `self.x` assignments in `__init__` and constructor-call attributes such as
+    /// `name = models.CharField()`. Functional `namedtuple(...)`, `NamedTuple(...)` and
+    /// `TypedDict(...)` definitions are extracted as classes too.
+    pub fn extract_type_defs(
+        &mut self,
+        source: &str,
+        file_path: &str,
+    ) -> Result<Vec<PythonClassDef>, Box<dyn Error + Send + Sync>> {
+        let tree = self.parser.parse(source, None).ok_or_else(|| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Failed to parse Python source",
+            )) as Box<dyn Error + Send + Sync>
+        })?;
+
+        let mut classes = Vec::new();
+        self.visit_for_classes(tree.root_node(), source, file_path, &mut classes);
+        Ok(classes)
+    }
+
+    fn visit_for_classes(
+        &self,
+        node: Node,
+        source: &str,
+        file_path: &str,
+        classes: &mut Vec<PythonClassDef>,
+    ) {
+        match node.kind() {
+            "class_definition" => classes.extend(self.class_def(node, node, source, file_path)),
+            "decorated_definition" => {
+                if let Some(definition) = node.child_by_field_name("definition") {
+                    if definition.kind() == "class_definition" {
+                        classes.extend(self.class_def(definition, node, source, file_path));
+                    }
+                }
+            }
+            // Classes local to a function are not part of the data model
+            "function_definition" => {}
+            "expression_statement" => classes.extend(functional_model(node, source, file_path)),
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.visit_for_classes(child, source, file_path, classes);
+                }
+            }
+        }
+    }
+
+    /// `node` is the `class_definition`, `outer` the `decorated_definition` around it, if any
+    fn class_def(
+        &self,
+        node: Node,
+        outer: Node,
+        source: &str,
+        file_path: &str,
+    ) -> Option<PythonClassDef> {
+        let name = node_text(node.child_by_field_name("name")?, source).to_string();
+
+        let mut bases = Vec::new();
+        let mut total = true;
+        if let Some(superclasses) = node.child_by_field_name("superclasses") {
+            let mut cursor = superclasses.walk();
+            for argument in superclasses.named_children(&mut cursor) {
+                match argument.kind() {
+                    // `class Movie(TypedDict, total=False)`
+                    "keyword_argument" => {
+                        if keyword_value(argument, "total", source) == Some("False") {
+                            total = false;
+                        }
+                    }
+                    "comment" => {}
+                    _ => bases.push(node_text(argument, source).to_string()),
+                }
+            }
+        }
+
+        let decorators = decorator_texts(outer, source);
+        let kind = PythonClassKind::detect(&bases, &decorators);
+
+        let mut fields: Vec<PythonFieldDef> = Vec::new();
+        let mut init_fields = Vec::new();
+        let mut methods = Vec::new();
+        if let Some(body) = node.child_by_field_name("body") {
+            let mut cursor = body.walk();
+            for statement in body.named_children(&mut cursor) {
+                let definition = if statement.kind() == "decorated_definition" {
+                    match statement.child_by_field_name("definition") {
+                        Some(definition) => definition,
+                        None => continue,
+                    }
+                } else {
+                    statement
+                };
+
+                match definition.kind() {
+                    "function_definition" => {
+                        let method = self.method_def(definition, statement, source);
+                        if method.name == "__init__" && kind == PythonClassKind::Class {
+                            init_fields = self_assignments(definition, source);
+                        }
+                        methods.push(method);
+                    }
+                    "expression_statement" => {
+                        fields.extend(class_field(definition, kind, total, source));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        for field in init_fields {
+            if !fields.iter().any(|existing| existing.name == field.name) {
+                fields.push(field);
+            }
+        }
+
+        Some(PythonClassDef {
+            name,
+            kind,
+            bases,
+            decorators,
+            fields,
+            methods,
+            start_line: outer.start_position().row + 1,
+            end_line: outer.end_position().row + 1,
+            file_path: file_path.to_string(),
+        })
+    }
+
+    fn method_def(&self, node: Node, outer: Node, source: &str) -> PythonMethodDef {
+        let parameters = typed_parameters(node, source)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, (name, _, _))| !(*i == 0 && (name == "self" || name == "cls")))
+            .map(|(_, (name, annotation, _))| match annotation {
+                Some(annotation) => format!("{name}: {annotation}"),
+                None => name,
+            })
+            .collect();
+
+        PythonMethodDef {
+            name: node
+                .child_by_field_name("name")
+                .map(|name| node_text(name, source).to_string())
+                .unwrap_or_default(),
+            parameters,
+            return_type: node
+                .child_by_field_name("return_type")
+                .map(|return_type| node_text(return_type, source).to_string()),
+            decorators: decorator_texts(outer, source)
+                .into_iter()
+                .map(|decorator| decorator.split('(').next().unwrap_or("").trim().to_string())
+                .collect(),
+            body: node
+                .child_by_field_name("body")
+                .map(|body| Rc::new(self.convert_node(body, source, &mut 0))),
+        }
+    }
+
     fn extract_functions_from_node(
         &self,
         node: Node,
@@ -222,6 +424,325 @@ impl PythonParser {
     }
 }
 
+/// Line spans of the runs of statements directly under `node` (the module or a `__main__` guard)
+///
+/// Imports and function and class definitions end a run. `ends_run(end, start)` says whether a
+/// run also ends between a statement ending on line `end` and the next one starting on `start`.
+pub(crate) fn collect_statement_runs(
+    node: Node,
+    source: &str,
+    ends_run: &impl Fn(u32, u32) -> bool,
+    runs: &mut Vec<(u32, u32)>,
+) {
+    let mut current: Option<(u32, u32)> = None;
+    let mut cursor = node.walk();
+    for statement in node.named_children(&mut cursor) {
+        match statement.kind() {
+            "comment" => continue,
+            "function_definition"
+            | "class_definition"
+            | "decorated_definition"
+            | "import_statement"
+            | "import_from_statement"
+            | "future_import_statement" => {
+                runs.extend(current.take());
+                continue;
+            }
+            "if_statement" if is_main_guard(statement, source) => {
+                runs.extend(current.take());
+                if let Some(body) = statement.child_by_field_name("consequence") {
+                    collect_statement_runs(body, source, ends_run, runs);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let start = statement.start_position().row as u32 + 1;
+        let end = statement.end_position().row as u32 + 1;
+        current = match current {
+            Some((block_start, block_end)) if !ends_run(block_end, start) => {
+                Some((block_start, end))
+            }
+            _ => {
+                runs.extend(current.take());
+                Some((start, end))
+            }
+        };
+    }
+    runs.extend(current);
+}
+
+/// Whether any line strictly between lines `after` and `before` (1-based) is blank
+fn has_blank_line(lines: &[&str], after: u32, before: u32) -> bool {
+    (after + 1..before)
+        .any(|line| lines.get(line as usize - 1).is_some_and(|l| l.trim().is_empty()))
+}
+
+/// `if __name__ == "__main__":`
+fn is_main_guard(statement: Node, source: &str) -> bool {
+    statement.child_by_field_name("condition").is_some_and(|condition| {
+        let condition = node_text(condition, source).replace('\'', "\"");
+        condition == "__name__ == \"__main__\"" || condition == "\"__main__\" == __name__"
+    })
+}
+
+fn node_text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
+
+/// Decorators of a `decorated_definition` without the `@`; empty for undecorated definitions
+fn decorator_texts(node: Node, source: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| child.kind() == "decorator")
+        .map(|decorator| node_text(decorator, source).trim_start_matches('@').trim().to_string())
+        .collect()
+}
+
+/// Value of `name=value` in a `keyword_argument` node
+fn keyword_value<'a>(argument: Node, name: &str, source: &'a str) -> Option<&'a str> {
+    let key = argument.child_by_field_name("name")?;
+    if node_text(key, source) != name {
+        return None;
+    }
+    Some(node_text(argument.child_by_field_name("value")?, source))
+}
+
+fn string_content<'a>(node: Node, source: &'a str) -> &'a str {
+    node_text(node, source).trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// `(name, annotation, has_default)` of each parameter of a function definition
+fn typed_parameters(node: Node, source: &str) -> Vec<(String, Option<String>, bool)> {
+    let Some(parameters) = node.child_by_field_name("parameters") else {
+        return Vec::new();
+    };
+    let annotation = |param: Node| {
+        param
+            .child_by_field_name("type")
+            .map(|annotation| node_text(annotation, source).to_string())
+    };
+
+    let mut cursor = parameters.walk();
+    parameters
+        .named_children(&mut cursor)
+        .filter_map(|param| match param.kind() {
+            "identifier" | "list_splat_pattern" | "dictionary_splat_pattern" => {
+                Some((node_text(param, source).to_string(), None, false))
+            }
+            // `x: int` and `*args: str`; the name is the first child
+            "typed_parameter" => Some((
+                node_text(param.named_child(0)?, source).to_string(),
+                annotation(param),
+                false,
+            )),
+            "default_parameter" | "typed_default_parameter" => Some((
+                node_text(param.child_by_field_name("name")?, source).to_string(),
+                annotation(param),
+                true,
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A field declared in a class body: `id: int`, `tags: list[str] = []`, `x = attr.ib()` or
+/// `name = models.CharField(max_length=100)`
+fn class_field(
+    statement: Node,
+    kind: PythonClassKind,
+    total: bool,
+    source: &str,
+) -> Option<PythonFieldDef> {
+    let assignment = statement.named_child(0).filter(|node| node.kind() == "assignment")?;
+    let left = assignment.child_by_field_name("left").filter(|node| node.kind() == "identifier")?;
+    let name = node_text(left, source);
+    if name.starts_with("__") || name == "model_config" {
+        return None;
+    }
+    let right = assignment.child_by_field_name("right");
+
+    if let Some(annotation) = assignment.child_by_field_name("type") {
+        let annotation = node_text(annotation, source);
+        let annotation = annotation.strip_prefix("typing.").unwrap_or(annotation);
+        if annotation.starts_with("ClassVar") {
+            return None;
+        }
+        let (annotation, required) = if let Some(inner) = annotation.strip_prefix("NotRequired[") {
+            (inner.trim_end_matches(']'), Some(false))
+        } else if let Some(inner) = annotation.strip_prefix("Required[") {
+            (inner.trim_end_matches(']'), Some(true))
+        } else {
+            (annotation, None)
+        };
+        return Some(PythonFieldDef {
+            name: name.to_string(),
+            annotation: Some(annotation.to_string()),
+            optional: required.map_or(right.is_some() || !total, |required| !required),
+        });
+    }
+
+    // Without an annotation only attrs and plain classes (Django, SQLAlchemy) declare fields,
+    // and only with a field constructor: `attr.ib()`, `attrs.field()`, `Column(String)`
+    if kind.is_data_model() && kind != PythonClassKind::Attrs {
+        return None;
+    }
+    let call = right.filter(|node| node.kind() == "call")?;
+    let callee = node_text(call.child_by_field_name("function")?, source);
+    let constructor = callee.rsplit('.').next().unwrap_or(callee);
+    let is_attrs_field = matches!(constructor, "ib" | "attrib" | "field");
+    if !is_attrs_field && !constructor.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    // `attr.ib()` says nothing about the type unless given `type=`
+    let mut annotation = (!is_attrs_field).then(|| constructor.to_string());
+    let mut optional = false;
+    if let Some(arguments) = call.child_by_field_name("arguments") {
+        let mut cursor = arguments.walk();
+        for argument in arguments.named_children(&mut cursor) {
+            if argument.kind() != "keyword_argument" {
+                continue;
+            }
+            if let Some(type_name) = keyword_value(argument, "type", source) {
+                annotation = Some(type_name.to_string());
+            }
+            optional |= ["default", "factory", "default_factory", "null"].iter().any(|key| {
+                keyword_value(argument, key, source).is_some_and(|value| value != "False")
+            });
+        }
+    }
+    Some(PythonFieldDef { name: name.to_string(), annotation, optional })
+}
+
+/// `self.x` assignments in `__init__`, typed by their annotation or by the parameter assigned
+fn self_assignments(init: Node, source: &str) -> Vec<PythonFieldDef> {
+    fn visit(
+        node: Node,
+        source: &str,
+        parameters: &[(String, Option<String>, bool)],
+        fields: &mut Vec<PythonFieldDef>,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "assignment" {
+                let attribute = child.child_by_field_name("left").and_then(|left| {
+                    node_text(left, source).strip_prefix("self.").map(str::to_string)
+                });
+                if let Some(name) = attribute.filter(|name| !name.contains('.')) {
+                    let parameter = child.child_by_field_name("right").and_then(|right| {
+                        parameters.iter().find(|(param, _, _)| param == node_text(right, source))
+                    });
+                    let annotation = child
+                        .child_by_field_name("type")
+                        .map(|annotation| node_text(annotation, source).to_string())
+                        .or_else(|| parameter.and_then(|(_, annotation, _)| annotation.clone()));
+                    if !fields.iter().any(|field| field.name == name) {
+                        fields.push(PythonFieldDef {
+                            name,
+                            annotation,
+                            optional: parameter.is_some_and(|(_, _, has_default)| *has_default),
+                        });
+                    }
+                }
+            }
+            // Assignments in nested functions and classes belong to other objects
+            if !matches!(child.kind(), "function_definition" | "class_definition") {
+                visit(child, source, parameters, fields);
+            }
+        }
+    }
+
+    let parameters = typed_parameters(init, source);
+    let mut fields = Vec::new();
+    if let Some(body) = init.child_by_field_name("body") {
+        visit(body, source, &parameters, &mut fields);
+    }
+    fields
+}
+
+/// `Point = namedtuple("Point", "x y")`, `Point = NamedTuple("Point", [("x", int)])` or
+/// `Movie = TypedDict("Movie", {"title": str}, total=False)`
+fn functional_model(statement: Node, source: &str, file_path: &str) -> Option<PythonClassDef> {
+    let assignment = statement.named_child(0).filter(|node| node.kind() == "assignment")?;
+    let left = assignment.child_by_field_name("left").filter(|node| node.kind() == "identifier")?;
+    let call = assignment.child_by_field_name("right").filter(|node| node.kind() == "call")?;
+    let callee = node_text(call.child_by_field_name("function")?, source);
+    let kind = match callee.rsplit('.').next() {
+        Some("namedtuple" | "NamedTuple") => PythonClassKind::NamedTuple,
+        Some("TypedDict") => PythonClassKind::TypedDict,
+        _ => return None,
+    };
+
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let arguments: Vec<Node> = arguments.named_children(&mut cursor).collect();
+    let total = !arguments.iter().any(|argument| {
+        argument.kind() == "keyword_argument"
+            && keyword_value(*argument, "total", source) == Some("False")
+    });
+    let field = |name: &str, annotation: Option<Node>| PythonFieldDef {
+        name: name.to_string(),
+        annotation: annotation.map(|annotation| node_text(annotation, source).to_string()),
+        optional: !total,
+    };
+
+    let spec = arguments.get(1)?;
+    let mut fields = Vec::new();
+    let mut cursor = spec.walk();
+    match spec.kind() {
+        "string" => fields.extend(
+            string_content(*spec, source)
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|name| !name.is_empty())
+                .map(|name| field(name, None)),
+        ),
+        "list" | "tuple" => {
+            for element in spec.named_children(&mut cursor) {
+                match element.kind() {
+                    "string" => fields.push(field(string_content(element, source), None)),
+                    // `("x", int)`
+                    "tuple" => {
+                        if let Some(name) =
+                            element.named_child(0).filter(|name| name.kind() == "string")
+                        {
+                            fields
+                                .push(field(string_content(name, source), element.named_child(1)));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        "dictionary" => {
+            for pair in spec.named_children(&mut cursor).filter(|pair| pair.kind() == "pair") {
+                if let Some(key) =
+                    pair.child_by_field_name("key").filter(|key| key.kind() == "string")
+                {
+                    fields.push(field(
+                        string_content(key, source),
+                        pair.child_by_field_name("value"),
+                    ));
+                }
+            }
+        }
+        _ => return None,
+    }
+
+    Some(PythonClassDef {
+        name: node_text(left, source).to_string(),
+        kind,
+        bases: Vec::new(),
+        decorators: Vec::new(),
+        fields,
+        methods: Vec::new(),
+        start_line: statement.start_position().row + 1,
+        end_line: statement.end_position().row + 1,
+        file_path: file_path.to_string(),
+    })
+}
+
 impl LanguageParser for PythonParser {
     fn parse(
         &mut self,
@@ -256,92 +777,24 @@ impl LanguageParser for PythonParser {
         Ok(self.extract_functions_from_node(root_node, source, None))
     }
 
+    /// Classes and data models as extracted by [`PythonParser::extract_type_defs`], with their
+    /// field names
     fn extract_types(
         &mut self,
         source: &str,
-        _filename: &str,
+        filename: &str,
     ) -> Result<Vec<GenericTypeDef>, Box<dyn Error + Send + Sync>> {
-        let tree = self.parser.parse(source, None).ok_or_else(|| {
-            Box::new(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Failed to parse Python source",
-            )) as Box<dyn Error + Send + Sync>
-        })?;
-
-        let root_node = tree.root_node();
-        let mut types = Vec::new();
-
-        fn visit_node_for_types(node: Node, source: &str, types: &mut Vec<GenericTypeDef>) {
-            if node.kind() == "class_definition" {
-                if let Some(name_node) = node.child_by_field_name("name") {
-                    if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
-                        types.push(GenericTypeDef {
-                            name: name.to_string(),
-                            kind: "class".to_string(),
-                            start_line: node.start_position().row as u32 + 1,
-                            end_line: node.end_position().row as u32 + 1,
-                            fields: extract_class_fields(node, source),
-                        });
-                    }
-                }
-            }
-
-            // Continue traversing
-            let mut cursor = node.walk();
-            for child in node.children(&mut cursor) {
-                visit_node_for_types(child, source, types);
-            }
-        }
-
-        fn extract_class_fields(node: Node, source: &str) -> Vec<String> {
-            let mut fields = Vec::new();
-
-            if let Some(body) = node.child_by_field_name("body") {
-                let mut cursor = body.walk();
-                for child in body.children(&mut cursor) {
-                    // Look for instance variable assignments in __init__ method
-                    if child.kind() == "function_definition" {
-                        if let Some(name_node) = child.child_by_field_name("name") {
-                            if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
-                                if name == "__init__" {
-                                    // Extract self.field assignments from __init__
-                                    if let Some(func_body) = child.child_by_field_name("body") {
-                                        extract_self_assignments(func_body, source, &mut fields);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            fields
-        }
-
-        fn extract_self_assignments(node: Node, source: &str, fields: &mut Vec<String>) {
-            let mut cursor = node.walk();
-            for child in node.children(&mut cursor) {
-                if child.kind() == "assignment" {
-                    if let Some(left) = child.child(0) {
-                        if left.kind() == "attribute" {
-                            if let Ok(text) = left.utf8_text(source.as_bytes()) {
-                                if text.starts_with("self.") {
-                                    let field_name = text.trim_start_matches("self.");
-                                    if !fields.contains(&field_name.to_string()) {
-                                        fields.push(field_name.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                // Recursively check nested nodes
-                extract_self_assignments(child, source, fields);
-            }
-        }
-
-        visit_node_for_types(root_node, source, &mut types);
-        Ok(types)
+        Ok(self
+            .extract_type_defs(source, filename)?
+            .into_iter()
+            .map(|class| GenericTypeDef {
+                name: class.name,
+                kind: class.kind.label().to_string(),
+                start_line: class.start_line as u32,
+                end_line: class.end_line as u32,
+                fields: class.fields.into_iter().map(|field| field.name).collect(),
+            })
+            .collect())
     }
 
     fn language(&self) -> Language {
@@ -403,5 +856,140 @@ class Admin(User):
         assert_eq!(types[0].name, "User");
         assert_eq!(types[0].kind, "class");
         assert_eq!(types[1].name, "Admin");
+        assert_eq!(types[1].fields, ["level"]);
+    }
+
+    #[test]
+    fn test_python_data_models() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = r#"
+from dataclasses import dataclass, field
+from typing import ClassVar, NamedTuple, NotRequired, TypedDict
+
+import attr
+from pydantic import BaseModel
+
+
+@dataclass(frozen=True)
+class User:
+    registry: ClassVar[dict] = {}
+    id: int
+    tags: list[str] = field(default_factory=list)
+
+    def display(self, prefix: str = "") -> str:
+        return prefix + str(self.id)
+
+
+class Point(NamedTuple):
+    x: float
+    y: float
+
+
+class Movie(TypedDict, total=False):
+    title: str
+    year: NotRequired[int]
+
+
+class Customer(BaseModel):
+    model_config = {"frozen": True}
+    id: int
+    name: str = "anonymous"
+
+
+@attr.s
+class Order:
+    id = attr.ib(type=int)
+    note = attr.ib(default="")
+
+
+class Account:
+    table = "accounts"
+    owner = models.ForeignKey(User, null=True)
+
+    def __init__(self, balance: float, currency="EUR"):
+        self.balance = balance
+        self.currency = currency
+
+
+Pair = namedtuple("Pair", "left right")
+Config = TypedDict("Config", {"debug": bool})
+"#;
+
+        let classes = parser.extract_type_defs(source, "models.py").unwrap();
+        let summary: Vec<(&str, &str, Vec<String>)> = classes
+            .iter()
+            .map(|class| {
+                let fields = class
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{}: {}{}",
+                            field.name,
+                            field.annotation.as_deref().unwrap_or("?"),
+                            if field.optional { " = ..." } else { "" }
+                        )
+                    })
+                    .collect();
+                (class.name.as_str(), class.kind.label(), fields)
+            })
+            .collect();
+        let fields = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            [
+                ("User", "dataclass", fields(&["id: int", "tags: list[str] = ..."])),
+                ("Point", "NamedTuple", fields(&["x: float", "y: float"])),
+                ("Movie", "TypedDict", fields(&["title: str = ...", "year: int = ..."])),
+                ("Customer", "pydantic", fields(&["id: int", "name: str = ..."])),
+                ("Order", "attrs", fields(&["id: int", "note: ? = ..."])),
+                (
+                    "Account",
+                    "class",
+                    fields(&["owner: ForeignKey = ...", "balance: float", "currency: ? = ..."])
+                ),
+                ("Pair", "NamedTuple", fields(&["left: ?", "right: ?"])),
+                ("Config", "TypedDict", fields(&["debug: bool"])),
+            ]
+        );
+
+        let display = &classes[0].methods[0];
+        assert_eq!(display.signature(), "(prefix: str) -> str");
+        assert!(display.body.is_some());
+        assert_eq!((classes[0].start_line, classes[0].end_line), (9, 16));
+    }
+
+    #[test]
+    fn test_python_module_blocks() {
+        let mut parser = PythonParser::new().unwrap();
+        let source = r#"import argparse
+import logging
+
+logging.basicConfig(level=logging.INFO)
+logger = logging.getLogger(__name__)
+
+# Arguments
+parser = argparse.ArgumentParser()
+parser.add_argument(
+    "--input",
+    required=True,
+)
+args = parser.parse_args()
+
+
+def main(path):
+    return path
+
+
+if __name__ == "__main__":
+    data = load(args.input)
+    main(data)
+"#;
+
+        let blocks = parser.extract_module_blocks(source).unwrap();
+        let names: Vec<&str> = blocks.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["<module:L4-L5>", "<module:L8-L13>", "<module:L21-L22>"]);
+        assert_eq!((blocks[1].body_start_line, blocks[1].body_end_line), (8, 13));
     }
 }
@@ -163,3 +163,308 @@ def longer_func2():
         .stdout(predicate::str::contains("longer_func2"))
         .stdout(predicate::str::contains("f1").not());
 }
+
+#[test]
+fn test_python_suggest_shared_function() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("repo.py");
+
+    let content = r#"
+def load_users(user_id):
+    response = fetch_users("/api/users", user_id)
+    if response is None:
+        raise ValueError("missing")
+    total = len(response)
+    return response, total
+
+def load_accounts(account_id):
+    result = fetch_accounts("/api/accounts", account_id)
+    if result is None:
+        raise ValueError("missing")
+    total = len(result)
+    return result, total
+"#;
+
+    fs::write(&file_path, content).unwrap();
+
+    Command::cargo_bin("similarity-py")
+        .unwrap()
+        .arg(&file_path)
+        .arg("--suggest")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Extract shared function: def load(user_id, fetch, value1):",
+        ))
+        .stdout(predicate::str::contains("response = fetch(value1, user_id)"))
+        .stdout(predicate::str::contains("load(account_id, fetch_accounts, \"/api/accounts\")"));
+}
+
+#[test]
+fn test_python_suggest_json_across_files() {
+    let dir = tempdir().unwrap();
+    let block = |fetch: &str, url: &str, id: &str| {
+        format!(
+            "import json\n\nresult = {fetch}(\"{url}\", {id})\nif result is None:\n    raise ValueError(\"missing\")\ntotal = len(result)\nprint(result, total)\n"
+        )
+    };
+    fs::write(dir.path().join("users.py"), block("fetch_users", "/api/users", "user_id")).unwrap();
+    fs::write(
+        dir.path().join("accounts.py"),
+        block("fetch_accounts", "/api/accounts", "account_id"),
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("similarity-py")
+        .unwrap()
+        .current_dir(dir.path())
+        .args(["users.py", "accounts.py", "--module-blocks", "--min-lines", "3"])
+        .args(["--suggest", "--suggest-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // stdout is the JSON alone, and each call site points at its own file
+    let suggestions: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let locations: Vec<&str> = suggestions[0]["call_sites"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|site| site["location"].as_str())
+        .collect();
+    assert!(locations.contains(&"users.py:3-7"), "{locations:?}");
+    assert!(locations.contains(&"accounts.py:3-7"), "{locations:?}");
+}
+
+#[test]
+fn test_python_diff_from_skips_unchanged_pairs() {
+    let dir = tempdir().unwrap();
+    let func = |name: &str| {
+        format!(
+            "def {name}(items):\n    result = []\n    for item in items:\n        if item > 0:\n            result.append(item * 2)\n    return result\n\n"
+        )
+    };
+    let content = format!("{}{}", func("process_items"), func("handle_items"));
+    fs::write(dir.path().join("test.py"), content).unwrap();
+
+    let run = |patch: &str| {
+        fs::write(dir.path().join("change.patch"), patch).unwrap();
+        Command::cargo_bin("similarity-py")
+            .unwrap()
+            .current_dir(dir.path())
+            .arg("test.py")
+            .arg("--diff-from")
+            .arg("change.patch")
+            .assert()
+            .success()
+    };
+
+    // A change inside handle_items keeps the pair
+    run("--- a/test.py\n+++ b/test.py\n@@ -9 +9 @@\n-    for item in items:\n+    for item in items:\n")
+        .stdout(predicate::str::contains("Total duplicate pairs found: 1"));
+
+    // A change in another file drops it
+    run("--- a/other.py\n+++ b/other.py\n@@ -1 +1 @@\n-x = 1\n+x = 2\n")
+        .stdout(predicate::str::contains("No duplicate functions found"));
+}
+
+#[test]
+fn test_python_classes_mode_matches_data_models_across_styles() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("models.py"),
+        r#"
+from dataclasses import dataclass, field
+
+
+@dataclass
+class User:
+    id: int
+    name: str
+    email: str
+    tags: list[str] = field(default_factory=list)
+
+    def display_name(self) -> str:
+        if self.name:
+            return self.name.title()
+        return self.email.split("@")[0]
+
+
+class Cache:
+    def __init__(self, size: int):
+        self.size = size
+        self.items = {}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("schemas.py"),
+        r#"
+from pydantic import BaseModel
+
+
+class Customer(BaseModel):
+    id: int
+    name: str
+    email: str
+    tags: list[str] = []
+
+    def display_name(self) -> str:
+        if self.name:
+            return self.name.title()
+        return self.email.split("@")[0]
+
+
+class Invoice(BaseModel):
+    number: str
+    total: float
+    paid: bool = False
+"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("similarity-py")
+        .unwrap()
+        .current_dir(dir.path())
+        .args(["--classes", "--no-functions", "."])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Found 4 classes (3 data models)"))
+        .stdout(predicate::str::contains("dataclass User | ./models.py:5-15"))
+        .stdout(predicate::str::contains("pydantic Customer | ./schemas.py:5-14"))
+        .stdout(predicate::str::contains("method bodies: 100.00%"))
+        .stdout(predicate::str::contains("Invoice").not())
+        .stdout(predicate::str::contains("Total similar class pairs found: 1"));
+}
+
+#[test]
+fn test_notebook_cells_and_functions_are_compared_across_files() {
+    let dir = tempdir().unwrap();
+    let notebook = |cells: &[(&str, &str)]| {
+        let cells: Vec<serde_json::Value> = cells
+            .iter()
+            .map(|(cell_type, source)| {
+                serde_json::json!({
+                    "cell_type": cell_type,
+                    "metadata": {},
+                    "outputs": [],
+                    "source": source,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "cells": cells,
+            "metadata": {"kernelspec": {"language": "python", "name": "python3"}},
+            "nbformat": 4,
+            "nbformat_minor": 5,
+        })
+        .to_string()
+    };
+    let report = r#"%matplotlib inline
+import pandas as pd
+
+df = pd.read_csv("sales.csv")
+df = df.dropna()
+df["total"] = df["price"] * df["quantity"]
+summary = df.groupby("region")["total"].sum()
+print(summary.sort_values())"#;
+
+    fs::write(
+        dir.path().join("sales.ipynb"),
+        notebook(&[("markdown", "# Sales"), ("code", report)]),
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("orders.ipynb"),
+        notebook(&[
+            ("code", "!pip install pandas"),
+            ("code", &report.replace("sales", "orders").replace("region", "country")),
+            (
+                "code",
+                "def normalize(frame):\n    frame = frame.rename(columns=str.lower)\n    frame = frame.drop_duplicates()\n    frame = frame.fillna(0)\n    return frame",
+            ),
+        ]),
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("util.py"),
+        "def normalize_frame(df):\n    df = df.rename(columns=str.lower)\n    df = df.drop_duplicates()\n    df = df.fillna(0)\n    return df\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("similarity-py")
+        .unwrap()
+        .current_dir(dir.path())
+        .args([".", "--threshold", "0.8"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Including 2 Jupyter notebooks"))
+        .stdout(predicate::str::contains(
+            "./orders.ipynb:cell 3:1-5 function normalize <-> ./util.py:1-5 function normalize_frame",
+        ))
+        .stdout(predicate::str::contains(
+            "./orders.ipynb:cell 2:4-8 block <cell 2> <-> ./sales.ipynb:cell 2:4-8 block <cell 2>",
+        ))
+        .stdout(predicate::str::contains("Total duplicate pairs found: 2"));
+}
+
+#[test]
+fn test_module_blocks_compare_top_level_script_code() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("load_sales.py"),
+        r#"import argparse
+import logging
+
+logging.basicConfig(level=logging.INFO, format="%(asctime)s %(message)s")
+logger = logging.getLogger("sales")
+logger.info("starting")
+
+parser = argparse.ArgumentParser(description="Load sales")
+parser.add_argument("--input", required=True)
+parser.add_argument("--output", default="sales.parquet")
+args = parser.parse_args()
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("load_orders.py"),
+        r#"import argparse
+import logging
+
+logging.basicConfig(level=logging.INFO, format="%(asctime)s %(message)s")
+logger = logging.getLogger("orders")
+logger.info("starting")
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser(description="Load orders")
+    parser.add_argument("--input", required=True)
+    parser.add_argument("--output", default="orders.parquet")
+    args = parser.parse_args()
+"#,
+    )
+    .unwrap();
+
+    // Without the flag, scripts without functions have nothing to compare
+    Command::cargo_bin("similarity-py")
+        .unwrap()
+        .current_dir(dir.path())
+        .arg(".")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No duplicate functions found"));
+
+    Command::cargo_bin("similarity-py")
+        .unwrap()
+        .current_dir(dir.path())
+        .args([".", "--module-blocks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "./load_orders.py:4-6 block <module:L4-L6> <-> ./load_sales.py:4-6 block <module:L4-L6>",
+        ))
+        .stdout(predicate::str::contains(
+            "./load_orders.py:9-12 block <module:L9-L12> <-> ./load_sales.py:8-11 block <module:L8-L11>",
+        ))
+        .stdout(predicate::str::contains("Total duplicate pairs found: 2"));
+}
@@ -46,6 +46,7 @@ def compute_total(values):
         min_tokens: None,
         size_penalty: false, // Disable for this test
         skip_test: false,
+        rename_consistency: false,
     };
 
     let similarity = calculate_tsed(&tree1, &tree2, &tsed_options);
@@ -120,6 +121,7 @@ def filter_positive(numbers):
         min_tokens: None,
         size_penalty: true, // Enable size penalty
         skip_test: false,
+        rename_consistency: false,
     };
 
     let similarity = calculate_tsed(&tree1, &tree2, &tsed_options);
@@ -166,6 +168,7 @@ class MathOperations:
         min_tokens: None,
         size_penalty: false,
         skip_test: false,
+        rename_consistency: false,
     };
 
     let similarity = calculate_tsed(&tree1, &tree2, &tsed_options);
@@ -203,6 +206,7 @@ def title(self):
         min_tokens: None,
         size_penalty: false,
         skip_test: false,
+        rename_consistency: false,
     };
 
     let similarity = calculate_tsed(&tree1, &tree2, &tsed_options);
@@ -278,6 +282,7 @@ def get_numbers(n):
         min_tokens: None,
         size_penalty: true,
         skip_test: false,
+        rename_consistency: false,
     };
 
     let similarity = calculate_tsed(&tree1, &tree2, &tsed_options);
@@ -316,6 +321,7 @@ async def get_data(endpoint):
         min_tokens: None,
         size_penalty: false,
         skip_test: false,
+        rename_consistency: false,
     };
 
     let similarity = calculate_tsed(&tree1, &tree2, &tsed_options);
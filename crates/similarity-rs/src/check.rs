@@ -2,6 +2,7 @@
 
 use crate::parallel::check_within_file_duplicates_parallel;
 use similarity_core::{
+    changed_lines::ChangedLines,
     cli_file_utils::collect_files,
     cli_output::{format_function_output, show_function_code},
     cli_parallel::SimilarityResult,
@@ -38,12 +39,14 @@ pub fn check_paths(
     min_lines: u32,
     min_tokens: Option<u32>,
     no_size_penalty: bool,
+    rename_consistency: bool,
     print: bool,
     _fast_mode: bool, // Rust doesn't support fast mode yet
     filter_function: Option<&String>,
     filter_function_body: Option<&String>,
     _exclude_patterns: &[String],
     skip_test: bool,
+    changed: Option<&ChangedLines>,
 ) -> anyhow::Result<usize> {
     let default_extensions = vec!["rs"];
     let exts: Vec<&str> =
@@ -57,6 +60,12 @@ pub fn check_paths(
     }
 
     println!("Checking {} files for duplicates...", files.len());
+    if let Some(changed) = changed {
+        println!(
+            "Reporting only duplicates touching lines changed in {} files",
+            changed.file_count()
+        );
+    }
 
     let mut options = TSEDOptions::default();
     options.apted_options.rename_cost = rename_cost;
@@ -64,12 +73,14 @@ pub fn check_paths(
     options.min_lines = min_lines;
     options.min_tokens = min_tokens;
     options.size_penalty = !no_size_penalty;
+    options.rename_consistency = rename_consistency;
     options.skip_test = skip_test;
 
     let mut all_results = Vec::new();
 
     // Check within each file
-    let within_file_results = check_within_file_duplicates_parallel(&files, threshold, &options);
+    let within_file_results =
+        check_within_file_duplicates_parallel(&files, threshold, &options, changed);
 
     // Collect within-file duplicates
     for (file, similar_pairs) in within_file_results {
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use similarity_core::cli_structures::ExtractedDefinitions;
+use similarity_core::{
+    cli_file_utils::collect_files, compare_impl_blocks, ChangedLines, RustImplComparison,
+    RustImplDef, TSEDOptions,
+};
+use std::path::Path;
+
+use crate::rust_parser::RustParser;
+use crate::{create_exclude_matcher, get_relative_path};
+
+struct SimilarImplPair {
+    index1: usize,
+    index2: usize,
+    comparison: RustImplComparison,
+}
+
+fn describe_impl(impl_def: &RustImplDef) -> String {
+    format!(
+        "{} | {}:{}-{}",
+        impl_def.header(),
+        get_relative_path(&impl_def.file_path),
+        impl_def.start_line,
+        impl_def.end_line
+    )
+}
+
+/// Whether two impl blocks implement the same thing: the same trait (generic arguments aside),
+/// or both inherent
+fn comparable(impl1: &RustImplDef, impl2: &RustImplDef) -> bool {
+    if impl1.trait_base_name() != impl2.trait_base_name() {
+        return false;
+    }
+    // Split blocks of one type (`impl Foo` twice, or `cfg`-gated copies of a trait impl)
+    !(impl1.self_type == impl2.self_type && impl1.trait_name == impl2.trait_name)
+}
+
+/// Check for similar `impl` blocks, reporting near-identical trait implementations across types
+/// method by method
+#[allow(clippy::too_many_arguments)]
+pub fn check_impls(
+    paths: Vec<String>,
+    threshold: f64,
+    rename_cost: f64,
+    extensions: Option<&Vec<String>>,
+    min_lines: u32,
+    no_size_penalty: bool,
+    print: bool,
+    exclude_patterns: &[String],
+    changed: Option<&ChangedLines>,
+) -> Result<usize> {
+    let default_extensions = vec!["rs"];
+    let exts: Vec<&str> =
+        extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
+
+    let mut files = collect_files(&paths, &exts)?;
+    if let Some(matcher) = create_exclude_matcher(exclude_patterns) {
+        files.retain(|file| !matcher.is_match(file));
+    }
+    if files.is_empty() {
+        println!("No Rust files found in specified paths");
+        return Ok(0);
+    }
+
+    println!("Checking {} files for similar impl blocks...", files.len());
+
+    let mut parser =
+        RustParser::new().map_err(|e| anyhow::anyhow!("Failed to create parser: {}", e))?;
+    let extracted = ExtractedDefinitions::extract(&files, |content, file_path| {
+        parser.extract_impl_defs(content, file_path)
+    });
+    let impls = &extracted.definitions;
+
+    println!(
+        "Found {} impl blocks ({} trait implementations)\n",
+        impls.len(),
+        impls.iter().filter(|impl_def| impl_def.trait_name.is_some()).count()
+    );
+
+    let mut options = TSEDOptions::default();
+    options.apted_options.rename_cost = rename_cost;
+    options.size_penalty = !no_size_penalty;
+
+    let large_enough = |impl_def: &RustImplDef| {
+        !impl_def.methods.is_empty()
+            && impl_def.end_line - impl_def.start_line + 1 >= min_lines as usize
+    };
+    let touches_changes = |impl_def: &RustImplDef| {
+        changed.is_none_or(|changed| {
+            changed.overlaps(
+                Path::new(&impl_def.file_path),
+                impl_def.start_line as u32,
+                impl_def.end_line as u32,
+            )
+        })
+    };
+
+    let mut pairs = Vec::new();
+    for i in 0..impls.len() {
+        for j in (i + 1)..impls.len() {
+            let (impl1, impl2) = (&impls[i], &impls[j]);
+            if !large_enough(impl1)
+                || !large_enough(impl2)
+                || !comparable(impl1, impl2)
+                || !(touches_changes(impl1) || touches_changes(impl2))
+            {
+                continue;
+            }
+
+            let comparison = compare_impl_blocks(impl1, impl2, &options);
+            if comparison.similarity >= threshold {
+                pairs.push(SimilarImplPair { index1: i, index2: j, comparison });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.comparison.similarity.partial_cmp(&a.comparison.similarity).unwrap());
+    let (trait_pairs, inherent_pairs): (Vec<SimilarImplPair>, Vec<SimilarImplPair>) =
+        pairs.into_iter().partition(|pair| impls[pair.index1].trait_name.is_some());
+
+    let print_pairs = |title: &str, pairs: &[SimilarImplPair]| {
+        println!("{title}");
+        println!("{}", "-".repeat(60));
+
+        for pair in pairs {
+            let impl1 = &impls[pair.index1];
+            let impl2 = &impls[pair.index2];
+
+            println!("\nSimilarity: {:.2}%", pair.comparison.similarity * 100.0);
+            println!("  {}", describe_impl(impl1));
+            println!("  {}", describe_impl(impl2));
+            for method_match in &pair.comparison.matches {
+                let method1 = &impl1.methods[method_match.method1];
+                let method2 = &impl2.methods[method_match.method2];
+                println!(
+                    "    {:.2}%  {} (L{}-{}) ~ {} (L{}-{})",
+                    method_match.similarity * 100.0,
+                    method1.name,
+                    method1.start_line,
+                    method1.end_line,
+                    method2.name,
+                    method2.start_line,
+                    method2.end_line
+                );
+            }
+            for (impl_def, unmatched) in
+                [(impl1, &pair.comparison.unmatched1), (impl2, &pair.comparison.unmatched2)]
+            {
+                if !unmatched.is_empty() {
+                    let names: Vec<&str> =
+                        unmatched.iter().map(|&i| impl_def.methods[i].name.as_str()).collect();
+                    println!("    only in {}: {}", impl_def.header(), names.join(", "));
+                }
+            }
+
+            if print {
+                for (index, impl_def) in [(pair.index1, impl1), (pair.index2, impl2)] {
+                    extracted.print_code(
+                        index,
+                        &impl_def.header(),
+                        impl_def.start_line,
+                        impl_def.end_line,
+                    );
+                }
+            }
+        }
+        println!("\n{}", "-".repeat(60));
+    };
+
+    if trait_pairs.is_empty() {
+        println!("No similar trait implementations found with threshold {:.0}%", threshold * 100.0);
+    } else {
+        print_pairs(
+            "Similar trait implementations (candidates for a blanket impl, derive or macro_rules!):",
+            &trait_pairs,
+        );
+        println!("Total similar trait implementation pairs found: {}", trait_pairs.len());
+    }
+
+    if !inherent_pairs.is_empty() {
+        print_pairs("\nSimilar inherent impl blocks:", &inherent_pairs);
+        println!("Total similar impl block pairs found: {}", inherent_pairs.len());
+    }
+
+    Ok(trait_pairs.len() + inherent_pairs.len())
+}
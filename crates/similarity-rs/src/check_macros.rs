@@ -0,0 +1,224 @@
+#![allow(clippy::uninlined_format_args)]
+
+use crate::create_exclude_matcher;
+use crate::parallel::{load_files_parallel, RustFileData};
+use crate::rust_parser::{MacroGeneratedFn, RustParser};
+use rayon::prelude::*;
+use similarity_core::{
+    changed_lines::ChangedLines,
+    cli_file_utils::collect_files,
+    cli_output::{format_function_output, show_function_code},
+    language_parser::GenericFunctionDef,
+    tsed::{calculate_tsed, TSEDOptions},
+};
+use std::path::PathBuf;
+
+/// A hand-written function that a local `macro_rules!` arm already generates
+pub struct MacroDuplicate {
+    pub file: PathBuf,
+    pub function: GenericFunctionDef,
+    pub macro_file: PathBuf,
+    pub generated: MacroGeneratedFn,
+    pub similarity: f64,
+}
+
+fn is_test_function(function: &GenericFunctionDef) -> bool {
+    function.name.starts_with("test_") || function.decorators.iter().any(|d| d.contains("test"))
+}
+
+/// Check for functions written out by hand that a local `macro_rules!` arm already generates
+#[allow(clippy::too_many_arguments)]
+pub fn check_macros(
+    paths: Vec<String>,
+    threshold: f64,
+    rename_cost: f64,
+    extensions: Option<&Vec<String>>,
+    min_lines: u32,
+    min_tokens: Option<u32>,
+    no_size_penalty: bool,
+    skip_test: bool,
+    print: bool,
+    exclude_patterns: &[String],
+    changed: Option<&ChangedLines>,
+) -> anyhow::Result<usize> {
+    let default_extensions = vec!["rs"];
+    let exts: Vec<&str> =
+        extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
+
+    let mut files = collect_files(&paths, &exts)?;
+    if let Some(matcher) = create_exclude_matcher(exclude_patterns) {
+        files.retain(|file| !matcher.is_match(file));
+    }
+    if files.is_empty() {
+        println!("No Rust files found in the specified paths.");
+        return Ok(0);
+    }
+
+    println!("Checking {} files for functions a local macro generates...", files.len());
+
+    let mut options = TSEDOptions::default();
+    options.apted_options.rename_cost = rename_cost;
+    options.min_lines = min_lines;
+    options.min_tokens = min_tokens;
+    options.size_penalty = !no_size_penalty;
+    options.skip_test = skip_test;
+
+    let file_data = load_files_parallel(&files);
+    let duplicates = find_macro_generated_duplicates(&file_data, threshold, &options, changed);
+    if duplicates.is_empty() {
+        println!("\nNo functions duplicating macro output found!");
+        return Ok(0);
+    }
+
+    Ok(display_macro_duplicates(&duplicates, print))
+}
+
+/// Find functions written out by hand that match a function generated by a `macro_rules!` arm
+/// in the checked files, keeping the best match of each
+pub fn find_macro_generated_duplicates(
+    files: &[RustFileData],
+    threshold: f64,
+    options: &TSEDOptions,
+    changed: Option<&ChangedLines>,
+) -> Vec<MacroDuplicate> {
+    let templates: Vec<(&PathBuf, MacroGeneratedFn)> = files
+        .par_iter()
+        .map_init(RustParser::new, |parser, file| {
+            let Ok(parser) = parser else { return Vec::new() };
+            let generated = parser.extract_macro_generated_fns(&file.content).unwrap_or_default();
+            generated.into_iter().map(|generated| (&file.path, generated)).collect::<Vec<_>>()
+        })
+        .flatten()
+        .collect();
+    if templates.is_empty() {
+        return Vec::new();
+    }
+
+    // Generated code names things after metavariables, so only the shape is compared
+    let mut options = options.clone();
+    options.apted_options.compare_values = false;
+
+    // Trees can't cross threads, so each worker parses the generated functions itself
+    let init = || {
+        let mut parser = RustParser::new().ok()?;
+        let trees: Vec<_> = templates
+            .iter()
+            .map(|(_, generated)| parser.parse_function_item(&generated.code))
+            .collect();
+        Some((parser, trees))
+    };
+
+    let mut duplicates: Vec<MacroDuplicate> = files
+        .par_iter()
+        .filter(|file| changed.is_none_or(|c| c.touches_file(&file.path)))
+        .map_init(init, |state, file| {
+            let Some((parser, trees)) = state else { return Vec::new() };
+            let lines: Vec<&str> = file.content.lines().collect();
+            let mut duplicates = Vec::new();
+
+            for function in &file.functions {
+                if function.name.ends_with('!')
+                    || function.end_line - function.start_line + 1 < options.min_lines
+                    || (options.skip_test && is_test_function(function))
+                    || changed.is_some_and(|c| {
+                        !c.overlaps(&file.path, function.start_line, function.end_line)
+                    })
+                {
+                    continue;
+                }
+
+                let start = (function.start_line as usize).saturating_sub(1);
+                let end = (function.end_line as usize).min(lines.len());
+                let Some(tree) = lines
+                    .get(start..end)
+                    .and_then(|text| parser.parse_function_item(&text.join("\n")))
+                else {
+                    continue;
+                };
+                if options.min_tokens.is_some_and(|min| (tree.get_subtree_size() as u32) < min) {
+                    continue;
+                }
+
+                let best = templates
+                    .iter()
+                    .zip(trees.iter())
+                    .filter_map(|((macro_file, generated), generated_tree)| {
+                        let generated_tree = generated_tree.as_ref()?;
+                        Some((
+                            macro_file,
+                            generated,
+                            calculate_tsed(&tree, generated_tree, &options),
+                        ))
+                    })
+                    .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal));
+
+                if let Some((macro_file, generated, similarity)) = best {
+                    if similarity >= threshold {
+                        duplicates.push(MacroDuplicate {
+                            file: file.path.clone(),
+                            function: function.clone(),
+                            macro_file: (*macro_file).clone(),
+                            generated: generated.clone(),
+                            similarity,
+                        });
+                    }
+                }
+            }
+            duplicates
+        })
+        .flatten()
+        .collect();
+
+    duplicates.sort_by(|a, b| {
+        b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    duplicates
+}
+
+/// Display functions duplicating macro-generated code
+pub fn display_macro_duplicates(duplicates: &[MacroDuplicate], print: bool) -> usize {
+    if duplicates.is_empty() {
+        return 0;
+    }
+
+    println!("\nFunctions duplicating code a local macro generates:");
+    println!("{}", "-".repeat(60));
+
+    for dup in duplicates {
+        let file_path = dup.file.to_string_lossy();
+        let macro_path = dup.macro_file.to_string_lossy();
+        let function = &dup.function;
+        let generated = &dup.generated;
+
+        println!(
+            "  {}  ~  {} arm {} | {}:{}-{}",
+            format_function_output(
+                &file_path,
+                &function.name,
+                function.start_line,
+                function.end_line
+            ),
+            generated.macro_name,
+            generated.arm,
+            macro_path,
+            generated.start_line,
+            generated.end_line
+        );
+        println!("  Similarity: {:.2}%", dup.similarity * 100.0);
+
+        if print {
+            show_function_code(&file_path, &function.name, function.start_line, function.end_line);
+            show_function_code(
+                &macro_path,
+                &generated.macro_name,
+                generated.start_line,
+                generated.end_line,
+            );
+            println!();
+        }
+    }
+
+    println!("\nTotal functions duplicating macro output: {}", duplicates.len());
+
+    duplicates.len()
+}
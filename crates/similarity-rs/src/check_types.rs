@@ -3,7 +3,7 @@ use ignore::WalkBuilder;
 use rayon::prelude::*;
 use similarity_core::language_parser::{GenericTypeDef, LanguageParser};
 use similarity_core::tsed::{calculate_tsed, TSEDOptions};
-use similarity_core::{RustStructureComparator, ComparisonOptions};
+use similarity_core::{ChangedLines, ComparisonOptions, RustStructureComparator, RustTypeDef};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
@@ -29,18 +29,6 @@ fn generate_type_fingerprint(type_def: &GenericTypeDef) -> String {
     fingerprint_parts.join(",")
 }
 
-/// Group types by their fingerprints for efficient comparison
-fn group_types_by_fingerprint(types: &[ExtractedType]) -> HashMap<String, Vec<usize>> {
-    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
-
-    for (index, extracted_type) in types.iter().enumerate() {
-        let fingerprint = generate_type_fingerprint(&extracted_type.type_def);
-        groups.entry(fingerprint).or_insert_with(Vec::new).push(index);
-    }
-
-    groups
-}
-
 /// Check if two fingerprints are similar enough to warrant detailed comparison
 fn are_fingerprints_similar(fp1: &str, fp2: &str) -> bool {
     let parts1: HashMap<&str, &str> = fp1
@@ -59,9 +47,11 @@ fn are_fingerprints_similar(fp1: &str, fp2: &str) -> bool {
         })
         .collect();
 
-    // Check if they have the same kind
+    // Check if they have the same kind; structs and enums are compared with each other
+    // (an enum duplicated by a struct of `Option`s)
     if let (Some(kind1), Some(kind2)) = (parts1.get("kind"), parts2.get("kind")) {
-        if kind1 != kind2 {
+        let struct_or_enum = |kind: &str| kind == "struct" || kind == "enum";
+        if kind1 != kind2 && !(struct_or_enum(kind1) && struct_or_enum(kind2)) {
             return false;
         }
     }
@@ -82,10 +72,28 @@ fn are_fingerprints_similar(fp1: &str, fp2: &str) -> bool {
 
 struct ExtractedType {
     type_def: GenericTypeDef,
+    /// Field types, variant payloads and derives of structs and enums
+    rust_def: Option<RustTypeDef>,
     file_path: String,
     content: String,
 }
 
+impl ExtractedType {
+    fn kind_label(&self) -> &str {
+        self.rust_def.as_ref().map_or(&self.type_def.kind, |def| def.kind_label())
+    }
+
+    /// Named structs are compared as trees; enums, tuple structs and pairs across kinds by
+    /// their members
+    fn compared_as_tree(&self) -> bool {
+        match &self.rust_def {
+            Some(RustTypeDef::Struct(struct_def)) => !struct_def.is_tuple_struct,
+            Some(RustTypeDef::Enum(_)) => false,
+            None => true,
+        }
+    }
+}
+
 /// Compare two types using structure comparison framework
 fn compare_types_with_structure(
     type1: &ExtractedType,
@@ -97,7 +105,10 @@ fn compare_types_with_structure(
         return Ok(0.0);
     }
     
-    let result = comparator.compare_generic_types(&type1.type_def, &type2.type_def);
+    let result = match (&type1.rust_def, &type2.rust_def) {
+        (Some(def1), Some(def2)) => comparator.compare_type_defs(def1, def2),
+        _ => comparator.compare_generic_types(&type1.type_def, &type2.type_def),
+    };
     Ok(result.overall_similarity)
 }
 
@@ -164,6 +175,7 @@ pub fn check_types(
     print: bool,
     exclude_patterns: &[String],
     use_structure_comparison: bool,
+    changed: Option<&ChangedLines>,
 ) -> Result<usize> {
     let default_extensions = vec!["rs".to_string()];
     let exts = extensions.unwrap_or(&default_extensions);
@@ -236,14 +248,22 @@ pub fn check_types(
 
             let mut parser = RustParser::new().ok()?;
             let types = parser.extract_types(&content, &file_path).ok()?;
+            let mut rust_defs = parser.extract_type_defs(&content, &file_path).ok()?;
 
             Some(
                 types
                     .into_iter()
-                    .map(move |type_def| ExtractedType {
-                        type_def,
-                        file_path: file_path.clone(),
-                        content: content.clone(),
+                    .map(move |type_def| {
+                        let rust_def = rust_defs
+                            .iter()
+                            .position(|def| def.start_line() == type_def.start_line as usize)
+                            .map(|index| rust_defs.swap_remove(index));
+                        ExtractedType {
+                            type_def,
+                            rust_def,
+                            file_path: file_path.clone(),
+                            content: content.clone(),
+                        }
                     })
                     .collect::<Vec<_>>(),
             )
@@ -258,8 +278,9 @@ pub fn check_types(
 
     println!("Found {} types to analyze\n", extracted_types.len());
 
-    // Group types by fingerprint for optimization
-    let fingerprint_groups = group_types_by_fingerprint(&extracted_types);
+    // Fingerprints rule out pairs of different kinds or sizes before any comparison
+    let fingerprints: Vec<String> =
+        extracted_types.iter().map(|t| generate_type_fingerprint(&t.type_def)).collect();
 
     // Set up comparison options
     let mut options = TSEDOptions::default();
@@ -267,82 +288,52 @@ pub fn check_types(
     options.apted_options.rename_cost = 0.8;
     options.apted_options.compare_values = true;
 
+    let structure_options = ComparisonOptions {
+        name_weight: 0.3,
+        structure_weight: 0.7,
+        threshold,
+        ..Default::default()
+    };
+    let mut comparator = RustStructureComparator::with_options(structure_options);
+    let mut parser =
+        RustParser::new().map_err(|e| anyhow::anyhow!("Failed to create parser: {}", e))?;
+
     // Find similar types
     let mut similar_pairs = Vec::new();
-    
-    if use_structure_comparison {
-        // Use new structure comparison framework
-        let structure_options = ComparisonOptions {
-            name_weight: 0.3,
-            structure_weight: 0.7,
-            threshold,
-            ..Default::default()
-        };
-        let mut comparator = RustStructureComparator::with_options(structure_options);
-        
-        // Compare all type pairs
-        for i in 0..extracted_types.len() {
-            for j in (i + 1)..extracted_types.len() {
-                let type1 = &extracted_types[i];
-                let type2 = &extracted_types[j];
-                
-                if let Ok(similarity) = compare_types_with_structure(type1, type2, &mut comparator) {
-                    if similarity >= threshold {
-                        similar_pairs.push((i, j, similarity));
-                    }
-                }
-            }
-        }
-    } else {
-        // Use existing AST-based comparison
-        let mut parser =
-            RustParser::new().map_err(|e| anyhow::anyhow!("Failed to create parser: {}", e))?;
-
-        // First, compare types within the same fingerprint group
-        for (_fingerprint, indices) in &fingerprint_groups {
-            if indices.len() < 2 {
+    for i in 0..extracted_types.len() {
+        for j in (i + 1)..extracted_types.len() {
+            if !are_fingerprints_similar(&fingerprints[i], &fingerprints[j]) {
                 continue;
             }
 
-            for i in 0..indices.len() {
-                for j in (i + 1)..indices.len() {
-                    let idx1 = indices[i];
-                    let idx2 = indices[j];
-                    let type1 = &extracted_types[idx1];
-                    let type2 = &extracted_types[idx2];
-
-                    if let Ok(similarity) = compare_types(type1, type2, &mut parser, &options) {
-                        if similarity >= threshold {
-                            similar_pairs.push((idx1, idx2, similarity));
-                        }
-                    }
+            let type1 = &extracted_types[i];
+            let type2 = &extracted_types[j];
+            let similarity = if !use_structure_comparison
+                && type1.compared_as_tree()
+                && type2.compared_as_tree()
+            {
+                compare_types(type1, type2, &mut parser, &options)
+            } else {
+                compare_types_with_structure(type1, type2, &mut comparator)
+            };
+
+            if let Ok(similarity) = similarity {
+                if similarity >= threshold {
+                    similar_pairs.push((i, j, similarity));
                 }
             }
         }
+    }
 
-        // Then, compare types from different groups but with similar fingerprints
-        let fingerprints: Vec<_> = fingerprint_groups.keys().collect();
-        for i in 0..fingerprints.len() {
-            for j in (i + 1)..fingerprints.len() {
-                let fp1 = fingerprints[i];
-                let fp2 = fingerprints[j];
-
-                if are_fingerprints_similar(fp1, fp2) {
-                    for &idx1 in &fingerprint_groups[fp1] {
-                        for &idx2 in &fingerprint_groups[fp2] {
-                            let type1 = &extracted_types[idx1];
-                            let type2 = &extracted_types[idx2];
-
-                            if let Ok(similarity) = compare_types(type1, type2, &mut parser, &options) {
-                                if similarity >= threshold {
-                                    similar_pairs.push((idx1, idx2, similarity));
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    // Only pairs with a side overlapping changed lines
+    if let Some(changed) = changed {
+        let touches_changes = |extracted: &ExtractedType| {
+            let type_def = &extracted.type_def;
+            changed.overlaps(Path::new(&extracted.file_path), type_def.start_line, type_def.end_line)
+        };
+        similar_pairs.retain(|&(i, j, _)| {
+            touches_changes(&extracted_types[i]) || touches_changes(&extracted_types[j])
+        });
     }
 
     // Sort by similarity (descending)
@@ -362,14 +353,14 @@ pub fn check_types(
             println!("\nSimilarity: {:.2}%", similarity * 100.0);
             println!(
                 "  {} {} | {}:{}",
-                type1.type_def.kind,
+                type1.kind_label(),
                 type1.type_def.name,
                 get_relative_path(&type1.file_path),
                 type1.type_def.start_line
             );
             println!(
                 "  {} {} | {}:{}",
-                type2.type_def.kind,
+                type2.kind_label(),
                 type2.type_def.name,
                 get_relative_path(&type2.file_path),
                 type2.type_def.start_line
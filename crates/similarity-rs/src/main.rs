@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::Parser;
+use similarity_core::ChangedLines;
 
 mod check;
+mod check_impls;
+mod check_macros;
 mod check_types;
 mod parallel;
 mod rust_parser;
@@ -43,6 +46,10 @@ struct Cli {
     #[arg(long)]
     no_size_penalty: bool,
 
+    /// Reward consistent identifier renames (a→x everywhere) and penalize conflicting ones
+    #[arg(long)]
+    rename_consistency: bool,
+
     /// Filter functions by name (substring match)
     #[arg(long)]
     filter_function: Option<String>,
@@ -79,19 +86,38 @@ struct Cli {
     #[arg(long, default_value = "0.25")]
     overlap_size_tolerance: f64,
 
+    /// Only report duplicates involving lines changed since this git revision (e.g. origin/main)
+    #[arg(long, conflicts_with = "diff_from")]
+    changed_since: Option<String>,
+
+    /// Only report duplicates involving lines changed by this unified diff (e.g. a .patch file)
+    #[arg(long)]
+    diff_from: Option<std::path::PathBuf>,
+
     /// Exit with code 1 if duplicates are found
     #[arg(long)]
     fail_on_duplicates: bool,
 
-    /// Enable type similarity checking for structs and enums (experimental)
-    #[arg(long = "experimental-types")]
+    /// Enable type similarity checking for structs and enums: fields by type, enum variants by
+    /// payload shape, discriminant and derives, tuple structs and structs of `Option`s included
+    #[arg(long, alias = "experimental-types")]
     types: bool,
 
+    /// Check for similar impl blocks, reporting near-identical trait implementations across
+    /// types method by method
+    #[arg(long)]
+    impls: bool,
+
+    /// Report functions written out by hand that a local `macro_rules!` arm already generates
+    #[arg(long)]
+    macros: bool,
+
     /// Disable function similarity checking
     #[arg(long = "no-functions")]
     no_functions: bool,
     
-    /// Use new generalized structure comparison framework (experimental)
+    /// Compare named structs with the structure comparison framework instead of as syntax trees
+    /// (enums, tuple structs and pairs across kinds always are)
     #[arg(long)]
     use_structure_comparison: bool,
 }
@@ -99,13 +125,22 @@ struct Cli {
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    let changed = ChangedLines::from_cli_options(
+        cli.changed_since.as_deref(),
+        cli.diff_from.as_deref(),
+    )
+    .map_err(anyhow::Error::msg)?;
+
     let functions_enabled = !cli.no_functions;
     let types_enabled = cli.types;
+    let impls_enabled = cli.impls;
+    let macros_enabled = cli.macros;
     let overlap_enabled = cli.overlap;
 
     // Validate that at least one analyzer is enabled
-    if !functions_enabled && !types_enabled && !overlap_enabled {
-        eprintln!("Error: At least one analyzer must be enabled. Use --experimental-types to enable type checking, --experimental-overlap for overlap detection, or remove --no-functions.");
+    if !functions_enabled && !types_enabled && !impls_enabled && !macros_enabled && !overlap_enabled
+    {
+        eprintln!("Error: At least one analyzer must be enabled. Use --types to enable type checking, --impls for impl block checking, --macros for macro output checking, --experimental-overlap for overlap detection, or remove --no-functions.");
         return Err(anyhow::anyhow!("No analyzer enabled"));
     }
 
@@ -125,12 +160,14 @@ fn main() -> Result<()> {
             cli.min_lines.unwrap_or(3),
             cli.min_tokens,
             cli.no_size_penalty,
+            cli.rename_consistency,
             cli.print,
             !cli.no_fast,
             cli.filter_function.as_ref(),
             cli.filter_function_body.as_ref(),
             &cli.exclude,
             cli.skip_test,
+            changed.as_ref(),
         )?;
         total_duplicates += duplicate_count;
     }
@@ -149,12 +186,57 @@ fn main() -> Result<()> {
             cli.print,
             &cli.exclude,
             cli.use_structure_comparison,
+            changed.as_ref(),
         )?;
         total_duplicates += type_duplicate_count;
     }
 
+    // Run impl block analysis if enabled
+    if impls_enabled && (functions_enabled || types_enabled) {
+        println!("\n{separator}\n");
+    }
+
+    if impls_enabled {
+        println!("=== Impl Block Similarity ===");
+        let impl_duplicate_count = check_impls::check_impls(
+            cli.paths.clone(),
+            cli.threshold,
+            cli.rename_cost,
+            cli.extensions.as_ref(),
+            cli.min_lines.unwrap_or(3),
+            cli.no_size_penalty,
+            cli.print,
+            &cli.exclude,
+            changed.as_ref(),
+        )?;
+        total_duplicates += impl_duplicate_count;
+    }
+
+    // Run macro output analysis if enabled
+    if macros_enabled && (functions_enabled || types_enabled || impls_enabled) {
+        println!("\n{separator}\n");
+    }
+
+    if macros_enabled {
+        println!("=== Macro-Generated Duplicates ===");
+        let macro_duplicate_count = check_macros::check_macros(
+            cli.paths.clone(),
+            cli.threshold,
+            cli.rename_cost,
+            cli.extensions.as_ref(),
+            cli.min_lines.unwrap_or(3),
+            cli.min_tokens,
+            cli.no_size_penalty,
+            cli.skip_test,
+            cli.print,
+            &cli.exclude,
+            changed.as_ref(),
+        )?;
+        total_duplicates += macro_duplicate_count;
+    }
+
     // Run overlap analysis if enabled
-    if overlap_enabled && (functions_enabled || types_enabled) {
+    if overlap_enabled && (functions_enabled || types_enabled || impls_enabled || macros_enabled) {
         println!("\n{separator}\n");
     }
 
@@ -169,6 +251,7 @@ fn main() -> Result<()> {
             cli.overlap_max_window,
             cli.overlap_size_tolerance,
             &cli.exclude,
+            changed.as_ref(),
         )?;
         total_duplicates += overlap_duplicate_count;
     }
@@ -191,6 +274,7 @@ fn check_overlaps(
     max_window_size: u32,
     size_tolerance: f64,
     exclude_patterns: &[String],
+    changed: Option<&ChangedLines>,
 ) -> anyhow::Result<usize> {
     use crate::rust_parser::RustParser;
     use ignore::WalkBuilder;
@@ -292,8 +376,15 @@ fn check_overlaps(
         RustParser::new().map_err(|e| anyhow::anyhow!("Failed to create Rust parser: {}", e))?;
 
     // Find overlaps
-    let overlaps = find_overlaps_across_files_generic(&mut parser, &file_contents, &options)
+    let mut overlaps = find_overlaps_across_files_generic(&mut parser, &file_contents, &options)
         .map_err(|e| anyhow::anyhow!("Failed to find overlaps: {}", e))?;
+    if let Some(changed) = changed {
+        overlaps.retain(|o| {
+            let (source, target) = (o.overlap.source_lines, o.overlap.target_lines);
+            changed.overlaps(Path::new(&o.source_file), source.0, source.1)
+                || changed.overlaps(Path::new(&o.target_file), target.0, target.1)
+        });
+    }
 
     if overlaps.is_empty() {
         println!("\nNo code overlaps found!");
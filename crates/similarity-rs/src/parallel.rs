@@ -2,19 +2,19 @@
 
 use rayon::prelude::*;
 use similarity_core::{
+    changed_lines::ChangedLines,
     cli_parallel::{FileData, SimilarityResult},
     language_parser::{GenericFunctionDef, LanguageParser},
     tsed::TSEDOptions,
 };
+use similarity_rs::rust_parser::strip_metavariables;
 use std::fs;
 use std::path::PathBuf;
 
 /// Rust file with its content and extracted functions
-#[allow(dead_code)]
 pub type RustFileData = FileData<GenericFunctionDef>;
 
 /// Load and parse Rust files in parallel
-#[allow(dead_code)]
 pub fn load_files_parallel(files: &[PathBuf]) -> Vec<RustFileData> {
     files
         .par_iter()
@@ -56,9 +56,11 @@ pub fn check_within_file_duplicates_parallel(
     files: &[PathBuf],
     threshold: f64,
     options: &TSEDOptions,
+    changed: Option<&ChangedLines>,
 ) -> Vec<(PathBuf, Vec<SimilarityResult<GenericFunctionDef>>)> {
     files
         .par_iter()
+        .filter(|file| changed.is_none_or(|c| c.touches_file(file)))
         .filter_map(|file| match fs::read_to_string(file) {
             Ok(code) => {
                 let file_str = file.to_string_lossy();
@@ -80,14 +82,32 @@ pub fn check_within_file_duplicates_parallel(
                                         !f.decorators.iter().any(|d| d.contains("test"))
                                     });
                                 }
+                                // Only pairs with a side in changed code are compared
+                                let touched: Vec<bool> = functions
+                                    .iter()
+                                    .map(|f| {
+                                        changed.is_none_or(|c| {
+                                            c.overlaps(file, f.start_line, f.end_line)
+                                        })
+                                    })
+                                    .collect();
                                 let mut similar_pairs = Vec::new();
 
                                 // Compare all pairs within the file
                                 for i in 0..functions.len() {
                                     for j in (i + 1)..functions.len() {
+                                        if !touched[i] && !touched[j] {
+                                            continue;
+                                        }
+
                                         let func1 = &functions[i];
                                         let func2 = &functions[j];
 
+                                        // Arms of one macro are alternatives, not copies
+                                        if func1.name == func2.name && func1.name.ends_with('!') {
+                                            continue;
+                                        }
+
                                         // Skip if functions don't meet minimum requirements
                                         if func1.end_line - func1.start_line + 1 < options.min_lines
                                             || func2.end_line - func2.start_line + 1
@@ -168,6 +188,8 @@ pub fn check_within_file_duplicates_parallel(
 }
 
 /// Extract function body only (excluding signature)
+///
+/// `macro_rules!` arms are compared with their metavariables as plain identifiers.
 fn extract_function_body(lines: &[&str], func: &GenericFunctionDef) -> String {
     // Extract only the body (between body_start_line and body_end_line)
     // If body_start_line/body_end_line are not set, fall back to using the whole function
@@ -187,5 +209,10 @@ fn extract_function_body(lines: &[&str], func: &GenericFunctionDef) -> String {
         return String::new();
     }
 
-    lines[start_idx..end_idx].join("\n")
+    let body = lines[start_idx..end_idx].join("\n");
+    if func.name.ends_with('!') {
+        strip_metavariables(&body)
+    } else {
+        body
+    }
 }
@@ -2,6 +2,10 @@ use similarity_core::language_parser::{
     GenericFunctionDef, GenericTypeDef, Language, LanguageParser,
 };
 use similarity_core::tree::TreeNode;
+use similarity_core::{
+    RustEnumDef, RustFieldDef, RustImplDef, RustImplMethodDef, RustStructDef, RustTypeDef,
+    RustVariantDef, RustVariantType,
+};
 use std::error::Error;
 use std::rc::Rc;
 use tree_sitter::{Node, Parser};
@@ -39,6 +43,16 @@ impl RustParser {
                     functions.push(func_def);
                 }
             }
+            "macro_definition" => {
+                // Each `macro_rules!` arm is a unit of its own
+                let Some(name) = node.child_by_field_name("name") else { return };
+                let name = format!("{}!", &source[name.byte_range()]);
+                for rule in node.named_children(&mut node.walk()) {
+                    if let Some(arm) = macro_arm_definition(&name, rule) {
+                        functions.push(arm);
+                    }
+                }
+            }
             "impl_item" => {
                 // Extract methods from impl blocks
                 for child in node.children(&mut node.walk()) {
@@ -223,8 +237,13 @@ impl RustParser {
         }
     }
 
-    #[allow(clippy::only_used_in_recursion)]
     fn convert_node_to_tree(&mut self, node: Node, source: &str) -> Rc<TreeNode> {
+        self.convert_node_with_offset(node, source, 0)
+    }
+
+    /// Convert a node whose source starts `offset` bytes into the original source, so that spans
+    /// of re-parsed macro arguments point at the macro invocation
+    fn convert_node_with_offset(&mut self, node: Node, source: &str, offset: i64) -> Rc<TreeNode> {
         let label = node.kind().to_string();
 
         let value = match node.kind() {
@@ -249,17 +268,76 @@ impl RustParser {
 
         let node_id = self.node_id_counter;
         self.node_id_counter += 1;
-        let mut tree_node = TreeNode::new(label, value, node_id);
+        let mut tree_node = TreeNode::new(label, value, node_id).with_span(
+            (node.start_byte() as i64 + offset) as u32,
+            (node.end_byte() as i64 + offset) as u32,
+        );
 
         for child in node.children(&mut node.walk()) {
-            if !child.is_extra() {
-                tree_node.add_child(self.convert_node_to_tree(child, source));
+            if child.is_extra() {
+                continue;
+            }
+            // `format!(..)`, `vec![..]`, `assert_eq!(..)`: arguments that parse as expressions are
+            // compared as such instead of as a flat list of tokens
+            if node.kind() == "macro_invocation" && child.kind() == "token_tree" {
+                if let Some(arguments) = self.convert_macro_arguments(child, source, offset) {
+                    tree_node.add_child(arguments);
+                    continue;
+                }
             }
+            tree_node.add_child(self.convert_node_with_offset(child, source, offset));
         }
 
         Rc::new(tree_node)
     }
 
+    /// Re-parse the token tree of a macro invocation as call arguments, or as array elements for
+    /// `vec![value; count]`; `None` when it is neither (custom DSLs)
+    fn convert_macro_arguments(
+        &mut self,
+        token_tree: Node,
+        source: &str,
+        offset: i64,
+    ) -> Option<Rc<TreeNode>> {
+        let text = &source[token_tree.byte_range()];
+        let inner = text.get(1..text.len().checked_sub(1)?)?;
+        if inner.trim().is_empty() {
+            return None;
+        }
+
+        for (prefix, suffix, list_kind) in
+            [("fn __m() { __m(", "); }", "arguments"), ("fn __m() { [", "]; }", "array_expression")]
+        {
+            let wrapped = format!("{prefix}{inner}{suffix}");
+            let Some(tree) = self.parser.parse(&wrapped, None) else { continue };
+            if tree.root_node().has_error() {
+                continue;
+            }
+            let Some(list) = find_first_of_kind(tree.root_node(), list_kind) else { continue };
+
+            let list_offset = offset + token_tree.start_byte() as i64 + 1 - prefix.len() as i64;
+            let node_id = self.node_id_counter;
+            self.node_id_counter += 1;
+            let mut arguments = TreeNode::new(list_kind.to_string(), String::new(), node_id)
+                .with_span(
+                    (token_tree.start_byte() as i64 + offset) as u32,
+                    (token_tree.end_byte() as i64 + offset) as u32,
+                );
+            for argument in list.named_children(&mut list.walk()) {
+                if !argument.is_extra() {
+                    arguments.add_child(self.convert_node_with_offset(
+                        argument,
+                        &wrapped,
+                        list_offset,
+                    ));
+                }
+            }
+            return Some(Rc::new(arguments));
+        }
+
+        None
+    }
+
     fn extract_types_from_node<'a>(
         &self,
         node: Node<'a>,
@@ -393,6 +471,454 @@ impl RustParser {
             None
         }
     }
+
+    /// Extract `impl` blocks with their methods as comparison trees
+    pub fn extract_impl_defs(
+        &mut self,
+        source: &str,
+        file_path: &str,
+    ) -> Result<Vec<RustImplDef>, Box<dyn Error + Send + Sync>> {
+        let tree = self.parser.parse(source, None).ok_or_else(|| {
+            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "Failed to parse source"))
+                as Box<dyn Error + Send + Sync>
+        })?;
+
+        let mut impls = Vec::new();
+        self.extract_impls_from_node(tree.root_node(), source, file_path, &mut impls);
+        Ok(impls)
+    }
+
+    fn extract_impls_from_node(
+        &mut self,
+        node: Node,
+        source: &str,
+        file_path: &str,
+        impls: &mut Vec<RustImplDef>,
+    ) {
+        if node.kind() == "impl_item" {
+            if let Some(impl_def) = self.extract_impl_definition(node, source, file_path) {
+                impls.push(impl_def);
+            }
+        }
+
+        for child in node.children(&mut node.walk()) {
+            self.extract_impls_from_node(child, source, file_path, impls);
+        }
+    }
+
+    fn extract_impl_definition(
+        &mut self,
+        node: Node,
+        source: &str,
+        file_path: &str,
+    ) -> Option<RustImplDef> {
+        let text = |n: Node| source[n.byte_range()].to_string();
+        let self_type = text(node.child_by_field_name("type")?);
+        let trait_name = node.child_by_field_name("trait").map(text);
+        let generics = node
+            .child_by_field_name("type_parameters")
+            .map(|params| params.named_children(&mut params.walk()).map(text).collect())
+            .unwrap_or_default();
+
+        let mut methods = Vec::new();
+        let mut associated_items = Vec::new();
+        if let Some(body) = node.child_by_field_name("body") {
+            for item in body.named_children(&mut body.walk()) {
+                match item.kind() {
+                    "function_item" => {
+                        let Some(name) = item.child_by_field_name("name") else { continue };
+                        let parameters =
+                            item.child_by_field_name("parameters").map(text).unwrap_or_default();
+                        let signature = match item.child_by_field_name("return_type") {
+                            Some(return_type) => format!("{} -> {}", parameters, text(return_type)),
+                            None => parameters,
+                        };
+                        self.node_id_counter = 0;
+                        methods.push(RustImplMethodDef {
+                            name: text(name),
+                            signature,
+                            tree: self.convert_node_to_tree(item, source),
+                            start_line: item.start_position().row + 1,
+                            end_line: item.end_position().row + 1,
+                        });
+                    }
+                    "type_item" | "const_item" => {
+                        if let Some(name) = item.child_by_field_name("name") {
+                            associated_items.push(text(name));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Some(RustImplDef {
+            self_type,
+            trait_name,
+            generics,
+            methods,
+            associated_items,
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+            file_path: file_path.to_string(),
+        })
+    }
+
+    /// Extract structs and enums with field types, variant payloads, discriminants and derives
+    pub fn extract_type_defs(
+        &mut self,
+        source: &str,
+        file_path: &str,
+    ) -> Result<Vec<RustTypeDef>, Box<dyn Error + Send + Sync>> {
+        let tree = self.parser.parse(source, None).ok_or_else(|| {
+            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "Failed to parse source"))
+                as Box<dyn Error + Send + Sync>
+        })?;
+
+        let mut types = Vec::new();
+        collect_type_defs(tree.root_node(), source, file_path, &mut types);
+        Ok(types)
+    }
+
+    /// Tree of the first function item in `text`, as compared with macro-generated functions
+    pub fn parse_function_item(&mut self, text: &str) -> Option<Rc<TreeNode>> {
+        let tree = self.parser.parse(text, None)?;
+        let function = find_first_function(tree.root_node())?;
+        self.node_id_counter = 0;
+        Some(self.convert_node_to_tree(function, text))
+    }
+
+    /// Functions written out in `macro_rules!` arms, such as the getters of a `getter!` macro
+    pub fn extract_macro_generated_fns(
+        &mut self,
+        source: &str,
+    ) -> Result<Vec<MacroGeneratedFn>, Box<dyn Error + Send + Sync>> {
+        let tree = self.parser.parse(source, None).ok_or_else(|| {
+            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "Failed to parse source"))
+                as Box<dyn Error + Send + Sync>
+        })?;
+
+        let mut rules = Vec::new();
+        collect_macro_rules(tree.root_node(), source, &mut rules);
+
+        let mut generated = Vec::new();
+        for (macro_name, arm, rule) in rules {
+            let Some(transcriber) = rule.child_by_field_name("right") else { continue };
+            let text = &source[transcriber.byte_range()];
+            let Some(inner) = text.get(1..text.len().saturating_sub(1)) else { continue };
+            let expanded = strip_metavariables(inner);
+
+            let Some(expanded_tree) = self.parser.parse(&expanded, None) else { continue };
+            let mut fn_nodes = Vec::new();
+            collect_function_items(expanded_tree.root_node(), &mut fn_nodes);
+            for fn_node in fn_nodes {
+                generated.push(MacroGeneratedFn {
+                    macro_name: macro_name.clone(),
+                    arm,
+                    start_line: rule.start_position().row as u32 + 1,
+                    end_line: rule.end_position().row as u32 + 1,
+                    code: expanded[fn_node.byte_range()].to_string(),
+                });
+            }
+        }
+        Ok(generated)
+    }
+}
+
+fn collect_type_defs(node: Node, source: &str, file_path: &str, types: &mut Vec<RustTypeDef>) {
+    match node.kind() {
+        "struct_item" => types.extend(struct_type_def(node, source, file_path)),
+        "enum_item" => types.extend(enum_type_def(node, source, file_path)),
+        _ => {
+            for child in node.children(&mut node.walk()) {
+                collect_type_defs(child, source, file_path, types);
+            }
+        }
+    }
+}
+
+/// Derives and other attributes written above an item
+fn item_attributes(node: Node, source: &str) -> (Vec<String>, Vec<String>) {
+    let mut attribute_texts = Vec::new();
+    let mut sibling = node.prev_sibling();
+    while let Some(attribute) = sibling {
+        match attribute.kind() {
+            "attribute_item" => {
+                let text = source[attribute.byte_range()].trim_start_matches("#[");
+                attribute_texts.push(text.strip_suffix(']').unwrap_or(text).trim());
+            }
+            "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        sibling = attribute.prev_sibling();
+    }
+
+    let mut derives = Vec::new();
+    let mut attributes = Vec::new();
+    for text in attribute_texts.into_iter().rev() {
+        match text.strip_prefix("derive(").and_then(|t| t.strip_suffix(')')) {
+            Some(list) => derives
+                .extend(list.split(',').map(|d| d.trim().to_string()).filter(|d| !d.is_empty())),
+            None => attributes.push(text.to_string()),
+        }
+    }
+    (derives, attributes)
+}
+
+fn item_generics(node: Node, source: &str) -> Vec<String> {
+    node.child_by_field_name("type_parameters")
+        .map(|params| {
+            params
+                .named_children(&mut params.walk())
+                .map(|param| source[param.byte_range()].to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn item_visibility(node: Node, source: &str) -> Option<String> {
+    node.children(&mut node.walk())
+        .find(|child| child.kind() == "visibility_modifier")
+        .map(|visibility| source[visibility.byte_range()].to_string())
+}
+
+/// Fields of a `{ .. }` list by name, or of a `( .. )` list by position (`0`, `1`, ..)
+fn field_defs(list: Node, source: &str) -> Vec<RustFieldDef> {
+    let text = |n: Node| source[n.byte_range()].to_string();
+    match list.kind() {
+        "field_declaration_list" => list
+            .named_children(&mut list.walk())
+            .filter(|field| field.kind() == "field_declaration")
+            .filter_map(|field| {
+                Some(RustFieldDef {
+                    name: text(field.child_by_field_name("name")?),
+                    field_type: text(field.child_by_field_name("type")?),
+                    visibility: item_visibility(field, source),
+                })
+            })
+            .collect(),
+        "ordered_field_declaration_list" => {
+            let mut cursor = list.walk();
+            list.children_by_field_name("type", &mut cursor)
+                .enumerate()
+                .map(|(index, field_type)| RustFieldDef {
+                    name: index.to_string(),
+                    field_type: text(field_type),
+                    visibility: None,
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn struct_type_def(node: Node, source: &str, file_path: &str) -> Option<RustTypeDef> {
+    let name = source[node.child_by_field_name("name")?.byte_range()].to_string();
+    let body = node.child_by_field_name("body");
+    let (derives, attributes) = item_attributes(node, source);
+
+    Some(RustTypeDef::Struct(RustStructDef {
+        name,
+        fields: body.map(|body| field_defs(body, source)).unwrap_or_default(),
+        generics: item_generics(node, source),
+        derives,
+        attributes,
+        visibility: item_visibility(node, source),
+        is_tuple_struct: body.is_some_and(|body| body.kind() == "ordered_field_declaration_list"),
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        file_path: file_path.to_string(),
+    }))
+}
+
+fn enum_type_def(node: Node, source: &str, file_path: &str) -> Option<RustTypeDef> {
+    let name = source[node.child_by_field_name("name")?.byte_range()].to_string();
+    let (derives, attributes) = item_attributes(node, source);
+
+    let mut variants = Vec::new();
+    if let Some(body) = node.child_by_field_name("body") {
+        for variant in body.named_children(&mut body.walk()) {
+            if variant.kind() != "enum_variant" {
+                continue;
+            }
+            let Some(variant_name) = variant.child_by_field_name("name") else { continue };
+            let variant_type = match variant.child_by_field_name("body") {
+                Some(list) if list.kind() == "field_declaration_list" => {
+                    RustVariantType::Struct(field_defs(list, source))
+                }
+                Some(list) => RustVariantType::Tuple(
+                    field_defs(list, source).into_iter().map(|field| field.field_type).collect(),
+                ),
+                None => RustVariantType::Unit,
+            };
+            variants.push(RustVariantDef {
+                name: source[variant_name.byte_range()].to_string(),
+                variant_type,
+                discriminant: variant
+                    .child_by_field_name("value")
+                    .map(|value| source[value.byte_range()].to_string()),
+            });
+        }
+    }
+
+    Some(RustTypeDef::Enum(RustEnumDef {
+        name,
+        variants,
+        generics: item_generics(node, source),
+        derives,
+        attributes,
+        visibility: item_visibility(node, source),
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        file_path: file_path.to_string(),
+    }))
+}
+
+/// A function that a `macro_rules!` arm generates
+#[derive(Debug, Clone)]
+pub struct MacroGeneratedFn {
+    /// `getter!`
+    pub macro_name: String,
+    /// 1-based index of the arm
+    pub arm: usize,
+    /// Lines of the arm
+    pub start_line: u32,
+    pub end_line: u32,
+    /// The function item with its metavariables stripped, for [`RustParser::parse_function_item`]
+    pub code: String,
+}
+
+/// A `macro_rules!` arm as a unit named after its macro (`getter!`), its body being the
+/// transcriber
+fn macro_arm_definition(name: &str, rule: Node) -> Option<GenericFunctionDef> {
+    if rule.kind() != "macro_rule" {
+        return None;
+    }
+    let transcriber = rule.child_by_field_name("right")?;
+    let start_line = rule.start_position().row as u32 + 1;
+    let end_line = rule.end_position().row as u32 + 1;
+
+    // Inside the delimiters of a multi-line transcriber, or the whole arm
+    let (body_start_line, body_end_line) = match (
+        transcriber.start_position().row as u32 + 2,
+        transcriber.end_position().row as u32,
+    ) {
+        (body_start, body_end) if body_start <= body_end => (body_start, body_end),
+        _ => (start_line, end_line),
+    };
+
+    Some(GenericFunctionDef {
+        name: name.to_string(),
+        start_line,
+        end_line,
+        body_start_line,
+        body_end_line,
+        parameters: Vec::new(),
+        is_method: false,
+        class_name: None,
+        is_async: false,
+        is_generator: false,
+        decorators: Vec::new(),
+    })
+}
+
+/// `(macro name, 1-based arm index, macro_rule node)` of every `macro_rules!` arm
+fn collect_macro_rules<'a>(
+    node: Node<'a>,
+    source: &str,
+    rules: &mut Vec<(String, usize, Node<'a>)>,
+) {
+    if node.kind() == "macro_definition" {
+        let Some(name) = node.child_by_field_name("name") else { return };
+        let name = format!("{}!", &source[name.byte_range()]);
+        let mut cursor = node.walk();
+        let arms = node.named_children(&mut cursor).filter(|n| n.kind() == "macro_rule");
+        for (index, rule) in arms.enumerate() {
+            rules.push((name.clone(), index + 1, rule));
+        }
+        return;
+    }
+
+    for child in node.children(&mut node.walk()) {
+        collect_macro_rules(child, source, rules);
+    }
+}
+
+fn collect_function_items<'a>(node: Node<'a>, functions: &mut Vec<Node<'a>>) {
+    if node.kind() == "function_item" {
+        functions.push(node);
+        return;
+    }
+
+    for child in node.children(&mut node.walk()) {
+        collect_function_items(child, functions);
+    }
+}
+
+/// Rewrite a `macro_rules!` transcriber as plain Rust: `$name` becomes `name`, and a repetition
+/// `$( ... ),*` is written out once
+pub fn strip_metavariables(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '$' {
+            output.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        match chars.get(i + 1) {
+            Some('(') => {
+                // Find the closing parenthesis of the repetition
+                let mut depth = 0;
+                let mut end = i + 1;
+                while end < chars.len() {
+                    match chars[end] {
+                        '(' => depth += 1,
+                        ')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    end += 1;
+                }
+                let inner: String = chars[(i + 2).min(end)..end.min(chars.len())].iter().collect();
+                output.push_str(&strip_metavariables(&inner));
+
+                // Skip the separator and the `*`, `+` or `?` operator
+                i = end + 1;
+                if matches!(chars.get(i), Some('*' | '+' | '?')) {
+                    i += 1;
+                } else if matches!(chars.get(i + 1), Some('*' | '+' | '?')) {
+                    i += 2;
+                }
+            }
+            Some(c) if c.is_alphabetic() || *c == '_' => i += 1,
+            _ => {
+                output.push('$');
+                i += 1;
+            }
+        }
+    }
+    output
+}
+
+fn find_first_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    if node.kind() == kind {
+        return Some(node);
+    }
+
+    for child in node.children(&mut node.walk()) {
+        if let Some(found) = find_first_of_kind(child, kind) {
+            return Some(found);
+        }
+    }
+
+    None
 }
 
 fn find_first_function(node: Node) -> Option<Node> {
@@ -514,6 +1040,7 @@ impl Default for RustParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use similarity_core::RustStructureComparator;
 
     #[test]
     fn test_rust_functions() {
@@ -601,4 +1128,218 @@ type Distance = f64;
         // assert_eq!(types[2].name, "Distance");
         // assert_eq!(types[2].kind, "type_alias");
     }
+
+    #[test]
+    fn test_rust_impl_defs() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+impl<T: Copy> std::ops::Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point { x: self.x + other.x }
+    }
+}
+
+impl Point<f64> {
+    const ORIGIN: Self = Point { x: 0.0 };
+
+    fn norm(&self) -> f64 {
+        self.x.abs()
+    }
+}
+"#;
+
+        let impls = parser.extract_impl_defs(source, "test.rs").unwrap();
+        assert_eq!(impls.len(), 2);
+
+        assert_eq!(impls[0].header(), "impl std::ops::Add for Point<T>");
+        assert_eq!(impls[0].trait_base_name(), Some("Add"));
+        assert_eq!(impls[0].generics, vec!["T: Copy"]);
+        assert_eq!(impls[0].associated_items, vec!["Output"]);
+        assert_eq!(impls[0].methods[0].name, "add");
+        assert_eq!(impls[0].methods[0].signature, "(self, other: Self) -> Self");
+        assert_eq!((impls[0].methods[0].start_line, impls[0].methods[0].end_line), (5, 7));
+
+        assert_eq!(impls[1].header(), "impl Point<f64>");
+        assert_eq!(impls[1].trait_base_name(), None);
+        assert_eq!(impls[1].associated_items, vec!["ORIGIN"]);
+        assert_eq!(impls[1].methods[0].signature, "(&self) -> f64");
+    }
+
+    fn find_label(node: &Rc<TreeNode>, label: &str) -> Option<Rc<TreeNode>> {
+        if node.label == label {
+            return Some(node.clone());
+        }
+        node.children.iter().find_map(|child| find_label(child, label))
+    }
+
+    #[test]
+    fn test_macro_arguments_are_parsed_as_expressions() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"fn report(a: i32, b: i32) {
+    let message = format!("{} items", a + b);
+    let custom = html! { <div class="x"></div> };
+}"#;
+
+        let tree = parser.parse(source, "test.rs").unwrap();
+        let arguments = find_label(&tree, "arguments").expect("format! arguments");
+        assert_eq!(arguments.children[0].label, "string_literal");
+        let sum = find_label(&arguments, "binary_expression").expect("a + b");
+        let (start, end) = sum.span.unwrap();
+        assert_eq!(&source[start as usize..end as usize], "a + b");
+
+        // A custom DSL stays a token tree
+        assert!(find_label(&tree, "token_tree").is_some());
+    }
+
+    #[test]
+    fn test_macro_rules_arms() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+macro_rules! getter {
+    ($name:ident, $field:ident) => {
+        pub fn $name(&self) -> &str {
+            &self.$field
+        }
+    };
+    ($($name:ident),+) => {
+        $(pub fn $name(&self) -> &str { &self.$name })+
+    };
+}
+"#;
+
+        let arms = parser.extract_functions(source, "test.rs").unwrap();
+        assert_eq!(arms.len(), 2);
+        assert_eq!(arms[0].name, "getter!");
+        assert_eq!((arms[0].start_line, arms[0].end_line), (3, 7));
+        assert_eq!((arms[0].body_start_line, arms[0].body_end_line), (4, 6));
+        assert_eq!((arms[1].body_start_line, arms[1].body_end_line), (9, 9));
+
+        let generated = parser.extract_macro_generated_fns(source).unwrap();
+        assert_eq!(generated.len(), 2);
+        assert_eq!((generated[1].macro_name.as_str(), generated[1].arm), ("getter!", 2));
+        assert_eq!(generated[1].code, "pub fn name(&self) -> &str { &self.name }");
+
+        assert_eq!(
+            strip_metavariables("$(pub fn $name(&self) -> &str { &self.$name })+"),
+            "pub fn name(&self) -> &str { &self.name }"
+        );
+        assert_eq!(strip_metavariables("vec![$($x),*]"), "vec![x]");
+    }
+
+    #[test]
+    fn test_rust_type_defs() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+/// A shape
+#[derive(Debug, Clone)]
+#[serde(tag = "kind")]
+pub enum Shape<T> {
+    Circle(T),
+    Rect { w: T, h: T },
+    Empty = 3,
+}
+
+pub struct Meters(pub f64, u8);
+"#;
+
+        let types = parser.extract_type_defs(source, "shapes.rs").unwrap();
+        assert_eq!(types.len(), 2);
+
+        let RustTypeDef::Enum(shape) = &types[0] else { panic!("expected an enum") };
+        assert_eq!(shape.derives, vec!["Debug", "Clone"]);
+        assert_eq!(shape.attributes, vec![r#"serde(tag = "kind")"#]);
+        assert_eq!(shape.generics, vec!["T"]);
+        assert_eq!(shape.visibility.as_deref(), Some("pub"));
+        assert_eq!((shape.start_line, shape.end_line), (5, 9));
+        let payloads: Vec<String> =
+            shape.variants.iter().map(|v| v.variant_type.describe()).collect();
+        assert_eq!(payloads, vec!["(T)", "{ w: T, h: T }", "unit"]);
+        assert_eq!(shape.variants[2].discriminant.as_deref(), Some("3"));
+
+        let RustTypeDef::Struct(meters) = &types[1] else { panic!("expected a struct") };
+        assert!(meters.is_tuple_struct);
+        let fields: Vec<(&str, &str)> =
+            meters.fields.iter().map(|f| (f.name.as_str(), f.field_type.as_str())).collect();
+        assert_eq!(fields, vec![("0", "f64"), ("1", "u8")]);
+        assert_eq!(types[1].kind_label(), "tuple struct");
+    }
+
+    #[test]
+    fn test_parsed_type_defs_compared_across_kinds() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+#[derive(Debug)]
+enum Shape {
+    Circle(f64),
+    Rect(f64, f64),
+    Empty,
+}
+
+#[derive(Debug)]
+enum Figure {
+    Nothing,
+    Round(f64),
+    Box(f64, f64),
+}
+
+struct Point { x: f64, y: f64 }
+
+struct Point2(f64, f64);
+
+enum Lookup {
+    Hit(HashMap<String, u32>, f64),
+    Miss,
+}
+
+struct LookupParts {
+    hit: Option<(HashMap<String, u32>, f64)>,
+    miss: Option<()>,
+}
+
+enum Level { Low, High = 10 }
+
+enum Grade { Low, High }
+"#;
+
+        // `check_types` pairs each generic type with its definition by start line
+        let generic_types = parser.extract_types(source, "shapes.rs").unwrap();
+        let mut type_defs = parser.extract_type_defs(source, "shapes.rs").unwrap();
+        assert_eq!(generic_types.len(), type_defs.len());
+        let defs: Vec<RustTypeDef> = generic_types
+            .iter()
+            .map(|type_def| {
+                let index = type_defs
+                    .iter()
+                    .position(|def| def.start_line() == type_def.start_line as usize)
+                    .unwrap();
+                let def = type_defs.swap_remove(index);
+                assert_eq!(def.name(), type_def.name);
+                def
+            })
+            .collect();
+        let def = |name: &str| defs.iter().find(|def| def.name() == name).unwrap();
+
+        let mut comparator = RustStructureComparator::new();
+        let result = comparator.compare_type_defs(def("Shape"), def("Figure"));
+        assert!(result.member_similarity > 0.7, "similarity: {}", result.member_similarity);
+        assert_eq!(result.member_matches.len(), 3);
+        assert_eq!(result.member_matches[0].member2, "Round");
+
+        let result = comparator.compare_type_defs(def("Point"), def("Point2"));
+        assert!(result.member_similarity > 0.99, "similarity: {}", result.member_similarity);
+
+        // Commas inside the payload's generics don't split the `Option` tuple
+        let result = comparator.compare_type_defs(def("LookupParts"), def("Lookup"));
+        assert!(result.member_similarity > 0.9, "similarity: {}", result.member_similarity);
+
+        // Fields that are not `Option`s don't stand for variants
+        assert!(comparator.compare_type_defs(def("Shape"), def("Point")).member_similarity < 0.2);
+
+        assert!(
+            comparator.compare_type_defs(def("Level"), def("Grade")).overall_similarity
+                < comparator.compare_type_defs(def("Grade"), def("Grade")).overall_similarity
+        );
+    }
 }
@@ -0,0 +1,111 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_detect_similar_trait_impls() {
+    let dir = tempdir().unwrap();
+
+    let meters = r#"
+pub struct Meters(f64);
+
+impl fmt::Display for Meters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 0.0 {
+            return write!(f, "-{:.2}", -self.0);
+        }
+        write!(f, "{:.2} m", self.0)
+    }
+}
+
+impl Default for Meters {
+    fn default() -> Self {
+        let value = std::env::var("DEFAULT_LENGTH").ok().and_then(|v| v.parse().ok());
+        Meters(value.unwrap_or(0.0))
+    }
+}
+"#;
+    let feet = r#"
+pub struct Feet(f64);
+
+impl fmt::Display for Feet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 0.0 {
+            return write!(f, "-{:.2}", -self.0);
+        }
+        write!(f, "{:.2} ft", self.0)
+    }
+}
+"#;
+    fs::write(dir.path().join("meters.rs"), meters).unwrap();
+    fs::write(dir.path().join("feet.rs"), feet).unwrap();
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--impls")
+        .arg("--no-functions")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("=== Impl Block Similarity ==="))
+        .stdout(predicate::str::contains("Found 3 impl blocks (3 trait implementations)"))
+        .stdout(predicate::str::contains("impl fmt::Display for Meters"))
+        .stdout(predicate::str::contains("impl fmt::Display for Feet"))
+        .stdout(predicate::str::is_match(r"100\.00%  fmt \(L5-10\) ~ fmt \(L5-10\)").unwrap())
+        .stdout(predicate::str::contains("impl Default for Meters |").not())
+        .stdout(predicate::str::contains("Total similar trait implementation pairs found: 1"));
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--impls")
+        .arg("--no-functions")
+        .arg("--exclude")
+        .arg("**/feet.rs")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Found 2 impl blocks (2 trait implementations)"))
+        .stdout(predicate::str::contains("No similar trait implementations found"));
+}
+
+#[test]
+fn test_split_impl_blocks_are_not_reported() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("queue.rs");
+
+    let content = r#"
+impl Queue {
+    pub fn push(&mut self, item: Item) {
+        self.items.push(item);
+        self.len += 1;
+    }
+}
+
+impl Queue {
+    pub fn push_front(&mut self, item: Item) {
+        self.items.insert(0, item);
+        self.len += 1;
+    }
+}
+
+impl Stack {
+    pub fn push(&mut self, item: Item) {
+        self.items.push(item);
+        self.len += 1;
+    }
+}
+"#;
+    fs::write(&file, content).unwrap();
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(&file)
+        .arg("--impls")
+        .arg("--no-functions")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Similar inherent impl blocks:"))
+        .stdout(predicate::str::contains("impl Stack"))
+        .stdout(predicate::str::contains("Total similar impl block pairs found: 2"));
+}
@@ -0,0 +1,123 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+const GETTER_CODE: &str = r#"
+macro_rules! getter {
+    ($name:ident, $field:ident, $ty:ty) => {
+        pub fn $name(&self) -> &$ty {
+            let value = &self.$field;
+            value
+        }
+    };
+}
+
+pub struct User {
+    name: String,
+    email: String,
+}
+
+impl User {
+    getter!(name, name, String);
+
+    pub fn email(&self) -> &String {
+        let value = &self.email;
+        value
+    }
+}
+"#;
+
+#[test]
+fn test_function_duplicating_macro_output() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("user.rs"), GETTER_CODE).unwrap();
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--macros")
+        .arg("--min-lines")
+        .arg("3")
+        .arg("--no-size-penalty")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Functions duplicating code a local macro generates:"))
+        .stdout(
+            predicate::str::is_match(r"user\.rs:19-22 email  ~  getter! arm 1 \| .*user\.rs:3-8")
+                .unwrap(),
+        )
+        .stdout(predicate::str::contains("Total functions duplicating macro output: 1"));
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--macros")
+        .arg("--no-functions")
+        .arg("--exclude")
+        .arg("**/user.rs")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No Rust files found in the specified paths."));
+}
+
+#[test]
+fn test_macro_output_check_is_opt_in() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("user.rs"), GETTER_CODE).unwrap();
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--min-lines")
+        .arg("3")
+        .arg("--no-size-penalty")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("=== Macro-Generated Duplicates ===").not())
+        .stdout(predicate::str::contains("duplicating code a local macro generates").not());
+}
+
+#[test]
+fn test_macro_arm_compared_with_function() {
+    let dir = tempdir().unwrap();
+
+    let code = r#"
+macro_rules! clamp_score {
+    ($($item:expr),+) => {{
+        let mut total = 0;
+        $(
+            if $item > 10 {
+                total += $item * 2;
+            } else {
+                total -= 1;
+            }
+        )+
+        total
+    }};
+}
+
+fn score(input: i32) -> i32 {
+    let mut total = 0;
+    if input > 10 {
+        total += input * 2;
+    } else {
+        total -= 1;
+    }
+    total
+}
+"#;
+    fs::write(dir.path().join("score.rs"), code).unwrap();
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--threshold")
+        .arg("0.95")
+        .arg("--no-size-penalty")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("function clamp_score!"))
+        .stdout(predicate::str::contains("function score"))
+        .stdout(predicate::str::contains("Total duplicate pairs found: 1"));
+}
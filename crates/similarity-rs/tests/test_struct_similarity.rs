@@ -50,7 +50,7 @@ struct Customer {
     Command::cargo_bin("similarity-rs")
         .unwrap()
         .arg(dir.path())
-        .arg("--experimental-types")
+        .arg("--types")
         .arg("--no-functions")
         .arg("--threshold")
         .arg("0.7")
@@ -94,14 +94,17 @@ enum TaskStatus {
     Command::cargo_bin("similarity-rs")
         .unwrap()
         .arg(dir.path())
-        .arg("--experimental-types")
+        .arg("--types")
         .arg("--no-functions")
         .arg("--threshold")
         .arg("0.7")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Status"))
-        .stdout(predicate::str::contains("State"));
+        .stdout(predicate::str::contains("enum Status"))
+        .stdout(predicate::str::contains("enum State"))
+        // Status ~ State and Status ~ TaskStatus; State ~ TaskStatus shares neither variant
+        // nor type names
+        .stdout(predicate::str::contains("Total similar type pairs found: 2"));
 }
 
 #[test]
@@ -134,7 +137,7 @@ struct ServerResponse<T> {
     Command::cargo_bin("similarity-rs")
         .unwrap()
         .arg(dir.path())
-        .arg("--experimental-types")
+        .arg("--types")
         .arg("--no-functions")
         .arg("--threshold")
         .arg("0.7")
@@ -172,7 +175,7 @@ struct Type{} {{
     Command::cargo_bin("similarity-rs")
         .unwrap()
         .arg(dir.path())
-        .arg("--experimental-types")
+        .arg("--types")
         .arg("--no-functions")
         .arg("--threshold")
         .arg("0.95")
@@ -180,3 +183,131 @@ struct Type{} {{
         .assert()
         .success();
 }
+
+#[test]
+fn test_enum_variant_payloads() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("commands.rs");
+
+    let content = r#"
+#[derive(Debug, Clone)]
+pub enum Command {
+    Move { x: i32, y: i32 },
+    Write(String),
+    Color(u8, u8, u8),
+    Quit,
+}
+
+#[derive(Debug, Clone)]
+pub enum EditorCommand {
+    Jump { dx: i32, dy: i32 },
+    Print(String),
+    Paint(u8, u8, u8),
+    Exit,
+}
+
+pub enum Token {
+    Number(f64),
+    Ident(String),
+    Plus,
+    Minus,
+}
+"#;
+
+    fs::write(&file, content).unwrap();
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--types")
+        .arg("--no-functions")
+        .arg("--threshold")
+        .arg("0.7")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("enum Command"))
+        .stdout(predicate::str::contains("enum EditorCommand"))
+        .stdout(predicate::str::contains("enum Token").not())
+        .stdout(predicate::str::contains("Total similar type pairs found: 1"));
+}
+
+#[test]
+fn test_cross_kind_type_similarity() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("shapes.rs");
+
+    let content = r#"
+pub struct Point {
+    x: f64,
+    y: f64,
+}
+
+pub struct Point3(f64, f64);
+
+pub enum Shape {
+    Circle(f64),
+    Square(f64),
+    Empty,
+}
+
+pub struct ShapeParts {
+    circle: Option<f64>,
+    square: Option<f64>,
+    empty: Option<()>,
+}
+"#;
+
+    fs::write(&file, content).unwrap();
+
+    // `--experimental-types` is still accepted
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--experimental-types")
+        .arg("--no-functions")
+        .arg("--threshold")
+        .arg("0.7")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("enum Shape"))
+        .stdout(predicate::str::contains("struct ShapeParts"))
+        .stdout(predicate::str::contains("tuple struct Point3"))
+        .stdout(predicate::str::contains("Total similar type pairs found: 2"));
+}
+
+#[test]
+fn test_diff_from_reports_only_type_pairs_touching_changed_lines() {
+    let dir = tempdir().unwrap();
+    let user_fields = "    id: u64,\n    name: String,\n    email: String,\n";
+    let order_fields =
+        "    order_id: String,\n    total: f64,\n    items: Vec<String>,\n    paid: bool,\n";
+    fs::write(dir.path().join("user.rs"), format!("struct User {{\n{user_fields}}}\n")).unwrap();
+    fs::write(dir.path().join("person.rs"), format!("struct Person {{\n{user_fields}}}\n"))
+        .unwrap();
+    fs::write(dir.path().join("order.rs"), format!("struct Order {{\n{order_fields}}}\n")).unwrap();
+    fs::write(dir.path().join("purchase.rs"), format!("struct Purchase {{\n{order_fields}}}\n"))
+        .unwrap();
+
+    // Only a field of `Order` changed
+    fs::write(
+        dir.path().join("change.patch"),
+        "--- a/order.rs\n+++ b/order.rs\n@@ -3 +3 @@\n-    total: f32,\n+    total: f64,\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("similarity-rs")
+        .unwrap()
+        .current_dir(dir.path())
+        .arg(".")
+        .arg("--types")
+        .arg("--no-functions")
+        .arg("--threshold")
+        .arg("0.7")
+        .arg("--diff-from")
+        .arg("change.patch")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("struct Order"))
+        .stdout(predicate::str::contains("struct User").not())
+        .stdout(predicate::str::contains("Total similar type pairs found: 1"));
+}
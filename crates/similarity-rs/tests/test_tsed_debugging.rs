@@ -28,6 +28,7 @@ fn test_short_function_similarity() {
         min_tokens: None,
         size_penalty: true,
         skip_test: false,
+        rename_consistency: false,
     };
 
     let sim12 = calculate_tsed(&tree1, &tree2, &options);
@@ -171,7 +171,8 @@ fn benchmark_within_file_comparison(c: &mut Criterion) {
 
         group.bench_with_input(BenchmarkId::new("parallel", num_files), &file_paths, |b, paths| {
             b.iter(|| {
-                let results = check_within_file_duplicates_parallel(paths, 0.8, &options, false);
+                let results =
+                    check_within_file_duplicates_parallel(paths, 0.8, &options, false, None);
                 black_box(results)
             });
         });
@@ -215,7 +216,8 @@ fn benchmark_cross_file_comparison(c: &mut Criterion) {
             &file_data_par,
             |b, data| {
                 b.iter(|| {
-                    let results = check_cross_file_duplicates_parallel(data, 0.8, &options, false);
+                    let results =
+                        check_cross_file_duplicates_parallel(data, 0.8, &options, false, None);
                     black_box(results)
                 });
             },
@@ -246,8 +248,9 @@ fn benchmark_scaling(c: &mut Criterion) {
                 // Set thread count for this iteration
                 rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap().install(
                     || {
-                        let results =
-                            check_within_file_duplicates_parallel(paths, 0.8, &options, false);
+                        let results = check_within_file_duplicates_parallel(
+                            paths, 0.8, &options, false, None,
+                        );
                         black_box(results)
                     },
                 )
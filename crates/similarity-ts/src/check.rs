@@ -5,8 +5,14 @@ use crate::parallel::{
     load_files_parallel,
 };
 use ignore::WalkBuilder;
-use similarity_core::TSEDOptions;
-use std::collections::HashSet;
+use similarity_core::{
+    analyze_function_identifiers, changed_lines::ChangedLines, explain_functions, extract_module,
+    find_similar_modules, format_structural_diff, format_suggestion, group_into_clusters,
+    is_test_file, parse_and_convert_to_tree, suggest_parameterized_extraction, CloneMember,
+    FunctionDefinition, ImportRelation, ModuleComparisonOptions, ModuleGraph,
+    RefactoringSuggestion, SimilarModulePair, SuggestionLanguage, TSEDOptions, TestCodeMode,
+};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -87,11 +93,156 @@ fn show_function_code(file_path: &str, function_name: &str, start_line: u32, end
     }
 }
 
+/// Display a structural diff explaining how two functions differ
+fn show_structural_diff(dup: &DuplicateResult, label1: &str, label2: &str, options: &TSEDOptions) {
+    let (Ok(content1), Ok(content2)) =
+        (fs::read_to_string(&dup.file1), fs::read_to_string(&dup.file2))
+    else {
+        eprintln!("Error reading files for explanation");
+        return;
+    };
+
+    let file1 = dup.file1.to_string_lossy();
+    let file2 = dup.file2.to_string_lossy();
+    match explain_functions(
+        &dup.result.func1,
+        &dup.result.func2,
+        &file1,
+        &content1,
+        &file2,
+        &content2,
+        options,
+    ) {
+        Ok(diff) => {
+            println!("\n\x1b[36m--- structural diff ---\x1b[0m");
+            print!("{}", format_structural_diff(&diff, label1, label2));
+        }
+        Err(e) => eprintln!("Error explaining {} vs {}: {}", label1, label2, e),
+    }
+
+    if let Ok(mapping) = analyze_function_identifiers(
+        &dup.result.func1,
+        &dup.result.func2,
+        &file1,
+        &content1,
+        &file2,
+        &content2,
+        &options.apted_options,
+    ) {
+        if !mapping.renames.is_empty() {
+            let renames: Vec<String> = mapping
+                .renames
+                .iter()
+                .map(|r| {
+                    let marker = if r.consistent { "" } else { " (inconsistent)" };
+                    format!("{}→{}{}", r.from, r.to, marker)
+                })
+                .collect();
+            println!(
+                "Identifier mapping ({:.0}% consistent): {}",
+                mapping.consistency() * 100.0,
+                renames.join(", ")
+            );
+        }
+    }
+}
+
+/// Suggest shared functions for clusters of duplicates, as text or JSON
+fn show_refactoring_suggestions(results: &[DuplicateResult], format: &str, options: &TSEDOptions) {
+    // Number every distinct function taking part in a duplicate pair
+    let mut functions: Vec<(&PathBuf, &FunctionDefinition)> = Vec::new();
+    let mut index_of: HashMap<(&PathBuf, &str, u32), usize> = HashMap::new();
+    let mut pairs = Vec::new();
+    for dup in results {
+        let mut pair = [0; 2];
+        for (slot, (file, func)) in
+            [(&dup.file1, &dup.result.func1), (&dup.file2, &dup.result.func2)]
+                .into_iter()
+                .enumerate()
+        {
+            pair[slot] =
+                *index_of.entry((file, func.name.as_str(), func.start_line)).or_insert_with(|| {
+                    functions.push((file, func));
+                    functions.len() - 1
+                });
+        }
+        pairs.push((pair[0], pair[1]));
+    }
+
+    let mut contents: HashMap<&PathBuf, String> = HashMap::new();
+    for (file, _) in &functions {
+        if !contents.contains_key(file) {
+            match fs::read_to_string(file) {
+                Ok(content) => {
+                    contents.insert(file, content);
+                }
+                Err(e) => eprintln!("Error reading file {}: {}", file.display(), e),
+            }
+        }
+    }
+
+    let mut suggestions: Vec<RefactoringSuggestion> = Vec::new();
+    for cluster in group_into_clusters(&pairs, functions.len()) {
+        let members: Vec<CloneMember> = cluster
+            .iter()
+            .filter_map(|&i| {
+                let (file, func) = functions[i];
+                let content = contents.get(file)?;
+                let source =
+                    content.get(func.body_span.start as usize..func.body_span.end as usize)?;
+                let tree = parse_and_convert_to_tree(&file.to_string_lossy(), source).ok()?;
+                let path = std::env::current_dir()
+                    .ok()
+                    .and_then(|dir| file.strip_prefix(dir).ok().map(Path::to_path_buf))
+                    .unwrap_or_else(|| file.clone());
+                Some(CloneMember {
+                    name: func.name.clone(),
+                    location: format!("{}:{}-{}", path.display(), func.start_line, func.end_line),
+                    parameters: func.parameters.clone(),
+                    tree,
+                    source,
+                })
+            })
+            .collect();
+
+        if let Some(suggestion) = suggest_parameterized_extraction(
+            &members,
+            SuggestionLanguage::TypeScript,
+            &options.apted_options,
+        ) {
+            suggestions.push(suggestion);
+        }
+    }
+
+    if format == "json" {
+        match serde_json::to_string_pretty(&suggestions) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error serializing suggestions: {}", e),
+        }
+        return;
+    }
+
+    if suggestions.is_empty() {
+        return;
+    }
+    println!("\n{}", "=".repeat(60));
+    println!(
+        "💡 Refactoring suggestions for {} cluster(s) of similar functions:",
+        suggestions.len()
+    );
+    for suggestion in &suggestions {
+        println!();
+        print!("{}", format_suggestion(suggestion));
+    }
+}
+
 /// Structure to hold all similarity results
 struct DuplicateResult {
     file1: PathBuf,
     file2: PathBuf,
     result: similarity_core::SimilarityResult,
+    /// How the two files relate in the import graph, with `--import-graph`
+    relation: Option<ImportRelation>,
 }
 
 impl DuplicateResult {
@@ -99,7 +250,103 @@ impl DuplicateResult {
         // Score = Similarity × Average lines
         let avg_lines =
             (self.result.func1.line_count() + self.result.func2.line_count()) as f64 / 2.0;
-        self.result.similarity * avg_lines
+        let weight = self.relation.as_ref().map_or(1.0, ImportRelation::consolidation_weight);
+        self.result.similarity * avg_lines * weight
+    }
+
+    fn keeps_tests(&self, tests: TestCodeMode, paths: &[String]) -> bool {
+        tests.keeps(
+            is_test_code(&self.file1, &self.result.func1, paths),
+            is_test_code(&self.file2, &self.result.func2, paths),
+        )
+    }
+}
+
+/// Functions in test files, and `describe`/`it` blocks anywhere
+fn is_test_code(file: &Path, func: &FunctionDefinition, paths: &[String]) -> bool {
+    func.is_test || is_test_file(&file.to_string_lossy(), paths)
+}
+
+fn relative_path(path: &Path) -> String {
+    std::env::current_dir()
+        .ok()
+        .and_then(|current_dir| path.strip_prefix(current_dir).ok())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// `same package web, src/a.ts imports src/b.ts, dependents 2 / 0, ranking ×1.75`
+fn describe_relation(relation: &ImportRelation, path1: &str, path2: &str) -> String {
+    let package = match &relation.same_package {
+        _ if relation.same_file => "same file".to_string(),
+        Some(package) => format!("same package {}", package),
+        None => "no shared package".to_string(),
+    };
+    let imports = match (relation.first_imports_second, relation.second_imports_first) {
+        (true, true) => "import each other".to_string(),
+        (true, false) => format!("{} imports {}", path1, path2),
+        (false, true) => format!("{} imports {}", path2, path1),
+        (false, false) => "no direct import".to_string(),
+    };
+    format!(
+        "{}, {}, dependents {} / {}, ranking ×{:.2}",
+        package,
+        imports,
+        relation.dependents.0,
+        relation.dependents.1,
+        relation.consolidation_weight()
+    )
+}
+
+/// Display near-identical file pairs, moving the function duplicates between the two files
+/// out of `all_results` and under the file pair
+fn display_file_pairs(pairs: &[SimilarModulePair], all_results: &mut Vec<DuplicateResult>) {
+    if pairs.is_empty() {
+        println!("\nNo similar files found!");
+        return;
+    }
+
+    println!("\nFound {} similar file pairs:", pairs.len());
+    println!("{}", "-".repeat(60));
+
+    for pair in pairs {
+        let (file1, file2) = (Path::new(&pair.file1), Path::new(&pair.file2));
+        println!(
+            "\nSimilarity: {:.2}% (tree: {:.2}%, imports: {:.2}%, exports: {:.2}%), shared functions: {:.0}%",
+            pair.similarity * 100.0,
+            pair.tree_similarity * 100.0,
+            pair.import_similarity * 100.0,
+            pair.export_similarity * 100.0,
+            pair.shared_functions * 100.0
+        );
+        println!("  similar-file: {}", relative_path(file1));
+        println!("  similar-file: {}", relative_path(file2));
+
+        let (collapsed, rest): (Vec<_>, Vec<_>) =
+            std::mem::take(all_results).into_iter().partition(|dup| {
+                (dup.file1 == file1 && dup.file2 == file2)
+                    || (dup.file1 == file2 && dup.file2 == file1)
+            });
+        *all_results = rest;
+
+        for dup in &collapsed {
+            let (func1, func2) = if dup.file1 == file1 {
+                (&dup.result.func1, &dup.result.func2)
+            } else {
+                (&dup.result.func2, &dup.result.func1)
+            };
+            println!(
+                "    {:.2}%  {} (L{}-{}) ~ {} (L{}-{})",
+                dup.result.similarity * 100.0,
+                func1.name,
+                func1.start_line,
+                func1.end_line,
+                func2.name,
+                func2.start_line,
+                func2.end_line
+            );
+        }
     }
 }
 
@@ -107,10 +354,13 @@ impl DuplicateResult {
 fn display_all_results(
     mut all_results: Vec<DuplicateResult>,
     print: bool,
+    explain: Option<&TSEDOptions>,
+    suggest: Option<(&str, &TSEDOptions)>,
     filter_function: Option<&String>,
     filter_function_body: Option<&String>,
 ) -> usize {
-    if all_results.is_empty() {
+    let json = matches!(suggest, Some(("json", _)));
+    if all_results.is_empty() && !json {
         println!("\nNo duplicate functions found!");
         return 0;
     }
@@ -167,7 +417,7 @@ fn display_all_results(
         });
     }
 
-    if all_results.is_empty() {
+    if all_results.is_empty() && !json {
         println!("\nNo duplicate functions found matching the filters!");
         return 0;
     }
@@ -177,6 +427,12 @@ fn display_all_results(
         b.priority().partial_cmp(&a.priority()).unwrap_or(std::cmp::Ordering::Equal)
     });
 
+    // The JSON replaces the text report so it can be piped
+    if let Some(("json", options)) = suggest {
+        show_refactoring_suggestions(&all_results, "json", options);
+        return all_results.len();
+    }
+
     println!("\nFound {} duplicate pairs:", all_results.len());
     println!("{}", "-".repeat(60));
 
@@ -233,6 +489,12 @@ fn display_all_results(
                 dup.result.func2.end_line,
             )
         );
+        if let Some(relation) = &dup.relation {
+            println!(
+                "  import-graph: {}",
+                describe_relation(relation, &relative_path1, &relative_path2)
+            );
+        }
 
         if print {
             show_function_code(
@@ -248,6 +510,19 @@ fn display_all_results(
                 dup.result.func2.end_line,
             );
         }
+
+        if let Some(options) = explain {
+            show_structural_diff(
+                dup,
+                &format!("{}:{}", relative_path1, dup.result.func1.name),
+                &format!("{}:{}", relative_path2, dup.result.func2.name),
+                options,
+            );
+        }
+    }
+
+    if let Some((format, options)) = suggest {
+        show_refactoring_suggestions(&all_results, format, options);
     }
 
     all_results.len()
@@ -262,13 +537,21 @@ pub fn check_paths(
     min_lines: u32,
     min_tokens: Option<u32>,
     no_size_penalty: bool,
+    rename_consistency: bool,
     print: bool,
+    explain: bool,
+    suggest: Option<&str>,
     fast_mode: bool,
     filter_function: Option<&String>,
     filter_function_body: Option<&String>,
     exclude_patterns: &[String],
+    changed: Option<&ChangedLines>,
+    compare_files: bool,
+    tests: TestCodeMode,
+    import_graph: bool,
 ) -> anyhow::Result<usize> {
-    let default_extensions = vec!["ts", "tsx", "js", "jsx", "mjs", "cjs", "mts", "cts"];
+    let default_extensions =
+        vec!["ts", "tsx", "js", "jsx", "mjs", "cjs", "mts", "cts", "vue", "svelte", "astro"];
     let exts: Vec<&str> =
         extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
 
@@ -350,36 +633,69 @@ pub fn check_paths(
     // Sort files for consistent output
     files.sort();
 
+    // With JSON suggestions stdout carries only the JSON
+    let json = suggest == Some("json");
+    let status = |message: String| {
+        if json {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    };
+
+    if tests == TestCodeMode::Skip {
+        let before = files.len();
+        files.retain(|file| !is_test_file(&file.to_string_lossy(), &paths));
+        if files.len() < before {
+            status(format!("Skipping {} test files", before - files.len()));
+        }
+    }
+
     if files.is_empty() {
-        println!("No TypeScript/JavaScript files found in the specified paths.");
+        status("No TypeScript/JavaScript files found in the specified paths.".to_string());
+        if json {
+            println!("[]");
+        }
         return Ok(0);
     }
 
-    println!("Checking {} files for duplicates...", files.len());
+    status(format!("Checking {} files for duplicates...", files.len()));
+    if let Some(changed) = changed {
+        status(format!(
+            "Reporting only duplicates touching lines changed in {} files",
+            changed.file_count()
+        ));
+    }
 
     let mut options = TSEDOptions::default();
     options.apted_options.rename_cost = rename_cost;
     options.min_lines = min_lines;
     options.min_tokens = min_tokens;
     options.size_penalty = !no_size_penalty;
+    options.rename_consistency = rename_consistency;
 
     let mut all_results = Vec::new();
 
     // Check within each file in parallel
     let within_file_results =
-        check_within_file_duplicates_parallel(&files, threshold, &options, fast_mode);
+        check_within_file_duplicates_parallel(&files, threshold, &options, fast_mode, changed);
 
     // Collect within-file duplicates
     for (file, similar_pairs) in within_file_results {
         for result in similar_pairs {
-            all_results.push(DuplicateResult { file1: file.clone(), file2: file.clone(), result });
+            all_results.push(DuplicateResult {
+                file1: file.clone(),
+                file2: file.clone(),
+                result,
+                relation: None,
+            });
         }
     }
 
     // Check across files in parallel
     let file_data = load_files_parallel(&files);
     let cross_file_results =
-        check_cross_file_duplicates_parallel(&file_data, threshold, &options, fast_mode);
+        check_cross_file_duplicates_parallel(&file_data, threshold, &options, fast_mode, changed);
 
     // Collect cross-file duplicates
     for (file1, result, file2) in cross_file_results {
@@ -387,12 +703,60 @@ pub fn check_paths(
             file1: PathBuf::from(file1),
             file2: PathBuf::from(file2),
             result,
+            relation: None,
         });
     }
 
-    // Display all results together
-    let duplicate_count =
-        display_all_results(all_results, print, filter_function, filter_function_body);
+    // Test blocks inside production files are only known per function
+    all_results.retain(|dup| dup.keeps_tests(tests, &paths));
 
-    Ok(duplicate_count)
+    if import_graph {
+        let graph = ModuleGraph::build(
+            file_data.iter().map(|file| (file.path.as_path(), file.content.as_str())),
+        );
+        for dup in &mut all_results {
+            dup.relation = Some(graph.relation(&dup.file1, &dup.file2));
+        }
+    }
+
+    // Compare whole files and group their function duplicates under the file pair
+    let mut file_pair_count = 0;
+    if compare_files {
+        let modules: Vec<_> = file_data
+            .iter()
+            .filter_map(|file| extract_module(&file.path.to_string_lossy(), &file.content).ok())
+            .collect();
+        let module_options =
+            ModuleComparisonOptions { tsed_options: options.clone(), ..Default::default() };
+        let skipped = modules.iter().filter(|m| !module_options.is_comparable(m)).count();
+        if skipped > 0 {
+            println!(
+                "Skipping {} files outside the file comparison limits (under {} lines or over {} nodes)",
+                skipped, options.min_lines, module_options.max_tree_nodes
+            );
+        }
+
+        let mut file_pairs = find_similar_modules(&modules, threshold, &module_options);
+        file_pairs.retain(|pair| {
+            tests.keeps(is_test_file(&pair.file1, &paths), is_test_file(&pair.file2, &paths))
+                && changed.is_none_or(|changed| {
+                    changed.touches_file(Path::new(&pair.file1))
+                        || changed.touches_file(Path::new(&pair.file2))
+                })
+        });
+        display_file_pairs(&file_pairs, &mut all_results);
+        file_pair_count = file_pairs.len();
+    }
+
+    // Display all results together
+    let duplicate_count = display_all_results(
+        all_results,
+        print,
+        explain.then_some(&options),
+        suggest.map(|format| (format, &options)),
+        filter_function,
+        filter_function_body,
+    );
+
+    Ok(file_pair_count + duplicate_count)
 }
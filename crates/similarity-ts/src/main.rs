@@ -1,6 +1,9 @@
 #![allow(clippy::uninlined_format_args)]
 
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use similarity_core::{is_test_file, ChangedLines, TestCodeMode};
 
 mod check;
 pub mod parallel;
@@ -18,6 +21,10 @@ struct Cli {
     #[arg(short, long)]
     print: bool,
 
+    /// Explain each function duplicate with a structural diff (renames, changed literals, inserted/deleted statements)
+    #[arg(long)]
+    explain: bool,
+
     /// Similarity threshold (0.0-1.0)
     #[arg(short, long, default_value = "0.87")]
     threshold: f64,
@@ -42,6 +49,15 @@ struct Cli {
     #[arg(long)]
     classes_only: bool,
 
+    /// Compare class method bodies: report classes sharing near-identical methods (a candidate
+    /// base class or mixin) and methods duplicating an inherited implementation (implies --classes)
+    #[arg(long)]
+    class_methods: bool,
+
+    /// Minimum number of near-identical methods for two classes to be reported
+    #[arg(long, default_value = "2")]
+    min_shared_methods: usize,
+
     /// Include classes with inheritance (extends) - excluded by default
     #[arg(long)]
     include_inheritance: bool,
@@ -50,10 +66,27 @@ struct Cli {
     #[arg(long)]
     include_implements: bool,
 
-    /// Show refactoring suggestions for excluded classes
+    /// Compare whole files (full-program trees plus import/export sets) and group the function
+    /// duplicates between near-identical files under the file pair
+    #[arg(long)]
+    files: bool,
+
+    /// Enable React component checking: duplicate function components (with their hooks) and repeated JSX markup
+    #[arg(long)]
+    components: bool,
+
+    /// Minimum number of nodes for repeated JSX markup to be reported
+    #[arg(long, default_value = "12")]
+    jsx_min_nodes: usize,
+
+    /// Show refactoring suggestions: shared functions for clusters of similar functions, utility types (`Pick`/`Omit`/`Partial`) for types restating another, and interfaces for excluded classes
     #[arg(long)]
     suggest: bool,
 
+    /// Output format for function refactoring suggestions (text or json); json writes only the suggestions to stdout and skips type checking unless --types is given
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    suggest_format: String,
+
     /// File extensions to check
     #[arg(short, long, value_delimiter = ',')]
     extensions: Option<Vec<String>>,
@@ -74,6 +107,10 @@ struct Cli {
     #[arg(long)]
     no_size_penalty: bool,
 
+    /// Reward consistent identifier renames (a→x everywhere) and penalize conflicting ones
+    #[arg(long)]
+    rename_consistency: bool,
+
     /// Filter functions by name (substring match)
     #[arg(long)]
     filter_function: Option<String>,
@@ -106,6 +143,16 @@ struct Cli {
     #[arg(long, default_value = "0.4")]
     naming_weight: f64,
 
+    /// Flatten `extends` chains and intersections (`A & B`) within the analyzed files before
+    /// comparing types
+    #[arg(long)]
+    resolve_extends: bool,
+
+    /// Rename type parameters by position before comparing types, so `Box<T>` and `Box<U>`
+    /// match
+    #[arg(long)]
+    normalize_type_params: bool,
+
     /// Only check type literals (excludes type aliases and interfaces)
     #[arg(long)]
     type_literals_only: bool,
@@ -126,6 +173,22 @@ struct Cli {
     #[arg(long)]
     exclude: Vec<String>,
 
+    /// How test code (`*.test.ts`, `*.spec.ts`, `__tests__/`, `describe`/`it` blocks) takes part
+    /// in duplicates: include, skip, only (pairs of test code) or separate (no test/production
+    /// pairs). Types, classes, components and overlaps are test code by file name only
+    #[arg(long, default_value = "include", value_parser = ["include", "skip", "only", "separate"])]
+    tests: String,
+
+    /// Skip test code (same as --tests skip)
+    #[arg(long, conflicts_with = "tests")]
+    skip_test: bool,
+
+    /// Build the import graph of the analyzed files and rank function duplicates by how easy
+    /// they are to consolidate: same package, one file already importing the other, and the
+    /// number of dependents of each copy
+    #[arg(long)]
+    import_graph: bool,
+
     /// Enable experimental overlap detection mode
     #[arg(long = "experimental-overlap")]
     overlap: bool,
@@ -142,28 +205,58 @@ struct Cli {
     #[arg(long, default_value = "0.25")]
     overlap_size_tolerance: f64,
 
+    /// Only report duplicates involving lines changed since this git revision (e.g. origin/main)
+    #[arg(long, conflicts_with = "diff_from")]
+    changed_since: Option<String>,
+
+    /// Only report duplicates involving lines changed by this unified diff (e.g. a .patch file)
+    #[arg(long)]
+    diff_from: Option<std::path::PathBuf>,
+
     /// Exit with code 1 if duplicates are found
     #[arg(long)]
     fail_on_duplicates: bool,
-    
+
     /// Use new generalized structure comparison framework (experimental)
     #[arg(long)]
     use_structure_comparison: bool,
 }
 
 fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    let changed =
+        ChangedLines::from_cli_options(cli.changed_since.as_deref(), cli.diff_from.as_deref())
+            .map_err(anyhow::Error::msg)?;
+    let tests = if cli.skip_test {
+        TestCodeMode::Skip
+    } else {
+        TestCodeMode::parse(&cli.tests).unwrap_or_default()
+    };
+
+    // JSON suggestions are the whole output, so they cover function analysis only: type
+    // checking, on by default, is left out unless `--types` is given
+    let json_suggestions = cli.suggest && cli.suggest_format == "json";
+    let types_requested = matches.value_source("types") == Some(ValueSource::CommandLine);
 
     let functions_enabled = !cli.no_functions && !cli.classes_only;
-    let types_enabled = (cli.types && !cli.no_types) && !cli.classes_only;
-    let classes_enabled = cli.classes || cli.classes_only;
+    let types_enabled =
+        (cli.types && !cli.no_types) && !cli.classes_only && (!json_suggestions || types_requested);
+    let classes_enabled = cli.classes || cli.classes_only || cli.class_methods;
+    let components_enabled = cli.components;
     let overlap_enabled = cli.overlap;
     let unified_types_enabled = cli.unified_types && !cli.no_unified_types;
     let include_type_literals = true; // Always include type literals
 
     // Validate that at least one analyzer is enabled
-    if !functions_enabled && !types_enabled && !classes_enabled && !overlap_enabled {
-        eprintln!("Error: At least one analyzer must be enabled. Remove --no-types to enable type checking, use --classes for class checking, use --overlap for overlap detection, or remove --no-functions.");
+    if !functions_enabled
+        && !types_enabled
+        && !classes_enabled
+        && !components_enabled
+        && !overlap_enabled
+    {
+        eprintln!("Error: At least one analyzer must be enabled. Remove --no-types to enable type checking, use --classes for class checking, use --components for React component checking, use --overlap for overlap detection, or remove --no-functions.");
         return Err(anyhow::anyhow!("No analyzer enabled"));
     }
 
@@ -179,14 +272,35 @@ fn main() -> anyhow::Result<()> {
         (lines, tokens) => (lines, tokens),
     };
 
-    println!("Analyzing code similarity...\n");
+    // Nothing else may write to stdout next to JSON suggestions
+    if json_suggestions
+        && (!functions_enabled
+            || types_enabled
+            || classes_enabled
+            || components_enabled
+            || overlap_enabled
+            || cli.files)
+    {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--suggest-format json only covers function similarity. Run types, classes, components, overlap or --files analysis separately.",
+            )
+            .exit();
+    }
+
+    if !json_suggestions {
+        println!("Analyzing code similarity...\n");
+    }
 
     let separator = "-".repeat(60);
     let mut total_duplicates = 0;
 
     // Run functions analysis if enabled
     if functions_enabled {
-        println!("=== Function Similarity ===");
+        if !json_suggestions {
+            println!("=== Function Similarity ===");
+        }
         let duplicate_count = check::check_paths(
             cli.paths.clone(),
             cli.threshold,
@@ -195,11 +309,18 @@ fn main() -> anyhow::Result<()> {
             min_lines.unwrap_or(3),
             min_tokens,
             cli.no_size_penalty,
+            cli.rename_consistency,
             cli.print,
+            cli.explain,
+            cli.suggest.then_some(cli.suggest_format.as_str()),
             !cli.no_fast,
             cli.filter_function.as_ref(),
             cli.filter_function_body.as_ref(),
             &cli.exclude,
+            changed.as_ref(),
+            cli.files,
+            tests,
+            cli.import_graph,
         )?;
         total_duplicates += duplicate_count;
     }
@@ -226,6 +347,11 @@ fn main() -> anyhow::Result<()> {
             unified_types_enabled,
             &cli.exclude,
             cli.use_structure_comparison,
+            cli.resolve_extends,
+            cli.normalize_type_params,
+            cli.suggest,
+            changed.as_ref(),
+            tests,
         )?;
         total_duplicates += type_duplicate_count;
     }
@@ -246,12 +372,41 @@ fn main() -> anyhow::Result<()> {
             !cli.include_implements,
             cli.suggest,
             &cli.exclude,
+            cli.class_methods.then_some(cli.min_shared_methods),
+            cli.rename_cost,
+            cli.no_size_penalty,
+            changed.as_ref(),
+            tests,
         )?;
         total_duplicates += class_duplicate_count;
     }
 
+    // Run component analysis if enabled
+    if components_enabled && (functions_enabled || types_enabled || classes_enabled) {
+        println!("\n{}\n", separator);
+    }
+
+    if components_enabled {
+        println!("=== Component Similarity ===");
+        let component_duplicate_count = check_components(
+            cli.paths.clone(),
+            cli.threshold,
+            cli.rename_cost,
+            cli.no_size_penalty,
+            cli.extensions.as_ref(),
+            cli.print,
+            cli.jsx_min_nodes,
+            &cli.exclude,
+            changed.as_ref(),
+            tests,
+        )?;
+        total_duplicates += component_duplicate_count;
+    }
+
     // Run overlap analysis if enabled
-    if overlap_enabled && (functions_enabled || types_enabled || classes_enabled) {
+    if overlap_enabled
+        && (functions_enabled || types_enabled || classes_enabled || components_enabled)
+    {
         println!("\n{}\n", separator);
     }
 
@@ -266,6 +421,8 @@ fn main() -> anyhow::Result<()> {
             cli.overlap_max_window,
             cli.overlap_size_tolerance,
             &cli.exclude,
+            changed.as_ref(),
+            tests,
         )?;
         total_duplicates += overlap_duplicate_count;
     }
@@ -331,18 +488,24 @@ fn check_types(
     unified_types: bool,
     exclude_patterns: &[String],
     use_structure_comparison: bool,
+    resolve_extends: bool,
+    normalize_type_params: bool,
+    suggest: bool,
+    changed: Option<&ChangedLines>,
+    tests: TestCodeMode,
 ) -> anyhow::Result<usize> {
     use ignore::WalkBuilder;
     use similarity_core::{
         extract_type_literals_from_code, extract_types_from_code, find_similar_type_literals,
         find_similar_types, find_similar_unified_types, find_similar_unified_types_structured,
-        TypeComparisonOptions, TypeKind, UnifiedType, ComparisonOptions,
+        find_type_derivations, resolve_type_hierarchy, ComparisonOptions, TypeComparisonOptions,
+        TypeDerivationOptions, TypeKind, UnifiedType,
     };
     use std::collections::HashSet;
     use std::fs;
     use std::path::Path;
 
-    let default_extensions = vec!["ts", "tsx", "mts", "cts"];
+    let default_extensions = vec!["ts", "tsx", "mts", "cts", "vue", "svelte", "astro"];
     let exts: Vec<&str> =
         extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
 
@@ -440,13 +603,7 @@ fn check_types(
                 // Extract regular types unless type_literals_only is set
                 if !type_literals_only {
                     match extract_types_from_code(&content, &file_str) {
-                        Ok(mut types) => {
-                            // Filter types based on command line options
-                            if types_only {
-                                types.retain(|t| t.kind == TypeKind::TypeAlias);
-                            } else if interfaces_only {
-                                types.retain(|t| t.kind == TypeKind::Interface);
-                            }
+                        Ok(types) => {
                             all_types.extend(types);
                         }
                         Err(e) => {
@@ -479,6 +636,18 @@ fn check_types(
         }
     }
 
+    // Resolve parents before filtering so interfaces can still inherit from type aliases
+    if resolve_extends {
+        all_types = resolve_type_hierarchy(&all_types);
+    }
+
+    // Filter types based on command line options
+    if types_only {
+        all_types.retain(|t| t.kind.is_type_alias());
+    } else if interfaces_only {
+        all_types.retain(|t| t.kind == TypeKind::Interface);
+    }
+
     if all_types.is_empty() && all_type_literals.is_empty() {
         println!("No type definitions or type literals found!");
         return Ok(0);
@@ -490,12 +659,13 @@ fn check_types(
     }
 
     // Set up comparison options
-    let options = TypeComparisonOptions {
+    let mut options = TypeComparisonOptions {
         allow_cross_kind_comparison: allow_cross_kind,
         structural_weight,
         naming_weight,
         ..Default::default()
     };
+    options.normalization_options.normalize_type_parameters = normalize_type_params;
 
     // Validate weights
     if (structural_weight + naming_weight - 1.0).abs() > 0.001 {
@@ -579,6 +749,42 @@ fn check_types(
 
         (similar_pairs, type_literal_pairs, type_literal_to_literal_pairs)
     };
+    // Types have no test blocks, so test code is known by file name
+    let similar_pairs: Vec<_> = similar_pairs
+        .into_iter()
+        .filter(|p| {
+            let (t1, t2) = (&p.type1, &p.type2);
+            keeps_test_pair(tests, &paths, &t1.file_path, &t2.file_path)
+                && keeps_changed_pair(
+                    changed,
+                    (&t1.file_path, t1.start_line, t1.end_line),
+                    (&t2.file_path, t2.start_line, t2.end_line),
+                )
+        })
+        .collect();
+    let type_literal_pairs: Vec<_> = type_literal_pairs
+        .into_iter()
+        .filter(|p| {
+            let (literal, def) = (&p.type_literal, &p.type_definition);
+            keeps_test_pair(tests, &paths, &literal.file_path, &def.file_path)
+                && keeps_changed_pair(
+                    changed,
+                    (&literal.file_path, literal.start_line, literal.end_line),
+                    (&def.file_path, def.start_line, def.end_line),
+                )
+        })
+        .collect();
+    let type_literal_to_literal_pairs: Vec<_> = type_literal_to_literal_pairs
+        .into_iter()
+        .filter(|(literal1, literal2, _)| {
+            keeps_test_pair(tests, &paths, &literal1.file_path, &literal2.file_path)
+                && keeps_changed_pair(
+                    changed,
+                    (&literal1.file_path, literal1.start_line, literal1.end_line),
+                    (&literal2.file_path, literal2.start_line, literal2.end_line),
+                )
+        })
+        .collect();
 
     if similar_pairs.is_empty()
         && type_literal_pairs.is_empty()
@@ -708,9 +914,62 @@ fn check_types(
         }
     }
 
+    // Suggest utility types for types restating part of another (only when --suggest is enabled)
+    if suggest {
+        let mut derivations = find_type_derivations(&all_types, &TypeDerivationOptions::default());
+        derivations.retain(|d| {
+            keeps_test_pair(tests, &paths, &d.derived.file_path, &d.base.file_path)
+                && keeps_changed_pair(
+                    changed,
+                    (&d.derived.file_path, d.derived.start_line, d.derived.end_line),
+                    (&d.base.file_path, d.base.start_line, d.base.end_line),
+                )
+        });
+        if !derivations.is_empty() {
+            println!("\n{}", "=".repeat(60));
+            println!("💡 Types derivable from another type:");
+
+            for derivation in &derivations {
+                println!(
+                    "\n  {}:{} | L{}-{} derived-type: {} ({})",
+                    get_relative_path(&derivation.derived.file_path),
+                    derivation.derived.start_line,
+                    derivation.derived.start_line,
+                    derivation.derived.end_line,
+                    derivation.derived.name,
+                    format_type_kind(&derivation.derived.kind)
+                );
+                println!(
+                    "    = {}  (base: {}:{})",
+                    derivation.suggestion(),
+                    get_relative_path(&derivation.base.file_path),
+                    derivation.base.start_line
+                );
+            }
+        }
+    }
+
     Ok(similar_pairs.len() + type_literal_pairs.len() + type_literal_to_literal_pairs.len())
 }
 
+/// `--tests` for pairs outside function analysis, where test code is known by file name only
+fn keeps_test_pair(tests: TestCodeMode, paths: &[String], file1: &str, file2: &str) -> bool {
+    tests.keeps(is_test_file(file1, paths), is_test_file(file2, paths))
+}
+
+/// `--changed-since`/`--diff-from`: whether either side of a pair, as file and line range,
+/// overlaps changed lines
+fn keeps_changed_pair(
+    changed: Option<&ChangedLines>,
+    (file1, start1, end1): (&str, usize, usize),
+    (file2, start2, end2): (&str, usize, usize),
+) -> bool {
+    changed.is_none_or(|changed| {
+        changed.overlaps(std::path::Path::new(file1), start1 as u32, end1 as u32)
+            || changed.overlaps(std::path::Path::new(file2), start2 as u32, end2 as u32)
+    })
+}
+
 fn get_relative_path(file_path: &str) -> String {
     if let Ok(current_dir) = std::env::current_dir() {
         std::path::Path::new(file_path)
@@ -728,6 +987,10 @@ fn format_type_kind(kind: &similarity_core::TypeKind) -> &'static str {
         similarity_core::TypeKind::Interface => "interface",
         similarity_core::TypeKind::TypeAlias => "type",
         similarity_core::TypeKind::TypeLiteral => "type literal",
+        similarity_core::TypeKind::Enum => "enum",
+        similarity_core::TypeKind::Union => "union",
+        similarity_core::TypeKind::Tuple => "tuple",
+        similarity_core::TypeKind::Function => "function type",
     }
 }
 
@@ -794,6 +1057,8 @@ fn check_overlaps(
     max_window_size: u32,
     size_tolerance: f64,
     exclude_patterns: &[String],
+    changed: Option<&ChangedLines>,
+    tests: TestCodeMode,
 ) -> anyhow::Result<usize> {
     use ignore::WalkBuilder;
     use similarity_core::{find_overlaps_across_files, OverlapOptions};
@@ -801,7 +1066,8 @@ fn check_overlaps(
     use std::fs;
     use std::path::Path;
 
-    let default_extensions = vec!["js", "ts", "jsx", "tsx", "mjs", "mts", "cjs", "cts"];
+    let default_extensions =
+        vec!["js", "ts", "jsx", "tsx", "mjs", "mts", "cjs", "cts", "vue", "svelte", "astro"];
     let exts: Vec<&str> =
         extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
 
@@ -905,7 +1171,16 @@ fn check_overlaps(
     let options = OverlapOptions { min_window_size, max_window_size, threshold, size_tolerance };
 
     // Find overlaps
-    let overlaps = find_overlaps_across_files(&file_contents, &options)?;
+    let mut overlaps = find_overlaps_across_files(&file_contents, &options)?;
+    overlaps.retain(|o| {
+        let (source, target) = (o.overlap.source_lines, o.overlap.target_lines);
+        keeps_test_pair(tests, &paths, &o.source_file, &o.target_file)
+            && keeps_changed_pair(
+                changed,
+                (&o.source_file, source.0 as usize, source.1 as usize),
+                (&o.target_file, target.0 as usize, target.1 as usize),
+            )
+    });
 
     if overlaps.is_empty() {
         println!("\nNo code overlaps found!");
@@ -1021,14 +1296,22 @@ fn check_classes(
     no_implements: bool,
     suggest: bool,
     exclude_patterns: &[String],
+    min_shared_methods: Option<usize>,
+    rename_cost: f64,
+    no_size_penalty: bool,
+    changed: Option<&ChangedLines>,
+    tests: TestCodeMode,
 ) -> anyhow::Result<usize> {
     use ignore::WalkBuilder;
-    use similarity_core::{extract_classes_from_code, find_similar_classes};
+    use similarity_core::{
+        extract_classes_from_code, extract_classes_with_method_bodies_from_code,
+        find_inherited_duplicates, find_shared_methods, find_similar_classes, TSEDOptions,
+    };
     use std::collections::HashSet;
     use std::fs;
     use std::path::Path;
 
-    let default_extensions = vec!["ts", "tsx", "mts", "cts"];
+    let default_extensions = vec!["ts", "tsx", "mts", "cts", "vue", "svelte", "astro"];
     let exts: Vec<&str> =
         extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
 
@@ -1123,8 +1406,13 @@ fn check_classes(
             Ok(content) => {
                 let file_str = file.to_string_lossy();
 
-                // Extract classes
-                match extract_classes_from_code(&content, &file_str) {
+                // Method trees are only needed to compare implementations
+                let extracted = if min_shared_methods.is_some() {
+                    extract_classes_with_method_bodies_from_code(&content, &file_str)
+                } else {
+                    extract_classes_from_code(&content, &file_str)
+                };
+                match extracted {
                     Ok(classes) => {
                         for class in classes {
                             // Check if class should be excluded
@@ -1176,7 +1464,17 @@ fn check_classes(
     }
 
     // Find similar classes across all files
-    let similar_pairs = find_similar_classes(&all_classes, threshold);
+    let mut similar_pairs = find_similar_classes(&all_classes, threshold);
+    let keeps_class_pair = |class1: &similarity_core::ClassDefinition,
+                            class2: &similarity_core::ClassDefinition| {
+        keeps_test_pair(tests, &paths, &class1.file_path, &class2.file_path)
+            && keeps_changed_pair(
+                changed,
+                (&class1.file_path, class1.start_line, class1.end_line),
+                (&class2.file_path, class2.start_line, class2.end_line),
+            )
+    };
+    similar_pairs.retain(|p| keeps_class_pair(&p.class1, &p.class2));
 
     if similar_pairs.is_empty() {
         println!("\nNo similar classes found!");
@@ -1222,6 +1520,100 @@ fn check_classes(
         println!("\nTotal similar class pairs found: {}", similar_pairs.len());
     }
 
+    // Compare method implementations (only when --class-methods is enabled)
+    let mut method_duplicate_count = 0;
+    if let Some(min_shared_methods) = min_shared_methods {
+        let mut options = TSEDOptions::default();
+        options.apted_options.rename_cost = rename_cost;
+        options.size_penalty = !no_size_penalty;
+
+        // Subclasses are needed here even when they are excluded from class comparison
+        let classes: Vec<_> = all_classes.iter().chain(&excluded_classes).cloned().collect();
+        let mut shared = find_shared_methods(&classes, threshold, min_shared_methods, &options);
+        shared.retain(|p| keeps_class_pair(&p.class1, &p.class2));
+        let mut inherited = find_inherited_duplicates(&classes, threshold, &options);
+        inherited.retain(|d| {
+            keeps_test_pair(tests, &paths, &d.class.file_path, &d.ancestor.file_path)
+                && keeps_changed_pair(
+                    changed,
+                    (&d.class.file_path, d.method.start_line, d.method.end_line),
+                    (&d.ancestor.file_path, d.inherited.start_line, d.inherited.end_line),
+                )
+        });
+        method_duplicate_count = shared.len() + inherited.len();
+
+        if shared.is_empty() && inherited.is_empty() {
+            println!("\nNo duplicated methods found!");
+        }
+
+        if !shared.is_empty() {
+            println!("\nClasses sharing near-identical methods (candidate base class or mixin):");
+            println!("{}", "-".repeat(60));
+
+            for pair in &shared {
+                println!(
+                    "\n  {}:{} | L{}-{} class: {}",
+                    get_relative_path(&pair.class1.file_path),
+                    pair.class1.start_line,
+                    pair.class1.start_line,
+                    pair.class1.end_line,
+                    pair.class1.name
+                );
+                println!(
+                    "  {}:{} | L{}-{} class: {}",
+                    get_relative_path(&pair.class2.file_path),
+                    pair.class2.start_line,
+                    pair.class2.start_line,
+                    pair.class2.end_line,
+                    pair.class2.name
+                );
+                for method in &pair.methods {
+                    println!(
+                        "    {:.2}%  {}.{} (L{}-{}) ~ {}.{} (L{}-{})",
+                        method.similarity * 100.0,
+                        pair.class1.name,
+                        method.method1.name,
+                        method.method1.start_line,
+                        method.method1.end_line,
+                        pair.class2.name,
+                        method.method2.name,
+                        method.method2.start_line,
+                        method.method2.end_line
+                    );
+                }
+            }
+
+            println!("\nTotal class pairs sharing methods: {}", shared.len());
+        }
+
+        if !inherited.is_empty() {
+            println!("\nMethods duplicating an inherited implementation:");
+            println!("{}", "-".repeat(60));
+
+            for duplicate in &inherited {
+                println!(
+                    "\n  {}:{} | L{}-{} method: {}.{}",
+                    get_relative_path(&duplicate.class.file_path),
+                    duplicate.method.start_line,
+                    duplicate.method.start_line,
+                    duplicate.method.end_line,
+                    duplicate.class.name,
+                    duplicate.method.name
+                );
+                println!(
+                    "    {:.2}% duplicates inherited {}.{} ({}:{})",
+                    duplicate.similarity * 100.0,
+                    duplicate.ancestor.name,
+                    duplicate.inherited.name,
+                    get_relative_path(&duplicate.ancestor.file_path),
+                    duplicate.inherited.start_line
+                );
+            }
+
+            println!("\nTotal inherited duplicates found: {}", inherited.len());
+        }
+    }
+
     // Suggest possible interface implementations (only when --suggest is enabled)
     if suggest && !excluded_classes.is_empty() {
         println!("\n{}", "=".repeat(60));
@@ -1267,7 +1659,265 @@ fn check_classes(
         }
     }
 
-    Ok(similar_pairs.len())
+    Ok(similar_pairs.len() + method_duplicate_count)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_components(
+    paths: Vec<String>,
+    threshold: f64,
+    rename_cost: f64,
+    no_size_penalty: bool,
+    extensions: Option<&Vec<String>>,
+    print: bool,
+    jsx_min_nodes: usize,
+    exclude_patterns: &[String],
+    changed: Option<&ChangedLines>,
+    tests: TestCodeMode,
+) -> anyhow::Result<usize> {
+    use ignore::WalkBuilder;
+    use similarity_core::{
+        extract_components_from_code, find_duplicate_jsx, find_similar_components, TSEDOptions,
+    };
+    use std::collections::{HashMap, HashSet};
+    use std::fs;
+    use std::path::Path;
+
+    let default_extensions = vec!["tsx", "jsx", "js"];
+    let exts: Vec<&str> =
+        extensions.map_or(default_extensions, |v| v.iter().map(String::as_str).collect());
+
+    let exclude_matcher = create_exclude_matcher(exclude_patterns);
+    let mut files = Vec::new();
+    let mut visited = HashSet::new();
+
+    for path_str in &paths {
+        let path = Path::new(path_str);
+
+        if path.is_file() {
+            if path.extension().and_then(|e| e.to_str()).is_some_and(|e| exts.contains(&e)) {
+                if let Ok(canonical) = path.canonicalize() {
+                    if visited.insert(canonical) {
+                        files.push(path.to_path_buf());
+                    }
+                }
+            }
+        } else if path.is_dir() {
+            let walker = WalkBuilder::new(path)
+                .follow_links(false)
+                .git_ignore(true)
+                .git_global(true)
+                .git_exclude(true)
+                .build();
+
+            for entry in walker {
+                let entry = entry?;
+                let entry_path = entry.path();
+
+                if !entry_path.is_file() {
+                    continue;
+                }
+
+                if let Some(ref matcher) = exclude_matcher {
+                    if matcher.is_match(entry_path) {
+                        continue;
+                    }
+                    if let Ok(current_dir) = std::env::current_dir() {
+                        if let Ok(relative) = entry_path.strip_prefix(&current_dir) {
+                            if matcher.is_match(relative) {
+                                continue;
+                            }
+                        }
+                    }
+                }
+
+                if entry_path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| exts.contains(&e))
+                {
+                    if let Ok(canonical) = entry_path.canonicalize() {
+                        if visited.insert(canonical) {
+                            files.push(entry_path.to_path_buf());
+                        }
+                    }
+                }
+            }
+        } else {
+            eprintln!("Warning: Path not found: {}", path_str);
+        }
+    }
+
+    files.sort();
+
+    if files.is_empty() {
+        println!("No JSX/TSX files found in specified paths");
+        return Ok(0);
+    }
+
+    println!("Checking {} files for similar React components...\n", files.len());
+
+    let mut file_contents = HashMap::new();
+    let mut all_components = Vec::new();
+
+    for file in &files {
+        match fs::read_to_string(file) {
+            Ok(content) => {
+                let file_str = file.to_string_lossy().to_string();
+                match extract_components_from_code(&content, &file_str) {
+                    Ok(components) => all_components.extend(components),
+                    Err(e) => {
+                        // Skip files with parse errors silently
+                        if !e.contains("Parse errors:") {
+                            eprintln!("Error in {}: {}", file.display(), e);
+                        }
+                    }
+                }
+                file_contents.insert(file_str, content);
+            }
+            Err(e) => {
+                eprintln!("Error reading {}: {}", file.display(), e);
+            }
+        }
+    }
+
+    if all_components.is_empty() {
+        println!("No React components found!");
+        return Ok(0);
+    }
+
+    println!("Found {} components", all_components.len());
+
+    let mut options = TSEDOptions::default();
+    options.apted_options.rename_cost = rename_cost;
+    options.size_penalty = !no_size_penalty;
+
+    let touches_changes = |file_path: &str, start_line: u32, end_line: u32| {
+        changed.is_none_or(|changed| changed.overlaps(Path::new(file_path), start_line, end_line))
+    };
+
+    let mut component_pairs = find_similar_components(&all_components, threshold, &options);
+    component_pairs.retain(|pair| {
+        let (c1, c2) = (&pair.component1, &pair.component2);
+        keeps_test_pair(tests, &paths, &c1.file_path, &c2.file_path)
+            && (touches_changes(&c1.file_path, c1.start_line, c1.end_line)
+                || touches_changes(&c2.file_path, c2.start_line, c2.end_line))
+    });
+
+    // Markup shared by two duplicate components is already covered by the component pair
+    let duplicate_components: HashSet<_> = component_pairs
+        .iter()
+        .flat_map(|pair| {
+            let key1 = (pair.component1.file_path.as_str(), pair.component1.name.as_str());
+            let key2 = (pair.component2.file_path.as_str(), pair.component2.name.as_str());
+            [(key1, key2), (key2, key1)]
+        })
+        .collect();
+    let mut jsx_pairs = find_duplicate_jsx(&all_components, threshold, jsx_min_nodes, &options);
+    jsx_pairs.retain(|pair| {
+        let (j1, j2) = (&pair.jsx1, &pair.jsx2);
+        let key1 = (j1.file_path.as_str(), j1.component.as_str());
+        let key2 = (j2.file_path.as_str(), j2.component.as_str());
+        !duplicate_components.contains(&(key1, key2))
+            && keeps_test_pair(tests, &paths, &j1.file_path, &j2.file_path)
+            && (touches_changes(&j1.file_path, j1.start_line, j1.end_line)
+                || touches_changes(&j2.file_path, j2.start_line, j2.end_line))
+    });
+
+    let show_code = |file_path: &str, start_line: u32, end_line: u32| {
+        if let Some(content) = file_contents.get(file_path) {
+            if let Ok(segment) = extract_code_lines(content, start_line, end_line) {
+                println!(
+                    "\n\x1b[36m--- {}:{}-{} ---\x1b[0m",
+                    get_relative_path(file_path),
+                    start_line,
+                    end_line
+                );
+                println!("{}", segment);
+            }
+        }
+    };
+
+    if component_pairs.is_empty() && jsx_pairs.is_empty() {
+        println!("\nNo similar components found!");
+        return Ok(0);
+    }
+
+    if !component_pairs.is_empty() {
+        println!("\nSimilar components found:");
+        println!("{}", "-".repeat(60));
+
+        for pair in &component_pairs {
+            println!("\nSimilarity: {:.2}%", pair.similarity * 100.0);
+            for component in [&pair.component1, &pair.component2] {
+                let hooks = if component.hooks.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (hooks: {})", component.hooks.join(", "))
+                };
+                println!(
+                    "  {}:{} | L{}-{} similar-component: {}{}",
+                    get_relative_path(&component.file_path),
+                    component.start_line,
+                    component.start_line,
+                    component.end_line,
+                    component.name,
+                    hooks
+                );
+            }
+            if !pair.shared_hooks.is_empty() {
+                println!("  Shared hooks: {}", pair.shared_hooks.join(", "));
+            }
+
+            if print {
+                show_code(
+                    &pair.component1.file_path,
+                    pair.component1.start_line,
+                    pair.component1.end_line,
+                );
+                show_code(
+                    &pair.component2.file_path,
+                    pair.component2.start_line,
+                    pair.component2.end_line,
+                );
+            }
+        }
+
+        println!("\nTotal similar component pairs found: {}", component_pairs.len());
+    }
+
+    if !jsx_pairs.is_empty() {
+        println!("\nRepeated JSX markup (candidates for a shared component):");
+        println!("{}", "-".repeat(60));
+
+        for pair in &jsx_pairs {
+            println!(
+                "\nSimilarity: {:.2}% | {} nodes",
+                pair.similarity * 100.0,
+                pair.jsx1.tree.get_subtree_size().min(pair.jsx2.tree.get_subtree_size())
+            );
+            for jsx in [&pair.jsx1, &pair.jsx2] {
+                println!(
+                    "  {}:{} | L{}-{} <{}> in component: {}",
+                    get_relative_path(&jsx.file_path),
+                    jsx.start_line,
+                    jsx.start_line,
+                    jsx.end_line,
+                    jsx.tag.trim_start_matches('<').trim_end_matches('>'),
+                    jsx.component
+                );
+            }
+
+            if print {
+                show_code(&pair.jsx1.file_path, pair.jsx1.start_line, pair.jsx1.end_line);
+                show_code(&pair.jsx2.file_path, pair.jsx2.start_line, pair.jsx2.end_line);
+            }
+        }
+
+        println!("\nTotal repeated JSX pairs found: {}", jsx_pairs.len());
+    }
+
+    Ok(component_pairs.len() + jsx_pairs.len())
 }
 
 fn show_class_details(class: &similarity_core::ClassDefinition) {
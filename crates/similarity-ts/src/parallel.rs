@@ -1,6 +1,6 @@
 use rayon::prelude::*;
 use similarity_core::{
-    extract_functions, find_similar_functions_fast, find_similar_functions_in_file,
+    extract_functions, find_similar_functions_fast, find_similar_functions_in_file, ChangedLines,
     FastSimilarityOptions, FunctionDefinition, SimilarityResult, TSEDOptions,
 };
 use std::fs;
@@ -43,9 +43,11 @@ pub fn check_within_file_duplicates_parallel(
     threshold: f64,
     options: &TSEDOptions,
     fast_mode: bool,
+    changed: Option<&ChangedLines>,
 ) -> Vec<(PathBuf, Vec<SimilarityResult>)> {
     files
         .par_iter()
+        .filter(|file| changed.is_none_or(|c| c.touches_file(file)))
         .filter_map(|file| match fs::read_to_string(file) {
             Ok(code) => {
                 let file_str = file.to_string_lossy();
@@ -62,7 +64,17 @@ pub fn check_within_file_duplicates_parallel(
                     find_similar_functions_in_file(&file_str, &code, threshold, options).ok()
                 };
 
-                similar_pairs.and_then(|pairs| {
+                similar_pairs.and_then(|mut pairs| {
+                    if let Some(changed) = changed {
+                        pairs.retain(|pair| {
+                            changed.overlaps(file, pair.func1.start_line, pair.func1.end_line)
+                                || changed.overlaps(
+                                    file,
+                                    pair.func2.start_line,
+                                    pair.func2.end_line,
+                                )
+                        });
+                    }
                     if pairs.is_empty() {
                         None
                     } else {
@@ -81,13 +93,18 @@ pub fn check_cross_file_duplicates_parallel(
     threshold: f64,
     options: &TSEDOptions,
     _fast_mode: bool,
+    changed: Option<&ChangedLines>,
 ) -> Vec<(String, SimilarityResult, String)> {
     // Prepare all function pairs with file information
     let mut all_functions = Vec::new();
+    let mut touched = Vec::new();
     for data in file_data {
         let filename = data.path.to_string_lossy().to_string();
         for func in &data.functions {
             all_functions.push((filename.clone(), data.content.clone(), func.clone()));
+            touched.push(
+                changed.is_none_or(|c| c.overlaps(&data.path, func.start_line, func.end_line)),
+            );
         }
     }
 
@@ -98,8 +115,8 @@ pub fn check_cross_file_duplicates_parallel(
             let (file1, _, _) = &all_functions[i];
             let (file2, _, _) = &all_functions[j];
 
-            // Only check across different files
-            if file1 != file2 {
+            // Only check across different files, with at least one side in changed code
+            if file1 != file2 && (touched[i] || touched[j]) {
                 pairs_to_check.push((i, j));
             }
         }
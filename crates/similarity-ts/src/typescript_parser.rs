@@ -81,8 +81,12 @@ impl LanguageParser for TypeScriptParser {
                 name: t.name,
                 kind: match t.kind {
                     TypeKind::Interface => "interface".to_string(),
-                    TypeKind::TypeAlias => "type_alias".to_string(),
                     TypeKind::TypeLiteral => "type_literal".to_string(),
+                    TypeKind::Enum => "enum".to_string(),
+                    TypeKind::TypeAlias
+                    | TypeKind::Union
+                    | TypeKind::Tuple
+                    | TypeKind::Function => "type_alias".to_string(),
                 },
                 start_line: t.start_line as u32,
                 end_line: t.end_line as u32,
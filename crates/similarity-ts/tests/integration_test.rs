@@ -330,3 +330,587 @@ export function found() {
         .success()
         .stdout(predicate::str::contains("No duplicate functions found"));
 }
+
+#[test]
+fn test_suggest_shared_function_for_similar_functions() {
+    let dir = tempdir().unwrap();
+    let sample_path = dir.path().join("api.ts");
+
+    fs::write(
+        &sample_path,
+        r#"
+function loadUsers(id: string) {
+    const response = fetchUsers("/api/users", id);
+    if (!response) {
+        throw new Error("missing");
+    }
+    return response;
+}
+
+function loadAccounts(key: string) {
+    const response = fetchAccounts("/api/accounts", key);
+    if (!response) {
+        throw new Error("missing");
+    }
+    return response;
+}
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+    cmd.arg(dir.path())
+        .arg("--no-types")
+        .arg("--no-size-penalty")
+        .arg("--suggest")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Extract shared function: function load(id, fetch, value1)",
+        ))
+        .stdout(predicate::str::contains("const response = fetch(value1, id);"))
+        .stdout(predicate::str::contains("load(key, fetchAccounts, \"/api/accounts\")"));
+
+    let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+    cmd.arg(dir.path())
+        .arg("--no-types")
+        .arg("--no-size-penalty")
+        .arg("--suggest")
+        .arg("--suggest-format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"function_name\": \"load\""))
+        .stdout(predicate::str::contains("\"kind\": \"Literal\""))
+        .stdout(predicate::str::contains("Similarity:").not())
+        .stdout(predicate::function(|out: &str| {
+            serde_json::from_str::<serde_json::Value>(out).is_ok()
+        }));
+}
+
+#[test]
+fn test_suggest_json_skips_default_type_checking() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("sample.ts"),
+        r#"
+interface User {
+    id: string;
+    name: string;
+}
+
+interface Person {
+    id: string;
+    name: string;
+}
+
+function loadUsers(id: string) {
+    const response = fetchUsers("/api/users", id);
+    if (!response) {
+        throw new Error("missing");
+    }
+    return response;
+}
+
+function loadAccounts(key: string) {
+    const response = fetchAccounts("/api/accounts", key);
+    if (!response) {
+        throw new Error("missing");
+    }
+    return response;
+}
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+    cmd.arg(dir.path())
+        .arg("--no-size-penalty")
+        .arg("--suggest")
+        .arg("--suggest-format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"function_name\": \"load\""))
+        .stdout(predicate::str::contains("Person").not())
+        .stdout(predicate::function(|out: &str| {
+            serde_json::from_str::<serde_json::Value>(out).is_ok()
+        }));
+
+    // Asking for types explicitly is still an error
+    let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+    cmd.arg(dir.path())
+        .arg("--types")
+        .arg("--suggest")
+        .arg("--suggest-format")
+        .arg("json")
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("--suggest-format json only covers function similarity"));
+}
+
+#[test]
+fn test_diff_from_reports_only_pairs_touching_changed_lines() {
+    let dir = tempdir().unwrap();
+    let body = |name: &str, arg: &str| {
+        format!(
+            "function {name}({arg}: number[]) {{\n  let total = 0;\n  for (const v of {arg}) {{\n    total += v;\n  }}\n  return total;\n}}\n"
+        )
+    };
+    fs::write(
+        dir.path().join("a.ts"),
+        format!("{}\n{}", body("sumA", "values"), body("sumB", "items")),
+    )
+    .unwrap();
+    fs::write(dir.path().join("b.ts"), body("sumC", "xs")).unwrap();
+
+    // Only line 11 (inside sumB) changed
+    fs::write(
+        dir.path().join("change.patch"),
+        "--- a/a.ts\n+++ b/a.ts\n@@ -11 +11 @@\n-    total += v;\n+    total += v;\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+    cmd.current_dir(dir.path())
+        .arg(".")
+        .arg("--no-types")
+        .arg("--no-size-penalty")
+        .arg("--diff-from")
+        .arg("change.patch")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Found 2 duplicate pairs"))
+        .stdout(predicate::str::contains("sumB"));
+}
+
+#[test]
+fn test_diff_from_reports_only_type_pairs_touching_changed_lines() {
+    let dir = tempdir().unwrap();
+    let user_fields = "  id: number;\n  name: string;\n  email: string;\n";
+    let order_fields =
+        "  orderId: string;\n  total: number;\n  items: string[];\n  paid: boolean;\n";
+    fs::write(dir.path().join("user.ts"), format!("interface User {{\n{user_fields}}}\n")).unwrap();
+    fs::write(dir.path().join("person.ts"), format!("interface Person {{\n{user_fields}}}\n"))
+        .unwrap();
+    fs::write(dir.path().join("order.ts"), format!("interface Order {{\n{order_fields}}}\n"))
+        .unwrap();
+    fs::write(dir.path().join("purchase.ts"), format!("interface Purchase {{\n{order_fields}}}\n"))
+        .unwrap();
+
+    // Only a property of `User` changed
+    fs::write(
+        dir.path().join("change.patch"),
+        "--- a/user.ts\n+++ b/user.ts\n@@ -3 +3 @@\n-  name: String;\n+  name: string;\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+    cmd.current_dir(dir.path())
+        .arg(".")
+        .arg("--no-functions")
+        .arg("--diff-from")
+        .arg("change.patch")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("similar-type: User"))
+        .stdout(predicate::str::contains("similar-type: Order").not())
+        .stdout(predicate::str::contains("Total similar type pairs found: 1"));
+}
+
+#[test]
+fn test_class_methods_reports_shared_and_inherited_methods() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("services.ts"),
+        r#"
+class UserService {
+    async fetchAll(client: Client) {
+        const response = await client.get("/users");
+        if (!response.ok) {
+            throw new Error("Failed to load users");
+        }
+        const items = await response.json();
+        return items.map((item) => normalize(item));
+    }
+
+    describe(user: User) {
+        const parts = [user.firstName, user.lastName].filter(Boolean);
+        const label = parts.join(" ").trim();
+        if (label.length === 0) {
+            return "(unnamed)";
+        }
+        return label.toUpperCase();
+    }
+}
+
+class ProjectService {
+    async loadEverything(client: Client) {
+        const response = await client.get("/projects");
+        if (!response.ok) {
+            throw new Error("Failed to load projects");
+        }
+        const items = await response.json();
+        return items.map((item) => normalize(item));
+    }
+
+    describe(project: Project) {
+        const parts = [project.firstName, project.lastName].filter(Boolean);
+        const label = parts.join(" ").trim();
+        if (label.length === 0) {
+            return "(unnamed)";
+        }
+        return label.toUpperCase();
+    }
+}
+
+class AdminService extends UserService {
+    describe(admin: User) {
+        const parts = [admin.firstName, admin.lastName].filter(Boolean);
+        const label = parts.join(" ").trim();
+        if (label.length === 0) {
+            return "(unnamed)";
+        }
+        return label.toUpperCase();
+    }
+
+    promote(admin: User) {
+        return this.client.post(`/admins/${admin.id}`);
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("similarity-ts")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--class-methods")
+        .arg("--no-functions")
+        .arg("--threshold")
+        .arg("0.7")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("class: UserService"))
+        .stdout(predicate::str::contains(
+            "UserService.fetchAll (L3-10) ~ ProjectService.loadEverything (L23-30)",
+        ))
+        .stdout(predicate::str::contains("method: AdminService.describe"))
+        .stdout(predicate::str::contains("duplicates inherited UserService.describe"));
+}
+
+#[test]
+fn test_files_mode_groups_function_duplicates_under_file_pair() {
+    let dir = tempdir().unwrap();
+    let client = r#"import axios from "axios";
+import { Config } from "./config";
+
+export async function fetchUsers(config: Config) {
+    const response = await axios.get(`${config.baseUrl}/users`);
+    if (response.status !== 200) {
+        throw new Error("Failed to fetch users");
+    }
+    const users = response.data.map((user) => ({ ...user, name: user.name.trim() }));
+    console.log(`Loaded ${users.length} users`);
+    return users;
+}
+
+export async function fetchProjects(config: Config) {
+    const response = await axios.get(`${config.baseUrl}/projects`);
+    if (response.status !== 200) {
+        throw new Error("Failed to fetch projects");
+    }
+    const projects = response.data.map((project) => ({ ...project, name: project.name.trim() }));
+    console.log(`Loaded ${projects.length} projects`);
+    return projects;
+}
+
+export default { fetchUsers, fetchProjects };
+"#;
+    fs::create_dir_all(dir.path().join("a")).unwrap();
+    fs::create_dir_all(dir.path().join("b")).unwrap();
+    fs::write(dir.path().join("a/client.ts"), client).unwrap();
+    fs::write(dir.path().join("b/client.ts"), client.replace("Failed to fetch", "Could not load"))
+        .unwrap();
+
+    let output = Command::cargo_bin("similarity-ts")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--files")
+        .arg("--no-types")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("Found 1 similar file pairs:"), "{stdout}");
+    assert!(stdout.contains("shared functions: 100%"), "{stdout}");
+    assert!(stdout.contains("fetchUsers (L4-12) ~ fetchUsers (L4-12)"), "{stdout}");
+    // The per-function pairs are only listed under the file pair
+    assert!(!stdout.contains("Found 2 duplicate pairs"), "{stdout}");
+}
+
+#[test]
+fn test_component_scripts_are_analyzed_with_file_line_numbers() {
+    let dir = tempdir().unwrap();
+    let script = r#"interface Member {
+    id: string;
+    name: string;
+    email: string;
+    active: boolean;
+}
+
+function activeMembers(members: Member[], query: string): Member[] {
+    const needle = query.trim().toLowerCase();
+    const result = members.filter((member) => member.active);
+    if (needle.length === 0) {
+        return result;
+    }
+    return result
+        .filter((member) => member.name.toLowerCase().includes(needle))
+        .sort((a, b) => a.name.localeCompare(b.name));
+}
+"#;
+    let vue = format!(
+        "<template>\n  <ul>\n    <li v-for=\"m in members\">{{{{ m.name }}}}</li>\n  </ul>\n</template>\n\n<script setup lang=\"ts\">\n{script}</script>\n\n<style scoped>\nul {{ padding: 0; }}\n</style>\n"
+    );
+    let svelte = format!(
+        "<script lang=\"ts\">\n{}</script>\n\n<ul>\n  {{#each members as m}}<li>{{m.name}}</li>{{/each}}\n</ul>\n",
+        script.replace("Member", "TeamMember").replace("activeMembers", "activeTeamMembers")
+    );
+    fs::write(dir.path().join("MemberList.vue"), vue).unwrap();
+    fs::write(dir.path().join("TeamList.svelte"), svelte).unwrap();
+
+    let output = Command::cargo_bin("similarity-ts")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--threshold")
+        .arg("0.8")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    // Line numbers point into the component files, past the template
+    assert!(stdout.contains("MemberList.vue:15-24 activeMembers"), "{stdout}");
+    assert!(stdout.contains("TeamList.svelte:9-18 activeTeamMembers"), "{stdout}");
+    assert!(stdout.contains("MemberList.vue:8 | L8-13 similar-type: Member"), "{stdout}");
+    assert!(stdout.contains("TeamList.svelte:2 | L2-7 similar-type: TeamMember"), "{stdout}");
+}
+
+#[test]
+fn test_tests_modes_for_test_files_and_blocks() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("user.ts"),
+        r#"export function normalizeUser(input: { name: string; email: string; roles?: string[] }) {
+    const name = input.name.trim();
+    const email = input.email.trim().toLowerCase();
+    const roles = (input.roles ?? []).filter((role) => role.length > 0);
+    if (name.length === 0) {
+        throw new Error("User name is required");
+    }
+    if (!email.includes("@")) {
+        throw new Error("User email is invalid");
+    }
+    return { name, email, roles, createdAt: new Date() };
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("user.test.ts"),
+        r#"import { describe, it, expect } from "vitest";
+import { normalizeUser } from "./user";
+
+function makeUser(input: { name: string; email: string; roles?: string[] }) {
+    const name = input.name.trim();
+    const email = input.email.trim().toLowerCase();
+    const roles = (input.roles ?? []).filter((role) => role.length > 0);
+    if (name.length === 0) {
+        throw new Error("Fixture name is required");
+    }
+    if (!email.includes("@")) {
+        throw new Error("Fixture email is invalid");
+    }
+    return { name, email, roles, createdAt: new Date(0) };
+}
+
+describe("normalizeUser", () => {
+    it("trims the name", () => {
+        const user = normalizeUser({ name: "  Ada ", email: "ada@example.com" });
+        expect(user.name).toBe("Ada");
+        expect(user.email).toBe("ada@example.com");
+        expect(user.roles).toEqual([]);
+        expect(user.createdAt).toBeInstanceOf(Date);
+        expect(makeUser({ name: "Ada", email: "ada@example.com" }).name).toBe("Ada");
+        expect(() => normalizeUser({ name: "", email: "ada@example.com" })).toThrow();
+        expect(() => normalizeUser({ name: "Ada", email: "ada" })).toThrow();
+    });
+
+    it("lowercases the email", () => {
+        const user = normalizeUser({ name: "Grace", email: "GRACE@example.com" });
+        expect(user.name).toBe("Grace");
+        expect(user.email).toBe("grace@example.com");
+        expect(user.roles).toEqual([]);
+        expect(user.createdAt).toBeInstanceOf(Date);
+        expect(makeUser({ name: "Grace", email: "grace@example.com" }).name).toBe("Grace");
+        expect(() => normalizeUser({ name: "", email: "grace@example.com" })).toThrow();
+        expect(() => normalizeUser({ name: "Grace", email: "grace" })).toThrow();
+    });
+});
+"#,
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::cargo_bin("similarity-ts")
+            .unwrap()
+            .arg(dir.path())
+            .arg("--no-types")
+            .args(args)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let fixture_pair = "user.test.ts:4-15 makeUser";
+    let test_pair = "user.test.ts:18-27 it: normalizeUser > trims the name";
+
+    let stdout = run(&[]);
+    assert!(stdout.contains("Found 2 duplicate pairs"), "{stdout}");
+    assert!(stdout.contains(fixture_pair) && stdout.contains(test_pair), "{stdout}");
+
+    let stdout = run(&["--skip-test"]);
+    assert!(stdout.contains("Skipping 1 test files"), "{stdout}");
+    assert!(stdout.contains("No duplicate functions found!"), "{stdout}");
+
+    for mode in ["only", "separate"] {
+        let stdout = run(&["--tests", mode]);
+        assert!(stdout.contains("Found 1 duplicate pairs"), "{mode}: {stdout}");
+        assert!(stdout.contains(test_pair), "{mode}: {stdout}");
+        assert!(!stdout.contains(fixture_pair), "{mode}: {stdout}");
+    }
+}
+
+#[test]
+fn test_tests_modes_for_types_and_classes() {
+    let dir = tempdir().unwrap();
+    let model = |type_name: &str, class_name: &str| {
+        format!(
+            r#"interface {type_name} {{
+    id: string;
+    name: string;
+    email: string;
+    roles: string[];
+}}
+
+class {class_name} {{
+    private items: string[] = [];
+    add(item: string): void {{
+        this.items.push(item);
+    }}
+    count(): number {{
+        return this.items.length;
+    }}
+}}
+"#
+        )
+    };
+    fs::write(dir.path().join("account.ts"), model("Account", "AccountStore")).unwrap();
+    fs::write(dir.path().join("account.test.ts"), model("AccountFixture", "FakeStore")).unwrap();
+
+    let run = |tests: &str| {
+        let output = Command::cargo_bin("similarity-ts")
+            .unwrap()
+            .arg(dir.path())
+            .args(["--no-functions", "--classes", "--threshold", "0.7", "--tests", tests])
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let stdout = run("include");
+    assert!(stdout.contains("similar-type: AccountFixture"), "{stdout}");
+    assert!(stdout.contains("similar-class: FakeStore"), "{stdout}");
+
+    // Test files are only known by name here, so a test/production pair is dropped as a whole
+    for mode in ["skip", "only", "separate"] {
+        let stdout = run(mode);
+        assert!(stdout.contains("No similar types found!"), "{mode}: {stdout}");
+        assert!(stdout.contains("No similar classes found!"), "{mode}: {stdout}");
+    }
+}
+
+#[test]
+fn test_import_graph_ranks_and_annotates_duplicates() {
+    let dir = tempdir().unwrap();
+    let files = [
+        ("packages/shared/package.json", r#"{ "name": "@app/shared" }"#),
+        ("packages/web/package.json", r#"{ "name": "web" }"#),
+        (
+            "packages/shared/src/format.ts",
+            r#"export function formatPrice(amount: number, currency: string): string {
+    const rounded = Math.round(amount * 100) / 100;
+    const [whole, fraction = "00"] = rounded.toString().split(".");
+    const grouped = whole.replace(/\B(?=(\d{3})+(?!\d))/g, ",");
+    if (currency === "JPY") {
+        return `¥${grouped}`;
+    }
+    return `${currency} ${grouped}.${fraction.padEnd(2, "0")}`;
+}
+"#,
+        ),
+        (
+            "packages/web/src/page.ts",
+            r#"import { formatPrice } from "@app/shared/format";
+
+export function formatCost(value: number, unit: string): string {
+    const rounded = Math.round(value * 100) / 100;
+    const [whole, fraction = "00"] = rounded.toString().split(".");
+    const grouped = whole.replace(/\B(?=(\d{3})+(?!\d))/g, ",");
+    if (unit === "JPY") {
+        return `¥${grouped}`;
+    }
+    return `${unit} ${grouped}.${fraction.padEnd(2, "0")}`;
+}
+
+export const total = (n: number) => formatPrice(n, "USD");
+"#,
+        ),
+        (
+            "packages/web/src/legacy.ts",
+            r#"export function formatAmount(amount: number, code: string): string {
+    const rounded = Math.round(amount * 100) / 100;
+    const [whole, fraction = "00"] = rounded.toString().split(".");
+    const grouped = whole.replace(/\B(?=(\d{3})+(?!\d))/g, ",");
+    if (code === "JPY") {
+        return `¥${grouped}`;
+    }
+    return `${code} ${grouped}.${fraction.padEnd(2, "0")}`;
+}
+"#,
+        ),
+    ];
+    for (path, content) in files {
+        let path = dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    let output = Command::cargo_bin("similarity-ts")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--no-types")
+        .arg("--import-graph")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let position = |text: &str| stdout.find(text).unwrap_or_else(|| panic!("{text}: {stdout}"));
+    // Ranked by consolidation ease although formatPrice ~ formatAmount is the closest pair
+    let imported = position("page.ts imports ");
+    let same_package = position("same package web, no direct import, dependents 0 / 0");
+    let unrelated =
+        position("no shared package, no direct import, dependents 1 / 0, ranking ×1.10");
+    assert!(imported < same_package && same_package < unrelated, "{stdout}");
+    assert!(stdout.contains("dependents 1 / 0, ranking ×1.60"), "{stdout}");
+}
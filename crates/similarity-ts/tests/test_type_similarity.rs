@@ -315,3 +315,170 @@ interface Type{} {{
         .assert()
         .success();
 }
+
+#[test]
+fn test_enum_duplicated_by_string_literal_union() {
+    let dir = tempdir().unwrap();
+
+    fs::write(
+        dir.path().join("status.ts"),
+        r#"
+enum OrderStatus {
+    Pending = "pending",
+    Shipped = "shipped",
+    Delivered = "delivered",
+    Cancelled = "cancelled",
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("api.ts"),
+        r#"
+type OrderState = "pending" | "shipped" | "delivered" | "cancelled";
+
+type Coordinates = [lat: number, lng: number];
+"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("similarity-ts")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--no-functions")
+        .arg("--threshold")
+        .arg("0.8")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("similar-type: OrderStatus (enum)"))
+        .stdout(predicate::str::contains("similar-type: OrderState (union)"))
+        .stdout(predicate::str::contains("Coordinates").not());
+}
+
+#[test]
+fn test_resolve_extends_flattens_inherited_properties() {
+    let dir = tempdir().unwrap();
+
+    fs::write(
+        dir.path().join("models.ts"),
+        r#"
+interface Entity {
+    id: string;
+    createdAt: Date;
+    updatedAt: Date;
+}
+
+interface User extends Entity {
+    name: string;
+    email: string;
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("dto.ts"),
+        r#"
+type UserRecord = {
+    id: string;
+    createdAt: Date;
+    updatedAt: Date;
+    name: string;
+    email: string;
+};
+"#,
+    )
+    .unwrap();
+
+    let run = |resolve: bool| {
+        let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+        cmd.arg(dir.path()).arg("--no-functions").arg("--threshold").arg("0.85");
+        if resolve {
+            cmd.arg("--resolve-extends");
+        }
+        cmd.assert().success()
+    };
+
+    run(false).stdout(predicate::str::contains("similar-type: UserRecord").not());
+    run(true)
+        .stdout(predicate::str::contains("similar-type: User (interface)"))
+        .stdout(predicate::str::contains("similar-type: UserRecord (type)"));
+}
+
+#[test]
+fn test_suggest_reports_utility_type_derivations() {
+    let dir = tempdir().unwrap();
+
+    fs::write(
+        dir.path().join("user.ts"),
+        r#"
+interface User {
+    id: string;
+    name: string;
+    email: string;
+    createdAt: Date;
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("forms.ts"),
+        r#"
+interface UserSummary {
+    id: string;
+    name: string;
+}
+
+interface UserPatch {
+    name?: string;
+    email?: string;
+}
+"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("similarity-ts")
+        .unwrap()
+        .arg(dir.path())
+        .arg("--no-functions")
+        .arg("--suggest")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("derived-type: UserSummary (interface)"))
+        .stdout(predicate::str::contains("= Pick<User, 'id' | 'name'>"))
+        .stdout(predicate::str::contains("= Partial<Pick<User, 'name' | 'email'>>"));
+}
+
+#[test]
+fn test_normalize_type_params_matches_renamed_parameters() {
+    let dir = tempdir().unwrap();
+
+    fs::write(
+        dir.path().join("boxes.ts"),
+        r#"
+interface Box<T> {
+    value: T;
+    fallback: T;
+    label: string;
+}
+
+interface Wrapper<Item> {
+    value: Item;
+    fallback: Item;
+    label: string;
+}
+"#,
+    )
+    .unwrap();
+
+    let run = |normalize: bool| {
+        let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+        cmd.arg(dir.path()).arg("--no-functions").arg("--threshold").arg("0.9");
+        if normalize {
+            cmd.arg("--normalize-type-params");
+        }
+        cmd.assert().success()
+    };
+
+    run(false).stdout(predicate::str::contains("similar-type: Wrapper").not());
+    run(true).stdout(predicate::str::contains("similar-type: Wrapper (interface)"));
+}
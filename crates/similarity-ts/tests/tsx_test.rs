@@ -89,3 +89,87 @@ export function NameDisplay({ name }: { name: string }) {
         .success()
         .stdout(predicate::str::contains("Checking 2 files for duplicates"));
 }
+
+#[test]
+fn test_components_mode_reports_components_and_repeated_jsx() {
+    let dir = tempdir().unwrap();
+
+    fs::write(
+        dir.path().join("cards.tsx"),
+        r#"
+export function UserCard({ user, onSelect }: UserCardProps) {
+  const [expanded, setExpanded] = useState(false);
+  const theme = useTheme();
+  return (
+    <div className={theme.card} onClick={() => onSelect(user.id)}>
+      <img src={user.avatar} alt={user.name} />
+      <h2>{user.name}</h2>
+      <button onClick={() => setExpanded(!expanded)}>{expanded ? "Less" : "More"}</button>
+      {expanded && <p>{user.bio}</p>}
+    </div>
+  );
+}
+
+export const TeamCard = memo(({ team, onSelect }: TeamCardProps) => {
+  const [open, setOpen] = useState(false);
+  const theme = useTheme();
+  return (
+    <div className={theme.card} onClick={() => onSelect(team.id)}>
+      <img src={team.logo} alt={team.title} />
+      <h2>{team.title}</h2>
+      <button onClick={() => setOpen(!open)}>{open ? "Less" : "More"}</button>
+      {open && <p>{team.description}</p>}
+    </div>
+  );
+});
+"#,
+    )
+    .unwrap();
+
+    fs::write(
+        dir.path().join("forms.tsx"),
+        r#"
+export function Settings({ settings }: SettingsProps) {
+  const { save, saving } = useSave();
+  return (
+    <form onSubmit={save}>
+      <input name="email" defaultValue={settings.email} />
+      <footer>
+        <button type="submit" className="primary" disabled={saving}>Save</button>
+        <button type="reset" className="secondary">Reset</button>
+      </footer>
+    </form>
+  );
+}
+
+export function Profile({ profile }: ProfileProps) {
+  const [tab, setTab] = useState("info");
+  return (
+    <section>
+      <nav>{TABS.map((t) => <a key={t} onClick={() => setTab(t)}>{t}</a>)}</nav>
+      <footer>
+        <button type="submit" className="primary" disabled={tab === "info"}>Save</button>
+        <button type="reset" className="secondary">Reset</button>
+      </footer>
+    </section>
+  );
+}
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("similarity-ts").unwrap();
+    cmd.arg(dir.path())
+        .arg("--components")
+        .arg("--no-functions")
+        .arg("--no-types")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("=== Component Similarity ==="))
+        .stdout(predicate::str::contains("Found 4 components"))
+        .stdout(predicate::str::contains("similar-component: UserCard (hooks: useState, useTheme)"))
+        .stdout(predicate::str::contains("similar-component: TeamCard"))
+        .stdout(predicate::str::contains("<footer> in component: Settings"))
+        .stdout(predicate::str::contains("<footer> in component: Profile"))
+        .stdout(predicate::str::contains("Total repeated JSX pairs found: 1"));
+}